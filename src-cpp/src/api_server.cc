@@ -0,0 +1,357 @@
+#include "api_server.h"
+
+#include <arpa/inet.h>
+#include <netinet/in.h>
+#include <sys/socket.h>
+#include <sys/time.h>
+#include <unistd.h>
+
+#include <algorithm>
+#include <cctype>
+#include <cerrno>
+#include <chrono>
+#include <cstdlib>
+#include <sstream>
+
+// The maximum size of the request headers.
+const size_t kMaxHeadersSize = 64 * 1024;
+// The maximum size of the request body.
+const size_t kMaxRequestSize = 10 * 1024 * 1024;
+// How long to wait for a client to send the request before dropping it, so
+// that a slow client doesn't block the other ones.
+const int kReceiveTimeoutInSecs = 5;
+// How long to wait before accepting connections again after accept() fails,
+// e.g. when the app runs out of file descriptors.
+const int kAcceptRetryDelayInMs = 100;
+// How long to wait for an event client to accept the event before
+// disconnecting it.
+const int kSendTimeoutInSecs = 2;
+// The maximum number of events waiting to be sent. The oldest events are
+// dropped when the clients can't keep up.
+const size_t kMaxQueuedEvents = 1000;
+// How long stop() waits for the server thread to exit.
+const int kStopTimeoutInMs = 2000;
+// The path of the Server-Sent Events stream.
+const char *kEventsPath = "/events";
+
+namespace {
+
+std::string toLower(std::string str) {
+  std::transform(str.begin(), str.end(), str.begin(), [](unsigned char c) {
+    return std::tolower(c);
+  });
+  return str;
+}
+
+std::string trim(const std::string &str) {
+  auto begin = str.find_first_not_of(" \t\r");
+  if (begin == std::string::npos) {
+    return "";
+  }
+  auto end = str.find_last_not_of(" \t\r");
+  return str.substr(begin, end - begin + 1);
+}
+
+std::string statusText(int status) {
+  switch (status) {
+    case 200: return "OK";
+    case 400: return "Bad Request";
+    case 401: return "Unauthorized";
+    case 404: return "Not Found";
+    case 413: return "Payload Too Large";
+    case 431: return "Request Header Fields Too Large";
+    case 503: return "Service Unavailable";
+    default: return "Internal Server Error";
+  }
+}
+
+bool sendAll(int client_fd, const std::string &data) {
+  size_t sent = 0;
+  while (sent < data.size()) {
+    auto result = send(client_fd, data.data() + sent, data.size() - sent, 0);
+    if (result <= 0) {
+      return false;
+    }
+    sent += result;
+  }
+  return true;
+}
+
+void sendResponse(int client_fd, const ApiResponse &response) {
+  std::ostringstream out;
+  out << "HTTP/1.1 " << response.status << " " << statusText(response.status) << "\r\n"
+      << "Content-Type: application/json; charset=utf-8\r\n"
+      << "Content-Length: " << response.body.size() << "\r\n"
+      << "Connection: close\r\n\r\n"
+      << response.body;
+  sendAll(client_fd, out.str());
+}
+
+// Compares the strings in constant time to not leak the token.
+bool secureEquals(const std::string &a, const std::string &b) {
+  if (a.size() != b.size() || b.empty()) {
+    return false;
+  }
+  unsigned char diff = 0;
+  for (size_t i = 0; i < a.size(); ++i) {
+    diff |= a[i] ^ b[i];
+  }
+  return diff == 0;
+}
+
+}  // namespace
+
+ApiServer::ApiServer(Handler handler)
+    : handler_(std::move(handler)), port_(0) {}
+
+ApiServer::~ApiServer() {
+  stop();
+}
+
+bool ApiServer::start(int port, const std::string &token) {
+  std::lock_guard<std::mutex> lock(mutex_);
+  if (isRunning() || token.empty()) {
+    return false;
+  }
+  int fd = socket(AF_INET, SOCK_STREAM, 0);
+  if (fd < 0) {
+    return false;
+  }
+  int reuse = 1;
+  setsockopt(fd, SOL_SOCKET, SO_REUSEADDR, &reuse, sizeof(reuse));
+
+  sockaddr_in address{};
+  address.sin_family = AF_INET;
+  address.sin_port = htons(port);
+  // Accept connections from this Mac only.
+  address.sin_addr.s_addr = htonl(INADDR_LOOPBACK);
+  if (bind(fd, reinterpret_cast<sockaddr *>(&address), sizeof(address)) < 0 ||
+      listen(fd, 16) < 0) {
+    close(fd);
+    return false;
+  }
+  auto state = std::make_shared<State>();
+  state->handler = handler_;
+  state->token = token;
+  state->server_fd = fd;
+  state->running = true;
+  std::atomic_store(&state_, state);
+  port_ = port;
+  thread_ = std::thread(&ApiServer::run, state);
+  events_thread_ = std::thread(&ApiServer::sendEvents, state);
+  return true;
+}
+
+void ApiServer::stop() {
+  std::lock_guard<std::mutex> lock(mutex_);
+  auto state = std::atomic_load(&state_);
+  if (!state || !state->running) {
+    return;
+  }
+  {
+    std::lock_guard<std::mutex> events_lock(state->event_clients_mutex);
+    state->running = false;
+  }
+  state->events_cv.notify_all();
+  if (events_thread_.joinable()) {
+    events_thread_.join();
+  }
+  closeEventClients(*state);
+  int fd = state->server_fd.exchange(-1);
+  if (fd >= 0) {
+    // Unblock accept() in the server thread.
+    shutdown(fd, SHUT_RDWR);
+    close(fd);
+  }
+  std::unique_lock<std::mutex> finished_lock(state->finished_mutex);
+  bool finished = state->finished_cv.wait_for(
+      finished_lock, std::chrono::milliseconds(kStopTimeoutInMs),
+      [&state] { return state->finished; });
+  finished_lock.unlock();
+  if (thread_.joinable()) {
+    if (finished) {
+      thread_.join();
+    } else {
+      // The thread exits after the handler returns. It holds its own
+      // reference to the state, so it's safe to leave it running.
+      thread_.detach();
+    }
+  }
+}
+
+bool ApiServer::isRunning() const {
+  auto state = std::atomic_load(&state_);
+  return state && state->running;
+}
+
+int ApiServer::port() const {
+  return port_;
+}
+
+void ApiServer::run(const std::shared_ptr<State> &state) {
+  int server_fd = state->server_fd;
+  while (state->running) {
+    int client_fd = accept(server_fd, nullptr, nullptr);
+    if (client_fd < 0) {
+      if (!state->running || errno == EBADF || errno == EINVAL) {
+        break;
+      }
+      if (errno != EINTR && errno != ECONNABORTED) {
+        std::this_thread::sleep_for(std::chrono::milliseconds(kAcceptRetryDelayInMs));
+      }
+      continue;
+    }
+#ifdef SO_NOSIGPIPE
+    // Don't crash the app when a client disconnects while we're writing.
+    int no_sigpipe = 1;
+    setsockopt(client_fd, SOL_SOCKET, SO_NOSIGPIPE, &no_sigpipe, sizeof(no_sigpipe));
+#endif
+    timeval timeout{kReceiveTimeoutInSecs, 0};
+    setsockopt(client_fd, SOL_SOCKET, SO_RCVTIMEO, &timeout, sizeof(timeout));
+    if (!handleConnection(*state, client_fd)) {
+      close(client_fd);
+    }
+  }
+  std::lock_guard<std::mutex> lock(state->finished_mutex);
+  state->finished = true;
+  state->finished_cv.notify_all();
+}
+
+void ApiServer::broadcast(const std::string &event, const std::string &data) {
+  auto state = std::atomic_load(&state_);
+  if (!state || !state->running) {
+    return;
+  }
+  {
+    std::lock_guard<std::mutex> lock(state->event_clients_mutex);
+    if (state->event_clients.empty()) {
+      return;
+    }
+    if (state->events.size() >= kMaxQueuedEvents) {
+      state->events.pop_front();
+    }
+    state->events.push_back("event: " + event + "\ndata: " + data + "\n\n");
+  }
+  state->events_cv.notify_one();
+}
+
+void ApiServer::sendEvents(const std::shared_ptr<State> &state) {
+  std::unique_lock<std::mutex> lock(state->event_clients_mutex);
+  while (true) {
+    state->events_cv.wait(lock, [&state] {
+      return !state->running || !state->events.empty();
+    });
+    if (!state->running) {
+      break;
+    }
+    std::string message = std::move(state->events.front());
+    state->events.pop_front();
+    auto clients = state->event_clients;
+    // Don't block broadcast() and new clients while sending.
+    lock.unlock();
+    std::vector<int> failed_clients;
+    for (int client_fd : clients) {
+      if (!sendAll(client_fd, message)) {
+        failed_clients.push_back(client_fd);
+      }
+    }
+    lock.lock();
+    for (int client_fd : failed_clients) {
+      auto it = std::find(state->event_clients.begin(), state->event_clients.end(), client_fd);
+      if (it != state->event_clients.end()) {
+        close(client_fd);
+        state->event_clients.erase(it);
+      }
+    }
+  }
+}
+
+void ApiServer::closeEventClients(State &state) {
+  std::lock_guard<std::mutex> lock(state.event_clients_mutex);
+  for (int client_fd : state.event_clients) {
+    close(client_fd);
+  }
+  state.event_clients.clear();
+  state.events.clear();
+}
+
+bool ApiServer::handleConnection(State &state, int client_fd) {
+  std::string data;
+  char buffer[8192];
+  size_t header_end = std::string::npos;
+  while (header_end == std::string::npos) {
+    auto received = recv(client_fd, buffer, sizeof(buffer), 0);
+    if (received <= 0) {
+      return false;
+    }
+    data.append(buffer, received);
+    header_end = data.find("\r\n\r\n");
+    if (header_end == std::string::npos && data.size() > kMaxHeadersSize) {
+      sendResponse(client_fd, {431, R"({"error":"Request headers are too large"})"});
+      return false;
+    }
+  }
+
+  ApiRequest request;
+  std::istringstream headers(data.substr(0, header_end));
+  std::string line;
+  std::getline(headers, line);
+  std::istringstream request_line(line);
+  std::string target;
+  request_line >> request.method >> target;
+  auto query_start = target.find('?');
+  request.path = target.substr(0, query_start);
+  if (query_start != std::string::npos) {
+    request.query = target.substr(query_start + 1);
+  }
+  while (std::getline(headers, line)) {
+    auto colon = line.find(':');
+    if (colon != std::string::npos) {
+      request.headers[toLower(trim(line.substr(0, colon)))] = trim(line.substr(colon + 1));
+    }
+  }
+
+  // Reject the unauthenticated requests before reading their body.
+  auto authorization = request.headers["authorization"];
+  std::string prefix = "Bearer ";
+  if (authorization.rfind(prefix, 0) != 0 ||
+      !secureEquals(authorization.substr(prefix.size()), state.token)) {
+    sendResponse(client_fd, {401, R"({"error":"Invalid or missing token"})"});
+    return false;
+  }
+
+  size_t content_length = 0;
+  if (request.headers.count("content-length")) {
+    content_length = std::strtoul(request.headers["content-length"].c_str(), nullptr, 10);
+  }
+  if (content_length > kMaxRequestSize) {
+    sendResponse(client_fd, {413, R"({"error":"Request is too large"})"});
+    return false;
+  }
+  request.body = data.substr(header_end + 4);
+  while (request.body.size() < content_length) {
+    auto received = recv(client_fd, buffer, sizeof(buffer), 0);
+    if (received <= 0) {
+      return false;
+    }
+    request.body.append(buffer, received);
+  }
+
+  if (request.method == "GET" && request.path == kEventsPath) {
+    if (!sendAll(client_fd, "HTTP/1.1 200 OK\r\n"
+                            "Content-Type: text/event-stream\r\n"
+                            "Cache-Control: no-cache\r\n"
+                            "Connection: keep-alive\r\n\r\n")) {
+      return false;
+    }
+    // Disconnect the clients that stop reading the events instead of
+    // blocking the other clients.
+    timeval timeout{kSendTimeoutInSecs, 0};
+    setsockopt(client_fd, SOL_SOCKET, SO_SNDTIMEO, &timeout, sizeof(timeout));
+    std::lock_guard<std::mutex> lock(state.event_clients_mutex);
+    state.event_clients.push_back(client_fd);
+    return true;
+  }
+  sendResponse(client_fd, state.handler(request));
+  return false;
+}
@@ -0,0 +1,84 @@
+#ifndef CLIPBOOK_API_SERVER_H_
+#define CLIPBOOK_API_SERVER_H_
+
+#include <atomic>
+#include <condition_variable>
+#include <deque>
+#include <functional>
+#include <map>
+#include <memory>
+#include <mutex>
+#include <string>
+#include <thread>
+#include <vector>
+
+struct ApiRequest {
+  std::string method;
+  std::string path;
+  std::string query;
+  std::string body;
+  std::map<std::string, std::string> headers;
+};
+
+struct ApiResponse {
+  int status = 200;
+  std::string body;
+};
+
+// A minimal HTTP/1.1 server that listens on the loopback interface and
+// passes the authenticated requests to the handler. Every request must
+// contain the "Authorization: Bearer <token>" header. The clients that
+// request the "/events" path are kept connected and receive the broadcast
+// events as Server-Sent Events.
+class ApiServer {
+ public:
+  using Handler = std::function<ApiResponse(const ApiRequest &request)>;
+
+  explicit ApiServer(Handler handler);
+  ~ApiServer();
+
+  bool start(int port, const std::string &token);
+  // Closes the sockets and waits until the server thread exits. The wait is
+  // limited because the thread might be waiting for the handler that runs
+  // JavaScript on the thread that called stop().
+  void stop();
+  bool isRunning() const;
+  int port() const;
+
+  // Queues the event with the given JSON data for all connected event
+  // clients. The events are sent on a separate thread, so a slow client
+  // never blocks the caller.
+  void broadcast(const std::string &event, const std::string &data);
+
+ private:
+  // The state shared with the server thread, so that the thread never
+  // accesses the server object that might be destroyed before it exits.
+  struct State {
+    Handler handler;
+    std::string token;
+    std::atomic<bool> running{false};
+    std::atomic<int> server_fd{-1};
+    std::vector<int> event_clients;
+    std::deque<std::string> events;
+    std::mutex event_clients_mutex;
+    std::condition_variable events_cv;
+    bool finished = false;
+    std::mutex finished_mutex;
+    std::condition_variable finished_cv;
+  };
+
+  static void run(const std::shared_ptr<State> &state);
+  static void sendEvents(const std::shared_ptr<State> &state);
+  // Returns true if the connection must be kept open.
+  static bool handleConnection(State &state, int client_fd);
+  static void closeEventClients(State &state);
+
+  Handler handler_;
+  int port_;
+  std::shared_ptr<State> state_;
+  std::thread thread_;
+  std::thread events_thread_;
+  std::mutex mutex_;
+};
+
+#endif  // CLIPBOOK_API_SERVER_H_
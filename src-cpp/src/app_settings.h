@@ -2,6 +2,7 @@
 #define CLIPBOOK_APP_SETTINGS_H_
 
 #include <memory>
+#include <vector>
 
 #include "molybden.hpp"
 
@@ -21,6 +22,17 @@ class AppSettings {
 
   virtual bool isDeviceManaged() = 0;
 
+  // Saves the preferences from the JSON object whose keys are the names of
+  // the preferences, e.g. "app.theme". Only the known portable preferences
+  // whose values have the expected types are saved. The machine-specific,
+  // secret, network and managed preferences are skipped. Returns the number
+  // of the saved preferences.
+  virtual int applyPreferences(const std::string &json) = 0;
+  // Returns the JSON object with the preferences that can be moved to
+  // another machine. These are the same preferences that applyPreferences()
+  // accepts.
+  virtual std::string exportPreferences() = 0;
+
   virtual void saveLastSystemBootTime(long time) = 0;
   virtual long getLastSystemBootTime() = 0;
 
@@ -132,6 +144,140 @@ class AppSettings {
   virtual bool shouldCopyOnNumberAction() = 0;
   virtual bool isCopyOnNumberActionManaged() = 0;
 
+  virtual void saveBackupIntervalHours(int hours) = 0;
+  virtual int getBackupIntervalHours() = 0;
+
+  virtual void saveMaxBackups(int count) = 0;
+  virtual int getMaxBackups() = 0;
+
+  virtual void saveAnnounceClipboardChanges(bool announce) = 0;
+  virtual bool shouldAnnounceClipboardChanges() = 0;
+  virtual bool isAnnounceClipboardChangesManaged() = 0;
+
+  virtual void saveClearHistoryOnLogout(bool clear) = 0;
+  virtual bool shouldClearHistoryOnLogout() = 0;
+  virtual bool isClearHistoryOnLogoutManaged() = 0;
+
+  virtual void saveAutoClearIntervalHours(int hours) = 0;
+  virtual int getAutoClearIntervalHours() = 0;
+
+  virtual void saveVaultDirectory(std::string dir) = 0;
+  virtual std::string getVaultDirectory() = 0;
+
+  // The folders the user has chosen in the folder dialog, e.g. the sync
+  // folder. The app reads, writes and deletes files only in these folders,
+  // the vault directory, its own data directory and the files the user has
+  // chosen in the file dialog.
+  virtual void addGrantedDirectory(std::string dir) = 0;
+  virtual std::vector<std::string> getGrantedDirectories() = 0;
+
+  virtual void saveApiServerEnabled(bool enabled) = 0;
+  virtual bool shouldApiServerEnabled() = 0;
+  virtual bool isApiServerEnabledManaged() = 0;
+
+  virtual void saveApiServerPort(int port) = 0;
+  virtual int getApiServerPort() = 0;
+
+  // The token saved by the previous versions. The token is stored in the
+  // Keychain now, and an empty token removes the saved one.
+  virtual void saveApiServerToken(std::string token) = 0;
+  virtual std::string getApiServerToken() = 0;
+
+  virtual void saveLanSharingEnabled(bool enabled) = 0;
+  virtual bool shouldLanSharingEnabled() = 0;
+  virtual bool isLanSharingEnabledManaged() = 0;
+
+  // The key saved by the previous versions. The key is stored in the
+  // Keychain now, and an empty key removes the saved one.
+  virtual void saveLanSharingKey(std::string key) = 0;
+  virtual std::string getLanSharingKey() = 0;
+
+  virtual void saveAppLockEnabled(bool enabled) = 0;
+  virtual bool shouldAppLockEnabled() = 0;
+  virtual bool isAppLockEnabledManaged() = 0;
+
+  virtual void saveAutoLockMinutes(int minutes) = 0;
+  virtual int getAutoLockMinutes() = 0;
+
+  virtual void saveSecureDelete(bool enabled) = 0;
+  virtual bool shouldSecureDelete() = 0;
+  virtual bool isSecureDeleteManaged() = 0;
+
+  // The number of days to keep the items of the given type, e.g. "text" or
+  // "image". Zero means the items never expire.
+  virtual void saveRetentionDays(std::string type, int days) = 0;
+  virtual int getRetentionDays(std::string type) = 0;
+  virtual void saveRetentionKeepFavorites(bool keep) = 0;
+  virtual bool shouldRetentionKeepFavorites() = 0;
+
+  virtual void saveMaxHistoryItems(int count) = 0;
+  virtual int getMaxHistoryItems() = 0;
+
+  virtual void saveMaxHistoryMegabytes(int megabytes) = 0;
+  virtual int getMaxHistoryMegabytes() = 0;
+
+  virtual void saveFetchLinkPreviews(bool fetch) = 0;
+  virtual bool shouldFetchLinkPreviews() = 0;
+  virtual bool isFetchLinkPreviewsManaged() = 0;
+
+  virtual void saveUsePasteRecentShortcuts(bool enabled) = 0;
+  virtual bool shouldUsePasteRecentShortcuts() = 0;
+  virtual bool isUsePasteRecentShortcutsManaged() = 0;
+
+  virtual void saveTrashRetentionDays(int days) = 0;
+  virtual int getTrashRetentionDays() = 0;
+
+  virtual void saveDatabaseMaxAttempts(int attempts) = 0;
+  virtual int getDatabaseMaxAttempts() = 0;
+
+  virtual void saveIgnoreOwnChanges(bool ignore) = 0;
+  virtual bool shouldIgnoreOwnChanges() = 0;
+  virtual bool isIgnoreOwnChangesManaged() = 0;
+
+  virtual void saveShowTodayCountInMenuBar(bool show) = 0;
+  virtual bool shouldShowTodayCountInMenuBar() = 0;
+  virtual bool isShowTodayCountInMenuBarManaged() = 0;
+
+  virtual void saveShowCopyNotification(bool show) = 0;
+  virtual bool shouldShowCopyNotification() = 0;
+  virtual bool isShowCopyNotificationManaged() = 0;
+
+  virtual void saveCycleProfileShortcut(std::string shortcut) = 0;
+  virtual std::string getCycleProfileShortcut() = 0;
+
+  virtual void savePauseWhenScreenSharing(bool pause) = 0;
+  virtual bool shouldPauseWhenScreenSharing() = 0;
+  virtual bool isPauseWhenScreenSharingManaged() = 0;
+
+  virtual void saveMaxItemKilobytes(int kilobytes) = 0;
+  virtual int getMaxItemKilobytes() = 0;
+
+  virtual void saveLargeItemPolicy(std::string policy) = 0;
+  virtual std::string getLargeItemPolicy() = 0;
+
+  virtual void saveDoubleTapModifier(std::string modifier) = 0;
+  virtual std::string getDoubleTapModifier() = 0;
+
+  virtual void saveDoubleTapAction(std::string action) = 0;
+  virtual std::string getDoubleTapAction() = 0;
+
+  virtual void saveDeduplicateSimilarImages(bool deduplicate) = 0;
+  virtual bool shouldDeduplicateSimilarImages() = 0;
+  virtual bool isDeduplicateSimilarImagesManaged() = 0;
+
+  virtual void saveCaptureAllowedTypes(std::string types) = 0;
+  virtual std::string getCaptureAllowedTypes() = 0;
+
+  virtual void saveCaptureIgnoredTypes(std::string types) = 0;
+  virtual std::string getCaptureIgnoredTypes() = 0;
+
+  virtual void saveMonitorFindPasteboard(bool monitor) = 0;
+  virtual bool shouldMonitorFindPasteboard() = 0;
+  virtual bool isMonitorFindPasteboardManaged() = 0;
+
+  virtual void saveLogLevel(std::string level) = 0;
+  virtual std::string getLogLevel() = 0;
+
   virtual bool allowCheckForUpdates() = 0;
 
   // Shortcuts.
@@ -10,6 +10,8 @@ class AppSettingsMac : public AppSettings {
   AppSettingsMac();
 
   bool isDeviceManaged() override;
+  int applyPreferences(const std::string &json) override;
+  std::string exportPreferences() override;
 
   void saveLastSystemBootTime(long time) override;
   long getLastSystemBootTime() override;
@@ -122,6 +124,130 @@ class AppSettingsMac : public AppSettings {
   bool shouldCopyOnNumberAction() override;
   bool isCopyOnNumberActionManaged() override;
 
+  void saveBackupIntervalHours(int hours) override;
+  int getBackupIntervalHours() override;
+
+  void saveMaxBackups(int count) override;
+  int getMaxBackups() override;
+
+  void saveAnnounceClipboardChanges(bool announce) override;
+  bool shouldAnnounceClipboardChanges() override;
+  bool isAnnounceClipboardChangesManaged() override;
+
+  void saveClearHistoryOnLogout(bool clear) override;
+  bool shouldClearHistoryOnLogout() override;
+  bool isClearHistoryOnLogoutManaged() override;
+
+  void saveAutoClearIntervalHours(int hours) override;
+  int getAutoClearIntervalHours() override;
+
+  void saveVaultDirectory(std::string dir) override;
+  std::string getVaultDirectory() override;
+
+  void addGrantedDirectory(std::string dir) override;
+  std::vector<std::string> getGrantedDirectories() override;
+
+  void saveApiServerEnabled(bool enabled) override;
+  bool shouldApiServerEnabled() override;
+  bool isApiServerEnabledManaged() override;
+
+  void saveApiServerPort(int port) override;
+  int getApiServerPort() override;
+
+  void saveApiServerToken(std::string token) override;
+  std::string getApiServerToken() override;
+
+  void saveLanSharingEnabled(bool enabled) override;
+  bool shouldLanSharingEnabled() override;
+  bool isLanSharingEnabledManaged() override;
+
+  void saveLanSharingKey(std::string key) override;
+  std::string getLanSharingKey() override;
+
+  void saveAppLockEnabled(bool enabled) override;
+  bool shouldAppLockEnabled() override;
+  bool isAppLockEnabledManaged() override;
+
+  void saveAutoLockMinutes(int minutes) override;
+  int getAutoLockMinutes() override;
+
+  void saveSecureDelete(bool enabled) override;
+  bool shouldSecureDelete() override;
+  bool isSecureDeleteManaged() override;
+
+  void saveRetentionDays(std::string type, int days) override;
+  int getRetentionDays(std::string type) override;
+  void saveRetentionKeepFavorites(bool keep) override;
+  bool shouldRetentionKeepFavorites() override;
+
+  void saveMaxHistoryItems(int count) override;
+  int getMaxHistoryItems() override;
+
+  void saveMaxHistoryMegabytes(int megabytes) override;
+  int getMaxHistoryMegabytes() override;
+
+  void saveFetchLinkPreviews(bool fetch) override;
+  bool shouldFetchLinkPreviews() override;
+  bool isFetchLinkPreviewsManaged() override;
+
+  void saveUsePasteRecentShortcuts(bool enabled) override;
+  bool shouldUsePasteRecentShortcuts() override;
+  bool isUsePasteRecentShortcutsManaged() override;
+
+  void saveTrashRetentionDays(int days) override;
+  int getTrashRetentionDays() override;
+
+  void saveDatabaseMaxAttempts(int attempts) override;
+  int getDatabaseMaxAttempts() override;
+
+  void saveIgnoreOwnChanges(bool ignore) override;
+  bool shouldIgnoreOwnChanges() override;
+  bool isIgnoreOwnChangesManaged() override;
+
+  void saveShowTodayCountInMenuBar(bool show) override;
+  bool shouldShowTodayCountInMenuBar() override;
+  bool isShowTodayCountInMenuBarManaged() override;
+
+  void saveShowCopyNotification(bool show) override;
+  bool shouldShowCopyNotification() override;
+  bool isShowCopyNotificationManaged() override;
+
+  void saveCycleProfileShortcut(std::string shortcut) override;
+  std::string getCycleProfileShortcut() override;
+
+  void savePauseWhenScreenSharing(bool pause) override;
+  bool shouldPauseWhenScreenSharing() override;
+  bool isPauseWhenScreenSharingManaged() override;
+
+  void saveMaxItemKilobytes(int kilobytes) override;
+  int getMaxItemKilobytes() override;
+
+  void saveLargeItemPolicy(std::string policy) override;
+  std::string getLargeItemPolicy() override;
+
+  void saveDoubleTapModifier(std::string modifier) override;
+  std::string getDoubleTapModifier() override;
+
+  void saveDoubleTapAction(std::string action) override;
+  std::string getDoubleTapAction() override;
+
+  void saveDeduplicateSimilarImages(bool deduplicate) override;
+  bool shouldDeduplicateSimilarImages() override;
+  bool isDeduplicateSimilarImagesManaged() override;
+
+  void saveCaptureAllowedTypes(std::string types) override;
+  std::string getCaptureAllowedTypes() override;
+
+  void saveCaptureIgnoredTypes(std::string types) override;
+  std::string getCaptureIgnoredTypes() override;
+
+  void saveMonitorFindPasteboard(bool monitor) override;
+  bool shouldMonitorFindPasteboard() override;
+  bool isMonitorFindPasteboardManaged() override;
+
+  void saveLogLevel(std::string level) override;
+  std::string getLogLevel() override;
+
   bool allowCheckForUpdates() override;
 
   // Shortcuts.
@@ -40,9 +40,45 @@ NSString *prefPlaySoundOnCopy = @"play_sound_on_copy";
 NSString *prefAlwaysDisplay = @"always_display";
 NSString *prefCopyOnDoubleClick = @"copy_on_double_click";
 NSString *prefCopyOnNumberAction = @"copy_on_number_action";
+NSString *prefBackupIntervalHours = @"backup.interval_hours";
+NSString *prefMaxBackups = @"backup.max_backups";
+NSString *prefAnnounceClipboardChanges = @"accessibility.announce_clipboard_changes";
+NSString *prefClearHistoryOnLogout = @"clear_history_on_logout";
+NSString *prefAutoClearIntervalHours = @"auto_clear.interval_hours";
+NSString *prefVaultDirectory = @"vault.directory";
+NSString *prefApiServerEnabled = @"api.enabled";
+NSString *prefApiServerPort = @"api.port";
+NSString *prefApiServerToken = @"api.token";
+NSString *prefLanSharingEnabled = @"lan.enabled";
+NSString *prefLanSharingKey = @"lan.key";
+NSString *prefAppLockEnabled = @"app_lock.enabled";
+NSString *prefAutoLockMinutes = @"app_lock.idle_minutes";
+NSString *prefSecureDelete = @"history.secure_delete";
+NSString *prefRetentionKeepFavorites = @"history.retention.keep_favorites";
+NSString *prefMaxHistoryItems = @"history.max_items";
+NSString *prefMaxHistoryMegabytes = @"history.max_megabytes";
+NSString *prefFetchLinkPreviews = @"link_preview.fetch";
+NSString *prefUsePasteRecentShortcuts = @"paste_recent_shortcuts";
+NSString *prefTrashRetentionDays = @"history.trash_retention_days";
+NSString *prefDatabaseMaxAttempts = @"database.max_attempts";
+NSString *prefIgnoreOwnChanges = @"ignore_own_changes";
+NSString *prefShowTodayCountInMenuBar = @"app.show_today_count_in_menu_bar";
+NSString *prefShowCopyNotification = @"notifications.show_on_copy";
+NSString *prefCycleProfileShortcut = @"app.cycle_profile_shortcut";
+NSString *prefPauseWhenScreenSharing = @"app.pause_when_screen_sharing";
+NSString *prefMaxItemKilobytes = @"app.max_item_kilobytes";
+NSString *prefLargeItemPolicy = @"app.large_item_policy";
+NSString *prefDoubleTapModifier = @"app.double_tap_modifier";
+NSString *prefDoubleTapAction = @"app.double_tap_action";
+NSString *prefDeduplicateSimilarImages = @"history.deduplicate_similar_images";
+NSString *prefCaptureAllowedTypes = @"privacy.capture_allowed_types";
+NSString *prefCaptureIgnoredTypes = @"privacy.capture_ignored_types";
+NSString *prefMonitorFindPasteboard = @"history.monitor_find_pasteboard";
+NSString *prefLogLevel = @"app.log_level";
 
 NSString *prefLastSystemBootTime = @"last_system_boot_time";
 NSString *prefLicenseKey = @"license_key";
+NSString *prefGrantedDirectories = @"granted_directories";
 
 // Shortcuts.
 NSString *prefOpenAppShortcut = @"app.open_app_shortcut2";
@@ -1288,6 +1324,579 @@ std::string AppSettingsMac::getToggleFilterShortcut() {
   return "MetaLeft + KeyF";
 }
 
+void AppSettingsMac::saveBackupIntervalHours(int hours) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSNumber numberWithInt:hours] forKey:prefBackupIntervalHours];
+  [defaults synchronize];
+}
+
+int AppSettingsMac::getBackupIntervalHours() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSNumber *value = [defaults objectForKey:prefBackupIntervalHours];
+  if (value != nil) {
+    return [value intValue];
+  }
+  return 0;
+}
+
+void AppSettingsMac::saveMaxBackups(int count) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSNumber numberWithInt:count] forKey:prefMaxBackups];
+  [defaults synchronize];
+}
+
+int AppSettingsMac::getMaxBackups() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSNumber *value = [defaults objectForKey:prefMaxBackups];
+  if (value != nil) {
+    return [value intValue];
+  }
+  return 10;
+}
+
+void AppSettingsMac::saveAnnounceClipboardChanges(bool announce) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:announce forKey:prefAnnounceClipboardChanges];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldAnnounceClipboardChanges() {
+  return prefReadBoolValue(prefAnnounceClipboardChanges, false);
+}
+
+bool AppSettingsMac::isAnnounceClipboardChangesManaged() {
+  return isManaged(prefAnnounceClipboardChanges);
+}
+
+void AppSettingsMac::saveClearHistoryOnLogout(bool clear) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:clear forKey:prefClearHistoryOnLogout];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldClearHistoryOnLogout() {
+  return prefReadBoolValue(prefClearHistoryOnLogout, false);
+}
+
+bool AppSettingsMac::isClearHistoryOnLogoutManaged() {
+  return isManaged(prefClearHistoryOnLogout);
+}
+
+void AppSettingsMac::saveAutoClearIntervalHours(int hours) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSNumber numberWithInt:hours] forKey:prefAutoClearIntervalHours];
+  [defaults synchronize];
+}
+
+int AppSettingsMac::getAutoClearIntervalHours() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSNumber *value = [defaults objectForKey:prefAutoClearIntervalHours];
+  if (value != nil) {
+    return [value intValue];
+  }
+  return 0;
+}
+
+void AppSettingsMac::saveVaultDirectory(std::string dir) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSString stringWithUTF8String:dir.c_str()] forKey:prefVaultDirectory];
+  [defaults synchronize];
+}
+
+std::string AppSettingsMac::getVaultDirectory() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSString *value = [defaults objectForKey:prefVaultDirectory];
+  if (value != nil) {
+    return {[value UTF8String]};
+  }
+  return "";
+}
+
+void AppSettingsMac::addGrantedDirectory(std::string dir) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSArray *dirs = [defaults arrayForKey:prefGrantedDirectories];
+  NSString *value = [NSString stringWithUTF8String:dir.c_str()];
+  if (dirs != nil && [dirs containsObject:value]) {
+    return;
+  }
+  NSMutableArray *newDirs = dirs != nil ? [NSMutableArray arrayWithArray:dirs] : [NSMutableArray array];
+  [newDirs addObject:value];
+  [defaults setObject:newDirs forKey:prefGrantedDirectories];
+  [defaults synchronize];
+}
+
+std::vector<std::string> AppSettingsMac::getGrantedDirectories() {
+  std::vector<std::string> result;
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  for (id dir in [defaults arrayForKey:prefGrantedDirectories]) {
+    if ([dir isKindOfClass:[NSString class]]) {
+      result.emplace_back([dir UTF8String]);
+    }
+  }
+  return result;
+}
+
+void AppSettingsMac::saveApiServerEnabled(bool enabled) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:enabled forKey:prefApiServerEnabled];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldApiServerEnabled() {
+  return prefReadBoolValue(prefApiServerEnabled, false);
+}
+
+bool AppSettingsMac::isApiServerEnabledManaged() {
+  return isManaged(prefApiServerEnabled);
+}
+
+void AppSettingsMac::saveApiServerPort(int port) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSNumber numberWithInt:port] forKey:prefApiServerPort];
+  [defaults synchronize];
+}
+
+int AppSettingsMac::getApiServerPort() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSNumber *value = [defaults objectForKey:prefApiServerPort];
+  if (value != nil) {
+    return [value intValue];
+  }
+  return 8765;
+}
+
+void AppSettingsMac::saveApiServerToken(std::string token) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  if (token.empty()) {
+    [defaults removeObjectForKey:prefApiServerToken];
+  } else {
+    [defaults setObject:[NSString stringWithUTF8String:token.c_str()] forKey:prefApiServerToken];
+  }
+  [defaults synchronize];
+}
+
+std::string AppSettingsMac::getApiServerToken() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSString *value = [defaults objectForKey:prefApiServerToken];
+  if (value != nil) {
+    return {[value UTF8String]};
+  }
+  return "";
+}
+
+void AppSettingsMac::saveLanSharingEnabled(bool enabled) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:enabled forKey:prefLanSharingEnabled];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldLanSharingEnabled() {
+  return prefReadBoolValue(prefLanSharingEnabled, false);
+}
+
+bool AppSettingsMac::isLanSharingEnabledManaged() {
+  return isManaged(prefLanSharingEnabled);
+}
+
+void AppSettingsMac::saveLanSharingKey(std::string key) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  if (key.empty()) {
+    [defaults removeObjectForKey:prefLanSharingKey];
+  } else {
+    [defaults setObject:[NSString stringWithUTF8String:key.c_str()] forKey:prefLanSharingKey];
+  }
+  [defaults synchronize];
+}
+
+std::string AppSettingsMac::getLanSharingKey() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSString *value = [defaults objectForKey:prefLanSharingKey];
+  if (value != nil) {
+    return {[value UTF8String]};
+  }
+  return "";
+}
+
+void AppSettingsMac::saveAppLockEnabled(bool enabled) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:enabled forKey:prefAppLockEnabled];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldAppLockEnabled() {
+  return prefReadBoolValue(prefAppLockEnabled, false);
+}
+
+bool AppSettingsMac::isAppLockEnabledManaged() {
+  return isManaged(prefAppLockEnabled);
+}
+
+void AppSettingsMac::saveAutoLockMinutes(int minutes) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSNumber numberWithInt:minutes] forKey:prefAutoLockMinutes];
+  [defaults synchronize];
+}
+
+int AppSettingsMac::getAutoLockMinutes() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSNumber *value = [defaults objectForKey:prefAutoLockMinutes];
+  if (value != nil) {
+    return [value intValue];
+  }
+  return 5;
+}
+
+void AppSettingsMac::saveSecureDelete(bool enabled) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:enabled forKey:prefSecureDelete];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldSecureDelete() {
+  return prefReadBoolValue(prefSecureDelete, false);
+}
+
+bool AppSettingsMac::isSecureDeleteManaged() {
+  return isManaged(prefSecureDelete);
+}
+
+// Returns the preference that keeps the retention days of the item type,
+// e.g. "history.retention.text_days", or nil if the type is unknown.
+static NSString *getRetentionDaysPref(const std::string &type) {
+  static NSSet<NSString *> *types = [[NSSet alloc] initWithArray:@[
+      @"text", @"link", @"email", @"color", @"image", @"file"]];
+  NSString *name = [NSString stringWithUTF8String:type.c_str()];
+  if (![types containsObject:name]) {
+    return nil;
+  }
+  return [NSString stringWithFormat:@"history.retention.%@_days", name];
+}
+
+void AppSettingsMac::saveRetentionDays(std::string type, int days) {
+  NSString *pref = getRetentionDaysPref(type);
+  if (pref == nil) {
+    return;
+  }
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSNumber numberWithInt:days] forKey:pref];
+  [defaults synchronize];
+}
+
+int AppSettingsMac::getRetentionDays(std::string type) {
+  NSString *pref = getRetentionDaysPref(type);
+  if (pref == nil) {
+    return 0;
+  }
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSNumber *value = [defaults objectForKey:pref];
+  if (value != nil) {
+    return [value intValue];
+  }
+  return 0;
+}
+
+void AppSettingsMac::saveRetentionKeepFavorites(bool keep) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:keep forKey:prefRetentionKeepFavorites];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldRetentionKeepFavorites() {
+  return prefReadBoolValue(prefRetentionKeepFavorites, true);
+}
+
+void AppSettingsMac::saveMaxHistoryItems(int count) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSNumber numberWithInt:count] forKey:prefMaxHistoryItems];
+  [defaults synchronize];
+}
+
+int AppSettingsMac::getMaxHistoryItems() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSNumber *value = [defaults objectForKey:prefMaxHistoryItems];
+  if (value != nil) {
+    return [value intValue];
+  }
+  return 0;
+}
+
+void AppSettingsMac::saveMaxHistoryMegabytes(int megabytes) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSNumber numberWithInt:megabytes] forKey:prefMaxHistoryMegabytes];
+  [defaults synchronize];
+}
+
+int AppSettingsMac::getMaxHistoryMegabytes() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSNumber *value = [defaults objectForKey:prefMaxHistoryMegabytes];
+  if (value != nil) {
+    return [value intValue];
+  }
+  return 0;
+}
+
+void AppSettingsMac::saveFetchLinkPreviews(bool fetch) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:fetch forKey:prefFetchLinkPreviews];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldFetchLinkPreviews() {
+  return prefReadBoolValue(prefFetchLinkPreviews, true);
+}
+
+bool AppSettingsMac::isFetchLinkPreviewsManaged() {
+  return isManaged(prefFetchLinkPreviews);
+}
+
+void AppSettingsMac::saveUsePasteRecentShortcuts(bool enabled) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:enabled forKey:prefUsePasteRecentShortcuts];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldUsePasteRecentShortcuts() {
+  return prefReadBoolValue(prefUsePasteRecentShortcuts, false);
+}
+
+bool AppSettingsMac::isUsePasteRecentShortcutsManaged() {
+  return isManaged(prefUsePasteRecentShortcuts);
+}
+
+void AppSettingsMac::saveTrashRetentionDays(int days) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSNumber numberWithInt:days] forKey:prefTrashRetentionDays];
+  [defaults synchronize];
+}
+
+int AppSettingsMac::getTrashRetentionDays() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSNumber *value = [defaults objectForKey:prefTrashRetentionDays];
+  if (value != nil) {
+    return [value intValue];
+  }
+  return 30;
+}
+
+void AppSettingsMac::saveDatabaseMaxAttempts(int attempts) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSNumber numberWithInt:attempts] forKey:prefDatabaseMaxAttempts];
+  [defaults synchronize];
+}
+
+int AppSettingsMac::getDatabaseMaxAttempts() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSNumber *value = [defaults objectForKey:prefDatabaseMaxAttempts];
+  if (value != nil) {
+    return [value intValue];
+  }
+  return 3;
+}
+
+void AppSettingsMac::saveIgnoreOwnChanges(bool ignore) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:ignore forKey:prefIgnoreOwnChanges];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldIgnoreOwnChanges() {
+  return prefReadBoolValue(prefIgnoreOwnChanges, false);
+}
+
+bool AppSettingsMac::isIgnoreOwnChangesManaged() {
+  return isManaged(prefIgnoreOwnChanges);
+}
+
+void AppSettingsMac::saveShowTodayCountInMenuBar(bool show) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:show forKey:prefShowTodayCountInMenuBar];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldShowTodayCountInMenuBar() {
+  return prefReadBoolValue(prefShowTodayCountInMenuBar, false);
+}
+
+bool AppSettingsMac::isShowTodayCountInMenuBarManaged() {
+  return isManaged(prefShowTodayCountInMenuBar);
+}
+
+void AppSettingsMac::saveShowCopyNotification(bool show) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:show forKey:prefShowCopyNotification];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldShowCopyNotification() {
+  return prefReadBoolValue(prefShowCopyNotification, false);
+}
+
+bool AppSettingsMac::isShowCopyNotificationManaged() {
+  return isManaged(prefShowCopyNotification);
+}
+
+void AppSettingsMac::saveCycleProfileShortcut(std::string shortcut) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSString stringWithUTF8String:shortcut.c_str()] forKey:prefCycleProfileShortcut];
+  [defaults synchronize];
+}
+
+std::string AppSettingsMac::getCycleProfileShortcut() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSString *value = [defaults objectForKey:prefCycleProfileShortcut];
+  if (value != nil) {
+    return {[value UTF8String]};
+  }
+  return "";
+}
+
+void AppSettingsMac::savePauseWhenScreenSharing(bool pause) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:pause forKey:prefPauseWhenScreenSharing];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldPauseWhenScreenSharing() {
+  return prefReadBoolValue(prefPauseWhenScreenSharing, false);
+}
+
+bool AppSettingsMac::isPauseWhenScreenSharingManaged() {
+  return isManaged(prefPauseWhenScreenSharing);
+}
+
+void AppSettingsMac::saveMaxItemKilobytes(int kilobytes) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSNumber numberWithInt:kilobytes] forKey:prefMaxItemKilobytes];
+  [defaults synchronize];
+}
+
+int AppSettingsMac::getMaxItemKilobytes() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSNumber *value = [defaults objectForKey:prefMaxItemKilobytes];
+  if (value != nil) {
+    return [value intValue];
+  }
+  return 1024;
+}
+
+void AppSettingsMac::saveLargeItemPolicy(std::string policy) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSString stringWithUTF8String:policy.c_str()] forKey:prefLargeItemPolicy];
+  [defaults synchronize];
+}
+
+std::string AppSettingsMac::getLargeItemPolicy() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSString *value = [defaults objectForKey:prefLargeItemPolicy];
+  if (value != nil) {
+    return {[value UTF8String]};
+  }
+  return "truncate";
+}
+
+void AppSettingsMac::saveDoubleTapModifier(std::string modifier) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSString stringWithUTF8String:modifier.c_str()] forKey:prefDoubleTapModifier];
+  [defaults synchronize];
+}
+
+std::string AppSettingsMac::getDoubleTapModifier() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSString *value = [defaults objectForKey:prefDoubleTapModifier];
+  if (value != nil) {
+    return {[value UTF8String]};
+  }
+  return "";
+}
+
+void AppSettingsMac::saveDoubleTapAction(std::string action) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSString stringWithUTF8String:action.c_str()] forKey:prefDoubleTapAction];
+  [defaults synchronize];
+}
+
+std::string AppSettingsMac::getDoubleTapAction() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSString *value = [defaults objectForKey:prefDoubleTapAction];
+  if (value != nil) {
+    return {[value UTF8String]};
+  }
+  return "openApp";
+}
+
+void AppSettingsMac::saveDeduplicateSimilarImages(bool deduplicate) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:deduplicate forKey:prefDeduplicateSimilarImages];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldDeduplicateSimilarImages() {
+  return prefReadBoolValue(prefDeduplicateSimilarImages, false);
+}
+
+bool AppSettingsMac::isDeduplicateSimilarImagesManaged() {
+  return isManaged(prefDeduplicateSimilarImages);
+}
+
+void AppSettingsMac::saveCaptureAllowedTypes(std::string types) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSString stringWithUTF8String:types.c_str()] forKey:prefCaptureAllowedTypes];
+  [defaults synchronize];
+}
+
+std::string AppSettingsMac::getCaptureAllowedTypes() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSString *value = [defaults objectForKey:prefCaptureAllowedTypes];
+  if (value != nil) {
+    return {[value UTF8String]};
+  }
+  return "";
+}
+
+void AppSettingsMac::saveCaptureIgnoredTypes(std::string types) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSString stringWithUTF8String:types.c_str()] forKey:prefCaptureIgnoredTypes];
+  [defaults synchronize];
+}
+
+std::string AppSettingsMac::getCaptureIgnoredTypes() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSString *value = [defaults objectForKey:prefCaptureIgnoredTypes];
+  if (value != nil) {
+    return {[value UTF8String]};
+  }
+  return "de.petermaurer.TransientPasteboardType,com.typeit4me.clipping,Pasteboard generator type,com.agilebits.onepassword,net.antelle.keeweb";
+}
+
+void AppSettingsMac::saveMonitorFindPasteboard(bool monitor) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setBool:monitor forKey:prefMonitorFindPasteboard];
+  [defaults synchronize];
+}
+
+bool AppSettingsMac::shouldMonitorFindPasteboard() {
+  return prefReadBoolValue(prefMonitorFindPasteboard, false);
+}
+
+bool AppSettingsMac::isMonitorFindPasteboardManaged() {
+  return isManaged(prefMonitorFindPasteboard);
+}
+
+void AppSettingsMac::saveLogLevel(std::string level) {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  [defaults setObject:[NSString stringWithUTF8String:level.c_str()] forKey:prefLogLevel];
+  [defaults synchronize];
+}
+
+std::string AppSettingsMac::getLogLevel() {
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  NSString *value = [defaults objectForKey:prefLogLevel];
+  if (value != nil) {
+    return {[value UTF8String]};
+  }
+  return "info";
+}
+
 bool AppSettingsMac::allowCheckForUpdates() {
   return prefReadBoolValue(prefAllowCheckForUpdates, true);
 }
@@ -1295,3 +1904,195 @@ bool AppSettingsMac::allowCheckForUpdates() {
 bool AppSettingsMac::isDeviceManaged() {
   return ::isDeviceManaged();
 }
+
+enum class PrefType {
+  Bool,
+  Int,
+  String,
+};
+
+// The preferences that can be moved to another machine and changed from
+// outside the app, e.g. from the config file or the settings bundle, with
+// the types of their values. The preferences that describe this machine, contain the secrets
+// or make the app reachable from the network aren't listed, so they can be
+// changed only in the settings.
+static NSDictionary<NSString *, NSNumber *> *createPortablePrefTypes() {
+  NSMutableDictionary<NSString *, NSNumber *> *result = [NSMutableDictionary dictionary];
+  for (NSString *key in @[
+      prefIgnoreConfidentialContent,
+      prefIgnoreTransientContent,
+      prefOpenAtLogin,
+      prefCheckForUpdatesAutomatically,
+      prefWarnOnClearHistory,
+      prefKeepFavoritesOnClearHistory,
+      prefShowIconInMenuBar,
+      prefCopyAndMergeEnabled,
+      prefCopyToClipboardAfterMerge,
+      prefClearHistoryOnQuit,
+      prefClearHistoryOnMacReboot,
+      prefTreatDigitNumbersAsColor,
+      prefShowPreviewForLinks,
+      prefUpdateHistoryAfterAction,
+      prefPasteOnClick,
+      prefPlaySoundOnCopy,
+      prefAlwaysDisplay,
+      prefCopyOnDoubleClick,
+      prefCopyOnNumberAction,
+      prefAnnounceClipboardChanges,
+      prefClearHistoryOnLogout,
+      prefSecureDelete,
+      prefRetentionKeepFavorites,
+      prefUsePasteRecentShortcuts,
+      prefIgnoreOwnChanges,
+      prefShowTodayCountInMenuBar,
+      prefShowCopyNotification,
+      prefPauseWhenScreenSharing,
+      prefDeduplicateSimilarImages,
+      prefMonitorFindPasteboard,
+  ]) {
+    result[key] = @(static_cast<int>(PrefType::Bool));
+  }
+  for (NSString *key in @[
+      prefBackupIntervalHours,
+      prefMaxBackups,
+      prefAutoClearIntervalHours,
+      prefAutoLockMinutes,
+      prefMaxHistoryItems,
+      prefMaxHistoryMegabytes,
+      prefTrashRetentionDays,
+      prefDatabaseMaxAttempts,
+      prefMaxItemKilobytes,
+  ]) {
+    result[key] = @(static_cast<int>(PrefType::Int));
+  }
+  for (NSString *type in @[@"text", @"link", @"email", @"color", @"image", @"file"]) {
+    result[[NSString stringWithFormat:@"history.retention.%@_days", type]] = @(static_cast<int>(PrefType::Int));
+  }
+  for (NSString *key in @[
+      prefAppLanguage,
+      prefAppTheme,
+      prefIgnoreApps,
+      prefCopyAndMergeSeparator,
+      prefOpenWindowStrategy,
+      prefCycleProfileShortcut,
+      prefLargeItemPolicy,
+      prefDoubleTapModifier,
+      prefDoubleTapAction,
+      prefCaptureAllowedTypes,
+      prefCaptureIgnoredTypes,
+      prefLogLevel,
+      prefOpenAppShortcut,
+      prefCloseAppShortcut,
+      prefCloseAppShortcut2,
+      prefCloseAppShortcut3,
+      prefSelectNextItemShortcut,
+      prefSelectPreviousItemShortcut,
+      prefPasteSelectedItemToActiveAppShortcut,
+      prefPasteSelectedObjectToActiveAppShortcut,
+      prefEditHistoryItemShortcut,
+      prefOpenInBrowserShortcut,
+      prefShowInFinderShortcut,
+      prefQuickLookShortcut,
+      prefOpenInDefaultAppShortcut,
+      prefCopyToClipboardShortcut,
+      prefCopyObjectToClipboardShortcut,
+      prefCopyImageFromTextShortcut,
+      prefDeleteHistoryItemShortcut,
+      prefClearHistoryShortcut,
+      prefSearchHistoryShortcut,
+      prefTogglePreviewShortcut,
+      prefShowMoreActionsShortcut,
+      prefZoomUIInShortcut,
+      prefZoomUIOutShortcut,
+      prefZoomUIResetShortcut,
+      prefOpenSettingsShortcut,
+      prefToggleFavoriteShortcut,
+      prefNavigateToFirstItemShortcut,
+      prefNavigateToLastItemShortcut,
+      prefNavigateToNextGroupOfItemsShortcut,
+      prefNavigateToPrevGroupOfItemsShortcut,
+      prefSaveImageAsFileShortcut,
+      prefPauseResumeShortcut,
+      prefRenameItemShortcut,
+      prefMakeLowerCaseShortcut,
+      prefMakeUpperCaseShortcut,
+      prefCapitalizeShortcut,
+      prefSentenceCaseShortcut,
+      prefRemoveEmptyLinesShortcut,
+      prefStripAllWhitespacesShortcut,
+      prefTrimSurroundingWhitespacesShortcut,
+      prefToggleFilterShortcut,
+      prefPasteNextItemShortcut,
+  ]) {
+    result[key] = @(static_cast<int>(PrefType::String));
+  }
+  return [result copy];
+}
+
+static NSDictionary<NSString *, NSNumber *> *getPortablePrefTypes() {
+  static NSDictionary<NSString *, NSNumber *> *types = createPortablePrefTypes();
+  return types;
+}
+
+// Returns the value converted to the type of the preference, or nil if the
+// value has a different type.
+static id getPortablePrefValue(NSString *key, id value) {
+  NSNumber *type = getPortablePrefTypes()[key];
+  if (type == nil) {
+    return nil;
+  }
+  bool isBool = [value isKindOfClass:[NSNumber class]] &&
+      CFGetTypeID((__bridge CFTypeRef) value) == CFBooleanGetTypeID();
+  switch (static_cast<PrefType>([type intValue])) {
+    case PrefType::Bool:
+      return isBool ? value : nil;
+    case PrefType::Int:
+      if (isBool || ![value isKindOfClass:[NSNumber class]]) {
+        return nil;
+      }
+      return [NSNumber numberWithInt:[value intValue]];
+    case PrefType::String:
+      return [value isKindOfClass:[NSString class]] ? value : nil;
+  }
+  return nil;
+}
+
+std::string AppSettingsMac::exportPreferences() {
+  NSString *domain = [[NSBundle mainBundle] bundleIdentifier];
+  NSDictionary *values = [[NSUserDefaults standardUserDefaults] persistentDomainForName:domain];
+  NSMutableDictionary *prefs = [NSMutableDictionary dictionary];
+  for (NSString *key in values) {
+    id value = getPortablePrefValue(key, values[key]);
+    if (value != nil) {
+      prefs[key] = value;
+    }
+  }
+  NSData *data = [NSJSONSerialization dataWithJSONObject:prefs options:NSJSONWritingSortedKeys error:nil];
+  if (data == nil) {
+    return "{}";
+  }
+  return {static_cast<const char *>(data.bytes), data.length};
+}
+
+int AppSettingsMac::applyPreferences(const std::string &json) {
+  NSData *data = [NSData dataWithBytes:json.data() length:json.size()];
+  id prefs = [NSJSONSerialization JSONObjectWithData:data options:0 error:nil];
+  if (![prefs isKindOfClass:[NSDictionary class]]) {
+    return 0;
+  }
+  NSUserDefaults *defaults = [NSUserDefaults standardUserDefaults];
+  int count = 0;
+  for (NSString *key in prefs) {
+    if (![key isKindOfClass:[NSString class]] || isManaged(key)) {
+      continue;
+    }
+    id value = getPortablePrefValue(key, prefs[key]);
+    if (value == nil) {
+      continue;
+    }
+    [defaults setObject:value forKey:key];
+    count++;
+  }
+  [defaults synchronize];
+  return count;
+}
@@ -0,0 +1,129 @@
+#include "capture_journal.h"
+
+#include <cstdio>
+#include <fstream>
+#include <utility>
+
+// The number of the launches a capture is replayed on before it's dropped,
+// e.g. because it makes the app crash.
+const int kMaxReplayAttempts = 3;
+
+CaptureJournal::CaptureJournal(std::string file_path) : file_path_(std::move(file_path)) {
+  load();
+  for (auto it = recovered_.begin(); it != recovered_.end();) {
+    if (++attempts_[it->first] > kMaxReplayAttempts) {
+      attempts_.erase(it->first);
+      it = recovered_.erase(it);
+    } else {
+      ++it;
+    }
+  }
+  rewrite();
+}
+
+void CaptureJournal::load() {
+  std::ifstream file(file_path_);
+  if (!file.is_open()) {
+    return;
+  }
+  std::string line;
+  while (std::getline(file, line)) {
+    if (line.size() < 3 || line[1] != ' ') {
+      continue;
+    }
+    auto separator = line.find(' ', 2);
+    long long id;
+    try {
+      id = std::stoll(line.substr(2, separator == std::string::npos ? std::string::npos : separator - 2));
+    } catch (...) {
+      continue;
+    }
+    // The last line may have been cut off by a crash.
+    if (line[0] == 'E' && separator != std::string::npos && line.back() == '}') {
+      recovered_[id] = line.substr(separator + 1);
+    } else if (line[0] == 'A') {
+      recovered_.erase(id);
+      attempts_.erase(id);
+    } else if (line[0] == 'R' && separator != std::string::npos) {
+      try {
+        attempts_[id] = std::stoi(line.substr(separator + 1));
+      } catch (...) {
+        continue;
+      }
+    }
+    if (id >= next_id_) {
+      next_id_ = id + 1;
+    }
+  }
+}
+
+void CaptureJournal::rewrite() {
+  // Write to a temporary file and replace the journal, so that the captures
+  // aren't lost if the app crashes meanwhile.
+  std::string temp_path = file_path_ + ".tmp";
+  {
+    std::ofstream file(temp_path, std::ios::trunc);
+    for (const auto &[id, data] : recovered_) {
+      file << "E " << id << " " << data << '\n';
+      file << "R " << id << " " << attempts_[id] << '\n';
+    }
+    for (const auto &[id, data] : pending_) {
+      file << "E " << id << " " << data << '\n';
+    }
+    file.flush();
+    if (file.fail()) {
+      std::remove(temp_path.c_str());
+      return;
+    }
+  }
+  std::rename(temp_path.c_str(), file_path_.c_str());
+}
+
+void CaptureJournal::compact() {
+  std::lock_guard<std::mutex> guard(mutex_);
+  rewrite();
+}
+
+void CaptureJournal::clear() {
+  std::lock_guard<std::mutex> guard(mutex_);
+  recovered_.clear();
+  attempts_.clear();
+  rewrite();
+}
+
+void CaptureJournal::writeLine(const std::string &line) {
+  std::ofstream file(file_path_, std::ios::app);
+  file << line << '\n';
+  file.flush();
+}
+
+long long CaptureJournal::append(const std::string &data) {
+  std::lock_guard<std::mutex> guard(mutex_);
+  long long id = next_id_++;
+  pending_[id] = data;
+  writeLine("E " + std::to_string(id) + " " + data);
+  return id;
+}
+
+void CaptureJournal::acknowledge(long long id) {
+  std::lock_guard<std::mutex> guard(mutex_);
+  if (pending_.erase(id) == 0 && recovered_.erase(id) == 0) {
+    return;
+  }
+  attempts_.erase(id);
+  if (pending_.empty() && recovered_.empty()) {
+    // Nothing to replay, so the journal can start from scratch.
+    std::ofstream file(file_path_, std::ios::trunc);
+    return;
+  }
+  writeLine("A " + std::to_string(id));
+}
+
+std::vector<CaptureJournal::Entry> CaptureJournal::getRecoveredEntries() {
+  std::lock_guard<std::mutex> guard(mutex_);
+  std::vector<Entry> entries;
+  for (const auto &[id, data] : recovered_) {
+    entries.push_back({id, data});
+  }
+  return entries;
+}
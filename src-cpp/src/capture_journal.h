@@ -0,0 +1,55 @@
+#ifndef CLIPBOOK_CAPTURE_JOURNAL_H_
+#define CLIPBOOK_CAPTURE_JOURNAL_H_
+
+#include <map>
+#include <mutex>
+#include <string>
+#include <vector>
+
+// The append-only journal of the captured clipboard data. The monitor writes
+// every capture to the journal before passing it to the app window, and the
+// app acknowledges it once the item is saved to the database. The captures
+// left unacknowledged after a crash are replayed on the next launch.
+//
+// The journal consists of the lines "E <id> <json>" for the captures,
+// "A <id>" for the acknowledgements and "R <id> <attempts>" for the number of
+// the launches the capture has been replayed on. A capture that fails to be
+// saved after a few replays is dropped. The file is rewritten with only the
+// unacknowledged captures on every launch and truncated whenever there are
+// no pending captures, so it stays small.
+class CaptureJournal {
+ public:
+  struct Entry {
+    long long id;
+    std::string data;
+  };
+
+  explicit CaptureJournal(std::string file_path);
+
+  // Writes the capture to the journal and returns its id.
+  long long append(const std::string &data);
+  void acknowledge(long long id);
+  // Returns the captures that hadn't been acknowledged before the launch.
+  std::vector<Entry> getRecoveredEntries();
+  // Rewrites the journal with only the unacknowledged captures, so the data
+  // of the saved captures no longer stays on the disk.
+  void compact();
+  // Drops the captures recovered on launch and compacts the journal, e.g.
+  // when the history is cleared, so they aren't replayed later.
+  void clear();
+
+ private:
+  void load();
+  void rewrite();
+  void writeLine(const std::string &line);
+
+  std::string file_path_;
+  std::mutex mutex_;
+  long long next_id_ = 1;
+  std::map<long long, std::string> recovered_;
+  // The number of the launches the recovered captures have been replayed on.
+  std::map<long long, int> attempts_;
+  std::map<long long, std::string> pending_;
+};
+
+#endif // CLIPBOOK_CAPTURE_JOURNAL_H_
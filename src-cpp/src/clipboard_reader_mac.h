@@ -3,7 +3,13 @@
 
 #include "main_app.h"
 
+#include <atomic>
+#include <cstdint>
+#include <deque>
+#include <map>
 #include <mutex>
+#include <string>
+#include <vector>
 
 #ifdef __OBJC__
 #import <Cocoa/Cocoa.h>
@@ -33,8 +39,15 @@ struct ClipboardData {
   std::string rtf;
   ImageInfo image_info;
   std::vector<FilePathInfo> file_paths;
+  // The device the data was copied on if it came with Universal Clipboard.
+  std::string source_device;
 };
 
+#ifdef __OBJC__
+// Recognizes the text in the image with the Vision framework.
+std::string recognizeTextInImage(NSImage *image);
+#endif
+
 class ClipboardReaderMac {
  public:
   explicit ClipboardReaderMac();
@@ -42,16 +55,35 @@ class ClipboardReaderMac {
 
   void start(const std::shared_ptr<MainApp> &app);
   void copyToClipboardAfterMerge(std::string text);
+  // Makes the reader skip the clipboard change with the given change count,
+  // because the data was put to the clipboard by the app itself.
+  void ignoreChange(long change_count);
+  // Makes the reader skip the change of the find pasteboard with the given
+  // change count.
+  void ignoreFindChange(long change_count);
+  // Returns the pasteboard types (UTIs) of the recently copied data, the
+  // most recent first.
+  std::vector<std::string> getRecentTypes();
 
  private:
 #ifdef __OBJC__
-  static std::string readPasteboard(NSPasteboardType type);
+  std::string readPasteboard(NSPasteboardType type);
+  bool isTypeAllowed(NSPasteboardType type) const;
+  void rememberTypes(NSArray *types);
 #endif
-  static bool readTextData(const std::shared_ptr<ClipboardData> &data);
+  bool readTextData(const std::shared_ptr<ClipboardData> &data);
 
   void readClipboardData();
+  // Reads the text of the find pasteboard the apps share for the search
+  // terms (e.g. with ⌘E) and passes it to the app.
+  void readFindPasteboard();
   bool readClipboardData(const std::shared_ptr<ClipboardData> &data);
   bool readImageData(const std::shared_ptr<ClipboardData> &data);
+  // Looks for the stored image of the same size whose difference hash is
+  // within the maximum distance from the given hash.
+  bool findSimilarImage(uint64_t hash, const std::string &imagesDir,
+                        const std::shared_ptr<ClipboardData> &data);
+  void rememberImageHash(const std::string &fileName, int width, int height, uint64_t hash);
   bool readFilesData(const std::shared_ptr<ClipboardData> &data);
   void addClipboardData(const std::shared_ptr<ClipboardData>& data);
   void mergeClipboardData(const std::shared_ptr<ClipboardData>& data);
@@ -60,7 +92,24 @@ class ClipboardReaderMac {
   std::shared_ptr<MainApp> app_;
   std::shared_ptr<ClipboardData> data_;
   long last_change_count_ = 0;
-  bool copy_and_merge_requested_ = false;
+  std::atomic<long> ignored_change_count_{-1};
+  long last_find_change_count_ = 0;
+  std::atomic<long> ignored_find_change_count_{-1};
+  std::atomic<bool> copy_and_merge_requested_{false};
+  // The pasteboard types that are read. Empty if all the types are read.
+  std::vector<std::string> allowed_types_;
+  std::deque<std::string> recent_types_;
+  std::mutex recent_types_mutex_;
+  struct ImageHash {
+    int width;
+    int height;
+    uint64_t hash;
+  };
+  // The difference hashes of the stored images by the image file name. The
+  // hashes are read from the info files once, so the info files aren't
+  // read on every copied image.
+  std::map<std::string, ImageHash> image_hashes_;
+  bool image_hashes_loaded_ = false;
 #ifdef __OBJC__
   id monitor_ = nil;
   NSSound *sound_ = nil;
@@ -5,6 +5,8 @@
 #import <QuickLookThumbnailing/QuickLookThumbnailing.h>
 #import <Vision/Vision.h>
 
+#include <algorithm>
+#include <cstdlib>
 #include <filesystem>
 #include <fstream>
 #include <memory>
@@ -16,24 +18,52 @@ namespace fs = std::filesystem;
 
 static int kCheckInterval = 500;
 static int kCopyToClipboardAfterMergeDelay = 500;
+// The maximum time to wait for the file thumbnail, so a slow Quick Look
+// generator doesn't stall reading the clipboard.
+static int kThumbnailTimeout = 5000;
+// The size of the difference hash grid and the maximum number of the
+// different bits of the hashes of the images considered duplicates.
+static const size_t kDifferenceHashSize = 8;
+static const int kMaxDifferenceHashDistance = 2;
+// The hashes with fewer set or unset bits belong to the blank or uniform
+// images, e.g. the screenshots of white pages, which look alike even when
+// they differ.
+static const int kMinDifferenceHashBits = 8;
+// The maximum number of the recently seen pasteboard types to remember.
+static const size_t kMaxRecentTypes = 50;
+// The source device of the data copied on another device and received with
+// Universal Clipboard. The pasteboard doesn't tell which device it was.
+static const char *kRemoteDevice = "remote";
+
+// Splits the comma-separated list of the pasteboard types.
+static std::vector<std::string> splitTypes(const std::string &types) {
+  std::vector<std::string> result;
+  NSString *string = [NSString stringWithUTF8String:types.c_str()];
+  for (NSString *type in [string componentsSeparatedByString:@","]) {
+    NSString *trimmed = [type stringByTrimmingCharactersInSet:[NSCharacterSet whitespaceCharacterSet]];
+    if (trimmed.length > 0) {
+      result.emplace_back([trimmed UTF8String]);
+    }
+  }
+  return result;
+}
 
 bool hasCustomClip(NSPasteboard *pasteboard) {
   return [pasteboard availableTypeFromArray:@[@"com.clipbook.data"]] != nil;
 }
 
-void extractTextFromImage(NSImage *image,
-                          const std::shared_ptr<MainApp> &app,
-                          const std::shared_ptr<ClipboardData> &data) {
+std::string recognizeTextInImage(NSImage *image) {
   // Convert NSImage to CGImage
   CGImageRef cgImage = [image CGImageForProposedRect:nullptr context:nil hints:nil];
   if (cgImage == nil) {
-    return;
+    return "";
   }
 
   // Create a VNImageRequestHandler with the CGImage
   VNImageRequestHandler *handler = [[VNImageRequestHandler alloc] initWithCGImage:cgImage options:@{}];
 
   // Create a text recognition request
+  __block std::string content;
   VNRecognizeTextRequest *textRequest = [[VNRecognizeTextRequest alloc] initWithCompletionHandler:^(
       VNRequest *request,
       NSError *_Nullable error) {
@@ -42,7 +72,6 @@ void extractTextFromImage(NSImage *image,
     }
 
     // Process the text recognition results
-    std::string content;
     NSArray *observations = request.results;
     for (VNRecognizedTextObservation *observation in observations) {
       VNRecognizedText *recognizedText = [[observation topCandidates:1] firstObject];
@@ -57,12 +86,6 @@ void extractTextFromImage(NSImage *image,
         }
       }
     }
-
-    if (content.empty()) {
-      return;
-    }
-
-    data->image_info.text = content;
   }];
 
   // Perform the request
@@ -73,6 +96,16 @@ void extractTextFromImage(NSImage *image,
   [textRequest release];
   [handler release];
   CGImageRelease(cgImage);
+  return content;
+}
+
+void extractTextFromImage(NSImage *image,
+                          const std::shared_ptr<MainApp> &app,
+                          const std::shared_ptr<ClipboardData> &data) {
+  std::string content = recognizeTextInImage(image);
+  if (!content.empty()) {
+    data->image_info.text = content;
+  }
 }
 
 NSImage *createThumbnail(NSImage *image, int width, int height) {
@@ -217,6 +250,101 @@ bool findIdenticalImage(NSImage *srcImage,
   return false;
 }
 
+// Computes the difference hash of the image: the image is scaled down to
+// 9x8 grayscale pixels and every bit tells whether the pixel is brighter
+// than its right neighbor. The hashes of the similar images, e.g. the
+// screenshots of the same area, differ in a few bits only.
+static uint64_t computeDifferenceHash(CGImageRef image) {
+  const size_t width = kDifferenceHashSize + 1;
+  const size_t height = kDifferenceHashSize;
+  uint8_t pixels[width * height] = {0};
+  CGColorSpaceRef color_space = CGColorSpaceCreateDeviceGray();
+  CGContextRef context = CGBitmapContextCreate(pixels, width, height, 8, width,
+                                               color_space, kCGImageAlphaNone);
+  CGColorSpaceRelease(color_space);
+  if (context == nullptr) {
+    return 0;
+  }
+  CGContextSetInterpolationQuality(context, kCGInterpolationMedium);
+  CGContextDrawImage(context, CGRectMake(0, 0, width, height), image);
+  CGContextRelease(context);
+  uint64_t hash = 0;
+  for (size_t y = 0; y < height; ++y) {
+    for (size_t x = 0; x < width - 1; ++x) {
+      hash = (hash << 1) | (pixels[y * width + x] > pixels[y * width + x + 1] ? 1 : 0);
+    }
+  }
+  return hash;
+}
+
+static std::string formatDifferenceHash(uint64_t hash) {
+  char buffer[17];
+  snprintf(buffer, sizeof(buffer), "%016llx", static_cast<unsigned long long>(hash));
+  return buffer;
+}
+
+static bool isDegenerateDifferenceHash(uint64_t hash) {
+  int bits = __builtin_popcountll(hash);
+  return bits < kMinDifferenceHashBits || bits > 64 - kMinDifferenceHashBits;
+}
+
+void ClipboardReaderMac::rememberImageHash(const std::string &fileName, int width, int height, uint64_t hash) {
+  if (!isDegenerateDifferenceHash(hash)) {
+    image_hashes_[fileName] = {width, height, hash};
+  }
+}
+
+bool ClipboardReaderMac::findSimilarImage(uint64_t hash,
+                                          const std::string &imagesDir,
+                                          const std::shared_ptr<ClipboardData> &data) {
+  if (isDegenerateDifferenceHash(hash)) {
+    return false;
+  }
+  if (!image_hashes_loaded_) {
+    image_hashes_loaded_ = true;
+    for (const auto &image_path : findImages(imagesDir, "image_", "image_thumb_", ".png")) {
+      std::ifstream input_file(fs::path(image_path).replace_extension(".info"));
+      if (!input_file.is_open()) {
+        continue;
+      }
+      int width = 0;
+      int height = 0;
+      std::string hash_text;
+      std::string line;
+      while (std::getline(input_file, line)) {
+        if (line.find("width: ") == 0) {
+          width = std::atoi(line.substr(7).c_str());
+        } else if (line.find("height: ") == 0) {
+          height = std::atoi(line.substr(8).c_str());
+        } else if (line.find("dhash: ") == 0) {
+          hash_text = line.substr(7);
+        }
+      }
+      if (!hash_text.empty()) {
+        rememberImageHash(image_path.filename().string(), width, height,
+                          std::strtoull(hash_text.c_str(), nullptr, 16));
+      }
+    }
+  }
+  for (auto it = image_hashes_.begin(); it != image_hashes_.end();) {
+    const auto &image = it->second;
+    if (image.width != data->image_info.width || image.height != data->image_info.height ||
+        __builtin_popcountll(hash ^ image.hash) > kMaxDifferenceHashDistance) {
+      ++it;
+      continue;
+    }
+    // The image might be deleted with its history item.
+    if (!fs::exists(fs::path(imagesDir) / it->first)) {
+      it = image_hashes_.erase(it);
+      continue;
+    }
+    data->image_info.file_name = it->first;
+    data->image_info.thumb_file_name = getThumbImageFileName(data->image_info.file_name);
+    return true;
+  }
+  return false;
+}
+
 NSImage *getThumbnailForFile(NSString *filePath, CGSize maxSize) {
   @autoreleasepool {
     NSURL *fileURL = [NSURL fileURLWithPath:filePath];
@@ -241,7 +369,11 @@ NSImage *getThumbnailForFile(NSString *filePath, CGSize maxSize) {
                              dispatch_semaphore_signal(semaphore);
                            }];
 
-    dispatch_semaphore_wait(semaphore, DISPATCH_TIME_FOREVER);
+    long timed_out = dispatch_semaphore_wait(
+        semaphore, dispatch_time(DISPATCH_TIME_NOW, (int64_t) kThumbnailTimeout * NSEC_PER_MSEC));
+    if (timed_out) {
+      return nil;
+    }
 
     // Release the request manually to avoid memory leaks
     request = nil;
@@ -286,6 +418,7 @@ void ClipboardReaderMac::start(const std::shared_ptr<MainApp> &app) {
 
   // Initialize the last change count on start to ignore the initial clipboard content.
   last_change_count_ = [[NSPasteboard generalPasteboard] changeCount];
+  last_find_change_count_ = [[NSPasteboard pasteboardWithName:NSPasteboardNameFind] changeCount];
 
   monitor_ = [NSEvent addGlobalMonitorForEventsMatchingMask:NSEventMaskKeyDown handler:^(NSEvent *event) {
     if (!app_->settings()->isCopyAndMergeEnabled() || app_->isPaused()) {
@@ -305,6 +438,7 @@ void ClipboardReaderMac::start(const std::shared_ptr<MainApp> &app) {
     while (true) {
       std::this_thread::sleep_for(std::chrono::milliseconds(kCheckInterval));
       readClipboardData();
+      readFindPasteboard();
     }
   });
   t.detach();
@@ -327,6 +461,87 @@ void ClipboardReaderMac::copyToClipboardAfterMerge(std::string text) {
   t.detach();
 }
 
+void ClipboardReaderMac::ignoreChange(long change_count) {
+  ignored_change_count_ = change_count;
+}
+
+void ClipboardReaderMac::ignoreFindChange(long change_count) {
+  ignored_find_change_count_ = change_count;
+}
+
+void ClipboardReaderMac::readFindPasteboard() {
+  NSPasteboard *pasteboard = [NSPasteboard pasteboardWithName:NSPasteboardNameFind];
+  auto change_count = [pasteboard changeCount];
+  if (change_count == last_find_change_count_) {
+    return;
+  }
+  last_find_change_count_ = change_count;
+  if (change_count == ignored_find_change_count_ || app_->isPaused() ||
+      !app_->settings()->shouldMonitorFindPasteboard()) {
+    return;
+  }
+  NSString *string = [pasteboard stringForType:NSPasteboardTypeString];
+  if (!string) {
+    return;
+  }
+  std::string text = [string UTF8String];
+  if (isEmptyOrSpaces(text)) {
+    return;
+  }
+  auto active_app_info = app_->getActiveAppInfo();
+  auto frame = app_->browser()->mainFrame();
+  auto window = frame->executeJavaScript("window");
+  window.asJsObject()->call("addFindPasteboardText", text, active_app_info.path);
+}
+
+std::vector<std::string> ClipboardReaderMac::getRecentTypes() {
+  std::lock_guard<std::mutex> guard(recent_types_mutex_);
+  return {recent_types_.begin(), recent_types_.end()};
+}
+
+void ClipboardReaderMac::rememberTypes(NSArray *types) {
+  std::lock_guard<std::mutex> guard(recent_types_mutex_);
+  for (NSString *type in [types reverseObjectEnumerator]) {
+    std::string value = [type UTF8String];
+    auto it = std::find(recent_types_.begin(), recent_types_.end(), value);
+    if (it != recent_types_.end()) {
+      recent_types_.erase(it);
+    }
+    recent_types_.push_front(value);
+  }
+  while (recent_types_.size() > kMaxRecentTypes) {
+    recent_types_.pop_back();
+  }
+}
+
+bool ClipboardReaderMac::isTypeAllowed(NSPasteboardType type) const {
+  if (allowed_types_.empty()) {
+    return true;
+  }
+  return std::find(allowed_types_.begin(), allowed_types_.end(), [type UTF8String]) != allowed_types_.end();
+}
+
+// Serializes the arguments of addClipboardData() for the capture journal.
+static std::string toJournalData(const std::shared_ptr<ClipboardData> &data, const FilePathInfo &file_path) {
+  const auto &image = data->image_info;
+  return "{\"text\":" + toJsonString(file_path.file_path.empty() ? data->text : file_path.file_path) +
+      ",\"appPath\":" + toJsonString(data->active_app_info.path) +
+      ",\"imageFileName\":" + toJsonString(image.file_name) +
+      ",\"imageThumbFileName\":" + toJsonString(image.thumb_file_name) +
+      ",\"imageWidth\":" + std::to_string(image.width) +
+      ",\"imageHeight\":" + std::to_string(image.height) +
+      ",\"imageSizeInBytes\":" + std::to_string(image.size_in_bytes) +
+      ",\"imageText\":" + toJsonString(image.text) +
+      ",\"filePath\":" + toJsonString(file_path.file_path) +
+      ",\"filePathFileName\":" + toJsonString(file_path.file_preview_name) +
+      ",\"filePathThumbFileName\":" + toJsonString(file_path.file_thumb_name) +
+      ",\"fileSizeInBytes\":" + std::to_string(file_path.size_in_bytes) +
+      ",\"folder\":" + (file_path.folder ? "true" : "false") +
+      ",\"rtf\":" + toJsonString(file_path.file_path.empty() ? data->rtf : "") +
+      ",\"html\":" + toJsonString(file_path.file_path.empty() ? data->html : "") +
+      ",\"sourceDevice\":" + toJsonString(data->source_device) + "}";
+}
+
 void ClipboardReaderMac::addClipboardData(const std::shared_ptr<ClipboardData> &data) {
   if (app_->settings()->shouldPlaySoundOnCopy()) {
     [sound_ play];
@@ -334,7 +549,11 @@ void ClipboardReaderMac::addClipboardData(const std::shared_ptr<ClipboardData> &
 
   auto frame = app_->browser()->mainFrame();
   auto window = frame->executeJavaScript("window");
+  auto journal = app_->captureJournal();
   if (data->file_paths.empty()) {
+    // The capture is journaled first, so it's not lost if the app crashes
+    // before the item is saved. The id is passed as a JS number.
+    double journal_id = static_cast<double>(journal->append(toJournalData(data, {})));
     window.asJsObject()->call("addClipboardData",
                               data->text,
                               data->active_app_info.path,
@@ -350,9 +569,12 @@ void ClipboardReaderMac::addClipboardData(const std::shared_ptr<ClipboardData> &
                               0,
                               false,
                               data->rtf,
-                              data->html);
+                              data->html,
+                              data->source_device,
+                              journal_id);
   } else {
     for (const auto &file_path : data->file_paths) {
+      double journal_id = static_cast<double>(journal->append(toJournalData(data, file_path)));
       window.asJsObject()->call("addClipboardData",
                                 file_path.file_path,
                                 data->active_app_info.path,
@@ -368,7 +590,9 @@ void ClipboardReaderMac::addClipboardData(const std::shared_ptr<ClipboardData> &
                                 file_path.size_in_bytes,
                                 file_path.folder,
                                 "",
-                                "");
+                                "",
+                                data->source_device,
+                                journal_id);
     }
   }
 }
@@ -419,28 +643,37 @@ void ClipboardReaderMac::readClipboardData() {
       return;
     }
   }
-  std::lock_guard<std::mutex> guard(mutex_);
   std::shared_ptr<ClipboardData> data = std::make_shared<ClipboardData>();
-  if (readClipboardData(data)) {
-    data_ = data;
-    if (copy_and_merge_requested_) {
-      mergeClipboardData(data);
-    } else {
-      addClipboardData(data);
+  bool merge;
+  {
+    std::lock_guard<std::mutex> guard(mutex_);
+    bool has_data = readClipboardData(data);
+    merge = copy_and_merge_requested_.exchange(false);
+    if (!has_data) {
+      return;
     }
+    data_ = data;
+  }
+  // Pass the data to the app without holding the lock. The call waits for
+  // the renderer, and the renderer may ask to write to the clipboard.
+  if (merge) {
+    mergeClipboardData(data);
+  } else {
+    addClipboardData(data);
   }
-  copy_and_merge_requested_ = false;
 }
 
 bool ClipboardReaderMac::readImageData(const std::shared_ptr<ClipboardData> &data) {
   NSPasteboard *pasteboard = [NSPasteboard generalPasteboard];
   NSArray *types = [pasteboard types];
   // If the clipboard contains a file URL, then skip reading the image data.
-  if ([types containsObject:NSPasteboardTypeFileURL]) {
+  if ([types containsObject:NSPasteboardTypeFileURL] && isTypeAllowed(NSPasteboardTypeFileURL)) {
     return false;
   }
+  bool has_png = [types containsObject:NSPasteboardTypePNG] && isTypeAllowed(NSPasteboardTypePNG);
+  bool has_tiff = [types containsObject:NSPasteboardTypeTIFF] && isTypeAllowed(NSPasteboardTypeTIFF);
   // Read image content in the PNG and TIFF formats.
-  if ([types containsObject:NSPasteboardTypePNG] || [types containsObject:NSPasteboardTypeTIFF]) {
+  if (has_png || has_tiff) {
     // Make sure the images directory exists.
     fs::path imagesDir = app_->getImagesDir();
     if (!fs::exists(imagesDir)) {
@@ -450,7 +683,7 @@ bool ClipboardReaderMac::readImageData(const std::shared_ptr<ClipboardData> &dat
     @autoreleasepool {
       // Get image info.
       NSData *png_data = nil;
-      if ([types containsObject:NSPasteboardTypePNG]) {
+      if (has_png) {
         png_data = [pasteboard dataForType:NSPasteboardTypePNG];
       } else {
         NSData *tiff_data = [pasteboard dataForType:NSPasteboardTypeTIFF];
@@ -474,6 +707,15 @@ bool ClipboardReaderMac::readImageData(const std::shared_ptr<ClipboardData> &dat
         return true;
       }
 
+      // Re-copied screenshots differ byte-wise, so look for the image that
+      // looks the same.
+      CGImageRef cg_image = [image CGImageForProposedRect:nullptr context:nil hints:nil];
+      uint64_t hash = cg_image ? computeDifferenceHash(cg_image) : 0;
+      if (cg_image && app_->settings()->shouldDeduplicateSimilarImages() &&
+          findSimilarImage(hash, imagesDir.string(), data)) {
+        return true;
+      }
+
       int creation_time_in_ms = (int) [[NSDate date] timeIntervalSince1970];
 
       // Save image to file.
@@ -487,6 +729,13 @@ bool ClipboardReaderMac::readImageData(const std::shared_ptr<ClipboardData> &dat
       std::string image_info_filename = "image_" + std::to_string(creation_time_in_ms) + ".info";
       std::string text_to_write = "width: " + std::to_string(data->image_info.width) + "\n" +
                                   "height: " + std::to_string(data->image_info.height) + "\n";
+      if (cg_image) {
+        text_to_write += "dhash: " + formatDifferenceHash(hash) + "\n";
+        if (image_hashes_loaded_) {
+          rememberImageHash([image_filename UTF8String], data->image_info.width,
+                            data->image_info.height, hash);
+        }
+      }
       std::ofstream output_file(imagesDir / image_info_filename);
       if (output_file.is_open()) {
         output_file << text_to_write;
@@ -502,9 +751,8 @@ bool ClipboardReaderMac::readImageData(const std::shared_ptr<ClipboardData> &dat
       NSString *thumb_path = [images_dir stringByAppendingPathComponent:thumb_filename];
       [thumb_png_data writeToFile:thumb_path atomically:YES];
       data->image_info.thumb_file_name = [thumb_filename UTF8String];
-
-      // Extract text from image.
-      extractTextFromImage(image, app_, data);
+      // The text is recognized in the background after the image is added
+      // to the history.
     }
     return true;
   }
@@ -515,7 +763,7 @@ std::string ClipboardReaderMac::readPasteboard(NSPasteboardType type) {
   NSPasteboard *pasteboard = [NSPasteboard generalPasteboard];
   NSArray *types = [pasteboard types];
   // Read text content.
-  if ([types containsObject:type]) {
+  if ([types containsObject:type] && isTypeAllowed(type)) {
     auto string = [pasteboard stringForType:type];
     if (string) {
       const char *text = [string UTF8String];
@@ -538,7 +786,7 @@ bool ClipboardReaderMac::readFilesData(const std::shared_ptr<ClipboardData> &dat
   NSPasteboard *pasteboard = [NSPasteboard generalPasteboard];
   NSArray *types = [pasteboard types];
   bool success = false;
-  if ([types containsObject:NSPasteboardTypeFileURL]) {
+  if ([types containsObject:NSPasteboardTypeFileURL] && isTypeAllowed(NSPasteboardTypeFileURL)) {
     NSArray<NSURL *> *fileURLs = [pasteboard readObjectsForClasses:@[[NSURL class]] options:nil];
     if (fileURLs.count > 0) {
       for (NSURL *fileURL in fileURLs) {
@@ -624,6 +872,10 @@ bool ClipboardReaderMac::readClipboardData(const std::shared_ptr<ClipboardData>
   }
   last_change_count_ = changeCount;
 
+  if (changeCount == ignored_change_count_) {
+    return false;
+  }
+
   if (app_->isPaused()) {
     return false;
   }
@@ -649,12 +901,22 @@ bool ClipboardReaderMac::readClipboardData(const std::shared_ptr<ClipboardData>
   bool ignore_transient_content = settings->shouldIgnoreTransientContent();
   bool ignore_confidential_content = settings->shouldIgnoreConfidentialContent();
   NSArray *types = [pasteboard types];
+  rememberTypes(types);
   if (ignore_transient_content && [types containsObject:@"org.nspasteboard.TransientType"]) {
     return false;
   }
   if (ignore_confidential_content && [types containsObject:@"org.nspasteboard.ConcealedType"]) {
     return false;
   }
+  for (const auto &type : splitTypes(settings->getCaptureIgnoredTypes())) {
+    if ([types containsObject:[NSString stringWithUTF8String:type.c_str()]]) {
+      return false;
+    }
+  }
+  allowed_types_ = splitTypes(settings->getCaptureAllowedTypes());
+  if ([types containsObject:@"com.apple.is-remote-clipboard"]) {
+    data->source_device = kRemoteDevice;
+  }
 
   bool has_image = readImageData(data);
   bool has_text = readTextData(data);
@@ -0,0 +1,26 @@
+#ifndef CLIPBOOK_CONFIG_FILE_WATCHER_MAC_H_
+#define CLIPBOOK_CONFIG_FILE_WATCHER_MAC_H_
+
+#include <functional>
+#include <memory>
+#include <string>
+
+// Watches the config file and calls the handler when the file is created,
+// modified, or deleted. The directory is watched instead of the file,
+// because the editors usually save the file by replacing it.
+class ConfigFileWatcherMac {
+ public:
+  using Handler = std::function<void()>;
+
+  explicit ConfigFileWatcherMac(Handler handler);
+  ~ConfigFileWatcherMac();
+
+  bool start(const std::string &file_path);
+  void stop();
+
+ private:
+  struct Impl;
+  std::unique_ptr<Impl> impl_;
+};
+
+#endif // CLIPBOOK_CONFIG_FILE_WATCHER_MAC_H_
@@ -0,0 +1,105 @@
+#include "config_file_watcher_mac.h"
+
+#import <Foundation/Foundation.h>
+
+#include <fcntl.h>
+#include <filesystem>
+#include <sys/stat.h>
+#include <unistd.h>
+
+namespace fs = std::filesystem;
+
+// The editors may write the file in several steps, so the handler is called
+// once the changes settle down.
+static const int64_t kDebounceDelayMs = 200;
+
+struct ConfigFileWatcherMac::Impl {
+  Handler handler;
+  std::string file_path;
+  int dir_fd = -1;
+  dispatch_source_t source = nullptr;
+  dispatch_block_t pending = nullptr;
+  // The modification time of the file the last time the handler was called
+  // or 0 if the file didn't exist.
+  struct timespec last_modified = {0, 0};
+
+  struct timespec getModificationTime() const {
+    struct stat info{};
+    if (stat(file_path.c_str(), &info) != 0) {
+      return {0, 0};
+    }
+    return info.st_mtimespec;
+  }
+
+  void checkFile() {
+    auto modified = getModificationTime();
+    if (modified.tv_sec == last_modified.tv_sec && modified.tv_nsec == last_modified.tv_nsec) {
+      return;
+    }
+    last_modified = modified;
+    if (handler) {
+      handler();
+    }
+  }
+
+  void scheduleCheck() {
+    if (pending) {
+      dispatch_block_cancel(pending);
+      Block_release(pending);
+    }
+    pending = dispatch_block_create(static_cast<dispatch_block_flags_t>(0), ^{
+      checkFile();
+    });
+    dispatch_after(dispatch_time(DISPATCH_TIME_NOW, kDebounceDelayMs * NSEC_PER_MSEC),
+                   dispatch_get_main_queue(), pending);
+  }
+};
+
+ConfigFileWatcherMac::ConfigFileWatcherMac(Handler handler) : impl_(std::make_unique<Impl>()) {
+  impl_->handler = std::move(handler);
+}
+
+ConfigFileWatcherMac::~ConfigFileWatcherMac() {
+  stop();
+}
+
+bool ConfigFileWatcherMac::start(const std::string &file_path) {
+  stop();
+  auto dir_path = fs::path(file_path).parent_path();
+  std::error_code error;
+  fs::create_directories(dir_path, error);
+  int fd = open(dir_path.c_str(), O_EVTONLY);
+  if (fd < 0) {
+    return false;
+  }
+  impl_->file_path = file_path;
+  impl_->dir_fd = fd;
+  impl_->last_modified = impl_->getModificationTime();
+  impl_->source = dispatch_source_create(DISPATCH_SOURCE_TYPE_VNODE,
+                                         fd,
+                                         DISPATCH_VNODE_WRITE | DISPATCH_VNODE_DELETE | DISPATCH_VNODE_RENAME,
+                                         dispatch_get_main_queue());
+  Impl *impl = impl_.get();
+  dispatch_source_set_event_handler(impl_->source, ^{
+    impl->scheduleCheck();
+  });
+  dispatch_source_set_cancel_handler(impl_->source, ^{
+    close(fd);
+  });
+  dispatch_resume(impl_->source);
+  return true;
+}
+
+void ConfigFileWatcherMac::stop() {
+  if (impl_->pending) {
+    dispatch_block_cancel(impl_->pending);
+    Block_release(impl_->pending);
+    impl_->pending = nullptr;
+  }
+  if (impl_->source) {
+    dispatch_source_cancel(impl_->source);
+    dispatch_release(impl_->source);
+    impl_->source = nullptr;
+    impl_->dir_fd = -1;
+  }
+}
@@ -0,0 +1,30 @@
+#ifndef CLIPBOOK_DEEP_LINK_HANDLER_MAC_H_
+#define CLIPBOOK_DEEP_LINK_HANDLER_MAC_H_
+
+#import <Cocoa/Cocoa.h>
+
+#include <functional>
+#include <string>
+#include <vector>
+
+// Receives the clipbook-link:// URLs opened by other apps. The handler must
+// be installed before the app finishes launching to receive the URL that
+// launched the app. The URLs received before the callback is set are kept
+// and passed to the callback once it's set.
+@interface DeepLinkHandler : NSObject {
+ @private
+  std::function<void(const std::string &)> callback_;
+  std::vector<std::string> pending_urls_;
+}
+
++ (DeepLinkHandler *)sharedHandler;
+// Installs the Apple Event handler when the app will finish launching.
++ (void)installOnLaunch;
+- (void)setCallback:(std::function<void(const std::string &)>)callback;
+- (void)startHandling;
+- (void)handleGetURLEvent:(NSAppleEventDescriptor *)event
+           withReplyEvent:(NSAppleEventDescriptor *)replyEvent;
+
+@end
+
+#endif // CLIPBOOK_DEEP_LINK_HANDLER_MAC_H_
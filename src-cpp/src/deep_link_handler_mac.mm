@@ -0,0 +1,57 @@
+#include "deep_link_handler_mac.h"
+
+@implementation DeepLinkHandler
+
++ (DeepLinkHandler *)sharedHandler {
+  static DeepLinkHandler *handler = [[DeepLinkHandler alloc] init];
+  return handler;
+}
+
++ (void)installOnLaunch {
+  [[NSNotificationCenter defaultCenter]
+      addObserverForName:NSApplicationWillFinishLaunchingNotification
+                  object:nil
+                   queue:nil
+              usingBlock:^(NSNotification *notification) {
+                [[DeepLinkHandler sharedHandler] startHandling];
+              }];
+}
+
+- (void)setCallback:(std::function<void(const std::string &)>)callback {
+  callback_ = callback;
+  auto urls = std::move(pending_urls_);
+  pending_urls_.clear();
+  for (const auto &url : urls) {
+    callback_(url);
+  }
+}
+
+- (void)startHandling {
+  [[NSAppleEventManager sharedAppleEventManager]
+      setEventHandler:self
+          andSelector:@selector(handleGetURLEvent:withReplyEvent:)
+        forEventClass:kInternetEventClass
+           andEventID:kAEGetURL];
+}
+
+- (void)handleGetURLEvent:(NSAppleEventDescriptor *)event
+           withReplyEvent:(NSAppleEventDescriptor *)replyEvent {
+  NSString *url = [[event paramDescriptorForKeyword:keyDirectObject] stringValue];
+  if (!url) {
+    return;
+  }
+  if (callback_) {
+    callback_([url UTF8String]);
+  } else {
+    pending_urls_.emplace_back([url UTF8String]);
+  }
+}
+
+- (void)dealloc {
+  [[NSAppleEventManager sharedAppleEventManager]
+      removeEventHandlerForEventClass:kInternetEventClass
+                           andEventID:kAEGetURL];
+  [super dealloc];
+}
+
+@end
@@ -0,0 +1,49 @@
+#ifndef CLIPBOOK_DOUBLE_TAP_DETECTOR_MAC_H_
+#define CLIPBOOK_DOUBLE_TAP_DETECTOR_MAC_H_
+
+#include <CoreGraphics/CoreGraphics.h>
+
+#include <functional>
+#include <string>
+
+// Detects the double press of a modifier key (e.g. ⌘⌘ or ⇧⇧) with an event
+// tap. The modifier must be pressed and released alone twice within the
+// double-tap interval, so the regular shortcuts like ⌘C don't trigger it.
+class DoubleTapDetectorMac {
+ public:
+  using Handler = std::function<void()>;
+
+  static constexpr const char *kCommand = "command";
+  static constexpr const char *kShift = "shift";
+
+  explicit DoubleTapDetectorMac(Handler handler);
+  ~DoubleTapDetectorMac();
+
+  // Starts listening to the modifier changes. Returns false if the modifier
+  // is unknown or the event tap can't be created.
+  bool start(const std::string &modifier);
+  void stop();
+  bool isStarted() const;
+
+  // Checks whether the app may listen to the keyboard events by creating
+  // a temporary event tap. The tap can't be created without the Input
+  // Monitoring permission, even if the system reports it as not determined.
+  static bool canCreateEventTap();
+
+ private:
+  static CGEventRef onEvent(CGEventTapProxy proxy, CGEventType type, CGEventRef event, void *info);
+  void handleEvent(CGEventType type, CGEventRef event);
+  void reset();
+
+ private:
+  Handler handler_;
+  CGEventFlags modifier_flag_ = 0;
+  CFMachPortRef event_tap_ = nullptr;
+  CFRunLoopSourceRef run_loop_source_ = nullptr;
+  // Whether the modifier is currently pressed without other keys.
+  bool modifier_pressed_ = false;
+  // The time the modifier was released alone the last time.
+  CFAbsoluteTime last_tap_time_ = 0;
+};
+
+#endif // CLIPBOOK_DOUBLE_TAP_DETECTOR_MAC_H_
@@ -0,0 +1,133 @@
+#include "double_tap_detector_mac.h"
+
+#import <Foundation/Foundation.h>
+
+// The maximum interval in seconds between the two presses of the modifier.
+static const CFAbsoluteTime kDoubleTapInterval = 0.3;
+
+static const CGEventFlags kModifierFlagsMask = kCGEventFlagMaskCommand |
+    kCGEventFlagMaskShift |
+    kCGEventFlagMaskControl |
+    kCGEventFlagMaskAlternate;
+
+DoubleTapDetectorMac::DoubleTapDetectorMac(Handler handler) : handler_(std::move(handler)) {}
+
+DoubleTapDetectorMac::~DoubleTapDetectorMac() {
+  stop();
+}
+
+bool DoubleTapDetectorMac::start(const std::string &modifier) {
+  stop();
+  if (modifier == kCommand) {
+    modifier_flag_ = kCGEventFlagMaskCommand;
+  } else if (modifier == kShift) {
+    modifier_flag_ = kCGEventFlagMaskShift;
+  } else {
+    return false;
+  }
+  // The tap only listens to the events, so it never delays the key presses
+  // in the other apps.
+  CGEventMask mask = CGEventMaskBit(kCGEventFlagsChanged) | CGEventMaskBit(kCGEventKeyDown);
+  event_tap_ = CGEventTapCreate(kCGSessionEventTap,
+                                kCGHeadInsertEventTap,
+                                kCGEventTapOptionListenOnly,
+                                mask,
+                                &DoubleTapDetectorMac::onEvent,
+                                this);
+  if (event_tap_ == nullptr) {
+    return false;
+  }
+  run_loop_source_ = CFMachPortCreateRunLoopSource(kCFAllocatorDefault, event_tap_, 0);
+  CFRunLoopAddSource(CFRunLoopGetMain(), run_loop_source_, kCFRunLoopCommonModes);
+  CGEventTapEnable(event_tap_, true);
+  reset();
+  return true;
+}
+
+void DoubleTapDetectorMac::stop() {
+  if (run_loop_source_ != nullptr) {
+    CFRunLoopRemoveSource(CFRunLoopGetMain(), run_loop_source_, kCFRunLoopCommonModes);
+    CFRelease(run_loop_source_);
+    run_loop_source_ = nullptr;
+  }
+  if (event_tap_ != nullptr) {
+    CGEventTapEnable(event_tap_, false);
+    CFMachPortInvalidate(event_tap_);
+    CFRelease(event_tap_);
+    event_tap_ = nullptr;
+  }
+  reset();
+}
+
+bool DoubleTapDetectorMac::isStarted() const {
+  return event_tap_ != nullptr;
+}
+
+bool DoubleTapDetectorMac::canCreateEventTap() {
+  CFMachPortRef tap = CGEventTapCreate(kCGSessionEventTap,
+                                       kCGHeadInsertEventTap,
+                                       kCGEventTapOptionListenOnly,
+                                       CGEventMaskBit(kCGEventFlagsChanged),
+                                       [](CGEventTapProxy, CGEventType, CGEventRef event, void *) {
+                                         return event;
+                                       },
+                                       nullptr);
+  if (tap == nullptr) {
+    return false;
+  }
+  CFMachPortInvalidate(tap);
+  CFRelease(tap);
+  return true;
+}
+
+CGEventRef DoubleTapDetectorMac::onEvent(CGEventTapProxy proxy,
+                                         CGEventType type,
+                                         CGEventRef event,
+                                         void *info) {
+  auto detector = static_cast<DoubleTapDetectorMac *>(info);
+  detector->handleEvent(type, event);
+  return event;
+}
+
+void DoubleTapDetectorMac::handleEvent(CGEventType type, CGEventRef event) {
+  // The system disables the tap if it doesn't respond in time.
+  if (type == kCGEventTapDisabledByTimeout || type == kCGEventTapDisabledByUserInput) {
+    if (event_tap_ != nullptr) {
+      CGEventTapEnable(event_tap_, true);
+    }
+    reset();
+    return;
+  }
+  if (type == kCGEventKeyDown) {
+    reset();
+    return;
+  }
+  if (type != kCGEventFlagsChanged) {
+    return;
+  }
+  CGEventFlags flags = CGEventGetFlags(event) & kModifierFlagsMask;
+  if (flags == modifier_flag_) {
+    modifier_pressed_ = true;
+    return;
+  }
+  if (flags != 0 || !modifier_pressed_) {
+    reset();
+    return;
+  }
+  // The modifier has been released without pressing any other key.
+  modifier_pressed_ = false;
+  CFAbsoluteTime now = CFAbsoluteTimeGetCurrent();
+  if (now - last_tap_time_ <= kDoubleTapInterval) {
+    last_tap_time_ = 0;
+    if (handler_) {
+      handler_();
+    }
+  } else {
+    last_tap_time_ = now;
+  }
+}
+
+void DoubleTapDetectorMac::reset() {
+  modifier_pressed_ = false;
+  last_tap_time_ = 0;
+}
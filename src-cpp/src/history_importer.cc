@@ -0,0 +1,58 @@
+#include "history_importer.h"
+
+#include <sqlite3.h>
+
+#include "utils.h"
+
+// The number of seconds between the UNIX epoch and the Core Data reference
+// date (2001-01-01 00:00:00 UTC).
+const long long kCoreDataEpochOffset = 978307200;
+
+std::string readMaccyHistory(const std::string &db_path) {
+  sqlite3 *db = nullptr;
+  if (sqlite3_open_v2(db_path.c_str(), &db, SQLITE_OPEN_READONLY, nullptr) != SQLITE_OK) {
+    sqlite3_close(db);
+    return "";
+  }
+
+  const char *query =
+      "SELECT c.ZVALUE, i.ZLASTCOPIEDAT, i.ZNUMBEROFCOPIES, i.ZPIN "
+      "FROM ZHISTORYITEM i "
+      "JOIN ZHISTORYITEMCONTENT c ON c.ZITEM = i.Z_PK "
+      "WHERE c.ZTYPE = 'public.utf8-plain-text' "
+      "ORDER BY i.ZLASTCOPIEDAT";
+  sqlite3_stmt *stmt = nullptr;
+  if (sqlite3_prepare_v2(db, query, -1, &stmt, nullptr) != SQLITE_OK) {
+    sqlite3_close(db);
+    return "";
+  }
+
+  std::string result = "[";
+  bool first = true;
+  while (sqlite3_step(stmt) == SQLITE_ROW) {
+    auto data = static_cast<const char *>(sqlite3_column_blob(stmt, 0));
+    int size = sqlite3_column_bytes(stmt, 0);
+    if (data == nullptr || size == 0) {
+      continue;
+    }
+    std::string content(data, size);
+    auto copy_time = static_cast<long long>(
+        (sqlite3_column_double(stmt, 1) + kCoreDataEpochOffset) * 1000);
+    int number_of_copies = sqlite3_column_int(stmt, 2);
+    bool pinned = sqlite3_column_type(stmt, 3) != SQLITE_NULL;
+
+    if (!first) {
+      result += ",";
+    }
+    first = false;
+    result += "{\"content\":" + toJsonString(content) +
+        ",\"copyTime\":" + std::to_string(copy_time) +
+        ",\"numberOfCopies\":" + std::to_string(number_of_copies) +
+        ",\"favorite\":" + (pinned ? "true" : "false") + "}";
+  }
+  result += "]";
+
+  sqlite3_finalize(stmt);
+  sqlite3_close(db);
+  return result;
+}
@@ -0,0 +1,12 @@
+#ifndef CLIPBOOK_HISTORY_IMPORTER_H_
+#define CLIPBOOK_HISTORY_IMPORTER_H_
+
+#include <string>
+
+// Reads the clipboard history from the Maccy's Core Data SQLite database at
+// the given path and returns it as a JSON array of objects with the
+// "content", "copyTime", "numberOfCopies", and "favorite" properties.
+// Returns an empty string if the database cannot be read.
+std::string readMaccyHistory(const std::string &db_path);
+
+#endif  // CLIPBOOK_HISTORY_IMPORTER_H_
@@ -0,0 +1,35 @@
+#ifndef CLIPBOOK_LAN_SHARE_MAC_H_
+#define CLIPBOOK_LAN_SHARE_MAC_H_
+
+#include <functional>
+#include <memory>
+#include <string>
+#include <vector>
+
+// Shares the history items with the other ClipBook instances on the local
+// network. The instances advertise themselves with Bonjour and exchange the
+// items over TLS authenticated with a pre-shared key derived from the
+// sharing key, so only the instances with the same key can talk to each
+// other.
+class LanShareMac {
+ public:
+  using Receiver = std::function<void(const std::string &payload)>;
+  using SendCallback = std::function<void(bool success)>;
+
+  explicit LanShareMac(Receiver receiver);
+  ~LanShareMac();
+
+  bool start(const std::string &device_name, const std::string &key);
+  void stop();
+  bool isRunning() const;
+
+  // Returns the names of the discovered peers.
+  std::vector<std::string> peers() const;
+  void send(const std::string &peer, const std::string &payload, SendCallback callback);
+
+ private:
+  struct Impl;
+  std::unique_ptr<Impl> impl_;
+};
+
+#endif // CLIPBOOK_LAN_SHARE_MAC_H_
@@ -0,0 +1,241 @@
+#include "lan_share_mac.h"
+
+#import <Foundation/Foundation.h>
+#import <CommonCrypto/CommonHMAC.h>
+#import <Network/Network.h>
+
+#include <map>
+
+static const char *kServiceType = "_clipbook._tcp";
+static const char *kPskIdentity = "ClipBook";
+// The largest payload accepted from a peer.
+static const uint32_t kMaxPayloadSize = 16 * 1024 * 1024;
+
+struct LanShareMac::Impl {
+  Receiver receiver;
+  std::string device_name;
+  std::string key;
+  nw_listener_t listener = nullptr;
+  nw_browser_t browser = nullptr;
+  std::map<std::string, nw_endpoint_t> peers;
+};
+
+// Derives the TLS pre-shared key from the sharing key, so the key itself is
+// never sent over the network.
+static dispatch_data_t createPreSharedKey(const std::string &key) {
+  unsigned char digest[CC_SHA256_DIGEST_LENGTH];
+  const char *label = "ClipBook LAN sharing";
+  CCHmac(kCCHmacAlgSHA256, key.data(), key.size(), label, strlen(label), digest);
+  return dispatch_data_create(digest, sizeof(digest), nullptr, DISPATCH_DATA_DESTRUCTOR_DEFAULT);
+}
+
+static nw_parameters_t createParameters(const std::string &key) {
+  dispatch_data_t psk = createPreSharedKey(key);
+  dispatch_data_t identity = dispatch_data_create(kPskIdentity, strlen(kPskIdentity), nullptr,
+                                                  DISPATCH_DATA_DESTRUCTOR_DEFAULT);
+  nw_parameters_t parameters = nw_parameters_create_secure_tcp(^(nw_protocol_options_t tls_options) {
+    sec_protocol_options_t options = nw_tls_copy_sec_protocol_options(tls_options);
+    sec_protocol_options_add_pre_shared_key(options, psk, identity);
+    sec_protocol_options_append_tls_ciphersuite(options, tls_ciphersuite_AES_128_GCM_SHA256);
+    sec_protocol_options_set_min_tls_protocol_version(options, tls_protocol_version_TLSv12);
+    nw_release(options);
+  }, NW_PARAMETERS_DEFAULT_CONFIGURATION);
+  nw_parameters_set_include_peer_to_peer(parameters, true);
+  dispatch_release(psk);
+  dispatch_release(identity);
+  return parameters;
+}
+
+static std::string serviceName(nw_endpoint_t endpoint) {
+  const char *name = nw_endpoint_get_bonjour_service_name(endpoint);
+  return name ? name : "";
+}
+
+// Reads the payload that starts with its size as a 4-byte big-endian number.
+static void receivePayload(nw_connection_t connection, LanShareMac::Receiver receiver) {
+  nw_connection_receive(connection, 4, 4, ^(dispatch_data_t header, nw_content_context_t,
+                                            bool, nw_error_t error) {
+    if (error || !header) {
+      nw_connection_cancel(connection);
+      return;
+    }
+    __block uint32_t size = 0;
+    dispatch_data_apply(header, ^bool(dispatch_data_t, size_t, const void *buffer, size_t length) {
+      for (size_t i = 0; i < length; ++i) {
+        size = (size << 8) | static_cast<const uint8_t *>(buffer)[i];
+      }
+      return true;
+    });
+    if (size == 0 || size > kMaxPayloadSize) {
+      nw_connection_cancel(connection);
+      return;
+    }
+    nw_connection_receive(connection, size, size, ^(dispatch_data_t body, nw_content_context_t,
+                                                    bool, nw_error_t error) {
+      if (!error && body) {
+        __block std::string payload;
+        dispatch_data_apply(body, ^bool(dispatch_data_t, size_t, const void *buffer, size_t length) {
+          payload.append(static_cast<const char *>(buffer), length);
+          return true;
+        });
+        receiver(payload);
+      }
+      nw_connection_cancel(connection);
+    });
+  });
+}
+
+LanShareMac::LanShareMac(Receiver receiver) : impl_(std::make_unique<Impl>()) {
+  impl_->receiver = std::move(receiver);
+}
+
+LanShareMac::~LanShareMac() {
+  stop();
+}
+
+bool LanShareMac::start(const std::string &device_name, const std::string &key) {
+  if (isRunning()) {
+    stop();
+  }
+  if (key.empty()) {
+    return false;
+  }
+  impl_->device_name = device_name;
+  impl_->key = key;
+
+  nw_parameters_t parameters = createParameters(key);
+  impl_->listener = nw_listener_create(parameters);
+  nw_release(parameters);
+  if (!impl_->listener) {
+    return false;
+  }
+  nw_advertise_descriptor_t advertise = nw_advertise_descriptor_create_bonjour_service(
+      device_name.c_str(), kServiceType, nullptr);
+  nw_listener_set_advertise_descriptor(impl_->listener, advertise);
+  nw_release(advertise);
+  nw_listener_set_queue(impl_->listener, dispatch_get_main_queue());
+  Impl *impl = impl_.get();
+  nw_listener_set_new_connection_handler(impl_->listener, ^(nw_connection_t connection) {
+    nw_connection_set_queue(connection, dispatch_get_main_queue());
+    nw_retain(connection);
+    nw_connection_set_state_changed_handler(connection, ^(nw_connection_state_t state, nw_error_t) {
+      if (state == nw_connection_state_ready) {
+        receivePayload(connection, impl->receiver);
+      } else if (state == nw_connection_state_cancelled) {
+        nw_release(connection);
+      } else if (state == nw_connection_state_failed) {
+        nw_connection_cancel(connection);
+      }
+    });
+    nw_connection_start(connection);
+  });
+  nw_listener_start(impl_->listener);
+
+  nw_browse_descriptor_t descriptor = nw_browse_descriptor_create_bonjour_service(kServiceType, nullptr);
+  nw_parameters_t browse_parameters = nw_parameters_create();
+  nw_parameters_set_include_peer_to_peer(browse_parameters, true);
+  impl_->browser = nw_browser_create(descriptor, browse_parameters);
+  nw_release(descriptor);
+  nw_release(browse_parameters);
+  nw_browser_set_queue(impl_->browser, dispatch_get_main_queue());
+  nw_browser_set_browse_results_changed_handler(impl_->browser, ^(nw_browse_result_t old_result,
+                                                                   nw_browse_result_t new_result,
+                                                                   bool) {
+    if (old_result) {
+      nw_endpoint_t endpoint = nw_browse_result_copy_endpoint(old_result);
+      auto it = impl->peers.find(serviceName(endpoint));
+      if (it != impl->peers.end()) {
+        nw_release(it->second);
+        impl->peers.erase(it);
+      }
+      nw_release(endpoint);
+    }
+    if (new_result) {
+      nw_endpoint_t endpoint = nw_browse_result_copy_endpoint(new_result);
+      std::string name = serviceName(endpoint);
+      if (name.empty() || name == impl->device_name || impl->peers.count(name)) {
+        nw_release(endpoint);
+      } else {
+        impl->peers[name] = endpoint;
+      }
+    }
+  });
+  nw_browser_start(impl_->browser);
+  return true;
+}
+
+void LanShareMac::stop() {
+  if (impl_->listener) {
+    nw_listener_cancel(impl_->listener);
+    nw_release(impl_->listener);
+    impl_->listener = nullptr;
+  }
+  if (impl_->browser) {
+    nw_browser_cancel(impl_->browser);
+    nw_release(impl_->browser);
+    impl_->browser = nullptr;
+  }
+  for (auto &peer : impl_->peers) {
+    nw_release(peer.second);
+  }
+  impl_->peers.clear();
+}
+
+bool LanShareMac::isRunning() const {
+  return impl_->listener != nullptr;
+}
+
+std::vector<std::string> LanShareMac::peers() const {
+  std::vector<std::string> names;
+  for (const auto &peer : impl_->peers) {
+    names.push_back(peer.first);
+  }
+  return names;
+}
+
+void LanShareMac::send(const std::string &peer, const std::string &payload, SendCallback callback) {
+  auto it = impl_->peers.find(peer);
+  if (it == impl_->peers.end() || payload.empty() || payload.size() > kMaxPayloadSize) {
+    callback(false);
+    return;
+  }
+  nw_parameters_t parameters = createParameters(impl_->key);
+  nw_connection_t connection = nw_connection_create(it->second, parameters);
+  nw_release(parameters);
+  nw_connection_set_queue(connection, dispatch_get_main_queue());
+
+  uint32_t size = static_cast<uint32_t>(payload.size());
+  std::string message;
+  message.push_back(static_cast<char>((size >> 24) & 0xFF));
+  message.push_back(static_cast<char>((size >> 16) & 0xFF));
+  message.push_back(static_cast<char>((size >> 8) & 0xFF));
+  message.push_back(static_cast<char>(size & 0xFF));
+  message += payload;
+  dispatch_data_t content = dispatch_data_create(message.data(), message.size(), nullptr,
+                                                 DISPATCH_DATA_DESTRUCTOR_DEFAULT);
+  // The callback must be called exactly once.
+  auto done = std::make_shared<bool>(false);
+  auto finish = [callback, done](bool success) {
+    if (!*done) {
+      *done = true;
+      callback(success);
+    }
+  };
+  nw_connection_set_state_changed_handler(connection, ^(nw_connection_state_t state, nw_error_t) {
+    if (state == nw_connection_state_ready) {
+      nw_connection_send(connection, content, NW_CONNECTION_FINAL_MESSAGE_CONTEXT, true,
+                         ^(nw_error_t error) {
+        finish(error == nullptr);
+        nw_connection_cancel(connection);
+      });
+    } else if (state == nw_connection_state_failed) {
+      finish(false);
+      nw_connection_cancel(connection);
+    } else if (state == nw_connection_state_cancelled) {
+      finish(false);
+      dispatch_release(content);
+      nw_release(connection);
+    }
+  });
+  nw_connection_start(connection);
+}
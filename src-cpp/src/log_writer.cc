@@ -0,0 +1,68 @@
+#include "log_writer.h"
+
+#include <deque>
+#include <filesystem>
+#include <fstream>
+#include <utility>
+
+namespace fs = std::filesystem;
+
+LogWriter::LogWriter(std::string dir) : dir_(std::move(dir)) {}
+
+std::string LogWriter::getFilePath(int index) const {
+  if (index == 0) {
+    return dir_ + "/clipbook.log";
+  }
+  return dir_ + "/clipbook." + std::to_string(index) + ".log";
+}
+
+void LogWriter::rotate() {
+  std::error_code error;
+  fs::remove(getFilePath(kMaxBackupFiles), error);
+  for (int index = kMaxBackupFiles - 1; index >= 0; index--) {
+    if (fs::exists(getFilePath(index), error)) {
+      fs::rename(getFilePath(index), getFilePath(index + 1), error);
+    }
+  }
+}
+
+bool LogWriter::write(const std::string &line) {
+  std::lock_guard<std::mutex> guard(mutex_);
+  std::error_code error;
+  if (!fs::exists(dir_, error)) {
+    fs::create_directories(dir_, error);
+  }
+  auto file_path = getFilePath(0);
+  if (fs::exists(file_path, error) && fs::file_size(file_path, error) >= kMaxFileSize) {
+    rotate();
+  }
+  std::ofstream file(file_path, std::ios::app);
+  if (!file.is_open()) {
+    return false;
+  }
+  file << line << '\n';
+  return !file.fail();
+}
+
+std::vector<std::string> LogWriter::readRecent(size_t count) {
+  std::lock_guard<std::mutex> guard(mutex_);
+  std::deque<std::string> lines;
+  // Read the files from the newest to the oldest until there are enough lines.
+  for (int index = 0; index <= kMaxBackupFiles && lines.size() < count; index++) {
+    std::ifstream file(getFilePath(index));
+    if (!file.is_open()) {
+      break;
+    }
+    std::vector<std::string> file_lines;
+    std::string line;
+    while (std::getline(file, line)) {
+      if (!line.empty()) {
+        file_lines.push_back(line);
+      }
+    }
+    for (auto it = file_lines.rbegin(); it != file_lines.rend() && lines.size() < count; ++it) {
+      lines.push_front(*it);
+    }
+  }
+  return {lines.begin(), lines.end()};
+}
@@ -0,0 +1,33 @@
+#ifndef CLIPBOOK_LOG_WRITER_H_
+#define CLIPBOOK_LOG_WRITER_H_
+
+#include <cstdint>
+#include <mutex>
+#include <string>
+#include <vector>
+
+// Appends the log lines to clipbook.log in the given directory. When the file
+// exceeds the size limit, it's renamed to clipbook.1.log, the older files are
+// shifted, and the oldest one is deleted, so the logs never take more than
+// kMaxFileSize * (kMaxBackupFiles + 1) bytes.
+class LogWriter {
+ public:
+  static constexpr uint64_t kMaxFileSize = 1024 * 1024;
+  static constexpr int kMaxBackupFiles = 4;
+
+  explicit LogWriter(std::string dir);
+
+  bool write(const std::string &line);
+  // Returns up to the given number of the most recent lines, the oldest
+  // line first.
+  std::vector<std::string> readRecent(size_t count);
+
+ private:
+  std::string getFilePath(int index) const;
+  void rotate();
+
+  std::string dir_;
+  std::mutex mutex_;
+};
+
+#endif // CLIPBOOK_LOG_WRITER_H_
@@ -33,6 +33,9 @@ void launch() {
   options.switches.emplace("--disable-component-update");
   options.switches.emplace("--disable-features=OptimizationHintsFetching,NativeNotifications,GatherProcessRequirementMetrics");
   options.switches.emplace("--disable-sync");
+#if OS_MAC
+  MainAppMac::installDeepLinkHandler();
+#endif
 
   App::init(options, [](std::shared_ptr<App> app) {
     std::shared_ptr<MainApp> main_app;
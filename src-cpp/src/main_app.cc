@@ -1,10 +1,13 @@
+#include <algorithm>
 #include <thread>
 #include <iostream>
 #include <fstream>
+#include <sstream>
 #include <filesystem>
 #include <utility>
 
 #include "main_app.h"
+#include "history_importer.h"
 #include "utils.h"
 #include "webview.h"
 
@@ -23,6 +26,14 @@ std::string kContactSupportUrl =
     "https://clipbook.app/contacts/?utm_source=clipbook";
 std::string kFeedbackUrl = "https://feedback.clipbook.app/?utm_source=clipbook";
 int32_t kUpdateCheckIntervalInHours = 24;
+// The maximum time to wait for the background tasks to finish on quit.
+int32_t kShutdownTimeoutInMs = 5000;
+int32_t kShutdownCheckIntervalInMs = 50;
+int32_t kPauseForDurationInSecs = 10 * 60;
+// The name of the API server token in the Keychain.
+std::string kApiServerTokenSecret = "api.token";
+// The name of the LAN sharing key in the Keychain.
+std::string kLanSharingKeySecret = "lan.key";
 
 std::string appDialogsUpdateAvailableTitle;
 std::string appDialogsUpdateAvailableMessage;
@@ -68,12 +79,20 @@ MainApp::MainApp(const std::shared_ptr<App> &app, const std::shared_ptr<AppSetti
       app_window_visible_(false),
       checking_for_updates_(false),
       app_paused_(false),
+      guest_mode_(false),
+      paused_until_(0),
+      pause_generation_(0),
       after_system_reboot_(false),
       update_available_(false),
       app_hide_time_(0),
       settings_(settings) {
   request_interceptor_ = std::make_shared<UrlRequestInterceptor>(
       app_->profile()->path(), app_->getPath(molybden::PathKey::kAppResources));
+  log_writer_ = std::make_unique<LogWriter>(getLogsDir());
+  capture_journal_ = std::make_shared<CaptureJournal>(app_->profile()->path() + "/capture.journal");
+  tray_state_ = std::make_shared<TrayStateController>([this](TrayState state, const std::string &badge) {
+    applyTrayState(state, badge);
+  });
 }
 
 bool MainApp::init() {
@@ -98,6 +117,10 @@ bool MainApp::init() {
     }
   });
 
+  pause_for_item_ = menu::Item("Pause for 10 Minutes", [this](const CustomMenuItemActionArgs &args) {
+    pause(kPauseForDurationInSecs);
+  });
+
   check_for_updates_item_ =
       menu::Item("Check for Updates...", [this](const CustomMenuItemActionArgs &args) {
         checkForUpdates(true);
@@ -146,8 +169,14 @@ bool MainApp::init() {
   enableOpenAppShortcut();
   //Register a global shortcut to pause/resume the app.
   enablePauseResumeShortcut();
+  // Register a global shortcut to switch to the next profile.
+  enableCycleProfileShortcut();
+  // Start detecting the double press of the modifier key.
+  enableDoubleTapShortcut();
   // Register a global shortcut to paste the next item to the active app.
   enablePasteNextItemShortcut();
+  // Register the global shortcuts to paste the recent items to the active app.
+  enablePasteRecentShortcuts();
   // Update the open settings shortcut.
   updateOpenSettingsShortcut();
 
@@ -297,6 +326,10 @@ std::shared_ptr<AppSettings> MainApp::settings() const {
   return settings_;
 }
 
+std::shared_ptr<CaptureJournal> MainApp::captureJournal() const {
+  return capture_journal_;
+}
+
 void MainApp::pasteNextItemToActiveApp() {
   std::thread([this]() {
     auto frame = app_window_->mainFrame();
@@ -306,6 +339,97 @@ void MainApp::pasteNextItemToActiveApp() {
   }).detach();
 }
 
+void MainApp::pasteRecentItemToActiveApp(int number) {
+  std::thread([this, number]() {
+    auto frame = app_window_->mainFrame();
+    if (frame) {
+      frame->executeJavaScript("pasteRecentItemToActiveApp(" + std::to_string(number) + ")");
+    }
+  }).detach();
+}
+
+// The register names are validated by the frontend, so they can be safely
+// passed as a string literal.
+void MainApp::pasteRegisterToActiveApp(const std::string &name) {
+  std::thread([this, name]() {
+    auto frame = app_window_->mainFrame();
+    if (frame) {
+      frame->executeJavaScript("pasteRegisterToActiveApp('" + name + "')");
+    }
+  }).detach();
+}
+
+void MainApp::cycleProfile() {
+  std::thread([this]() {
+    auto frame = app_window_->mainFrame();
+    if (frame) {
+      frame->executeJavaScript("cycleProfile()");
+    }
+  }).detach();
+}
+
+// The shortcut ids are constants defined by the app, so they can be safely
+// put in the JavaScript string.
+void MainApp::shortcutUsed(const std::string &id) {
+  std::thread([this, id]() {
+    auto frame = app_window_->mainFrame();
+    if (frame) {
+      frame->executeJavaScript("shortcutUsed('" + id + "')");
+    }
+  }).detach();
+}
+
+void MainApp::configFileChanged() {
+  std::thread([this]() {
+    auto frame = app_window_->mainFrame();
+    if (frame) {
+      frame->executeJavaScript("configFileChanged()");
+    }
+  }).detach();
+}
+
+std::string MainApp::getConfigFilePath() {
+  return getUserDataDir() + "/clipbook.toml";
+}
+
+void MainApp::performDoubleTapAction() {
+  auto action = settings_->getDoubleTapAction();
+  if (action == "pasteNextItem") {
+    pasteNextItemToActiveApp();
+  } else if (action == "pauseResume") {
+    if (isPaused()) {
+      resume();
+    } else {
+      pause();
+    }
+  } else if (action == "cycleProfile") {
+    cycleProfile();
+  } else {
+    show();
+  }
+}
+
+void MainApp::pasteTrayItemToActiveApp(int id) {
+  std::thread([this, id]() {
+    auto frame = app_window_->mainFrame();
+    if (frame) {
+      frame->executeJavaScript("pasteTrayItemToActiveApp(" + std::to_string(id) + ")");
+    }
+  }).detach();
+}
+
+void MainApp::clearTrayRecentItems() {
+  std::lock_guard<std::mutex> guard(tray_recent_items_mutex_);
+  tray_recent_items_.clear();
+}
+
+void MainApp::addTrayRecentItem(int id, const std::string &title) {
+  std::lock_guard<std::mutex> guard(tray_recent_items_mutex_);
+  tray_recent_items_.push_back(menu::Item(title, [this, id](const CustomMenuItemActionArgs &args) {
+    pasteTrayItemToActiveApp(id);
+  }));
+}
+
 void MainApp::setActiveAppInfo(const std::string &app_name, const std::string& app_icon) {
   std::thread([this, app_name, app_icon]() {
     auto frame = app_window_->mainFrame();
@@ -579,6 +703,9 @@ void MainApp::showSettingsWindow() {
 }
 
 void MainApp::showSettingsWindow(const std::string &section) {
+  if (guest_mode_) {
+    return;
+  }
   if (settings_window_ && !settings_window_->isClosed()) {
     settings_window_->navigation()->loadUrl(app_->baseUrl() + section);
     settings_window_->show();
@@ -674,12 +801,18 @@ void MainApp::initJavaScriptApi(const std::shared_ptr<molybden::JsObject> &windo
   window->putProperty("copyToClipboardAfterMerge", [this](std::string text) {
     copyToClipboardAfterMerge(std::move(text));
   });
+  window->putProperty("writeToFindPasteboard", [this](std::string text) {
+    writeToFindPasteboard(text);
+  });
   window->putProperty("saveImageAsFile", [this](std::string imageFileName, int imageWidth, int imageHeight) {
     saveImageAsFile(imageFileName, imageWidth, imageHeight);
   });
   window->putProperty("deleteImage", [this](std::string imageFileName) {
     deleteImage(std::move(imageFileName));
   });
+  window->putProperty("secureDeleteImage", [this](std::string imageFileName) {
+    deleteImage(std::move(imageFileName), true);
+  });
   window->putProperty("openInBrowser", [this](std::string url) {
     app_->desktop()->openUrl(url);
     hide();
@@ -694,6 +827,38 @@ void MainApp::initJavaScriptApi(const std::shared_ptr<molybden::JsObject> &windo
   window->putProperty("previewFile", [this](std::string filePath) {
     preview(filePath);
   });
+  window->putProperty("shareItem", [this](std::string text, std::string url, std::string imageFileName) {
+    share(text, url, imageFileName);
+  });
+  window->putProperty("announce", [this](std::string message) {
+    announce(message);
+  });
+  window->putProperty("showCopyNotification", [this](int item_id, std::string title, std::string body) {
+    // The action titles are translated in the renderer, so do not block it.
+    std::thread([this, item_id, title, body]() {
+      showCopyNotification(item_id, title, body);
+    }).detach();
+  });
+  window->putProperty("showNotification", [this](std::string title, std::string body) {
+    std::thread([this, title, body]() {
+      showNotification(title, body);
+    }).detach();
+  });
+  window->putProperty("isAccessibilityAccessGranted", [this]() -> bool {
+    return isAccessibilityAccessGranted();
+  });
+  window->putProperty("getPermissionStatus", [this](std::string kind) -> std::string {
+    return getPermissionStatus(kind);
+  });
+  window->putProperty("requestPermission", [this](std::string kind) {
+    requestPermission(kind);
+  });
+  window->putProperty("showAppWindow", [this]() {
+    show();
+  });
+  window->putProperty("isAppWindowVisible", [this]() -> bool {
+    return app_window_visible_;
+  });
   window->putProperty("hideAppWindow", [this]() {
     hide(true);
   });
@@ -708,6 +873,13 @@ void MainApp::initJavaScriptApi(const std::shared_ptr<molybden::JsObject> &windo
     activate();
     showSettingsWindow();
   });
+  window->putProperty("setGuestMode", [this](bool enabled) {
+    guest_mode_ = enabled;
+    if (enabled && settings_window_ && !settings_window_->isClosed()) {
+      settings_window_->hide();
+      settings_window_->close();
+    }
+  });
   window->putProperty("clearEntireHistory", [this]() {
     clearHistory();
   });
@@ -744,18 +916,72 @@ void MainApp::initJavaScriptApi(const std::shared_ptr<molybden::JsObject> &windo
   window->putProperty("disableOpenAppShortcut", [this]() {
     disableOpenAppShortcut();
   });
+  window->putProperty("clearTrayRecentItems", [this]() {
+    clearTrayRecentItems();
+  });
+  window->putProperty("addTrayRecentItem", [this](int id, std::string title) {
+    addTrayRecentItem(id, title);
+  });
+  window->putProperty("setTraySyncing", [this](bool syncing) {
+    tray_state_->setSyncing(syncing);
+  });
+  window->putProperty("setTrayError", [this](bool error) {
+    tray_state_->setError(error);
+  });
+  window->putProperty("setTrayBadgeCount", [this](int count) {
+    tray_state_->setBadgeCount(count);
+  });
+  window->putProperty("pauseApp", [this](int duration_secs) {
+    pause(duration_secs);
+  });
+  window->putProperty("resumeApp", [this]() {
+    resume();
+  });
+  window->putProperty("isAppPaused", [this]() -> bool {
+    return isPaused();
+  });
+  window->putProperty("getPausedUntil", [this]() -> double {
+    return static_cast<double>(getPausedUntil());
+  });
   window->putProperty("enablePauseResumeShortcut", [this]() {
     enablePauseResumeShortcut();
   });
   window->putProperty("disablePauseResumeShortcut", [this]() {
     disablePauseResumeShortcut();
   });
+  window->putProperty("enableCycleProfileShortcut", [this]() {
+    enableCycleProfileShortcut();
+  });
+  window->putProperty("disableCycleProfileShortcut", [this]() {
+    disableCycleProfileShortcut();
+  });
+  window->putProperty("enableDoubleTapShortcut", [this]() {
+    enableDoubleTapShortcut();
+  });
+  window->putProperty("disableDoubleTapShortcut", [this]() {
+    disableDoubleTapShortcut();
+  });
+  window->putProperty("isDoubleTapShortcutActive", [this]() -> bool {
+    return isDoubleTapShortcutActive();
+  });
   window->putProperty("enablePasteNextItemShortcut", [this]() {
     enablePasteNextItemShortcut();
   });
   window->putProperty("disablePasteNextItemShortcut", [this]() {
     disablePasteNextItemShortcut();
   });
+  window->putProperty("enablePasteRecentShortcuts", [this]() {
+    enablePasteRecentShortcuts();
+  });
+  window->putProperty("disablePasteRecentShortcuts", [this]() {
+    disablePasteRecentShortcuts();
+  });
+  window->putProperty("addRegisterShortcut", [this](std::string name, std::string shortcut) {
+    addRegisterShortcut(name, shortcut);
+  });
+  window->putProperty("clearRegisterShortcuts", [this]() {
+    clearRegisterShortcuts();
+  });
   window->putProperty("updateOpenSettingsShortcut", [this]() {
     updateOpenSettingsShortcut();
   });
@@ -768,6 +994,12 @@ void MainApp::initJavaScriptApi(const std::shared_ptr<molybden::JsObject> &windo
   window->putProperty("getAppInfo", [this](std::string appPath) -> std::string {
     return getAppInfo(appPath);
   });
+  window->putProperty("getAppBundleId", [this](std::string appPath) -> std::string {
+    return getAppBundleId(appPath);
+  });
+  window->putProperty("getAppIconFile", [this](std::string appPath) -> std::string {
+    return getAppIconFile(appPath);
+  });
   window->putProperty("getRecommendedAppsInfo", [this](std::string filePath) -> std::string {
     return getRecommendedAppsInfo(filePath);
   });
@@ -859,6 +1091,278 @@ void MainApp::initJavaScriptApi(const std::shared_ptr<molybden::JsObject> &windo
   window->putProperty("isFileExists", [](std::string filePath) -> bool {
     return fs::exists(filePath);
   });
+  window->putProperty("writeTextFile", [this](std::string filePath, std::string content) -> bool {
+    if (!isAccessiblePath(filePath)) {
+      LOG(WARNING) << "Writing files outside of the accessible directories is not allowed: " << filePath;
+      return false;
+    }
+    std::ofstream outputFile(filePath);
+    if (!outputFile.is_open()) {
+      return false;
+    }
+    outputFile << content;
+    outputFile.close();
+    return !outputFile.fail();
+  });
+  window->putProperty("writeTextFileAtomically", [this](std::string filePath, std::string content) -> bool {
+    if (!isAccessiblePath(filePath)) {
+      LOG(WARNING) << "Writing files outside of the accessible directories is not allowed: " << filePath;
+      return false;
+    }
+    // Write to a temporary file and replace the target, so that the readers
+    // never see a partially written file.
+    std::string tempPath = filePath + ".tmp";
+    std::ofstream outputFile(tempPath);
+    if (!outputFile.is_open()) {
+      return false;
+    }
+    outputFile << content;
+    outputFile.close();
+    std::error_code error;
+    if (outputFile.fail()) {
+      fs::remove(tempPath, error);
+      return false;
+    }
+    fs::rename(tempPath, filePath, error);
+    return !error;
+  });
+  window->putProperty("copyFile", [this](std::string sourcePath, std::string targetPath) -> bool {
+    if (!isAccessiblePath(sourcePath) || !isAccessiblePath(targetPath)) {
+      LOG(WARNING) << "Copying files outside of the accessible directories is not allowed: "
+                   << sourcePath << " -> " << targetPath;
+      return false;
+    }
+    std::error_code error;
+    return fs::copy_file(sourcePath, targetPath, fs::copy_options::none, error);
+  });
+  window->putProperty("readCaptureJournal", [this]() -> std::string {
+    std::string result = "[";
+    for (const auto &entry : capture_journal_->getRecoveredEntries()) {
+      if (result.size() > 1) {
+        result += ",";
+      }
+      result += "{\"id\":" + std::to_string(entry.id) + ",\"data\":" + entry.data + "}";
+    }
+    return result + "]";
+  });
+  window->putProperty("acknowledgeCapture", [this](double id) {
+    capture_journal_->acknowledge(static_cast<long long>(id));
+  });
+  window->putProperty("compactCaptureJournal", [this]() {
+    capture_journal_->compact();
+  });
+  window->putProperty("clearCaptureJournal", [this]() {
+    capture_journal_->clear();
+  });
+  window->putProperty("writeLogLine", [this](std::string line) -> bool {
+    return log_writer_->write(line);
+  });
+  window->putProperty("readRecentLogLines", [this](int count) -> std::string {
+    std::string result;
+    for (const auto &line : log_writer_->readRecent(count > 0 ? count : 0)) {
+      result += line + "\n";
+    }
+    return result;
+  });
+  window->putProperty("readTextFile", [this](std::string filePath) -> std::string {
+    if (!isAccessiblePath(filePath)) {
+      LOG(WARNING) << "Reading files outside of the accessible directories is not allowed: " << filePath;
+      return "";
+    }
+    std::ifstream inputFile(filePath);
+    if (!inputFile.is_open()) {
+      return "";
+    }
+    std::stringstream buffer;
+    buffer << inputFile.rdbuf();
+    return buffer.str();
+  });
+  window->putProperty("getBackupsDir", [this]() -> std::string {
+    auto backupsDir = getBackupsDir();
+    if (!fs::exists(backupsDir)) {
+      fs::create_directories(backupsDir);
+    }
+    return backupsDir;
+  });
+  window->putProperty("getBlobsDir", [this]() -> std::string {
+    auto blobsDir = getBlobsDir();
+    if (!fs::exists(blobsDir)) {
+      fs::create_directories(blobsDir);
+    }
+    return blobsDir;
+  });
+  window->putProperty("getBackupFiles", [this]() -> std::string {
+    std::vector<std::string> fileNames;
+    auto backupsDir = getBackupsDir();
+    if (fs::exists(backupsDir)) {
+      for (const auto &entry : fs::directory_iterator(backupsDir)) {
+        if (entry.is_regular_file() && entry.path().extension() == ".json") {
+          fileNames.push_back(entry.path().filename().string());
+        }
+      }
+    }
+    std::sort(fileNames.begin(), fileNames.end());
+    std::string result;
+    for (const auto &fileName : fileNames) {
+      if (!result.empty()) {
+        result += "*";
+      }
+      result += fileName;
+    }
+    return result;
+  });
+  window->putProperty("deleteBackupFile", [this](std::string fileName) {
+    auto filePath = fs::path(getBackupsDir()) / fs::path(fileName).filename();
+    if (fs::exists(filePath)) {
+      fs::remove(filePath);
+    }
+  });
+  window->putProperty("createDirectory", [this](std::string dirPath) -> bool {
+    if (!isAccessiblePath(dirPath)) {
+      LOG(WARNING) << "Creating directories outside of the accessible directories is not allowed: " << dirPath;
+      return false;
+    }
+    std::error_code error;
+    fs::create_directories(dirPath, error);
+    return fs::is_directory(dirPath);
+  });
+  window->putProperty("getFilesInDir", [this](std::string dirPath, std::string extension) -> std::string {
+    std::vector<std::string> fileNames;
+    if (!isAccessiblePath(dirPath)) {
+      LOG(WARNING) << "Listing files outside of the accessible directories is not allowed: " << dirPath;
+    } else if (fs::is_directory(dirPath)) {
+      for (const auto &entry : fs::directory_iterator(dirPath)) {
+        if (entry.is_regular_file() && entry.path().extension() == extension) {
+          fileNames.push_back(entry.path().filename().string());
+        }
+      }
+    }
+    std::sort(fileNames.begin(), fileNames.end());
+    std::string result;
+    for (const auto &fileName : fileNames) {
+      if (!result.empty()) {
+        result += "*";
+      }
+      result += fileName;
+    }
+    return result;
+  });
+  window->putProperty("deleteFile", [this](std::string filePath) {
+    if (!isAccessiblePath(filePath)) {
+      LOG(WARNING) << "Deleting files outside of the accessible directories is not allowed: " << filePath;
+      return;
+    }
+    std::error_code error;
+    fs::remove(filePath, error);
+  });
+  window->putProperty("secureDeleteFile", [this](std::string filePath) {
+    if (!isAccessiblePath(filePath)) {
+      LOG(WARNING) << "Deleting files outside of the accessible directories is not allowed: " << filePath;
+      return;
+    }
+    if (fs::exists(filePath) && !secureRemoveFile(filePath)) {
+      LOG(WARNING) << "Failed to securely delete file: " << filePath;
+    }
+  });
+  window->putProperty("startApiServer", [this](int port, std::string token) -> bool {
+    return startApiServer(port, token);
+  });
+  window->putProperty("stopApiServer", [this]() {
+    stopApiServer();
+  });
+  window->putProperty("isApiServerRunning", [this]() -> bool {
+    return isApiServerRunning();
+  });
+  window->putProperty("broadcastApiEvent", [this](std::string event, std::string data) {
+    if (api_server_) {
+      api_server_->broadcast(event, data);
+    }
+  });
+  window->putProperty("authenticateUser", [this](std::string reason) {
+    authenticateUser(reason);
+  });
+  window->putProperty("selectPath", [this, window](int request_id, std::string kind, std::string default_name) {
+    selectPath(window->frame()->browser(), request_id, kind, default_name);
+  });
+  window->putProperty("isScreenSharingActive", [this]() -> bool {
+    return isScreenSharingActive();
+  });
+  window->putProperty("generateQRCode", [this](std::string text, std::string imageFileName) -> bool {
+    return generateQRCode(text, imageFileName);
+  });
+  window->putProperty("requestSync", [this]() {
+    // The history is synced by the main window, so that the settings window
+    // doesn't sync it concurrently.
+    std::thread([this]() {
+      auto frame = app_window_->mainFrame();
+      if (frame) {
+        frame->executeJavaScript("syncNowInBackground()");
+      }
+    }).detach();
+  });
+  window->putProperty("saveSecret", [this](std::string name, std::string value) -> bool {
+    return saveSecret(name, value);
+  });
+  window->putProperty("getSecret", [this](std::string name) -> std::string {
+    return getSecret(name);
+  });
+  window->putProperty("recognizeImageText", [this](std::string imageFileName) {
+    recognizeImageText(imageFileName);
+  });
+  window->putProperty("startLanSharing", [this](std::string deviceName, std::string key) -> bool {
+    return startLanSharing(deviceName, key);
+  });
+  window->putProperty("stopLanSharing", [this]() {
+    stopLanSharing();
+  });
+  window->putProperty("getLanPeers", [this]() -> std::string {
+    std::string result;
+    for (const auto &peer : getLanPeers()) {
+      if (!result.empty()) {
+        result += "*";
+      }
+      result += peer;
+    }
+    return result;
+  });
+  window->putProperty("getRecentPasteboardTypes", [this]() -> std::string {
+    std::string result;
+    for (const auto &type : getRecentPasteboardTypes()) {
+      if (!result.empty()) {
+        result += ",";
+      }
+      result += type;
+    }
+    return result;
+  });
+  window->putProperty("getMemoryUsage", [this]() -> std::string {
+    auto usage = getMemoryUsage();
+    return "{\"residentSize\":" + std::to_string(usage.resident_size) +
+        ",\"peakResidentSize\":" + std::to_string(usage.peak_resident_size) +
+        ",\"footprint\":" + std::to_string(usage.footprint) +
+        ",\"allocatedSize\":" + std::to_string(usage.allocated_size) +
+        ",\"allocatedBlocks\":" + std::to_string(usage.allocated_blocks) + "}";
+  });
+  window->putProperty("getSystemInfo", [this]() -> std::string {
+    auto info = getSystemInfo();
+    return "{\"osVersion\":" + toJsonString(info.os_version) +
+        ",\"cpuCount\":" + std::to_string(info.cpu_count) +
+        ",\"loadAverage\":" + std::to_string(info.load_average) +
+        ",\"totalMemory\":" + std::to_string(info.total_memory) +
+        ",\"availableMemory\":" + std::to_string(info.available_memory) +
+        ",\"diskTotalSpace\":" + std::to_string(info.disk_total_space) +
+        ",\"diskFreeSpace\":" + std::to_string(info.disk_free_space) +
+        ",\"hasBattery\":" + (info.has_battery ? "true" : "false") +
+        ",\"batteryLevel\":" + std::to_string(info.battery_level) +
+        ",\"batteryCharging\":" + (info.battery_charging ? "true" : "false") +
+        ",\"onAcPower\":" + (info.on_ac_power ? "true" : "false") + "}";
+  });
+  window->putProperty("sendToLanPeer", [this](std::string peer, std::string payload) {
+    sendToLanPeer(peer, payload);
+  });
+  window->putProperty("readMaccyHistory", [](std::string dbPath) -> std::string {
+    return readMaccyHistory(dbPath);
+  });
 
   // Settings window.
   window->putProperty("saveLanguage", [this](std::string language) -> void {
@@ -1063,6 +1567,354 @@ void MainApp::initJavaScriptApi(const std::shared_ptr<molybden::JsObject> &windo
     return settings_->isCopyOnNumberActionManaged();
   });
 
+  window->putProperty("saveBackupIntervalHours", [this](int hours) -> void {
+    settings_->saveBackupIntervalHours(hours);
+  });
+  window->putProperty("getBackupIntervalHours", [this]() -> int {
+    return settings_->getBackupIntervalHours();
+  });
+
+  window->putProperty("saveMaxBackups", [this](int count) -> void {
+    settings_->saveMaxBackups(count);
+  });
+  window->putProperty("getMaxBackups", [this]() -> int {
+    return settings_->getMaxBackups();
+  });
+
+  window->putProperty("saveAnnounceClipboardChanges", [this](bool announce) -> void {
+    settings_->saveAnnounceClipboardChanges(announce);
+  });
+  window->putProperty("shouldAnnounceClipboardChanges", [this]() -> bool {
+    return settings_->shouldAnnounceClipboardChanges();
+  });
+  window->putProperty("isAnnounceClipboardChangesManaged", [this]() -> bool {
+    return settings_->isAnnounceClipboardChangesManaged();
+  });
+
+  window->putProperty("saveClearHistoryOnLogout", [this](bool clear) -> void {
+    settings_->saveClearHistoryOnLogout(clear);
+  });
+  window->putProperty("shouldClearHistoryOnLogout", [this]() -> bool {
+    return settings_->shouldClearHistoryOnLogout();
+  });
+  window->putProperty("isClearHistoryOnLogoutManaged", [this]() -> bool {
+    return settings_->isClearHistoryOnLogoutManaged();
+  });
+
+  window->putProperty("saveAutoClearIntervalHours", [this](int hours) -> void {
+    settings_->saveAutoClearIntervalHours(hours);
+  });
+  window->putProperty("getAutoClearIntervalHours", [this]() -> int {
+    return settings_->getAutoClearIntervalHours();
+  });
+
+  window->putProperty("saveVaultDirectory", [this](std::string dir) -> void {
+    settings_->saveVaultDirectory(dir);
+  });
+  window->putProperty("getVaultDirectory", [this]() -> std::string {
+    return settings_->getVaultDirectory();
+  });
+
+  window->putProperty("saveApiServerEnabled", [this](bool enabled) -> void {
+    settings_->saveApiServerEnabled(enabled);
+    if (!enabled) {
+      stopApiServer();
+      return;
+    }
+    // The API server is started by the main window that subscribes to the
+    // history events. This function can be called from the main window, so
+    // don't block its renderer.
+    std::thread([this]() {
+      auto frame = app_window_->mainFrame();
+      if (frame) {
+        frame->executeJavaScript("startApiIfEnabled()");
+      }
+    }).detach();
+  });
+  window->putProperty("shouldApiServerEnabled", [this]() -> bool {
+    return settings_->shouldApiServerEnabled();
+  });
+  window->putProperty("isApiServerEnabledManaged", [this]() -> bool {
+    return settings_->isApiServerEnabledManaged();
+  });
+
+  window->putProperty("saveApiServerPort", [this](int port) -> void {
+    settings_->saveApiServerPort(port);
+  });
+  window->putProperty("getApiServerPort", [this]() -> int {
+    return settings_->getApiServerPort();
+  });
+
+  window->putProperty("saveApiServerToken", [this](std::string token) -> bool {
+    if (!saveSecret(kApiServerTokenSecret, token)) {
+      return false;
+    }
+    settings_->saveApiServerToken("");
+    // Restart the running server so that the old token is no longer accepted.
+    if (isApiServerRunning()) {
+      stopApiServer();
+      startApiServer(settings_->getApiServerPort(), token);
+    }
+    return true;
+  });
+  window->putProperty("getApiServerToken", [this]() -> std::string {
+    return getApiServerToken();
+  });
+
+  window->putProperty("saveLanSharingEnabled", [this](bool enabled) -> void {
+    settings_->saveLanSharingEnabled(enabled);
+    restartLanSharing();
+  });
+  window->putProperty("shouldLanSharingEnabled", [this]() -> bool {
+    return settings_->shouldLanSharingEnabled();
+  });
+  window->putProperty("isLanSharingEnabledManaged", [this]() -> bool {
+    return settings_->isLanSharingEnabledManaged();
+  });
+
+  window->putProperty("saveLanSharingKey", [this](std::string key) -> void {
+    if (!saveSecret(kLanSharingKeySecret, key)) {
+      return;
+    }
+    settings_->saveLanSharingKey("");
+    restartLanSharing();
+  });
+  window->putProperty("getLanSharingKey", [this]() -> std::string {
+    return getLanSharingKey();
+  });
+
+  window->putProperty("saveAppLockEnabled", [this](bool enabled) -> void {
+    settings_->saveAppLockEnabled(enabled);
+    if (!enabled) {
+      std::thread([this]() {
+        auto frame = app_window_->mainFrame();
+        if (frame) {
+          frame->executeJavaScript("appLockDisabled()");
+        }
+      }).detach();
+    }
+  });
+  window->putProperty("shouldAppLockEnabled", [this]() -> bool {
+    return settings_->shouldAppLockEnabled();
+  });
+  window->putProperty("isAppLockEnabledManaged", [this]() -> bool {
+    return settings_->isAppLockEnabledManaged();
+  });
+
+  window->putProperty("saveAutoLockMinutes", [this](int minutes) -> void {
+    settings_->saveAutoLockMinutes(minutes);
+  });
+  window->putProperty("getAutoLockMinutes", [this]() -> int {
+    return settings_->getAutoLockMinutes();
+  });
+
+  window->putProperty("saveSecureDelete", [this](bool enabled) -> void {
+    settings_->saveSecureDelete(enabled);
+  });
+  window->putProperty("shouldSecureDelete", [this]() -> bool {
+    return settings_->shouldSecureDelete();
+  });
+  window->putProperty("isSecureDeleteManaged", [this]() -> bool {
+    return settings_->isSecureDeleteManaged();
+  });
+
+  window->putProperty("saveRetentionDays", [this](std::string type, int days) -> void {
+    settings_->saveRetentionDays(type, days);
+  });
+  window->putProperty("getRetentionDays", [this](std::string type) -> int {
+    return settings_->getRetentionDays(type);
+  });
+  window->putProperty("saveRetentionKeepFavorites", [this](bool keep) -> void {
+    settings_->saveRetentionKeepFavorites(keep);
+  });
+  window->putProperty("shouldRetentionKeepFavorites", [this]() -> bool {
+    return settings_->shouldRetentionKeepFavorites();
+  });
+
+  window->putProperty("saveMaxHistoryItems", [this](int count) -> void {
+    settings_->saveMaxHistoryItems(count);
+  });
+  window->putProperty("getMaxHistoryItems", [this]() -> int {
+    return settings_->getMaxHistoryItems();
+  });
+
+  window->putProperty("saveMaxHistoryMegabytes", [this](int megabytes) -> void {
+    settings_->saveMaxHistoryMegabytes(megabytes);
+  });
+  window->putProperty("getMaxHistoryMegabytes", [this]() -> int {
+    return settings_->getMaxHistoryMegabytes();
+  });
+
+  window->putProperty("saveFetchLinkPreviews", [this](bool fetch) -> void {
+    settings_->saveFetchLinkPreviews(fetch);
+  });
+  window->putProperty("shouldFetchLinkPreviews", [this]() -> bool {
+    return settings_->shouldFetchLinkPreviews();
+  });
+  window->putProperty("isFetchLinkPreviewsManaged", [this]() -> bool {
+    return settings_->isFetchLinkPreviewsManaged();
+  });
+
+  window->putProperty("saveUsePasteRecentShortcuts", [this](bool enabled) -> void {
+    settings_->saveUsePasteRecentShortcuts(enabled);
+  });
+  window->putProperty("shouldUsePasteRecentShortcuts", [this]() -> bool {
+    return settings_->shouldUsePasteRecentShortcuts();
+  });
+  window->putProperty("isUsePasteRecentShortcutsManaged", [this]() -> bool {
+    return settings_->isUsePasteRecentShortcutsManaged();
+  });
+
+  window->putProperty("saveTrashRetentionDays", [this](int days) -> void {
+    settings_->saveTrashRetentionDays(days);
+  });
+  window->putProperty("getTrashRetentionDays", [this]() -> int {
+    return settings_->getTrashRetentionDays();
+  });
+
+  window->putProperty("saveDatabaseMaxAttempts", [this](int attempts) -> void {
+    settings_->saveDatabaseMaxAttempts(attempts);
+  });
+  window->putProperty("getDatabaseMaxAttempts", [this]() -> int {
+    return settings_->getDatabaseMaxAttempts();
+  });
+
+  window->putProperty("saveIgnoreOwnChanges", [this](bool ignore) -> void {
+    settings_->saveIgnoreOwnChanges(ignore);
+  });
+  window->putProperty("shouldIgnoreOwnChanges", [this]() -> bool {
+    return settings_->shouldIgnoreOwnChanges();
+  });
+  window->putProperty("isIgnoreOwnChangesManaged", [this]() -> bool {
+    return settings_->isIgnoreOwnChangesManaged();
+  });
+
+  window->putProperty("saveShowTodayCountInMenuBar", [this](bool show) -> void {
+    settings_->saveShowTodayCountInMenuBar(show);
+  });
+  window->putProperty("shouldShowTodayCountInMenuBar", [this]() -> bool {
+    return settings_->shouldShowTodayCountInMenuBar();
+  });
+  window->putProperty("isShowTodayCountInMenuBarManaged", [this]() -> bool {
+    return settings_->isShowTodayCountInMenuBarManaged();
+  });
+
+  window->putProperty("saveShowCopyNotification", [this](bool show) -> void {
+    settings_->saveShowCopyNotification(show);
+  });
+  window->putProperty("shouldShowCopyNotification", [this]() -> bool {
+    return settings_->shouldShowCopyNotification();
+  });
+  window->putProperty("isShowCopyNotificationManaged", [this]() -> bool {
+    return settings_->isShowCopyNotificationManaged();
+  });
+
+  window->putProperty("saveCycleProfileShortcut", [this](std::string shortcut) -> void {
+    settings_->saveCycleProfileShortcut(shortcut);
+  });
+  window->putProperty("getCycleProfileShortcut", [this]() -> std::string {
+    return settings_->getCycleProfileShortcut();
+  });
+
+  window->putProperty("savePauseWhenScreenSharing", [this](bool pause) -> void {
+    settings_->savePauseWhenScreenSharing(pause);
+  });
+  window->putProperty("shouldPauseWhenScreenSharing", [this]() -> bool {
+    return settings_->shouldPauseWhenScreenSharing();
+  });
+  window->putProperty("isPauseWhenScreenSharingManaged", [this]() -> bool {
+    return settings_->isPauseWhenScreenSharingManaged();
+  });
+
+  window->putProperty("saveMaxItemKilobytes", [this](int kilobytes) -> void {
+    settings_->saveMaxItemKilobytes(kilobytes);
+  });
+  window->putProperty("getMaxItemKilobytes", [this]() -> int {
+    return settings_->getMaxItemKilobytes();
+  });
+
+  window->putProperty("saveLargeItemPolicy", [this](std::string policy) -> void {
+    settings_->saveLargeItemPolicy(policy);
+  });
+  window->putProperty("getLargeItemPolicy", [this]() -> std::string {
+    return settings_->getLargeItemPolicy();
+  });
+
+  window->putProperty("readConfigFile", [this]() -> std::string {
+    std::ifstream file(getConfigFilePath());
+    if (!file) {
+      return "";
+    }
+    std::stringstream content;
+    content << file.rdbuf();
+    return content.str();
+  });
+  window->putProperty("applyPreferences", [this](std::string json) -> int {
+    if (guest_mode_) {
+      LOG(WARNING) << "The preferences can't be changed in guest mode";
+      return 0;
+    }
+    return settings_->applyPreferences(json);
+  });
+  window->putProperty("exportPreferences", [this]() -> std::string {
+    return settings_->exportPreferences();
+  });
+
+  window->putProperty("saveDoubleTapModifier", [this](std::string modifier) -> void {
+    settings_->saveDoubleTapModifier(modifier);
+  });
+  window->putProperty("getDoubleTapModifier", [this]() -> std::string {
+    return settings_->getDoubleTapModifier();
+  });
+
+  window->putProperty("saveDoubleTapAction", [this](std::string action) -> void {
+    settings_->saveDoubleTapAction(action);
+  });
+  window->putProperty("getDoubleTapAction", [this]() -> std::string {
+    return settings_->getDoubleTapAction();
+  });
+
+  window->putProperty("saveDeduplicateSimilarImages", [this](bool deduplicate) -> void {
+    settings_->saveDeduplicateSimilarImages(deduplicate);
+  });
+  window->putProperty("shouldDeduplicateSimilarImages", [this]() -> bool {
+    return settings_->shouldDeduplicateSimilarImages();
+  });
+  window->putProperty("isDeduplicateSimilarImagesManaged", [this]() -> bool {
+    return settings_->isDeduplicateSimilarImagesManaged();
+  });
+
+  window->putProperty("saveCaptureAllowedTypes", [this](std::string types) -> void {
+    settings_->saveCaptureAllowedTypes(types);
+  });
+  window->putProperty("getCaptureAllowedTypes", [this]() -> std::string {
+    return settings_->getCaptureAllowedTypes();
+  });
+
+  window->putProperty("saveCaptureIgnoredTypes", [this](std::string types) -> void {
+    settings_->saveCaptureIgnoredTypes(types);
+  });
+  window->putProperty("getCaptureIgnoredTypes", [this]() -> std::string {
+    return settings_->getCaptureIgnoredTypes();
+  });
+
+  window->putProperty("saveMonitorFindPasteboard", [this](bool monitor) -> void {
+    settings_->saveMonitorFindPasteboard(monitor);
+  });
+  window->putProperty("shouldMonitorFindPasteboard", [this]() -> bool {
+    return settings_->shouldMonitorFindPasteboard();
+  });
+  window->putProperty("isMonitorFindPasteboardManaged", [this]() -> bool {
+    return settings_->isMonitorFindPasteboardManaged();
+  });
+
+  window->putProperty("saveLogLevel", [this](std::string level) -> void {
+    settings_->saveLogLevel(level);
+  });
+  window->putProperty("getLogLevel", [this]() -> std::string {
+    return settings_->getLogLevel();
+  });
+
   window->putProperty("getArch", [this]() -> std::string {
 #ifdef ARCH_MAC_X64
     return "Intel";
@@ -1433,16 +2285,36 @@ bool MainApp::isPaused() const {
   return app_paused_;
 }
 
-void MainApp::pause() {
-  tray_->setImage(app_->getPath(PathKey::kAppResources) + "/pausedTemplate.png");
+long long MainApp::getPausedUntil() const {
+  return paused_until_;
+}
+
+void MainApp::pause(int duration_secs) {
   pause_resume_item_->setTitle(i18n("app.menu.resume"));
   app_paused_ = true;
+  tray_state_->setPaused(true);
+  int generation = ++pause_generation_;
+  if (duration_secs <= 0) {
+    paused_until_ = 0;
+    return;
+  }
+  paused_until_ = std::chrono::duration_cast<std::chrono::milliseconds>(
+      std::chrono::system_clock::now().time_since_epoch()).count() + duration_secs * 1000LL;
+  std::thread([this, generation, duration_secs]() {
+    std::this_thread::sleep_for(std::chrono::seconds(duration_secs));
+    // Do not resume if the app was paused again or resumed in the meantime.
+    if (pause_generation_ == generation) {
+      resume();
+    }
+  }).detach();
 }
 
 void MainApp::resume() {
-  tray_->setImage(app_->getPath(PathKey::kAppResources) + "/imageTemplate.png");
   pause_resume_item_->setTitle(i18n("app.menu.pause"));
   app_paused_ = false;
+  tray_state_->setPaused(false);
+  paused_until_ = 0;
+  ++pause_generation_;
 }
 
 void MainApp::setShowIconInMenuBar(bool show) {
@@ -1459,44 +2331,49 @@ void MainApp::createTray() {
   }
 
   tray_ = Tray::create(app_);
-  if (app_paused_) {
-    tray_->setImage(app_->getPath(PathKey::kAppResources) + "/pausedTemplate.png");
-  } else {
-    tray_->setImage(app_->getPath(PathKey::kAppResources) + "/imageTemplate.png");
-  }
+  applyTrayState(tray_state_->state(), tray_state_->badge());
   tray_->onClicked += [this](const TrayClicked& event) {
     if (event.mouse_button == MouseButton::kPrimary && !event.key_modifiers.alt_down) {
       show();
     }
     if ((event.mouse_button == MouseButton::kSecondary) ||
         (event.mouse_button == MouseButton::kPrimary && event.key_modifiers.alt_down)) {
-      tray_->openMenu(CustomMenu::create(
-          {
-              open_app_item_,
-              menu::Separator(),
-              help_menu_,
-              menu::Separator(),
-              about_item_,
-              check_for_updates_item_,
-              open_settings_item_,
-              menu::Separator(),
-              pause_resume_item_,
-              quit_item_
-          }));
+      std::vector<std::shared_ptr<MenuItem>> items = {open_app_item_, menu::Separator()};
+      {
+        std::lock_guard<std::mutex> guard(tray_recent_items_mutex_);
+        if (!tray_recent_items_.empty()) {
+          items.insert(items.end(), tray_recent_items_.begin(), tray_recent_items_.end());
+          items.push_back(menu::Separator());
+        }
+      }
+      items.insert(items.end(), {
+          help_menu_,
+          menu::Separator(),
+          about_item_,
+          check_for_updates_item_,
+          open_settings_item_,
+          menu::Separator(),
+          pause_resume_item_,
+          pause_for_item_,
+          quit_item_
+      });
+      tray_->openMenu(CustomMenu::create(items));
     }
   };
 }
 
 void MainApp::quit() {
   if (settings_->shouldClearHistoryOnQuit()) {
-    auto frame = app_window_->mainFrame();
-    if (frame) {
-      frame->executeJavaScript("clearHistory()");
-    }
+    clearHistoryAndWait("quit");
   }
+  waitForShutdown();
   disableOpenAppShortcut();
   disablePauseResumeShortcut();
+  disableCycleProfileShortcut();
+  disableDoubleTapShortcut();
   disablePasteNextItemShortcut();
+  disablePasteRecentShortcuts();
+  clearRegisterShortcuts();
 
   if (welcome_window_) {
     welcome_window_->close();
@@ -1513,6 +2390,50 @@ void MainApp::quit() {
   app_->quit();
 }
 
+void MainApp::waitForShutdown() {
+  auto frame = app_window_->mainFrame();
+  if (!frame) {
+    return;
+  }
+  frame->executeJavaScript("shutdownApp()");
+  auto deadline = std::chrono::steady_clock::now() + std::chrono::milliseconds(kShutdownTimeoutInMs);
+  while (std::chrono::steady_clock::now() < deadline) {
+    if (frame->executeJavaScript("isShutdownComplete()").asBool()) {
+      return;
+    }
+    std::this_thread::sleep_for(std::chrono::milliseconds(kShutdownCheckIntervalInMs));
+  }
+  LOG(WARNING) << "Background tasks didn't finish in time";
+}
+
+void MainApp::clearHistoryAndWait(const std::string &trigger) {
+  auto frame = app_window_->mainFrame();
+  if (!frame) {
+    return;
+  }
+  frame->executeJavaScript("clearHistory('" + trigger + "')");
+  auto deadline = std::chrono::steady_clock::now() + std::chrono::milliseconds(kShutdownTimeoutInMs);
+  while (std::chrono::steady_clock::now() < deadline) {
+    if (!frame->executeJavaScript("isHistoryClearing()").asBool()) {
+      return;
+    }
+    std::this_thread::sleep_for(std::chrono::milliseconds(kShutdownCheckIntervalInMs));
+  }
+  LOG(WARNING) << "History clearing didn't finish in time";
+}
+
+void MainApp::applyTrayState(TrayState state, const std::string &badge) {
+  if (!tray_ || tray_->isDestroyed()) {
+    return;
+  }
+  if (state == TrayState::kPaused) {
+    tray_->setImage(app_->getPath(PathKey::kAppResources) + "/pausedTemplate.png");
+  } else {
+    tray_->setImage(app_->getPath(PathKey::kAppResources) + "/imageTemplate.png");
+  }
+  tray_->setTitle(badge);
+}
+
 void MainApp::destroyTray() {
   if (tray_ && !tray_->isDestroyed()) {
     tray_->destroy();
@@ -1538,6 +2459,137 @@ void MainApp::selectAppsToIgnore() {
   });
 }
 
+void MainApp::handleNotificationAction(const std::string &action, int item_id) {
+  if (action == "open") {
+    show();
+    return;
+  }
+  std::thread([this, action, item_id]() {
+    auto frame = app_window_->mainFrame();
+    if (frame) {
+      frame->executeJavaScript(
+          "handleNotificationAction(" + toJsonString(action) + ", " + std::to_string(item_id) + ")");
+    }
+  }).detach();
+}
+
+void MainApp::openDeepLink(const std::string &url) {
+  auto frame = app_window_->mainFrame();
+  if (frame) {
+    frame->executeJavaScript("handleDeepLink(" + toJsonString(url) + ")");
+  }
+}
+
+bool MainApp::startApiServer(int port, const std::string &token) {
+  if (!api_server_) {
+    api_server_ = std::make_unique<ApiServer>([this](const ApiRequest &request) {
+      return handleApiRequest(request);
+    });
+  }
+  return api_server_->start(port, token);
+}
+
+void MainApp::stopApiServer() {
+  if (api_server_) {
+    api_server_->stop();
+  }
+}
+
+bool MainApp::isApiServerRunning() const {
+  return api_server_ && api_server_->isRunning();
+}
+
+std::string MainApp::getApiServerToken() {
+  auto token = getSecret(kApiServerTokenSecret);
+  if (token.empty()) {
+    // Move the token saved by the previous versions to the Keychain.
+    token = settings_->getApiServerToken();
+    if (!token.empty() && saveSecret(kApiServerTokenSecret, token)) {
+      settings_->saveApiServerToken("");
+    }
+  }
+  return token;
+}
+
+std::string MainApp::getLanSharingKey() {
+  auto key = getSecret(kLanSharingKeySecret);
+  if (key.empty()) {
+    // Move the key saved by the previous versions to the Keychain.
+    key = settings_->getLanSharingKey();
+    if (!key.empty() && saveSecret(kLanSharingKeySecret, key)) {
+      settings_->saveLanSharingKey("");
+    }
+  }
+  return key;
+}
+
+void MainApp::restartLanSharing() {
+  // The sharing is managed by the main window. The preferences can be changed
+  // from the main window too, so don't block its renderer.
+  std::thread([this]() {
+    auto frame = app_window_->mainFrame();
+    if (frame) {
+      frame->executeJavaScript("restartSharing()");
+    }
+  }).detach();
+}
+
+void MainApp::receiveLanItem(const std::string &payload) {
+  auto frame = app_window_->mainFrame();
+  if (frame) {
+    frame->executeJavaScript("receiveLanItem(" + toJsonString(payload) + ")");
+  }
+}
+
+void MainApp::userAuthenticated(bool success) {
+  // The authentication can be requested by the app window or by the settings
+  // window. The window that didn't request it ignores the result.
+  std::string script = std::string("userAuthenticated(") + (success ? "true" : "false") + ")";
+  auto frame = app_window_->mainFrame();
+  if (frame) {
+    frame->executeJavaScript(script);
+  }
+  if (settings_window_) {
+    auto settings_frame = settings_window_->mainFrame();
+    if (settings_frame) {
+      settings_frame->executeJavaScript(script);
+    }
+  }
+}
+
+void MainApp::imageTextRecognized(const std::string &image_file_name, const std::string &text) {
+  auto frame = app_window_->mainFrame();
+  if (frame) {
+    frame->executeJavaScript(
+        "imageTextRecognized(" + toJsonString(image_file_name) + ", " + toJsonString(text) + ")");
+  }
+}
+
+void MainApp::lanItemSent(const std::string &peer, bool success) {
+  auto frame = app_window_->mainFrame();
+  if (frame) {
+    frame->executeJavaScript(
+        "lanItemSent(" + toJsonString(peer) + ", " + (success ? "true" : "false") + ")");
+  }
+}
+
+ApiResponse MainApp::handleApiRequest(const ApiRequest &request) {
+  auto frame = app_window_->mainFrame();
+  if (!frame) {
+    return {503, R"({"error":"ClipBook is not ready"})"};
+  }
+  auto result = frame->executeJavaScript(
+      "handleApiRequest(" + toJsonString(request.method) + ", " +
+      toJsonString(request.path) + ", " + toJsonString(request.query) + ", " +
+      toJsonString(request.body) + ")").asString();
+  // The result contains the status code and the JSON body separated by a new line.
+  auto separator = result.find('\n');
+  if (separator == std::string::npos) {
+    return {500, R"({"error":"Invalid response"})"};
+  }
+  return {std::atoi(result.substr(0, separator).c_str()), result.substr(separator + 1)};
+}
+
 std::string MainApp::getImagesDir() {
   return app_->profile()->path() + "/images";
 }
@@ -1546,12 +2598,66 @@ std::string MainApp::getLinkImagesDir() {
   return app_->profile()->path() + "/images/links";
 }
 
-void MainApp::deleteImage(const std::string &imageFileName) {
+std::string MainApp::getBackupsDir() {
+  return app_->profile()->path() + "/backups";
+}
+
+std::string MainApp::getBlobsDir() {
+  return app_->profile()->path() + "/blobs";
+}
+
+std::string MainApp::getLogsDir() {
+  return app_->profile()->path() + "/logs";
+}
+
+bool MainApp::isAccessiblePath(const std::string &path) {
+  std::error_code error;
+  auto target = fs::weakly_canonical(path, error);
+  if (error) {
+    return false;
+  }
+  {
+    std::lock_guard<std::mutex> lock(selected_files_mutex_);
+    if (selected_files_.count(target.string()) > 0) {
+      return true;
+    }
+  }
+  std::vector<std::string> roots = settings_->getGrantedDirectories();
+  roots.push_back(app_->profile()->path());
+  roots.push_back(settings_->getVaultDirectory());
+  for (const auto &root : roots) {
+    if (root.empty()) {
+      continue;
+    }
+    auto dir = fs::weakly_canonical(root, error);
+    if (error) {
+      continue;
+    }
+    // The path is inside the directory if it doesn't go up from it.
+    auto relative = target.lexically_relative(dir);
+    if (!relative.empty() && *relative.begin() != "..") {
+      return true;
+    }
+  }
+  return false;
+}
+
+std::string MainApp::getAppIconsDir() {
+  return app_->profile()->path() + "/app_icons";
+}
+
+void MainApp::deleteImage(const std::string &imageFileName, bool secure) {
   std::string filePath = getImagesDir() + "/" + imageFileName;
   if (fs::exists(filePath)) {
+      if (secure) {
+        secureRemoveFile(filePath);
+      }
       fs::remove(filePath);
       auto infoFilePath = fs::path(filePath).replace_extension(".info");
       if (fs::exists(infoFilePath)) {
+          if (secure) {
+            secureRemoveFile(infoFilePath.string());
+          }
           fs::remove(infoFilePath);
       }
   }
@@ -1561,6 +2667,21 @@ long MainApp::getSystemBootTime() {
   return -1;
 }
 
+MemoryUsage MainApp::getMemoryUsage() {
+  return {};
+}
+
+SystemInfo MainApp::getSystemInfo() {
+  SystemInfo info;
+  std::error_code error;
+  auto space = fs::space(getUserDataDir(), error);
+  if (!error) {
+    info.disk_total_space = space.capacity;
+    info.disk_free_space = space.available;
+  }
+  return info;
+}
+
 void MainApp::previewLink(const std::string &url) {
   if (!preview_window_ || preview_window_->isClosed()) {
     preview_window_ = Browser::create(app_);
@@ -1606,6 +2727,7 @@ void MainApp::onLanguageChanged() {
   } else {
     pause_resume_item_->setTitle(i18n("app.menu.pause"));
   }
+  pause_for_item_->setTitle(i18n("app.menu.pauseFor10Minutes"));
   about_item_->setTitle(i18n("app.menu.about"));
   quit_item_->setTitle(i18n("app.menu.quit"));
 
@@ -1,15 +1,53 @@
 #ifndef CLIPBOOK_MAIN_APP_H_
 #define CLIPBOOK_MAIN_APP_H_
 
+#include <atomic>
+#include <cstdint>
 #include <memory>
+#include <mutex>
+#include <set>
 #include <string>
 #include <list>
+#include <vector>
 
 #include "molybden.hpp"
+#include "api_server.h"
 #include "app_settings.h"
+#include "capture_journal.h"
+#include "log_writer.h"
+#include "tray_state_controller.h"
 #include "url_request_interceptor.h"
 #include "webview.h"
 
+// The memory used by the app process in bytes.
+struct MemoryUsage {
+  uint64_t resident_size = 0;
+  uint64_t peak_resident_size = 0;
+  // The memory the system accounts to the process, as in Activity Monitor.
+  uint64_t footprint = 0;
+  // The memory allocated with malloc and the number of the allocated blocks.
+  uint64_t allocated_size = 0;
+  uint64_t allocated_blocks = 0;
+};
+
+// The state of the system the app runs on. The sizes are in bytes.
+struct SystemInfo {
+  std::string os_version;
+  int cpu_count = 0;
+  // The average number of the runnable processes over the last minute.
+  double load_average = 0;
+  uint64_t total_memory = 0;
+  uint64_t available_memory = 0;
+  // The space of the volume the app data is stored on.
+  uint64_t disk_total_space = 0;
+  uint64_t disk_free_space = 0;
+  bool has_battery = false;
+  // The battery charge in percent or -1 if there's no battery.
+  int battery_level = -1;
+  bool battery_charging = false;
+  bool on_ac_power = true;
+};
+
 class MainApp : public std::enable_shared_from_this<MainApp> {
  public:
   enum Key {
@@ -25,15 +63,49 @@ class MainApp : public std::enable_shared_from_this<MainApp> {
   [[nodiscard]] std::shared_ptr<molybden::App> app() const;
   [[nodiscard]] std::shared_ptr<molybden::Browser> browser() const;
   [[nodiscard]] std::shared_ptr<AppSettings> settings() const;
+  [[nodiscard]] std::shared_ptr<CaptureJournal> captureJournal() const;
 
-  void pause();
+  // Pauses capturing the clipboard. If the duration is positive, the
+  // capturing is resumed automatically when it expires.
+  void pause(int duration_secs = 0);
   void resume();
   bool isPaused() const;
+  // Returns the time in milliseconds since Unix epoch when the capturing is
+  // resumed automatically or 0 if it's paused indefinitely or not paused.
+  long long getPausedUntil() const;
 
   void showWelcomeWindow();
 
   std::string getImagesDir();
   std::string getLinkImagesDir();
+  std::string getBackupsDir();
+  std::string getBlobsDir();
+  std::string getLogsDir();
+  std::string getAppIconsDir();
+  // Returns true if the path is inside the app data directory, the vault
+  // directory or one of the folders the user has chosen, or it's a file the
+  // user has chosen in the file dialog.
+  bool isAccessiblePath(const std::string &path);
+
+  // Passes the clipbook:// URL to the app window to perform the command.
+  void openDeepLink(const std::string &url);
+
+  // Returns the API server token stored in the Keychain.
+  std::string getApiServerToken();
+  bool startApiServer(int port, const std::string &token);
+  void stopApiServer();
+  bool isApiServerRunning() const;
+
+  // Returns the LAN sharing key stored in the Keychain.
+  std::string getLanSharingKey();
+  // Asks the app window to apply the changed LAN sharing preferences.
+  void restartLanSharing();
+  // Passes the item received from a peer on the local network to the app
+  // window to add it to the history.
+  void receiveLanItem(const std::string &payload);
+  void lanItemSent(const std::string &peer, bool success);
+  void userAuthenticated(bool success);
+  void imageTextRecognized(const std::string &image_file_name, const std::string &text);
 
   virtual bool init();
   virtual void launch();
@@ -58,15 +130,78 @@ class MainApp : public std::enable_shared_from_this<MainApp> {
                                const std::string &filePath,
                                bool ghost) = 0;
   virtual void copyToClipboardAfterMerge(std::string text) = 0;
+  // Puts the text to the find pasteboard, so the other apps search for it.
+  virtual void writeToFindPasteboard(const std::string &text) = 0;
   virtual void setOpenAtLogin(bool open) = 0;
   virtual AppInfo getAppInfo() = 0;
   virtual AppInfo getActiveAppInfo() = 0;
   virtual std::string getFileIconAsBase64(const std::string& app_path, bool large) = 0;
   virtual std::string getAppNameFromPath(const std::string &app_path) = 0;
+  virtual std::string getAppBundleId(const std::string &app_path) = 0;
+  // Returns the path to the PNG icon of the app cached in the app icons
+  // directory.
+  virtual std::string getAppIconFile(const std::string &app_path) = 0;
   virtual void preview(const std::string &file_path) = 0;
+  virtual void announce(const std::string &message) = 0;
+  // Shows the system share sheet for the text, the URL, or the image file.
+  virtual void share(const std::string &text, const std::string &url, const std::string &image_file_name) = 0;
+  // Shows the notification about the copied item with the "Paste" and
+  // "Favorite" buttons.
+  virtual void showCopyNotification(int item_id, const std::string &title, const std::string &body) = 0;
+  virtual void showNotification(const std::string &title, const std::string &body) = 0;
+  void handleNotificationAction(const std::string &action, int item_id);
+  virtual bool isAccessibilityAccessGranted() = 0;
+  // Returns "granted", "denied", "notDetermined" or "unknown" for the
+  // "accessibility", "inputMonitoring", "fullDiskAccess" and "notifications"
+  // permissions.
+  virtual std::string getPermissionStatus(const std::string &kind) = 0;
+  // Asks the system to grant the permission and opens the corresponding
+  // pane of System Settings.
+  virtual void requestPermission(const std::string &kind) = 0;
+  // Asks the user to authenticate with Touch ID or the system password and
+  // reports the result to the app window.
+  virtual void authenticateUser(const std::string &reason) = 0;
+  // Shows the dialog to choose a "file", a "folder" or, for "save", the path
+  // of a new file, and reports the chosen path or an empty string to the
+  // window that asked for it.
+  virtual void selectPath(const std::shared_ptr<molybden::Browser> &browser,
+                          int request_id,
+                          const std::string &kind,
+                          const std::string &default_name) = 0;
+  // Returns true if the screen is being shared or recorded by one of the
+  // known video conferencing apps.
+  virtual bool isScreenSharingActive() = 0;
+  // Recognizes the text in the image from the images directory in the
+  // background and reports it to the app window.
+  virtual void recognizeImageText(const std::string &image_file_name) = 0;
+  // Writes the QR code of the text as a PNG image to the images directory.
+  virtual bool generateQRCode(const std::string &text, const std::string &image_file_name) = 0;
+  virtual bool startLanSharing(const std::string &device_name, const std::string &key) = 0;
+  virtual void stopLanSharing() = 0;
+  virtual std::vector<std::string> getLanPeers() = 0;
+  virtual std::vector<std::string> getRecentPasteboardTypes() = 0;
+  virtual void sendToLanPeer(const std::string &peer, const std::string &payload) = 0;
+  // Stores the secret, e.g. a password, in the system credential storage.
+  // An empty value removes the secret.
+  virtual bool saveSecret(const std::string &name, const std::string &value) = 0;
+  // Returns an empty string if the secret isn't stored.
+  virtual std::string getSecret(const std::string &name) = 0;
 
  protected:
   void pasteNextItemToActiveApp();
+  void pasteRecentItemToActiveApp(int number);
+  void pasteTrayItemToActiveApp(int id);
+  void pasteRegisterToActiveApp(const std::string &name);
+  void cycleProfile();
+  // Lets the frontend track how often the global shortcut is used.
+  void shortcutUsed(const std::string &id);
+  // Lets the frontend re-read the config file after it's changed.
+  void configFileChanged();
+  std::string getConfigFilePath();
+  // Performs the action assigned to the double press of the modifier key.
+  void performDoubleTapAction();
+  void clearTrayRecentItems();
+  void addTrayRecentItem(int id, const std::string &title);
   void setActiveAppInfo(const std::string &app_name, const std::string& app_icon);
   void clearHistory();
   void checkForUpdates(bool user_initiated = false);
@@ -89,8 +224,9 @@ class MainApp : public std::enable_shared_from_this<MainApp> {
   void setShowIconInMenuBar(bool show);
   void createTray();
   void destroyTray();
+  void applyTrayState(TrayState state, const std::string &badge);
   void initJavaScriptApi(const std::shared_ptr<molybden::JsObject> &window);
-  void deleteImage(const std::string &imageFileName);
+  void deleteImage(const std::string &imageFileName, bool secure = false);
   void fetchLinkPreviewDetails(const std::string &url, const std::shared_ptr<molybden::JsObject> &callback);
   void previewLink(const std::string &url);
   void saveImageAsFile(const std::string &imageFileName, int imageWidth, int imageHeight);
@@ -99,16 +235,34 @@ class MainApp : public std::enable_shared_from_this<MainApp> {
   std::string i18n(const std::string &key);
 
   void quit();
+  // Asks the app to stop the background tasks and save their pending work,
+  // and waits until it's done or the timeout expires.
+  void waitForShutdown();
+  // Clears the history for the given trigger and waits until it's done or
+  // the timeout expires.
+  void clearHistoryAndWait(const std::string &trigger);
 
   // Returns the boot time of the system in seconds since Unix epoch or -1 if failed.
   virtual long getSystemBootTime();
+  // Returns the memory usage of the app process or zeros if unknown.
+  virtual MemoryUsage getMemoryUsage();
+  virtual SystemInfo getSystemInfo();
 
   virtual void enableOpenAppShortcut() = 0;
   virtual void disableOpenAppShortcut() = 0;
   virtual void enablePasteNextItemShortcut() = 0;
   virtual void disablePasteNextItemShortcut() = 0;
+  virtual void enablePasteRecentShortcuts() = 0;
+  virtual void disablePasteRecentShortcuts() = 0;
+  virtual void addRegisterShortcut(const std::string &name, const std::string &shortcut) = 0;
+  virtual void clearRegisterShortcuts() = 0;
   virtual void enablePauseResumeShortcut() = 0;
   virtual void disablePauseResumeShortcut() = 0;
+  virtual void enableCycleProfileShortcut() = 0;
+  virtual void disableCycleProfileShortcut() = 0;
+  virtual void enableDoubleTapShortcut() = 0;
+  virtual void disableDoubleTapShortcut() = 0;
+  virtual bool isDoubleTapShortcutActive() = 0;
   virtual void updateOpenSettingsShortcut() = 0;
   virtual std::string getUserDataDir() = 0;
   virtual std::string getUpdateServerUrl() = 0;
@@ -124,12 +278,21 @@ class MainApp : public std::enable_shared_from_this<MainApp> {
   bool app_window_visible_;
   bool checking_for_updates_;
   bool app_paused_;
+  // The settings can't be opened while the guest mode is on.
+  bool guest_mode_;
+  std::atomic<long long> paused_until_;
+  // Incremented on every pause and resume to cancel the pending auto resume.
+  std::atomic<int> pause_generation_;
   bool after_system_reboot_;
   bool update_available_;
   long long app_hide_time_;
   std::string save_images_dir_;
+  // The files the user has chosen in the file dialog during this session.
+  std::set<std::string> selected_files_;
+  std::mutex selected_files_mutex_;
   std::shared_ptr<molybden::App> app_;
   std::shared_ptr<molybden::Tray> tray_;
+  std::shared_ptr<TrayStateController> tray_state_;
   std::shared_ptr<molybden::Browser> app_window_;
   std::shared_ptr<molybden::Browser> preview_window_;
   std::shared_ptr<molybden::Browser> welcome_window_;
@@ -137,6 +300,10 @@ class MainApp : public std::enable_shared_from_this<MainApp> {
   std::shared_ptr<molybden::CustomMenuItem> open_app_item_;
   std::shared_ptr<molybden::CustomMenuItem> open_settings_item_;
   std::shared_ptr<molybden::CustomMenuItem> pause_resume_item_;
+  std::shared_ptr<molybden::CustomMenuItem> pause_for_item_;
+  // The recent history items displayed in the tray menu.
+  std::vector<std::shared_ptr<molybden::CustomMenuItem>> tray_recent_items_;
+  std::mutex tray_recent_items_mutex_;
   std::shared_ptr<molybden::CustomMenuItem> check_for_updates_item_;
   std::shared_ptr<molybden::CustomMenuItem> about_item_;
   std::shared_ptr<molybden::CustomMenuItem> quit_item_;
@@ -148,8 +315,13 @@ class MainApp : public std::enable_shared_from_this<MainApp> {
   std::shared_ptr<AppSettings> settings_;
 
   std::list<std::string> fetch_url_requests_;
+  std::unique_ptr<ApiServer> api_server_;
+  std::unique_ptr<LogWriter> log_writer_;
+  std::shared_ptr<CaptureJournal> capture_journal_;
 
  private:
+  ApiResponse handleApiRequest(const ApiRequest &request);
+
   std::shared_ptr<UrlRequestInterceptor> request_interceptor_;
 };
 
@@ -8,12 +8,20 @@
 #endif
 
 #include "clipboard_reader_mac.h"
+#include "config_file_watcher_mac.h"
+#include "double_tap_detector_mac.h"
+#include "lan_share_mac.h"
+#include "notification_center_mac.h"
 
 class MainAppMac : public MainApp {
  public:
   explicit MainAppMac(const std::shared_ptr<molybden::App> &app,
                       const std::shared_ptr<AppSettings> &settings);
 
+  // Must be called before the app is initialized, so that the deep link
+  // that launched the app is received.
+  static void installDeepLinkHandler();
+
 #ifdef __OBJC__
   void setActiveAppInfo(NSRunningApplication* activeApp);
 #endif
@@ -41,20 +49,54 @@ class MainAppMac : public MainApp {
                        const std::string &filePath,
                        bool ghost) override;
   void copyToClipboardAfterMerge(std::string text) override;
+  void writeToFindPasteboard(const std::string &text) override;
   void setOpenAtLogin(bool open) override;
   AppInfo getAppInfo() override;
   AppInfo getActiveAppInfo() override;
   std::string getFileIconAsBase64(const std::string& app_path, bool large) override;
   std::string getAppNameFromPath(const std::string &app_path) override;
+  std::string getAppBundleId(const std::string &app_path) override;
+  std::string getAppIconFile(const std::string &app_path) override;
   void preview(const std::string &file_path) override;
+  void announce(const std::string &message) override;
+  void share(const std::string &text, const std::string &url, const std::string &image_file_name) override;
+  void showCopyNotification(int item_id, const std::string &title, const std::string &body) override;
+  void showNotification(const std::string &title, const std::string &body) override;
+  bool isAccessibilityAccessGranted() override;
+  std::string getPermissionStatus(const std::string &kind) override;
+  void requestPermission(const std::string &kind) override;
+  void authenticateUser(const std::string &reason) override;
+  void selectPath(const std::shared_ptr<molybden::Browser> &browser,
+                  int request_id,
+                  const std::string &kind,
+                  const std::string &default_name) override;
+  bool isScreenSharingActive() override;
+  void recognizeImageText(const std::string &image_file_name) override;
+  bool generateQRCode(const std::string &text, const std::string &image_file_name) override;
+  bool startLanSharing(const std::string &device_name, const std::string &key) override;
+  void stopLanSharing() override;
+  std::vector<std::string> getLanPeers() override;
+  std::vector<std::string> getRecentPasteboardTypes() override;
+  void sendToLanPeer(const std::string &peer, const std::string &payload) override;
+  bool saveSecret(const std::string &name, const std::string &value) override;
+  std::string getSecret(const std::string &name) override;
 
  protected:
   void enableOpenAppShortcut() override;
   void disableOpenAppShortcut() override;
   void enablePasteNextItemShortcut() override;
   void disablePasteNextItemShortcut() override;
+  void enablePasteRecentShortcuts() override;
+  void disablePasteRecentShortcuts() override;
+  void addRegisterShortcut(const std::string &name, const std::string &shortcut) override;
+  void clearRegisterShortcuts() override;
   void enablePauseResumeShortcut() override;
   void disablePauseResumeShortcut() override;
+  void enableCycleProfileShortcut() override;
+  void disableCycleProfileShortcut() override;
+  void enableDoubleTapShortcut() override;
+  void disableDoubleTapShortcut() override;
+  bool isDoubleTapShortcutActive() override;
   void updateOpenSettingsShortcut() override;
   std::string getUserDataDir() override;
   std::string getUpdateServerUrl() override;
@@ -64,7 +106,6 @@ class MainAppMac : public MainApp {
   std::string getAllAppsInfo() override;
   void openInApp(const std::string &file_path, const std::string &app_path) override;
 
-  bool isAccessibilityAccessGranted();
   void showAccessibilityAccessDialog(const std::string &filePaths);
   void showAccessibilityAccessDialog(const std::string &text,
                                      const std::string &imageFileName,
@@ -88,13 +129,24 @@ class MainAppMac : public MainApp {
   static bool isAppInLoginItems();
 
   long getSystemBootTime() override;
+  MemoryUsage getMemoryUsage() override;
+  SystemInfo getSystemInfo() override;
 
  private:
   molybden::Shortcut open_app_shortcut_;
   molybden::Shortcut pause_resume_shortcut_;
+  molybden::Shortcut cycle_profile_shortcut_;
   molybden::Shortcut open_settings_shortcut_;
   molybden::Shortcut paste_next_item_shortcut_;
+  std::vector<molybden::Shortcut> paste_recent_shortcuts_;
+  std::vector<molybden::Shortcut> register_shortcuts_;
   std::shared_ptr<ClipboardReaderMac> clipboard_reader_;
+  std::unique_ptr<LanShareMac> lan_share_;
+  std::unique_ptr<DoubleTapDetectorMac> double_tap_detector_;
+  std::unique_ptr<ConfigFileWatcherMac> config_file_watcher_;
+  NotificationCenterMac *getNotificationCenter();
+
+  std::unique_ptr<NotificationCenterMac> notification_center_;
   bool should_activate_app_ = false;
 #ifdef __OBJC__
   pid_t active_app_pid_ = 0;
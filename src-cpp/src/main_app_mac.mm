@@ -1,13 +1,24 @@
 #include "main_app_mac.h"
 
 #include "active_app_observer.h"
+#include "deep_link_handler_mac.h"
 #include "quick_look_previewer_mac.h"
+#include "share_picker_mac.h"
 
 #import <ApplicationServices/ApplicationServices.h>
 #import <Cocoa/Cocoa.h>
+#import <CoreImage/CoreImage.h>
 #import <Foundation/Foundation.h>
+#import <IOKit/hidsystem/IOHIDLib.h>
+#import <IOKit/ps/IOPowerSources.h>
+#import <IOKit/ps/IOPSKeys.h>
+#import <LocalAuthentication/LocalAuthentication.h>
+#import <Security/Security.h>
+#import <UserNotifications/UserNotifications.h>
 
 #include <filesystem>
+#include <mach/mach.h>
+#include <malloc/malloc.h>
 #include <sys/sysctl.h>
 #include <thread>
 
@@ -246,6 +257,7 @@ void MainAppMac::enableOpenAppShortcut() {
   }
   auto shortcuts = app()->globalShortcuts();
   bool success = shortcuts->registerShortcut(open_app_shortcut_, [this](const Shortcut &) {
+    shortcutUsed("openApp");
     // Users can set the same shortcut for opening and closing the app.
     auto openAppShortcut = settings_->getOpenAppShortcut();
     auto closeAppShortcut = settings_->getCloseAppShortcut();
@@ -287,6 +299,7 @@ void MainAppMac::enablePauseResumeShortcut() {
   }
   auto shortcuts = app()->globalShortcuts();
   bool success = shortcuts->registerShortcut(pause_resume_shortcut_, [this](const Shortcut &) {
+    shortcutUsed("pauseResume");
     if (isPaused()) {
       resume();
     } else {
@@ -307,6 +320,60 @@ void MainAppMac::disablePauseResumeShortcut() {
   }
 }
 
+void MainAppMac::enableCycleProfileShortcut() {
+  disableCycleProfileShortcut();
+  auto shortcut_str = settings_->getCycleProfileShortcut();
+  cycle_profile_shortcut_ = createShortcut(shortcut_str);
+  if (cycle_profile_shortcut_.key == KeyCode::UNKNOWN) {
+    return;
+  }
+  auto shortcuts = app()->globalShortcuts();
+  bool success = shortcuts->registerShortcut(cycle_profile_shortcut_, [this](const Shortcut &) {
+    shortcutUsed("cycleProfile");
+    cycleProfile();
+  });
+  if (!success) {
+    LOG(ERROR) << "Failed to register global shortcut: " << shortcut_str;
+    cycle_profile_shortcut_ = molybden::Shortcut();
+  }
+}
+
+void MainAppMac::disableCycleProfileShortcut() {
+  if (cycle_profile_shortcut_.key != KeyCode::UNKNOWN) {
+    app()->globalShortcuts()->unregisterShortcut(cycle_profile_shortcut_);
+    cycle_profile_shortcut_.key = KeyCode::UNKNOWN;
+  }
+}
+
+void MainAppMac::enableDoubleTapShortcut() {
+  disableDoubleTapShortcut();
+  auto modifier = settings_->getDoubleTapModifier();
+  if (modifier.empty()) {
+    return;
+  }
+  if (!double_tap_detector_) {
+    double_tap_detector_ = std::make_unique<DoubleTapDetectorMac>([this]() {
+      performDoubleTapAction();
+    });
+  }
+  if (!double_tap_detector_->start(modifier)) {
+    // The global shortcuts are registered as the Carbon hot keys that don't
+    // need the Input Monitoring permission, so they keep working.
+    LOG(WARNING) << "Failed to start the double-tap detector: " << modifier
+                 << ". The Input Monitoring permission might not be granted.";
+  }
+}
+
+bool MainAppMac::isDoubleTapShortcutActive() {
+  return double_tap_detector_ && double_tap_detector_->isStarted();
+}
+
+void MainAppMac::disableDoubleTapShortcut() {
+  if (double_tap_detector_) {
+    double_tap_detector_->stop();
+  }
+}
+
 void MainAppMac::enablePasteNextItemShortcut() {
   disablePasteNextItemShortcut();
   auto shortcut_str = settings_->getPasteNextItemShortcut();
@@ -316,6 +383,7 @@ void MainAppMac::enablePasteNextItemShortcut() {
   }
   auto shortcuts = app()->globalShortcuts();
   bool success = shortcuts->registerShortcut(paste_next_item_shortcut_, [this](const Shortcut &) {
+    shortcutUsed("pasteNextItem");
     pasteNextItemToActiveApp();
   });
   if (!success) {
@@ -333,6 +401,53 @@ void MainAppMac::disablePasteNextItemShortcut() {
   }
 }
 
+void MainAppMac::enablePasteRecentShortcuts() {
+  disablePasteRecentShortcuts();
+  if (!settings_->shouldUsePasteRecentShortcuts()) {
+    return;
+  }
+  auto shortcuts = app()->globalShortcuts();
+  for (int number = 1; number <= 9; ++number) {
+    auto shortcut_str = "ControlLeft + AltLeft + Digit" + std::to_string(number);
+    auto shortcut = createShortcut(shortcut_str);
+    bool success = shortcuts->registerShortcut(shortcut, [this, number](const Shortcut &) {
+      shortcutUsed("pasteRecent" + std::to_string(number));
+      pasteRecentItemToActiveApp(number);
+    });
+    if (success) {
+      paste_recent_shortcuts_.push_back(shortcut);
+    } else {
+      LOG(ERROR) << "Failed to register global shortcut: " << shortcut_str;
+    }
+  }
+}
+
+void MainAppMac::disablePasteRecentShortcuts() {
+  for (const auto &shortcut : paste_recent_shortcuts_) {
+    app()->globalShortcuts()->unregisterShortcut(shortcut);
+  }
+  paste_recent_shortcuts_.clear();
+}
+
+void MainAppMac::addRegisterShortcut(const std::string &name, const std::string &shortcut_str) {
+  auto shortcut = createShortcut(shortcut_str);
+  bool success = app()->globalShortcuts()->registerShortcut(shortcut, [this, name](const Shortcut &) {
+    pasteRegisterToActiveApp(name);
+  });
+  if (success) {
+    register_shortcuts_.push_back(shortcut);
+  } else {
+    LOG(ERROR) << "Failed to register global shortcut: " << shortcut_str;
+  }
+}
+
+void MainAppMac::clearRegisterShortcuts() {
+  for (const auto &shortcut : register_shortcuts_) {
+    app()->globalShortcuts()->unregisterShortcut(shortcut);
+  }
+  register_shortcuts_.clear();
+}
+
 void MainAppMac::updateOpenSettingsShortcut() {
   auto shortcut_str = settings_->getOpenSettingsShortcut();
   open_settings_shortcut_ = createShortcut(shortcut_str);
@@ -471,6 +586,8 @@ void MainAppMac::copyToClipboard(const std::string &filePaths, bool ghost) {
   [pasteboard writeObjects:items];
   if (ghost) {
     copyCustomClip(pasteboard);
+  } else if (settings_->shouldIgnoreOwnChanges()) {
+    clipboard_reader_->ignoreChange([pasteboard changeCount]);
   }
 }
 
@@ -532,6 +649,8 @@ void MainAppMac::copyToClipboard(const std::string &text,
   [pasteboard writeObjects:items];
   if (ghost) {
     copyCustomClip(pasteboard);
+  } else if (settings_->shouldIgnoreOwnChanges()) {
+    clipboard_reader_->ignoreChange([pasteboard changeCount]);
   }
 }
 
@@ -539,6 +658,13 @@ void MainAppMac::copyToClipboardAfterMerge(std::string text) {
   clipboard_reader_->copyToClipboardAfterMerge(std::move(text));
 }
 
+void MainAppMac::writeToFindPasteboard(const std::string &text) {
+  auto pasteboard = [NSPasteboard pasteboardWithName:NSPasteboardNameFind];
+  [pasteboard clearContents];
+  [pasteboard setString:[NSString stringWithUTF8String:text.c_str()] forType:NSPasteboardTypeString];
+  clipboard_reader_->ignoreFindChange([pasteboard changeCount]);
+}
+
 std::string MainAppMac::getUpdateServerUrl() {
   if (isAppleSilicon()) {
     return "https://clipbook.app/downloads/mac/arm64";
@@ -813,6 +939,10 @@ void MainAppMac::removeAppFromLoginItems() {
   }
 }
 
+void MainAppMac::installDeepLinkHandler() {
+  [DeepLinkHandler installOnLaunch];
+}
+
 bool MainAppMac::init() {
   bool open_at_login = settings_->shouldOpenAtLogin();
   bool app_in_login_items = isAppInLoginItems();
@@ -835,8 +965,22 @@ void MainAppMac::launch() {
   }];
   [active_app_watcher startObserving];
 
+  // Handle the clipbook-link:// URLs opened from other apps, including the
+  // ones received while the app was launching.
+  [[DeepLinkHandler sharedHandler] setCallback:[this](const std::string &url) {
+    this->openDeepLink(url);
+  }];
+
   // Set up observer for application activation events.
   setupApplicationObservers();
+
+  // Apply the changes of the config file without restarting the app.
+  config_file_watcher_ = std::make_unique<ConfigFileWatcherMac>([this]() {
+    configFileChanged();
+  });
+  if (!config_file_watcher_->start(getConfigFilePath())) {
+    LOG(ERROR) << "Failed to watch the config file: " << getConfigFilePath();
+  }
 }
 
 bool MainAppMac::isAppInLoginItems() {
@@ -886,6 +1030,360 @@ bool MainAppMac::isAccessibilityAccessGranted() {
   return AXIsProcessTrusted();
 }
 
+static std::string kPermissionGranted = "granted";
+static std::string kPermissionDenied = "denied";
+static std::string kPermissionNotDetermined = "notDetermined";
+static std::string kPermissionUnknown = "unknown";
+
+// The event taps are the only thing that needs the Input Monitoring
+// permission, so the tap probe is the most reliable check.
+static std::string getInputMonitoringStatus() {
+  if (DoubleTapDetectorMac::canCreateEventTap()) {
+    return kPermissionGranted;
+  }
+  switch (IOHIDCheckAccess(kIOHIDRequestTypeListenEvent)) {
+    case kIOHIDAccessTypeDenied:
+      return kPermissionDenied;
+    default:
+      return kPermissionNotDetermined;
+  }
+}
+
+// There's no API to check Full Disk Access, so try to read the file that
+// is protected by it.
+static std::string getFullDiskAccessStatus() {
+  NSString *path = [NSHomeDirectory() stringByAppendingPathComponent:
+      @"Library/Application Support/com.apple.TCC/TCC.db"];
+  NSFileManager *file_manager = [NSFileManager defaultManager];
+  if (![file_manager fileExistsAtPath:path]) {
+    return kPermissionUnknown;
+  }
+  return [file_manager isReadableFileAtPath:path] ? kPermissionGranted : kPermissionDenied;
+}
+
+static std::string getNotificationsStatus() {
+  __block UNAuthorizationStatus status = UNAuthorizationStatusNotDetermined;
+  __block bool received = false;
+  dispatch_semaphore_t semaphore = dispatch_semaphore_create(0);
+  [[UNUserNotificationCenter currentNotificationCenter]
+      getNotificationSettingsWithCompletionHandler:^(UNNotificationSettings *settings) {
+        status = settings.authorizationStatus;
+        received = true;
+        dispatch_semaphore_signal(semaphore);
+      }];
+  dispatch_semaphore_wait(semaphore, dispatch_time(DISPATCH_TIME_NOW, NSEC_PER_SEC));
+  dispatch_release(semaphore);
+  if (!received) {
+    return kPermissionUnknown;
+  }
+  switch (status) {
+    case UNAuthorizationStatusAuthorized:
+    case UNAuthorizationStatusProvisional:
+      return kPermissionGranted;
+    case UNAuthorizationStatusDenied:
+      return kPermissionDenied;
+    default:
+      return kPermissionNotDetermined;
+  }
+}
+
+static void openPrivacySettings(NSString *pane) {
+  NSString *url = [@"x-apple.systempreferences:com.apple.preference.security?" stringByAppendingString:pane];
+  [[NSWorkspace sharedWorkspace] openURL:[NSURL URLWithString:url]];
+}
+
+std::string MainAppMac::getPermissionStatus(const std::string &kind) {
+  if (kind == "accessibility") {
+    return AXIsProcessTrusted() ? kPermissionGranted : kPermissionDenied;
+  }
+  if (kind == "inputMonitoring") {
+    return getInputMonitoringStatus();
+  }
+  if (kind == "fullDiskAccess") {
+    return getFullDiskAccessStatus();
+  }
+  if (kind == "notifications") {
+    return getNotificationsStatus();
+  }
+  return kPermissionUnknown;
+}
+
+void MainAppMac::requestPermission(const std::string &kind) {
+  if (kind == "accessibility") {
+    showSystemAccessibilityPreferencesDialog();
+  } else if (kind == "inputMonitoring") {
+    // Adds the app to the list in System Settings, so the user only needs
+    // to turn it on.
+    IOHIDRequestAccess(kIOHIDRequestTypeListenEvent);
+    openPrivacySettings(@"Privacy_ListenEvent");
+  } else if (kind == "fullDiskAccess") {
+    openPrivacySettings(@"Privacy_AllFiles");
+  } else if (kind == "notifications") {
+    UNUserNotificationCenter *center = [UNUserNotificationCenter currentNotificationCenter];
+    [center getNotificationSettingsWithCompletionHandler:^(UNNotificationSettings *settings) {
+      if (settings.authorizationStatus == UNAuthorizationStatusNotDetermined) {
+        [center requestAuthorizationWithOptions:UNAuthorizationOptionAlert
+                              completionHandler:^(BOOL granted, NSError *_Nullable error) {}];
+      } else {
+        dispatch_async(dispatch_get_main_queue(), ^{
+          [[NSWorkspace sharedWorkspace] openURL:[NSURL URLWithString:
+              @"x-apple.systempreferences:com.apple.preference.notifications"]];
+        });
+      }
+    }];
+  }
+}
+
+void MainAppMac::authenticateUser(const std::string &reason) {
+  LAContext *context = [[LAContext alloc] init];
+  NSString *localized_reason = [NSString stringWithUTF8String:reason.c_str()];
+  // Falls back to the system password if Touch ID is not available.
+  [context evaluatePolicy:LAPolicyDeviceOwnerAuthentication
+          localizedReason:localized_reason
+                    reply:^(BOOL success, NSError *error) {
+    dispatch_async(dispatch_get_main_queue(), ^{
+      userAuthenticated(success);
+    });
+    [context release];
+  }];
+}
+
+void MainAppMac::selectPath(const std::shared_ptr<molybden::Browser> &browser,
+                            int request_id,
+                            const std::string &kind,
+                            const std::string &default_name) {
+  auto target = browser;
+  std::string dialog_kind = kind;
+  NSString *name = [NSString stringWithUTF8String:default_name.c_str()];
+  dispatch_async(dispatch_get_main_queue(), ^{
+    NSSavePanel *panel;
+    if (dialog_kind == "save") {
+      panel = [NSSavePanel savePanel];
+      [panel setNameFieldStringValue:name];
+    } else {
+      NSOpenPanel *open_panel = [NSOpenPanel openPanel];
+      [open_panel setCanChooseFiles:dialog_kind != "folder"];
+      [open_panel setCanChooseDirectories:dialog_kind == "folder"];
+      [open_panel setAllowsMultipleSelection:NO];
+      panel = open_panel;
+    }
+    [panel setCanCreateDirectories:YES];
+    // The app window must stay visible while the dialog is shown.
+    auto_hide_disabled_ = true;
+    std::string path;
+    if ([panel runModal] == NSModalResponseOK) {
+      path = [[[panel URL] path] UTF8String];
+      if (dialog_kind == "folder") {
+        settings_->addGrantedDirectory(path);
+      } else {
+        std::lock_guard<std::mutex> lock(selected_files_mutex_);
+        selected_files_.insert(fs::weakly_canonical(path).string());
+      }
+    }
+    auto_hide_disabled_ = false;
+    auto frame = target->mainFrame();
+    if (frame) {
+      frame->executeJavaScript("window").asJsObject()->call("pathSelected", request_id, path);
+    }
+  });
+}
+
+bool MainAppMac::generateQRCode(const std::string &text, const std::string &image_file_name) {
+  // The scale of the generated QR code modules, so the image stays sharp.
+  const CGFloat kScale = 10.0;
+  @autoreleasepool {
+    NSData *data = [[NSString stringWithUTF8String:text.c_str()] dataUsingEncoding:NSUTF8StringEncoding];
+    CIFilter *filter = [CIFilter filterWithName:@"CIQRCodeGenerator"];
+    [filter setValue:data forKey:@"inputMessage"];
+    [filter setValue:@"M" forKey:@"inputCorrectionLevel"];
+    CIImage *qr_image = filter.outputImage;
+    if (!qr_image) {
+      // The text is too long to be encoded.
+      return false;
+    }
+    qr_image = [qr_image imageByApplyingTransform:CGAffineTransformMakeScale(kScale, kScale)];
+    NSCIImageRep *image_rep = [NSCIImageRep imageRepWithCIImage:qr_image];
+    NSImage *image = [[NSImage alloc] initWithSize:image_rep.size];
+    [image addRepresentation:image_rep];
+    NSBitmapImageRep *bitmap = [[NSBitmapImageRep alloc] initWithData:[image TIFFRepresentation]];
+    NSData *png_data = [bitmap representationUsingType:NSBitmapImageFileTypePNG properties:@{}];
+    std::string file_path = getImagesDir() + "/" + fs::path(image_file_name).filename().string();
+    bool written = [png_data writeToFile:[NSString stringWithUTF8String:file_path.c_str()] atomically:YES];
+    [bitmap release];
+    [image release];
+    return written;
+  }
+}
+
+// The Keychain service of the secrets stored by the app.
+static NSString *const kSecretService = @"com.ikryanov.clipbook";
+
+static NSDictionary *secretQuery(const std::string &name) {
+  return @{
+      (id) kSecClass: (id) kSecClassGenericPassword,
+      (id) kSecAttrService: kSecretService,
+      (id) kSecAttrAccount: [NSString stringWithUTF8String:name.c_str()],
+  };
+}
+
+bool MainAppMac::saveSecret(const std::string &name, const std::string &value) {
+  @autoreleasepool {
+    NSDictionary *query = secretQuery(name);
+    SecItemDelete((CFDictionaryRef) query);
+    if (value.empty()) {
+      return true;
+    }
+    NSMutableDictionary *item = [query mutableCopy];
+    item[(id) kSecValueData] = [NSData dataWithBytes:value.data() length:value.size()];
+    item[(id) kSecAttrAccessible] = (id) kSecAttrAccessibleAfterFirstUnlockThisDeviceOnly;
+    OSStatus status = SecItemAdd((CFDictionaryRef) item, nullptr);
+    [item release];
+    if (status != errSecSuccess) {
+      LOG(ERROR) << "Failed to save the secret " << name << " to the Keychain: " << status;
+    }
+    return status == errSecSuccess;
+  }
+}
+
+std::string MainAppMac::getSecret(const std::string &name) {
+  @autoreleasepool {
+    NSMutableDictionary *query = [secretQuery(name) mutableCopy];
+    query[(id) kSecReturnData] = @YES;
+    query[(id) kSecMatchLimit] = (id) kSecMatchLimitOne;
+    CFTypeRef result = nullptr;
+    OSStatus status = SecItemCopyMatching((CFDictionaryRef) query, &result);
+    [query release];
+    if (status != errSecSuccess || !result) {
+      return "";
+    }
+    NSData *data = (NSData *) result;
+    std::string value(static_cast<const char *>(data.bytes), data.length);
+    CFRelease(result);
+    return value;
+  }
+}
+
+bool MainAppMac::isScreenSharingActive() {
+  // The video conferencing apps show a toolbar or an indicator window while
+  // the screen is shared. The window titles are available only if the app
+  // has the Screen Recording permission, so nothing is detected without it.
+  static NSDictionary<NSString *, NSArray<NSString *> *> *indicators = @{
+      @"zoom.us": @[@"zoom share toolbar window", @"zoom share statusbar window"],
+      @"Microsoft Teams": @[@"Sharing control bar"],
+      @"Webex": @[@"Sharing"],
+      @"Slack": @[@"screen share"],
+  };
+  CFArrayRef windows = CGWindowListCopyWindowInfo(
+      kCGWindowListOptionOnScreenOnly | kCGWindowListExcludeDesktopElements, kCGNullWindowID);
+  if (!windows) {
+    return false;
+  }
+  bool sharing = false;
+  for (NSDictionary *window in (__bridge NSArray *) windows) {
+    NSString *owner = window[(__bridge NSString *) kCGWindowOwnerName];
+    NSString *title = window[(__bridge NSString *) kCGWindowName];
+    if (owner == nil || title == nil) {
+      continue;
+    }
+    // Chrome, Safari and Firefox show "<site> is sharing your screen".
+    if ([title localizedCaseInsensitiveContainsString:@"is sharing your screen"]) {
+      sharing = true;
+      break;
+    }
+    for (NSString *indicator in indicators[owner]) {
+      if ([title localizedCaseInsensitiveContainsString:indicator]) {
+        sharing = true;
+        break;
+      }
+    }
+    if (sharing) {
+      break;
+    }
+  }
+  CFRelease(windows);
+  return sharing;
+}
+
+void MainAppMac::recognizeImageText(const std::string &image_file_name) {
+  std::string file_path = getImagesDir() + "/" + fs::path(image_file_name).filename().string();
+  dispatch_async(dispatch_get_global_queue(QOS_CLASS_UTILITY, 0), ^{
+    std::string text;
+    @autoreleasepool {
+      NSImage *image = [[NSImage alloc] initWithContentsOfFile:[NSString stringWithUTF8String:file_path.c_str()]];
+      if (image) {
+        text = recognizeTextInImage(image);
+        [image release];
+      }
+    }
+    dispatch_async(dispatch_get_main_queue(), ^{
+      imageTextRecognized(image_file_name, text);
+    });
+  });
+}
+
+NotificationCenterMac *MainAppMac::getNotificationCenter() {
+  if (!notification_center_) {
+    notification_center_ = std::make_unique<NotificationCenterMac>(
+        [this](const std::string &action, int id) {
+          handleNotificationAction(action, id);
+        });
+  }
+  if (!notification_center_->isSetUp()) {
+    notification_center_->setUp(i18n("app.notifications.paste"), i18n("app.notifications.favorite"));
+  }
+  return notification_center_.get();
+}
+
+void MainAppMac::showCopyNotification(int item_id, const std::string &title, const std::string &body) {
+  getNotificationCenter()->showCopyNotification(item_id, title, body);
+}
+
+void MainAppMac::showNotification(const std::string &title, const std::string &body) {
+  getNotificationCenter()->showNotification(title, body);
+}
+
+bool MainAppMac::startLanSharing(const std::string &device_name, const std::string &key) {
+  if (!lan_share_) {
+    lan_share_ = std::make_unique<LanShareMac>([this](const std::string &payload) {
+      receiveLanItem(payload);
+    });
+  }
+  // Advertise the computer name unless another name is given.
+  std::string name = device_name;
+  if (name.empty()) {
+    NSString *computer_name = [[NSHost currentHost] localizedName];
+    name = computer_name ? [computer_name UTF8String] : "ClipBook";
+  }
+  return lan_share_->start(name, key);
+}
+
+void MainAppMac::stopLanSharing() {
+  if (lan_share_) {
+    lan_share_->stop();
+  }
+}
+
+std::vector<std::string> MainAppMac::getLanPeers() {
+  if (!lan_share_) {
+    return {};
+  }
+  return lan_share_->peers();
+}
+
+std::vector<std::string> MainAppMac::getRecentPasteboardTypes() {
+  return clipboard_reader_->getRecentTypes();
+}
+
+void MainAppMac::sendToLanPeer(const std::string &peer, const std::string &payload) {
+  if (!lan_share_ || !lan_share_->isRunning()) {
+    lanItemSent(peer, false);
+    return;
+  }
+  lan_share_->send(peer, payload, [this, peer](bool success) {
+    lanItemSent(peer, success);
+  });
+}
+
 void MainAppMac::showAccessibilityAccessDialog(const std::string &filePaths) {
   MessageDialogOptions options;
   options.message = i18n("app.dialogs.accessibilityAccessRequired.message");
@@ -987,6 +1485,48 @@ std::string MainAppMac::getAppNameFromPath(const std::string &app_path) {
   }
 }
 
+std::string MainAppMac::getAppBundleId(const std::string &app_path) {
+  @autoreleasepool {
+    std::string path = app_path;
+    // Check if the given app_path is "ClipBook.app".
+    if (app_path.find("ClipBook.app") != std::string::npos) {
+      path = getAppInfo().path;
+    }
+
+    NSBundle *appBundle = [NSBundle bundleWithPath:[NSString stringWithUTF8String:path.c_str()]];
+    if (appBundle && [appBundle bundleIdentifier]) {
+      return [[appBundle bundleIdentifier] UTF8String];
+    }
+    return {};
+  }
+}
+
+std::string MainAppMac::getAppIconFile(const std::string &app_path) {
+  auto bundle_id = getAppBundleId(app_path);
+  if (bundle_id.empty()) {
+    return "";
+  }
+  auto icons_dir = getAppIconsDir();
+  auto icon_path = icons_dir + "/" + bundle_id + ".png";
+  if (fs::exists(icon_path)) {
+    return icon_path;
+  }
+  auto icon = getFileIconAsBase64(app_path, false);
+  if (icon.empty()) {
+    return "";
+  }
+  @autoreleasepool {
+    NSData *data = [[NSData alloc] initWithBase64EncodedString:[NSString stringWithUTF8String:icon.c_str()] options:0];
+    if (!data) {
+      return "";
+    }
+    fs::create_directories(icons_dir);
+    bool written = [data writeToFile:[NSString stringWithUTF8String:icon_path.c_str()] atomically:YES];
+    [data release];
+    return written ? icon_path : "";
+  }
+}
+
 long MainAppMac::getSystemBootTime() {
   struct timeval boot_time{};
   size_t size = sizeof(boot_time);
@@ -997,6 +1537,75 @@ long MainAppMac::getSystemBootTime() {
   return -1;
 }
 
+MemoryUsage MainAppMac::getMemoryUsage() {
+  MemoryUsage usage;
+  mach_task_basic_info_data_t basic_info{};
+  mach_msg_type_number_t count = MACH_TASK_BASIC_INFO_COUNT;
+  if (task_info(mach_task_self(), MACH_TASK_BASIC_INFO,
+                reinterpret_cast<task_info_t>(&basic_info), &count) == KERN_SUCCESS) {
+    usage.resident_size = basic_info.resident_size;
+    usage.peak_resident_size = basic_info.resident_size_max;
+  }
+  task_vm_info_data_t vm_info{};
+  count = TASK_VM_INFO_COUNT;
+  if (task_info(mach_task_self(), TASK_VM_INFO,
+                reinterpret_cast<task_info_t>(&vm_info), &count) == KERN_SUCCESS) {
+    usage.footprint = vm_info.phys_footprint;
+  }
+  // Passing null collects the statistics of all the malloc zones.
+  malloc_statistics_t stats{};
+  malloc_zone_statistics(nullptr, &stats);
+  usage.allocated_size = stats.size_in_use;
+  usage.allocated_blocks = stats.blocks_in_use;
+  return usage;
+}
+
+SystemInfo MainAppMac::getSystemInfo() {
+  SystemInfo info = MainApp::getSystemInfo();
+  NSProcessInfo *process_info = [NSProcessInfo processInfo];
+  info.os_version = [[process_info operatingSystemVersionString] UTF8String];
+  info.cpu_count = static_cast<int>([process_info activeProcessorCount]);
+  info.total_memory = [process_info physicalMemory];
+  double load[1];
+  if (getloadavg(load, 1) == 1) {
+    info.load_average = load[0];
+  }
+  // The inactive pages are reclaimed when needed, so they're available too.
+  vm_statistics64_data_t vm_stats{};
+  mach_msg_type_number_t count = HOST_VM_INFO64_COUNT;
+  if (host_statistics64(mach_host_self(), HOST_VM_INFO64,
+                        reinterpret_cast<host_info64_t>(&vm_stats), &count) == KERN_SUCCESS) {
+    info.available_memory = static_cast<uint64_t>(vm_stats.free_count + vm_stats.inactive_count) * vm_kernel_page_size;
+  }
+
+  CFTypeRef power_info = IOPSCopyPowerSourcesInfo();
+  if (power_info == nullptr) {
+    return info;
+  }
+  CFStringRef power_source = IOPSGetProvidingPowerSourceType(power_info);
+  info.on_ac_power = power_source == nullptr || CFEqual(power_source, CFSTR(kIOPMACPowerKey));
+  CFArrayRef sources = IOPSCopyPowerSourcesList(power_info);
+  if (sources != nullptr) {
+    for (CFIndex i = 0; i < CFArrayGetCount(sources); i++) {
+      NSDictionary *source = (NSDictionary *) IOPSGetPowerSourceDescription(power_info, CFArrayGetValueAtIndex(sources, i));
+      if (![source[@kIOPSTypeKey] isEqualToString:@kIOPSInternalBatteryType]) {
+        continue;
+      }
+      NSNumber *capacity = source[@kIOPSCurrentCapacityKey];
+      NSNumber *max_capacity = source[@kIOPSMaxCapacityKey];
+      info.has_battery = true;
+      if (capacity && max_capacity && max_capacity.intValue > 0) {
+        info.battery_level = capacity.intValue * 100 / max_capacity.intValue;
+      }
+      info.battery_charging = [source[@kIOPSIsChargingKey] boolValue];
+      break;
+    }
+    CFRelease(sources);
+  }
+  CFRelease(power_info);
+  return info;
+}
+
 NSPoint MainAppMac::getInputCursorLocationOnScreen() {
   // Get the system-wide accessibility object
   AXUIElementRef systemWideElement = AXUIElementCreateSystemWide();
@@ -1062,7 +1671,8 @@ std::string MainAppMac::getAppInfo(const std::string &app_path) {
   if (appIcon.empty()) {
     return "";
   }
-  return appName + kAppInfoSeparator + app_path + kAppInfoSeparator + appIcon;
+  return appName + kAppInfoSeparator + app_path + kAppInfoSeparator + appIcon +
+      kAppInfoSeparator + getAppBundleId(app_path);
 }
 
 std::string MainAppMac::getDefaultAppInfo(const std::string &file_path) {
@@ -1143,6 +1753,48 @@ void MainAppMac::preview(const std::string &file_path) {
   });
 }
 
+void MainAppMac::share(const std::string &text, const std::string &url, const std::string &image_file_name) {
+  NSMutableArray *items = [NSMutableArray array];
+  if (!image_file_name.empty()) {
+    std::string file_path = getImagesDir() + "/" + fs::path(image_file_name).filename().string();
+    [items addObject:[NSURL fileURLWithPath:[NSString stringWithUTF8String:file_path.c_str()]]];
+  } else if (!url.empty()) {
+    NSURL *nsURL = [NSURL URLWithString:[NSString stringWithUTF8String:url.c_str()]];
+    if (nsURL) {
+      [items addObject:nsURL];
+    }
+  }
+  if (items.count == 0 && !text.empty()) {
+    [items addObject:[NSString stringWithUTF8String:text.c_str()]];
+  }
+  if (items.count == 0) {
+    return;
+  }
+  // The window must stay visible while the share sheet is shown.
+  auto_hide_disabled_ = true;
+  app_window_->setAlwaysOnTop(false);
+  dispatch_async(dispatch_get_main_queue(), ^{
+    [[SharePicker shared] setOnCloseCallback:^{
+      auto_hide_disabled_ = false;
+      app_window_->setAlwaysOnTop(true);
+    }];
+    [[SharePicker shared] shareItems:items];
+  });
+}
+
+void MainAppMac::announce(const std::string &message) {
+  NSString *announcement = [NSString stringWithUTF8String:message.c_str()];
+  dispatch_async(dispatch_get_main_queue(), ^{
+    NSDictionary *userInfo = @{
+        NSAccessibilityAnnouncementKey: announcement,
+        NSAccessibilityPriorityKey: @(NSAccessibilityPriorityHigh)
+    };
+    NSAccessibilityPostNotificationWithUserInfo([NSApp mainWindow] ?: NSApp,
+                                                NSAccessibilityAnnouncementRequestedNotification,
+                                                userInfo);
+  });
+}
+
 void MainAppMac::setupApplicationObservers() {
   // Set up observer for application activation events.
   [[NSNotificationCenter defaultCenter]
@@ -1162,4 +1814,17 @@ void MainAppMac::setupApplicationObservers() {
                       }).detach();
                     });
               }];
+
+  // Clear history when the user logs out or the Mac shuts down.
+  [[[NSWorkspace sharedWorkspace] notificationCenter]
+      addObserverForName:NSWorkspaceWillPowerOffNotification
+                  object:nil
+                   queue:[NSOperationQueue mainQueue]
+              usingBlock:^(NSNotification *notification) {
+                // Block until the history is cleared because the app is
+                // terminated right after the notification.
+                if (settings_->shouldClearHistoryOnLogout()) {
+                  clearHistoryAndWait("logout");
+                }
+              }];
 }
@@ -0,0 +1,37 @@
+#ifndef CLIPBOOK_NOTIFICATION_CENTER_MAC_H_
+#define CLIPBOOK_NOTIFICATION_CENTER_MAC_H_
+
+#include <functional>
+#include <memory>
+#include <string>
+
+// Shows the native notifications with the action buttons using the user
+// notifications framework. The action chosen by the user is passed to the
+// handler together with the id of the history item the notification is
+// about.
+class NotificationCenterMac {
+ public:
+  using ActionHandler = std::function<void(const std::string &action, int item_id)>;
+
+  static constexpr const char *kPasteAction = "paste";
+  static constexpr const char *kFavoriteAction = "favorite";
+  static constexpr const char *kOpenAction = "open";
+
+  explicit NotificationCenterMac(ActionHandler handler);
+  ~NotificationCenterMac();
+
+  // Registers the "Paste" and "Favorite" buttons of the copy notification
+  // with the given titles and asks the user for the permission to show the
+  // notifications if it hasn't been granted yet.
+  void setUp(const std::string &paste_title, const std::string &favorite_title);
+  bool isSetUp() const;
+  void showCopyNotification(int item_id, const std::string &title, const std::string &body);
+  // Shows the notification without the action buttons.
+  void showNotification(const std::string &title, const std::string &body);
+
+ private:
+  struct Impl;
+  std::unique_ptr<Impl> impl_;
+};
+
+#endif // CLIPBOOK_NOTIFICATION_CENTER_MAC_H_
@@ -0,0 +1,127 @@
+#include "notification_center_mac.h"
+
+#import <Foundation/Foundation.h>
+#import <UserNotifications/UserNotifications.h>
+
+static NSString *kCopyCategory = @"com.clipbook.copy";
+static NSString *kPasteActionId = @"com.clipbook.paste";
+static NSString *kFavoriteActionId = @"com.clipbook.favorite";
+static NSString *kInfoCategory = @"com.clipbook.info";
+static NSString *kItemIdKey = @"itemId";
+
+@interface NotificationCenterDelegate : NSObject <UNUserNotificationCenterDelegate> {
+ @private
+  NotificationCenterMac::ActionHandler handler_;
+}
+
+- (instancetype)initWithHandler:(NotificationCenterMac::ActionHandler)handler;
+
+@end
+
+@implementation NotificationCenterDelegate
+
+- (instancetype)initWithHandler:(NotificationCenterMac::ActionHandler)handler {
+  self = [super init];
+  if (self) {
+    handler_ = handler;
+  }
+  return self;
+}
+
+// Show the notification even if the app is active.
+- (void)userNotificationCenter:(UNUserNotificationCenter *)center
+       willPresentNotification:(UNNotification *)notification
+         withCompletionHandler:(void (^)(UNNotificationPresentationOptions))completionHandler {
+  completionHandler(UNNotificationPresentationOptionBanner);
+}
+
+- (void)userNotificationCenter:(UNUserNotificationCenter *)center
+didReceiveNotificationResponse:(UNNotificationResponse *)response
+         withCompletionHandler:(void (^)(void))completionHandler {
+  NSNumber *item_id = response.notification.request.content.userInfo[kItemIdKey];
+  NSString *action_id = response.actionIdentifier;
+  if (item_id && handler_) {
+    if ([action_id isEqualToString:kPasteActionId]) {
+      handler_(NotificationCenterMac::kPasteAction, [item_id intValue]);
+    } else if ([action_id isEqualToString:kFavoriteActionId]) {
+      handler_(NotificationCenterMac::kFavoriteAction, [item_id intValue]);
+    } else if ([action_id isEqualToString:UNNotificationDefaultActionIdentifier]) {
+      handler_(NotificationCenterMac::kOpenAction, [item_id intValue]);
+    }
+  }
+  completionHandler();
+}
+
+@end
+
+struct NotificationCenterMac::Impl {
+  NotificationCenterDelegate *delegate = nil;
+  bool set_up = false;
+};
+
+NotificationCenterMac::NotificationCenterMac(ActionHandler handler) : impl_(std::make_unique<Impl>()) {
+  impl_->delegate = [[NotificationCenterDelegate alloc] initWithHandler:std::move(handler)];
+  [UNUserNotificationCenter currentNotificationCenter].delegate = impl_->delegate;
+}
+
+NotificationCenterMac::~NotificationCenterMac() {
+  UNUserNotificationCenter *center = [UNUserNotificationCenter currentNotificationCenter];
+  if (center.delegate == impl_->delegate) {
+    center.delegate = nil;
+  }
+  [impl_->delegate release];
+}
+
+void NotificationCenterMac::setUp(const std::string &paste_title, const std::string &favorite_title) {
+  UNUserNotificationCenter *center = [UNUserNotificationCenter currentNotificationCenter];
+  UNNotificationAction *paste_action =
+      [UNNotificationAction actionWithIdentifier:kPasteActionId
+                                           title:[NSString stringWithUTF8String:paste_title.c_str()]
+                                         options:UNNotificationActionOptionNone];
+  UNNotificationAction *favorite_action =
+      [UNNotificationAction actionWithIdentifier:kFavoriteActionId
+                                           title:[NSString stringWithUTF8String:favorite_title.c_str()]
+                                         options:UNNotificationActionOptionNone];
+  UNNotificationCategory *category =
+      [UNNotificationCategory categoryWithIdentifier:kCopyCategory
+                                             actions:@[paste_action, favorite_action]
+                                   intentIdentifiers:@[]
+                                             options:UNNotificationCategoryOptionNone];
+  [center setNotificationCategories:[NSSet setWithObject:category]];
+  [center requestAuthorizationWithOptions:UNAuthorizationOptionAlert
+                        completionHandler:^(BOOL granted, NSError *_Nullable error) {}];
+  impl_->set_up = true;
+}
+
+bool NotificationCenterMac::isSetUp() const {
+  return impl_->set_up;
+}
+
+void NotificationCenterMac::showCopyNotification(int item_id,
+                                                 const std::string &title,
+                                                 const std::string &body) {
+  UNMutableNotificationContent *content = [[UNMutableNotificationContent alloc] init];
+  content.title = [NSString stringWithUTF8String:title.c_str()];
+  content.body = [NSString stringWithUTF8String:body.c_str()];
+  content.categoryIdentifier = kCopyCategory;
+  content.userInfo = @{kItemIdKey: @(item_id)};
+  // Replace the previous copy notification instead of piling them up.
+  UNNotificationRequest *request = [UNNotificationRequest requestWithIdentifier:kCopyCategory
+                                                                        content:content
+                                                                        trigger:nil];
+  [[UNUserNotificationCenter currentNotificationCenter] addNotificationRequest:request
+                                                         withCompletionHandler:nil];
+  [content release];
+}
+
+void NotificationCenterMac::showNotification(const std::string &title, const std::string &body) {
+  UNMutableNotificationContent *content = [[UNMutableNotificationContent alloc] init];
+  content.title = [NSString stringWithUTF8String:title.c_str()];
+  content.body = [NSString stringWithUTF8String:body.c_str()];
+  UNNotificationRequest *request = [UNNotificationRequest requestWithIdentifier:kInfoCategory
+                                                                        content:content
+                                                                        trigger:nil];
+  [[UNUserNotificationCenter currentNotificationCenter] addNotificationRequest:request
+                                                         withCompletionHandler:nil];
+  [content release];
+}
@@ -0,0 +1,11 @@
+#import <Foundation/Foundation.h>
+
+typedef void (^SharePickerDidCloseCallback)(void);
+
+@interface SharePicker : NSObject
+
++ (instancetype)shared;
+- (void)shareItems:(NSArray *)items;
+- (void)setOnCloseCallback:(SharePickerDidCloseCallback)callback;
+
+@end
@@ -0,0 +1,74 @@
+#import "share_picker_mac.h"
+
+#import <Cocoa/Cocoa.h>
+
+@interface SharePicker ()<NSSharingServicePickerDelegate, NSSharingServiceDelegate>
+@property(nonatomic, strong) NSSharingServicePicker *picker;
+@property(nonatomic, copy) SharePickerDidCloseCallback closeCallback;
+@end
+
+@implementation SharePicker
+
++ (instancetype)shared {
+  static SharePicker *sharedInstance = nil;
+  static dispatch_once_t onceToken;
+  dispatch_once(&onceToken, ^{
+    sharedInstance = [[SharePicker alloc] init];
+  });
+  return sharedInstance;
+}
+
+- (void)shareItems:(NSArray *)items {
+  NSView *view = [NSApp keyWindow].contentView ?: [NSApp mainWindow].contentView;
+  if (!view) {
+    [self close];
+    return;
+  }
+  self.picker = [[[NSSharingServicePicker alloc] initWithItems:items] autorelease];
+  self.picker.delegate = self;
+  // The picker is shown at the top center of the app window.
+  NSRect bounds = view.bounds;
+  NSRect rect = NSMakeRect(NSMidX(bounds), view.isFlipped ? NSMinY(bounds) : NSMaxY(bounds), 1, 1);
+  [self.picker showRelativeToRect:rect ofView:view preferredEdge:NSRectEdgeMinY];
+}
+
+- (void)setOnCloseCallback:(SharePickerDidCloseCallback)callback {
+  self.closeCallback = callback;
+}
+
+- (void)close {
+  self.picker = nil;
+  if (self.closeCallback) {
+    self.closeCallback();
+    self.closeCallback = nil;
+  }
+}
+
+#pragma mark - NSSharingServicePickerDelegate
+
+- (id<NSSharingServiceDelegate>)sharingServicePicker:(NSSharingServicePicker *)sharingServicePicker
+                           delegateForSharingService:(NSSharingService *)sharingService {
+  return self;
+}
+
+- (void)sharingServicePicker:(NSSharingServicePicker *)sharingServicePicker
+     didChooseSharingService:(NSSharingService *)service {
+  // The service is nil if the picker has been dismissed.
+  if (!service) {
+    [self close];
+  }
+}
+
+#pragma mark - NSSharingServiceDelegate
+
+- (void)sharingService:(NSSharingService *)sharingService didShareItems:(NSArray *)items {
+  [self close];
+}
+
+- (void)sharingService:(NSSharingService *)sharingService
+   didFailToShareItems:(NSArray *)items
+                 error:(NSError *)error {
+  [self close];
+}
+
+@end
@@ -0,0 +1,92 @@
+#include "tray_state_controller.h"
+
+#include <utility>
+
+TrayStateController::TrayStateController(Listener listener) : listener_(std::move(listener)) {}
+
+void TrayStateController::setPaused(bool paused) {
+  {
+    std::lock_guard<std::mutex> guard(mutex_);
+    paused_ = paused;
+  }
+  update();
+}
+
+void TrayStateController::setSyncing(bool syncing) {
+  {
+    std::lock_guard<std::mutex> guard(mutex_);
+    syncing_ = syncing;
+  }
+  update();
+}
+
+void TrayStateController::setError(bool error) {
+  {
+    std::lock_guard<std::mutex> guard(mutex_);
+    error_ = error;
+  }
+  update();
+}
+
+void TrayStateController::setBadgeCount(int count) {
+  {
+    std::lock_guard<std::mutex> guard(mutex_);
+    badge_count_ = count > 0 ? count : 0;
+  }
+  update();
+}
+
+TrayState TrayStateController::state() {
+  std::lock_guard<std::mutex> guard(mutex_);
+  return computeState();
+}
+
+std::string TrayStateController::badge() {
+  std::lock_guard<std::mutex> guard(mutex_);
+  return computeBadge();
+}
+
+TrayState TrayStateController::computeState() const {
+  if (error_) {
+    return TrayState::kError;
+  }
+  if (paused_) {
+    return TrayState::kPaused;
+  }
+  if (syncing_) {
+    return TrayState::kSyncing;
+  }
+  return TrayState::kNormal;
+}
+
+std::string TrayStateController::computeBadge() const {
+  std::string badge;
+  switch (computeState()) {
+    case TrayState::kError:
+      badge = "⚠";
+      break;
+    case TrayState::kSyncing:
+      badge = "↻";
+      break;
+    default:
+      break;
+  }
+  if (badge_count_ > 0) {
+    if (!badge.empty()) {
+      badge += " ";
+    }
+    badge += std::to_string(badge_count_);
+  }
+  return badge;
+}
+
+void TrayStateController::update() {
+  TrayState state;
+  std::string badge;
+  {
+    std::lock_guard<std::mutex> guard(mutex_);
+    state = computeState();
+    badge = computeBadge();
+  }
+  listener_(state, badge);
+}
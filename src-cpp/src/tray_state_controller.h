@@ -0,0 +1,47 @@
+#ifndef CLIPBOOK_TRAY_STATE_CONTROLLER_H_
+#define CLIPBOOK_TRAY_STATE_CONTROLLER_H_
+
+#include <functional>
+#include <mutex>
+#include <string>
+
+enum class TrayState {
+  kNormal,
+  kPaused,
+  kSyncing,
+  kError
+};
+
+// Combines the states reported by the app subsystems into the state of the
+// tray icon and the badge text displayed next to it. The error state takes
+// precedence over the paused state, and the paused state takes precedence
+// over the sync in progress.
+class TrayStateController {
+ public:
+  using Listener = std::function<void(TrayState state, const std::string &badge)>;
+
+  explicit TrayStateController(Listener listener);
+
+  void setPaused(bool paused);
+  void setSyncing(bool syncing);
+  void setError(bool error);
+  // Displays the number next to the tray icon. Zero hides the number.
+  void setBadgeCount(int count);
+
+  TrayState state();
+  std::string badge();
+
+ private:
+  TrayState computeState() const;
+  std::string computeBadge() const;
+  void update();
+
+  Listener listener_;
+  std::mutex mutex_;
+  bool paused_ = false;
+  bool syncing_ = false;
+  bool error_ = false;
+  int badge_count_ = 0;
+};
+
+#endif // CLIPBOOK_TRAY_STATE_CONTROLLER_H_
@@ -1,5 +1,13 @@
 #include "utils.h"
 
+#include <algorithm>
+#include <cstdio>
+#include <filesystem>
+#include <fstream>
+#include <vector>
+
+namespace fs = std::filesystem;
+
 bool isEmptyOrSpaces(const std::string &str) {
   // Check if the string is empty or contains only spaces
   return str.empty() || std::all_of(str.begin(), str.end(), [](char c) {
@@ -27,3 +35,53 @@ long long getCurrentTimeMillis() {
   return duration_cast<std::chrono::milliseconds>(
       std::chrono::system_clock::now().time_since_epoch()).count();
 }
+
+std::string toJsonString(const std::string &str) {
+  std::string result = "\"";
+  for (char c : str) {
+    switch (c) {
+      case '"': result += "\\\""; break;
+      case '\\': result += "\\\\"; break;
+      case '\b': result += "\\b"; break;
+      case '\f': result += "\\f"; break;
+      case '\n': result += "\\n"; break;
+      case '\r': result += "\\r"; break;
+      case '\t': result += "\\t"; break;
+      default:
+        if (static_cast<unsigned char>(c) < 0x20) {
+          char buf[7];
+          snprintf(buf, sizeof(buf), "\\u%04x", static_cast<unsigned char>(c));
+          result += buf;
+        } else {
+          result += c;
+        }
+    }
+  }
+  result += "\"";
+  return result;
+}
+
+bool secureRemoveFile(const std::string &filePath) {
+  std::error_code error;
+  auto size = fs::file_size(filePath, error);
+  if (error) {
+    return false;
+  }
+  {
+    std::fstream file(filePath, std::ios::in | std::ios::out | std::ios::binary);
+    if (!file) {
+      return false;
+    }
+    std::vector<char> zeros(64 * 1024, 0);
+    for (std::uintmax_t written = 0; written < size;) {
+      auto chunk = std::min<std::uintmax_t>(zeros.size(), size - written);
+      file.write(zeros.data(), static_cast<std::streamsize>(chunk));
+      written += chunk;
+    }
+    file.flush();
+    if (!file) {
+      return false;
+    }
+  }
+  return fs::remove(filePath, error);
+}
@@ -16,4 +16,12 @@ std::string getAppDataDir();
 // Returns the current time in milliseconds since the UNIX epoch.
 long long getCurrentTimeMillis();
 
+// Returns the given string as a quoted and escaped JSON string literal.
+std::string toJsonString(const std::string &str);
+
+// Overwrites the content of the file with zeros before removing it, so the
+// content can't be recovered from the freed disk blocks. Returns false if
+// the file can't be overwritten or removed.
+bool secureRemoveFile(const std::string &filePath);
+
 #endif  // CLIPBOOK_UTILS_H_
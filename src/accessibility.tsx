@@ -0,0 +1,40 @@
+import i18n from "i18next";
+import {Clip, ClipType} from "@/db";
+import {getSourceAppInfo} from "@/data";
+import {prefShouldAnnounceClipboardChanges} from "@/pref";
+
+declare const announce: (message: string) => void;
+
+function announceMessage(message: string) {
+  if (typeof announce === 'undefined' || !prefShouldAnnounceClipboardChanges()) {
+    return
+  }
+  announce(message)
+}
+
+function sourceAppName(item: Clip): string {
+  let appInfo = item.sourceApp ? getSourceAppInfo(item.sourceApp) : undefined
+  return appInfo ? appInfo.name : i18n.t("app.announcements.unknownApp")
+}
+
+// Announces the captured item via VoiceOver, e.g. "Copied: 42 characters from Safari".
+export function announceCopy(item: Clip) {
+  let app = sourceAppName(item)
+  if (item.type === ClipType.Image) {
+    announceMessage(i18n.t("app.announcements.copiedImage", {app}))
+  } else if (item.type === ClipType.File) {
+    announceMessage(i18n.t("app.announcements.copiedFile", {name: item.filePathFileName, app}))
+  } else {
+    announceMessage(i18n.t("app.announcements.copiedText", {count: item.content.length, app}))
+  }
+}
+
+export function announcePaste(item: Clip) {
+  if (item.type === ClipType.Image) {
+    announceMessage(i18n.t("app.announcements.pastedImage"))
+  } else if (item.type === ClipType.File) {
+    announceMessage(i18n.t("app.announcements.pastedFile", {name: item.filePathFileName}))
+  } else {
+    announceMessage(i18n.t("app.announcements.pastedText", {count: item.content.length}))
+  }
+}
@@ -1,6 +1,10 @@
 import mitt, {Emitter} from 'mitt';
 import {AppInfo, TextFormatOperation, TextType} from "@/data";
 import {Clip} from "@/db";
+import {SyncStatus} from "@/sync";
+import {PermissionChange} from "@/permissions";
+import {SnippetAction} from "@/snippets";
+import {CollectionAction} from "@/collections";
 
 export type OpenFileItemWithAppByIndexArgs = {
   appPath: string;
@@ -17,6 +21,28 @@ export type FormatTextByIndexArgs = {
   index: number;
 }
 
+export type ExportProgressArgs = {
+  processed: number;
+  total: number;
+}
+
+export type BackupProgressArgs = {
+  processed: number;
+  total: number;
+}
+
+export type BackupCompletedArgs = {
+  fileName: string;
+  incremental: boolean;
+  clips: number;
+}
+
+export type RestoreProgressArgs = {
+  processed: number;
+  total: number;
+  percent: number;
+}
+
 export type Events = {
   AddTagToItemWithId?: number;
   FilterHistory: void;
@@ -34,7 +60,9 @@ export type Events = {
   DeleteItem: void;
   DeleteItemByIndex: number;
   DeleteItems: void;
+  SecureDeleteItems: void;
   DeleteAllItems: void;
+  UndoClearHistory: void;
   RenameItem: void;
   RenameItemByIndex: number;
   UpdateItemById?: number;
@@ -45,6 +73,13 @@ export type Events = {
   ShowOpenWithCommands: void;
   ShowOpenWithCommandsByIndex: number;
   ShowPasteTransformationCommands: void;
+  ShowSnippetCommands: SnippetAction;
+  ShowCollectionCommands: CollectionAction;
+  ShowDeviceCommands: void;
+  ShowTrashCommands: void;
+  ShowCollection: number;
+  PinItems: number;
+  UnpinItems: void;
   ShowInFinder: void;
   ShowInFinderByIndex: number;
   ShowInHistory: Clip;
@@ -82,11 +117,30 @@ export type Events = {
   OpenInApp: AppInfo | undefined;
   OpenInAppByIndex: OpenInAppByIndexArgs;
   OpenSettings: void;
+  SyncNow: void;
   ZoomIn: void;
   ZoomOut: void;
   ResetZoom: void;
   UpdateLinkPreview: void;
   UpdateLanguage: void;
+  ExportProgress: ExportProgressArgs;
+  BackupProgress: BackupProgressArgs;
+  BackupCompleted: BackupCompletedArgs;
+  RestoreProgress: RestoreProgressArgs;
+  DatabaseIncompatible: string;
+  HistoryChanged: void;
+  HistoryItemAdded: Clip;
+  HistoryItemUpdated: Clip;
+  HistoryItemDeleted: Clip;
+  HistoryCleared: void;
+  SearchHistory: string;
+  SyncStatusChanged: SyncStatus;
+  LanItemSent: { peer: string, success: boolean };
+  AppLockChanged: boolean;
+  GuestModeChanged: boolean;
+  ScreenSharingChanged: boolean;
+  PermissionChanged: PermissionChange;
+  PreferencesChanged: string[];
 };
 
 export const emitter: Emitter<Events> = mitt<Events>();
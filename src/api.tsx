@@ -0,0 +1,1120 @@
+import {Clip, ClipType, getFilePath, getHTML, getImageFileName, getRTF} from "@/db";
+import {
+  canUndoClearHistory,
+  convertColor,
+  getHistoryItemById,
+  getItemPreview,
+  isItemPinned,
+  isItemSensitive,
+  markItemSensitive,
+  setItemNote,
+  updateItemContent,
+  revealItem,
+  getHistorySorted,
+  getSessionItems,
+  getRemoteItems,
+  getSessions,
+  getSourceAppUsage,
+  getTrashItems,
+  undoClearHistory,
+  searchHistory,
+  secureDeleteHistoryItem,
+  SortHistoryType
+} from "@/data";
+import {ColorFormat, kColorFormats} from "@/lib/colors";
+import {executePickerAction, getQuickPickerItems, PickerAction} from "@/picker";
+import {typeName} from "@/export";
+import {emitter} from "@/actions";
+import {ClipBookError, ErrorCode, serializeError} from "@/lib/errors";
+import {AppLockedError, isAppLocked, requireUnlocked} from "@/applock";
+import {enterGuestMode, GuestModeError, isGuestMode} from "@/guest";
+import {getFullContent} from "@/blobs";
+import {getCachedLinkPreview} from "@/linkpreview";
+import {generateQR} from "@/qrcode";
+import {exportItemToFile} from "@/itemexport";
+import {share} from "@/share";
+import {setFindPasteboardText} from "@/findpasteboard";
+import {compactHistory} from "@/compact";
+import {checkDatabaseIntegrity, getLastIntegrityReport} from "@/integrity";
+import {archiveItemsOlderThan, searchArchive, unarchiveItem} from "@/archive";
+import {deleteTagGlobally, getAllTags, mergeTags, renameTagGlobally, suggestTags} from "@/tagging";
+import {findTagById} from "@/tags";
+import {createProfile, findProfileById, getCurrentProfileId, listProfiles, switchProfile} from "@/profiles";
+import {
+  copyToRegister,
+  deleteRegister,
+  getRegisterItem,
+  isValidRegisterName,
+  listRegisters,
+  pasteRegister,
+  setRegisterShortcut
+} from "@/registers";
+import {cancelRestore, getRestoreJob, JobStatus, restoreFromBackup, RestoreMode, verifyBackup} from "@/restore";
+import {emptyTrash, restoreItem} from "@/trash";
+import {exportMetrics, getCacheMetrics, getMemoryMetrics, getPerformanceMetrics, MetricsFormat} from "@/metrics";
+import {getHistoryPage} from "@/historycache";
+import {searchHistoryRegex} from "@/regexsearch";
+import {createPasteSequence, getPasteSequence, pasteSequenceNext} from "@/sequence";
+import {
+  getCaptureFilters,
+  getCaptureState,
+  listRecentPasteboardTypes,
+  pauseCapture,
+  resumeCapture,
+  setCaptureFilters
+} from "@/capture";
+import {getSystemInfoCached, getSystemState} from "@/system";
+import {getAppHealth} from "@/health";
+import {getLogLevel, getRecentLogs, setLogLevel} from "@/logger";
+import {getTodaySummary, getUsageStatistics, kStatisticsPeriods, StatisticsPeriod} from "@/stats";
+import {
+  checkShortcut,
+  findShortcutBinding,
+  getDoubleTapShortcut,
+  getShortcuts,
+  setDoubleTapShortcut,
+  setShortcut
+} from "@/shortcuts";
+import {exportSettings, importSettings} from "@/settingsbundle";
+import {getHistoryGrouped, isTimelineGranularity, TimelineGranularity} from "@/timeline";
+import {getPermissionStatuses, isPermissionKind, requestSystemPermission} from "@/permissions";
+import {
+  DoubleTapAction,
+  DoubleTapModifier,
+  LogLevel,
+  prefGetApiServerPort,
+  prefGetApiServerToken,
+  prefSetApiServerToken,
+  prefShouldApiServerEnabled
+} from "@/pref";
+
+declare const startApiServer: (port: number, token: string) => boolean;
+declare const stopApiServer: () => void;
+declare const isApiServerRunning: () => boolean;
+declare const broadcastApiEvent: (event: string, data: string) => void;
+declare const copyToClipboard: (text: string, rtf: string, html: string, imageFileName: string, filePath: string, ghost: boolean) => void;
+
+const kDefaultLimit = 50
+
+// The requests that don't modify the history and are allowed in guest mode
+// besides the GET requests.
+const kGuestModeRequests = [
+  /^\/copy$/,
+  /^\/paste$/,
+  /^\/registers\/[^/]+\/paste$/,
+  /^\/sequence(\/next)?$/,
+  /^\/backups\/verify$/,
+]
+
+// The error codes of the HTTP status codes used by the API.
+const kErrorCodesByStatus: Record<number, ErrorCode> = {
+  400: ErrorCode.InvalidArgument,
+  404: ErrorCode.NotFound,
+  409: ErrorCode.Conflict,
+  422: ErrorCode.InvalidArgument,
+  503: ErrorCode.Unavailable,
+}
+
+// The HTTP status codes of the errors thrown by the commands.
+const kStatusesByErrorCode: Partial<Record<ErrorCode, number>> = {
+  [ErrorCode.PermissionDenied]: 403,
+  [ErrorCode.Locked]: 423,
+  [ErrorCode.ReadOnly]: 403,
+  [ErrorCode.Unavailable]: 503,
+  [ErrorCode.NotFound]: 404,
+  [ErrorCode.InvalidArgument]: 400,
+  [ErrorCode.Conflict]: 409,
+  [ErrorCode.DatabaseBusy]: 503,
+  [ErrorCode.StorageFull]: 507,
+}
+
+class ApiError extends ClipBookError {
+  status: number
+
+  constructor(status: number, message: string, code: ErrorCode = kErrorCodesByStatus[status] ?? ErrorCode.Internal) {
+    super(code, message)
+    this.status = status
+  }
+}
+
+function toApiLinkPreview(clip: Clip) {
+  let details = clip.type === ClipType.Link ? getCachedLinkPreview(clip.content) : undefined
+  if (!details) {
+    return undefined
+  }
+  return {
+    title: details.title,
+    description: details.description,
+    imageFileName: details.imageFileName || undefined,
+    faviconFileName: details.faviconFileName || undefined,
+  }
+}
+
+function toApiItem(clip: Clip) {
+  return {
+    id: clip.id,
+    name: clip.name,
+    type: typeName(clip.type),
+    content: clip.type === ClipType.Image ? clip.imageText : getItemPreview(clip),
+    sensitive: clip.sensitive === true,
+    note: clip.note,
+    color: clip.color,
+    filePath: clip.filePath || undefined,
+    sourceApp: clip.sourceApp,
+    sourceAppBundleId: clip.sourceAppBundleId,
+    favorite: clip.favorite,
+    tags: clip.tags ?? [],
+    copyTime: new Date(clip.copyTime).toISOString(),
+    numberOfCopies: clip.numberOfCopies,
+    pasteCount: clip.pasteCount ?? 0,
+    lastPastedAt: clip.lastPastedAt ? new Date(clip.lastPastedAt).toISOString() : undefined,
+    linkPreview: toApiLinkPreview(clip),
+    sessionId: clip.sessionId,
+    pasteboard: clip.pasteboard,
+    sourceDevice: clip.sourceDevice,
+    deletedAt: clip.deletedAt ? new Date(clip.deletedAt).toISOString() : undefined,
+  }
+}
+
+// The number of characters of the content in the item summaries.
+const kSummaryPreviewLength = 200
+
+// Returns the lightweight item for the lists. The full content is loaded
+// on demand with "/history/:id/content".
+function toApiItemSummary(clip: Clip) {
+  let preview = clip.type === ClipType.Image ? clip.imageText ?? "" : getItemPreview(clip)
+  return {
+    id: clip.id,
+    name: clip.name,
+    type: typeName(clip.type),
+    preview: preview.substring(0, kSummaryPreviewLength),
+    copyTime: new Date(clip.copyTime).toISOString(),
+    favorite: clip.favorite,
+    pinned: isItemPinned(clip),
+    sensitive: clip.sensitive === true,
+    hasNote: clip.note !== undefined,
+    tags: clip.tags ?? [],
+  }
+}
+
+// Returns the item representation requested by the `view` query parameter
+// of the list endpoints.
+function getItemMapper(params: URLSearchParams): (clip: Clip) => object {
+  let view = params.get("view") || "summary"
+  switch (view) {
+    case "summary":
+      return toApiItemSummary
+    case "full":
+      return toApiItem
+    default:
+      throw new ApiError(400, `Unknown view: ${view}`)
+  }
+}
+
+function getItemContent(clip: Clip) {
+  return {
+    id: clip.id,
+    type: typeName(clip.type),
+    content: isItemSensitive(clip) ? getItemPreview(clip) : getFullContent(clip),
+    rtf: isItemSensitive(clip) ? "" : getRTF(clip),
+    html: isItemSensitive(clip) ? "" : getHTML(clip),
+    imageText: clip.imageText,
+    imageFileName: clip.imageFileName || undefined,
+    filePath: clip.filePath || undefined,
+  }
+}
+
+// The sort orders of the history by the `sort` query parameter.
+const kSortTypes: Record<string, SortHistoryType> = {
+  recency: SortHistoryType.TimeOfLastCopy,
+  frequency: SortHistoryType.NumberOfCopies,
+  frecency: SortHistoryType.Frecency,
+}
+
+function getSort(params: URLSearchParams): SortHistoryType {
+  let sort = params.get("sort") || "recency"
+  if (!Object.prototype.hasOwnProperty.call(kSortTypes, sort)) {
+    throw new ApiError(400, `Unknown sort order: ${sort}`)
+  }
+  return kSortTypes[sort]
+}
+
+function parseBody(body: string): any {
+  if (!body) {
+    return {}
+  }
+  try {
+    return JSON.parse(body)
+  } catch (e) {
+    throw new ApiError(400, "The request body is not valid JSON")
+  }
+}
+
+function getLimit(params: URLSearchParams): number {
+  let limit = parseInt(params.get("limit") || "")
+  return isNaN(limit) || limit <= 0 ? kDefaultLimit : limit
+}
+
+function getDateParam(params: URLSearchParams, name: string): Date | undefined {
+  let value = params.get(name)
+  if (!value) {
+    return undefined
+  }
+  let date = new Date(value)
+  if (isNaN(date.getTime())) {
+    throw new ApiError(400, `Invalid date: ${name}=${value}`)
+  }
+  return date
+}
+
+function getHistoryTimeline(params: URLSearchParams) {
+  let granularity = params.get("granularity") || TimelineGranularity.Day
+  if (!isTimelineGranularity(granularity)) {
+    throw new ApiError(400, `Unknown granularity: ${granularity}`)
+  }
+  let mapper = getItemMapper(params)
+  let buckets = getHistoryGrouped(granularity, getDateParam(params, "from"), getDateParam(params, "to"), getLimit(params))
+  return buckets.map(bucket => ({
+    start: bucket.start.toISOString(),
+    end: bucket.end.toISOString(),
+    count: bucket.count,
+    items: bucket.items.map(mapper),
+  }))
+}
+
+function getItem(id: any): Clip {
+  let item = typeof id === "number" ? getHistoryItemById(id) : undefined
+  if (!item) {
+    throw new ApiError(404, `History item not found: ${id}`)
+  }
+  return item
+}
+
+function copy(body: any) {
+  if (typeof copyToClipboard === 'undefined') {
+    throw new ApiError(503, "Copying to the clipboard is not supported", ErrorCode.Clipboard)
+  }
+  if (typeof body.text === "string") {
+    copyToClipboard(body.text, "", "", "", "", false)
+    return {copied: true}
+  }
+  let item = getItem(body.id)
+  copyToClipboard(getFullContent(item), getRTF(item), getHTML(item), getImageFileName(item), getFilePath(item), false)
+  return {copied: true, id: item.id}
+}
+
+function paste(body: any) {
+  let item = getItem(body.id)
+  let action = body.plain ? PickerAction.PastePlain : PickerAction.Paste
+  executePickerAction(item.id!, action).catch(error => {
+    console.error('❌ API paste failed:', error)
+  })
+  return {pasted: true, id: item.id}
+}
+
+function markSensitive(body: any) {
+  let item = getItem(body.id)
+  let sensitive = body.sensitive !== false
+  markItemSensitive(item.id!, sensitive).catch(error => {
+    console.error('❌ Failed to mark item as sensitive:', error)
+  })
+  return {id: item.id, sensitive}
+}
+
+function setNote(id: number, body: any) {
+  let item = getItem(id)
+  if (typeof body.note !== "string") {
+    throw new ApiError(400, "The note must be a string")
+  }
+  setItemNote(item.id!, body.note).catch(error => {
+    console.error('❌ Failed to set item note:', error)
+  })
+  return {id: item.id, note: body.note.trim() || undefined}
+}
+
+function setContent(id: number, body: any) {
+  let item = getItem(id)
+  if (typeof body.content !== "string" || body.content.trim().length === 0) {
+    throw new ApiError(400, "The content must be a non-empty string")
+  }
+  if (item.type === ClipType.Image || item.type === ClipType.File) {
+    throw new ApiError(422, "The content of image and file items cannot be edited")
+  }
+  updateItemContent(item.id!, body.content).catch(error => {
+    console.error('❌ Failed to update item content:', error)
+  })
+  return {id: item.id, content: body.content}
+}
+
+function exportItem(id: number, body: any) {
+  let item = getItem(id)
+  if (typeof body.dir !== "string" || !body.dir) {
+    throw new ApiError(400, "The dir must be a directory path")
+  }
+  try {
+    return {id: item.id, path: exportItemToFile(item.id!, body.dir)}
+  } catch (e) {
+    if (e instanceof AppLockedError) {
+      throw e
+    }
+    throw new ApiError(422, (e as Error).message)
+  }
+}
+
+function createSequence(body: any) {
+  if (!Array.isArray(body.ids) || !body.ids.every((id: any) => typeof id === "number")) {
+    throw new ApiError(400, "The ids must be an array of history item ids")
+  }
+  try {
+    return createPasteSequence(body.ids)
+  } catch (e) {
+    throw new ApiError(422, (e as Error).message)
+  }
+}
+
+function pasteNextInSequence() {
+  let sequence = getPasteSequence()
+  if (!sequence) {
+    throw new ApiError(404, "There is no paste sequence")
+  }
+  pasteSequenceNext().catch(error => {
+    console.error('❌ API paste sequence failed:', error)
+  })
+  return {pasted: true, id: sequence.ids[sequence.position]}
+}
+
+// Compacts the history in the background. The number of the merged items is
+// sent to the "/events" clients when the compaction completes.
+function compact() {
+  compactHistory().then(result => {
+    publishEvent("history-compacted", result)
+  }).catch(error => {
+    console.error('❌ API history compaction failed:', error)
+  })
+  return {started: true}
+}
+
+// Checks the database in the background. The report is sent to the
+// "/events" clients when the check completes.
+function checkIntegrity(body: any) {
+  checkDatabaseIntegrity({quick: body.quick === true, repair: body.repair === true}).then(report => {
+    publishEvent("integrity-checked", report)
+  }).catch(error => {
+    console.error('❌ API integrity check failed:', error)
+  })
+  return {started: true}
+}
+
+function restoreBackup(body: any) {
+  if (typeof body.path !== "string" || !body.path) {
+    throw new ApiError(400, "The path of the backup file is required")
+  }
+  let mode = body.mode ?? RestoreMode.Replace
+  if (!Object.values(RestoreMode).includes(mode)) {
+    throw new ApiError(400, `Unknown restore mode: ${mode}`)
+  }
+  if (getRestoreJob()?.status === JobStatus.Running) {
+    throw new ApiError(409, "Restore is already in progress")
+  }
+  restoreFromBackup(body.path, mode).then(job => {
+    publishEvent("restore-finished", job)
+  }).catch(error => {
+    console.error('❌ API restore from backup failed:', error)
+  })
+  return {started: true}
+}
+
+function requirePath(body: any): string {
+  if (typeof body.path !== "string" || !body.path) {
+    throw new ApiError(400, "The path of the settings file is required")
+  }
+  return body.path
+}
+
+function startSettingsExport(body: any) {
+  let path = requirePath(body)
+  exportSettings(path).then(() => {
+    publishEvent("settings-exported", {path})
+  }).catch(error => {
+    console.error('❌ API export settings failed:', error)
+  })
+  return {started: true, path}
+}
+
+function startSettingsImport(body: any) {
+  let path = requirePath(body)
+  importSettings(path).then(report => {
+    publishEvent("settings-imported", {path, ...report})
+  }).catch(error => {
+    console.error('❌ API import settings failed:', error)
+  })
+  return {started: true, path}
+}
+
+function verify(body: any) {
+  if (typeof body.path !== "string" || !body.path) {
+    throw new ApiError(400, "The path of the backup file is required")
+  }
+  return verifyBackup(body.path)
+}
+
+function archive(body: any) {
+  if (typeof body.days !== "number" || body.days < 0) {
+    throw new ApiError(400, "The days must be a non-negative number")
+  }
+  archiveItemsOlderThan(body.days).then(count => {
+    publishEvent("items-archived", {count})
+  }).catch(error => {
+    console.error('❌ API archive failed:', error)
+  })
+  return {started: true}
+}
+
+function unarchive(id: number) {
+  if (!searchArchive("").some(item => item.id === id)) {
+    throw new ApiError(404, `Archived item not found: ${id}`)
+  }
+  unarchiveItem(id).catch(error => {
+    console.error('❌ API unarchive failed:', error)
+  })
+  return {unarchived: true, id}
+}
+
+function saveRegister(name: string, body: any) {
+  if (!isValidRegisterName(name)) {
+    throw new ApiError(400, `Invalid register name: ${name}`)
+  }
+  let item = getItem(body.id)
+  copyToRegister(name, item.id!).then(() => {
+    if (typeof body.shortcut === "string") {
+      return setRegisterShortcut(name, body.shortcut)
+    }
+  }).catch(error => {
+    console.error('❌ API copy to register failed:', error)
+  })
+  return {saved: true, name, id: item.id}
+}
+
+function pasteFromRegister(name: string) {
+  if (!getRegisterItem(name)) {
+    throw new ApiError(404, `Register not found: ${name}`)
+  }
+  pasteRegister(name).catch(error => {
+    console.error('❌ API paste register failed:', error)
+  })
+  return {pasted: true, name}
+}
+
+function listShortcuts() {
+  return getShortcuts().map(shortcut => ({
+    ...shortcut,
+    lastUsed: shortcut.lastUsed?.toISOString(),
+  }))
+}
+
+function updateShortcut(id: string, body: any) {
+  let binding = findShortcutBinding(id)
+  if (!binding) {
+    throw new ApiError(404, `Shortcut not found: ${id}`)
+  }
+  if (!binding.set) {
+    throw new ApiError(400, `The shortcut can't be changed: ${id}`)
+  }
+  if (typeof body.shortcut !== "string") {
+    throw new ApiError(400, "The shortcut must be a string")
+  }
+  checkShortcut(body.shortcut, id)
+  setShortcut(id, body.shortcut).catch(error => {
+    console.error('❌ API update shortcut failed:', error)
+  })
+  return {id, shortcut: body.shortcut}
+}
+
+function updateDoubleTapShortcut(body: any) {
+  let {modifier, action} = getDoubleTapShortcut()
+  let shortcut = {modifier, action}
+  if (body.modifier !== undefined) {
+    if (!Object.values(DoubleTapModifier).includes(body.modifier)) {
+      throw new ApiError(400, `Invalid double-tap modifier: ${body.modifier}`)
+    }
+    shortcut.modifier = body.modifier
+  }
+  if (body.action !== undefined) {
+    if (!Object.values(DoubleTapAction).includes(body.action)) {
+      throw new ApiError(400, `Invalid double-tap action: ${body.action}`)
+    }
+    shortcut.action = body.action
+  }
+  setDoubleTapShortcut(shortcut)
+  return getDoubleTapShortcut()
+}
+
+function getTag(id: number) {
+  let tag = findTagById(id)
+  if (!tag) {
+    throw new ApiError(404, `Tag not found: ${id}`)
+  }
+  return tag
+}
+
+function renameTag(id: number, body: any) {
+  let tag = getTag(id)
+  if (typeof body.name !== "string" || body.name.trim().length === 0) {
+    throw new ApiError(400, "The tag name is required")
+  }
+  renameTagGlobally(tag.id, body.name).catch(error => {
+    console.error('❌ API rename tag failed:', error)
+  })
+  return {renamed: true, id: tag.id}
+}
+
+function mergeTag(id: number, body: any) {
+  let tag = getTag(id)
+  let target = getTag(body.into)
+  mergeTags(tag.id, target.id).catch(error => {
+    console.error('❌ API merge tags failed:', error)
+  })
+  return {merged: true, id: tag.id, into: target.id}
+}
+
+function deleteTag(id: number) {
+  let tag = getTag(id)
+  deleteTagGlobally(tag.id).catch(error => {
+    console.error('❌ API delete tag failed:', error)
+  })
+  return {deleted: true, id: tag.id}
+}
+
+function addProfile(body: any) {
+  if (typeof body.name !== "string" || body.name.trim().length === 0) {
+    throw new ApiError(400, "The profile name is required")
+  }
+  try {
+    return createProfile(body.name)
+  } catch (e) {
+    throw new ApiError(409, (e as Error).message)
+  }
+}
+
+function selectProfile(id: string) {
+  if (!findProfileById(id)) {
+    throw new ApiError(404, `Profile not found: ${id}`)
+  }
+  switchProfile(id).catch(error => {
+    console.error('❌ API switch profile failed:', error)
+  })
+  return {switched: true, id}
+}
+
+function restore(id: number) {
+  if (!getTrashItems().some(item => item.id === id)) {
+    throw new ApiError(404, `Deleted item not found: ${id}`)
+  }
+  restoreItem(id).catch(error => {
+    console.error('❌ API restore failed:', error)
+  })
+  return {restored: true, id}
+}
+
+// Deletes the item permanently overwriting its content and files.
+function secureDeleteItem(id: number) {
+  let item = getHistoryItemById(id) ?? getTrashItems().find(item => item.id === id)
+  if (!item) {
+    throw new ApiError(404, `History item not found: ${id}`)
+  }
+  secureDeleteHistoryItem(item).catch(error => {
+    console.error('❌ API secure delete failed:', error)
+  })
+  return {deleted: true, id}
+}
+
+function pause(body: any) {
+  let durationSecs = body.durationSecs
+  if (durationSecs !== undefined && (typeof durationSecs !== "number" || durationSecs <= 0)) {
+    throw new ApiError(400, "The durationSecs must be a positive number")
+  }
+  pauseCapture(durationSecs)
+  return getCaptureState()
+}
+
+function updateCaptureFilters(body: any) {
+  for (const key of ["allowedTypes", "ignoredTypes"]) {
+    let types = body[key]
+    if (types !== undefined && (!Array.isArray(types) || !types.every((type: any) => typeof type === "string"))) {
+      throw new ApiError(400, `The ${key} must be an array of strings`)
+    }
+  }
+  setCaptureFilters(body)
+  return getCaptureFilters()
+}
+
+function writeFindPasteboard(body: any) {
+  if (typeof body.text !== "string" || !body.text) {
+    throw new ApiError(400, "The text must be a non-empty string")
+  }
+  try {
+    setFindPasteboardText(body.text)
+  } catch (e) {
+    throw new ApiError(503, (e as Error).message, ErrorCode.Clipboard)
+  }
+  return {text: body.text}
+}
+
+function updateLogLevel(body: any) {
+  if (!Object.values(LogLevel).includes(body.level)) {
+    throw new ApiError(400, `Unknown log level: ${body.level}`)
+  }
+  setLogLevel(body.level)
+  return {level: getLogLevel()}
+}
+
+function route(method: string, path: string, params: URLSearchParams, body: string): any {
+  if (method === "GET" && path === "/history") {
+    let sort = getSort(params)
+    let limit = getLimit(params)
+    let view = params.get("view") || "summary"
+    let mapper = getItemMapper(params)
+    return getHistoryPage(`${sort}:${limit}:${view}`, () => getHistorySorted(sort).slice(0, limit).map(mapper))
+  }
+  if (method === "GET" && path === "/history/grouped") {
+    return getHistoryTimeline(params)
+  }
+  if (method === "GET" && path === "/picker") {
+    return getQuickPickerItems(params.get("q") || "", getLimit(params))
+  }
+  if (method === "GET" && path === "/apps") {
+    return getSourceAppUsage()
+  }
+  if (method === "GET" && path === "/sessions") {
+    return getSessions().slice(0, getLimit(params)).map(session => ({
+      ...session,
+      startTime: session.startTime.toISOString(),
+      endTime: session.endTime.toISOString(),
+    }))
+  }
+  if (method === "GET" && path === "/history/remote") {
+    return getRemoteItems().slice(0, getLimit(params)).map(getItemMapper(params))
+  }
+  if (method === "GET" && path === "/search") {
+    return searchHistory(params.get("q") || "").slice(0, getLimit(params)).map(getItemMapper(params))
+  }
+  if (method === "GET" && path === "/search/regex") {
+    let pattern = params.get("pattern") || ""
+    let flags = params.get("flags") ?? "i"
+    return searchHistoryRegex(pattern, flags, getLimit(params)).map(getItemMapper(params))
+  }
+  if (method === "GET" && path === "/archive") {
+    return searchArchive(params.get("q") || "").slice(0, getLimit(params)).map(getItemMapper(params))
+  }
+  if (method === "POST" && path === "/archive") {
+    return archive(parseBody(body))
+  }
+  let match = path.match(/^\/history\/(\d+)$/)
+  if (method === "GET" && match) {
+    return toApiItem(getItem(parseInt(match[1])))
+  }
+  match = path.match(/^\/history\/(\d+)\/content$/)
+  if (method === "GET" && match) {
+    return getItemContent(getItem(parseInt(match[1])))
+  }
+  if (method === "PUT" && match) {
+    return setContent(parseInt(match[1]), parseBody(body))
+  }
+  match = path.match(/^\/sessions\/(\d+)$/)
+  if (method === "GET" && match) {
+    let items = getSessionItems(parseInt(match[1]))
+    if (items.length === 0) {
+      throw new ApiError(404, `Session not found: ${match[1]}`)
+    }
+    return items.map(getItemMapper(params))
+  }
+  match = path.match(/^\/history\/(\d+)\/color$/)
+  if (method === "GET" && match) {
+    let item = getItem(parseInt(match[1]))
+    let format = params.get("format") || "hex"
+    if (!kColorFormats.includes(format as ColorFormat)) {
+      throw new ApiError(400, `Unknown color format: ${format}`)
+    }
+    try {
+      return {id: item.id, color: convertColor(item.id!, format as ColorFormat)}
+    } catch (e) {
+      throw new ApiError(422, (e as Error).message)
+    }
+  }
+  match = path.match(/^\/history\/(\d+)\/qr$/)
+  if (method === "POST" && match) {
+    let item = getItem(parseInt(match[1]))
+    try {
+      return {id: item.id, imageFileName: generateQR(item.id!)}
+    } catch (e) {
+      if (e instanceof AppLockedError) {
+        throw e
+      }
+      throw new ApiError(422, (e as Error).message)
+    }
+  }
+  match = path.match(/^\/history\/(\d+)\/share$/)
+  if (method === "POST" && match) {
+    let item = getItem(parseInt(match[1]))
+    try {
+      share(item.id!)
+      return {id: item.id, shared: true}
+    } catch (e) {
+      if (e instanceof AppLockedError) {
+        throw e
+      }
+      throw new ApiError(422, (e as Error).message)
+    }
+  }
+  match = path.match(/^\/history\/(\d+)\/export$/)
+  if (method === "POST" && match) {
+    return exportItem(parseInt(match[1]), parseBody(body))
+  }
+  match = path.match(/^\/history\/(\d+)\/note$/)
+  if (method === "PUT" && match) {
+    return setNote(parseInt(match[1]), parseBody(body))
+  }
+  match = path.match(/^\/history\/(\d+)\/reveal$/)
+  if (method === "GET" && match) {
+    let item = getItem(parseInt(match[1]))
+    return {id: item.id, content: revealItem(item.id!)}
+  }
+  if (method === "POST" && path === "/sensitive") {
+    return markSensitive(parseBody(body))
+  }
+  if (method === "POST" && path === "/find-pasteboard") {
+    return writeFindPasteboard(parseBody(body))
+  }
+  if (method === "POST" && path === "/copy") {
+    return copy(parseBody(body))
+  }
+  if (method === "POST" && path === "/paste") {
+    return paste(parseBody(body))
+  }
+  if (method === "POST" && path === "/history/undo-clear") {
+    if (!canUndoClearHistory()) {
+      throw new ApiError(409, "There is no cleared history to restore")
+    }
+    undoClearHistory().catch(error => {
+      console.error('❌ API undo clear history failed:', error)
+    })
+    return {restoring: true}
+  }
+  if (method === "GET" && path === "/metrics") {
+    return getPerformanceMetrics()
+  }
+  if (method === "GET" && path === "/metrics/memory") {
+    let {current, samples} = getMemoryMetrics()
+    return {current, samples: samples.map(sample => ({...sample, time: sample.time.toISOString()}))}
+  }
+  if (method === "GET" && path === "/metrics/cache") {
+    return getCacheMetrics()
+  }
+  if (method === "GET" && path === "/metrics/export") {
+    let format = params.get("format") || "json"
+    if (format !== "json" && format !== "prometheus") {
+      throw new ApiError(400, `Unknown metrics format: ${format}`)
+    }
+    return {format, content: exportMetrics(format as MetricsFormat)}
+  }
+  if (method === "GET" && path === "/trash") {
+    return getTrashItems().slice(0, getLimit(params)).map(getItemMapper(params))
+  }
+  if (method === "DELETE" && path === "/trash") {
+    emptyTrash().catch(error => {
+      console.error('❌ API empty trash failed:', error)
+    })
+    return {emptied: true}
+  }
+  match = path.match(/^\/history\/(\d+)\/secure-delete$/)
+  if (method === "POST" && match) {
+    return secureDeleteItem(parseInt(match[1]))
+  }
+  match = path.match(/^\/trash\/(\d+)\/restore$/)
+  if (method === "POST" && match) {
+    return restore(parseInt(match[1]))
+  }
+  if (method === "GET" && path === "/profiles") {
+    return {current: getCurrentProfileId(), profiles: listProfiles()}
+  }
+  if (method === "POST" && path === "/profiles") {
+    return addProfile(parseBody(body))
+  }
+  match = path.match(/^\/profiles\/([^/]+)\/switch$/)
+  if (method === "POST" && match) {
+    return selectProfile(decodeURIComponent(match[1]))
+  }
+  if (method === "GET" && path === "/tags") {
+    return getAllTags()
+  }
+  if (method === "GET" && path === "/tags/suggest") {
+    let itemId = params.has("itemId") ? parseInt(params.get("itemId")!) : undefined
+    return suggestTags(params.get("prefix") || "", itemId, getLimit(params))
+  }
+  match = path.match(/^\/tags\/(\d+)$/)
+  if (method === "PATCH" && match) {
+    return renameTag(parseInt(match[1]), parseBody(body))
+  }
+  if (method === "DELETE" && match) {
+    return deleteTag(parseInt(match[1]))
+  }
+  match = path.match(/^\/tags\/(\d+)\/merge$/)
+  if (method === "POST" && match) {
+    return mergeTag(parseInt(match[1]), parseBody(body))
+  }
+  if (method === "GET" && path === "/registers") {
+    return listRegisters()
+  }
+  match = path.match(/^\/registers\/([^/]+)$/)
+  if (method === "PUT" && match) {
+    return saveRegister(match[1], parseBody(body))
+  }
+  if (method === "DELETE" && match) {
+    deleteRegister(match[1]).catch(error => {
+      console.error('❌ API delete register failed:', error)
+    })
+    return {deleted: true, name: match[1]}
+  }
+  match = path.match(/^\/registers\/([^/]+)\/paste$/)
+  if (method === "POST" && match) {
+    return pasteFromRegister(match[1])
+  }
+  if (method === "GET" && path === "/shortcuts") {
+    return listShortcuts()
+  }
+  if (method === "GET" && path === "/shortcuts/double-tap") {
+    return getDoubleTapShortcut()
+  }
+  if (method === "PUT" && path === "/shortcuts/double-tap") {
+    return updateDoubleTapShortcut(parseBody(body))
+  }
+  match = path.match(/^\/shortcuts\/([^/]+)$/)
+  if (method === "PUT" && match) {
+    return updateShortcut(match[1], parseBody(body))
+  }
+  match = path.match(/^\/archive\/(\d+)\/unarchive$/)
+  if (method === "POST" && match) {
+    return unarchive(parseInt(match[1]))
+  }
+  if (method === "POST" && path === "/maintenance/compact") {
+    return compact()
+  }
+  if (method === "GET" && path === "/maintenance/integrity") {
+    return getLastIntegrityReport() ?? null
+  }
+  if (method === "POST" && path === "/maintenance/integrity") {
+    return checkIntegrity(parseBody(body))
+  }
+  if (method === "GET" && path === "/backups/restore") {
+    return getRestoreJob() ?? null
+  }
+  if (method === "POST" && path === "/backups/restore") {
+    return restoreBackup(parseBody(body))
+  }
+  if (method === "POST" && path === "/backups/verify") {
+    return verify(parseBody(body))
+  }
+  if (method === "POST" && path === "/backups/restore/cancel") {
+    return {cancelled: cancelRestore()}
+  }
+  if (method === "POST" && path === "/settings/export") {
+    return startSettingsExport(parseBody(body))
+  }
+  if (method === "POST" && path === "/settings/import") {
+    return startSettingsImport(parseBody(body))
+  }
+  if (method === "GET" && path === "/sequence") {
+    return getPasteSequence() ?? null
+  }
+  if (method === "POST" && path === "/sequence") {
+    return createSequence(parseBody(body))
+  }
+  if (method === "POST" && path === "/sequence/next") {
+    return pasteNextInSequence()
+  }
+  if (method === "POST" && path === "/capture/pause") {
+    return pause(parseBody(body))
+  }
+  if (method === "POST" && path === "/capture/resume") {
+    resumeCapture()
+    return getCaptureState()
+  }
+  if (method === "GET" && path === "/capture/filters") {
+    return getCaptureFilters()
+  }
+  if (method === "PUT" && path === "/capture/filters") {
+    return updateCaptureFilters(parseBody(body))
+  }
+  if (method === "GET" && path === "/capture/types") {
+    return listRecentPasteboardTypes()
+  }
+  if (method === "GET" && path === "/guest") {
+    return {enabled: isGuestMode()}
+  }
+  // The guest mode can be exited only in the app, because it requires
+  // the user to authenticate.
+  if (method === "POST" && path === "/guest") {
+    enterGuestMode()
+    return {enabled: true}
+  }
+  if (method === "GET" && path === "/statistics/today") {
+    let summary = getTodaySummary()
+    return {...summary, recentItems: summary.recentItems.map(toApiItemSummary)}
+  }
+  if (method === "GET" && path === "/statistics") {
+    let period = params.get("period") || "week"
+    if (!kStatisticsPeriods.includes(period as StatisticsPeriod)) {
+      throw new ApiError(400, `Unknown statistics period: ${period}`)
+    }
+    return getUsageStatistics(period as StatisticsPeriod)
+  }
+  if (method === "GET" && path === "/permissions") {
+    return getPermissionStatuses()
+  }
+  match = path.match(/^\/permissions\/(\w+)\/request$/)
+  if (method === "POST" && match) {
+    if (!isPermissionKind(match[1])) {
+      throw new ApiError(404, `Unknown permission: ${match[1]}`)
+    }
+    requestSystemPermission(match[1])
+    return {requested: true, kind: match[1]}
+  }
+  if (method === "GET" && path === "/system/state") {
+    return getSystemState()
+  }
+  if (method === "GET" && path === "/logs") {
+    return getRecentLogs(getLimit(params))
+  }
+  if (method === "GET" && path === "/logs/level") {
+    return {level: getLogLevel()}
+  }
+  if (method === "PUT" && path === "/logs/level") {
+    return updateLogLevel(parseBody(body))
+  }
+  if (method === "GET" && path === "/health") {
+    return getAppHealth()
+  }
+  if (method === "GET" && path === "/system/info") {
+    let info = getSystemInfoCached()
+    if (!info) {
+      throw new ApiError(503, "The system info is not available")
+    }
+    return info
+  }
+  throw new ApiError(404, `Unknown endpoint: ${method} ${path}`)
+}
+
+// Handles the request received by the local API server. Returns the status
+// code and the JSON response body separated by a new line.
+export function handleApiRequest(method: string, path: string, query: string, body: string): string {
+  try {
+    requireUnlocked()
+    if (method !== "GET" && isGuestMode() && !kGuestModeRequests.some(r => r.test(path))) {
+      throw new GuestModeError()
+    }
+    let result = route(method, path, new URLSearchParams(query), body)
+    return "200\n" + JSON.stringify(result)
+  } catch (error) {
+    let {code, message, retryable} = serializeError(error)
+    let status = error instanceof ApiError ? error.status : kStatusesByErrorCode[code] ?? 500
+    return status + "\n" + JSON.stringify({error: message, code, retryable})
+  }
+}
+
+function publishEvent(event: string, data: any) {
+  if (isApiRunning() && typeof broadcastApiEvent !== 'undefined') {
+    broadcastApiEvent(event, JSON.stringify(data))
+  }
+}
+
+// Returns only the id of the item while the app is locked, so that the
+// "/events" clients can't read the history content until it's unlocked.
+function toApiEventItem(item: Clip) {
+  if (isAppLocked()) {
+    return {id: item.id}
+  }
+  return toApiItem(item)
+}
+
+let eventsSubscribed = false
+
+// Streams the history changes to the clients connected to the "/events"
+// endpoint of the API server.
+function subscribeToEvents() {
+  if (eventsSubscribed) {
+    return
+  }
+  eventsSubscribed = true
+  emitter.on("HistoryItemAdded", item => publishEvent("item-added", toApiEventItem(item)))
+  emitter.on("HistoryItemUpdated", item => publishEvent("item-updated", toApiEventItem(item)))
+  emitter.on("HistoryItemDeleted", item => publishEvent("item-deleted", {id: item.id}))
+  emitter.on("HistoryCleared", () => publishEvent("history-cleared", {}))
+  emitter.on("PermissionChanged", change => publishEvent("permission-changed", change))
+  emitter.on("PreferencesChanged", keys => publishEvent("preferences-changed", {keys}))
+  emitter.on("RestoreProgress", progress => publishEvent("restore-progress", progress))
+}
+
+function generateToken(): string {
+  let bytes = new Uint8Array(24)
+  crypto.getRandomValues(bytes)
+  return Array.from(bytes, byte => byte.toString(16).padStart(2, "0")).join("")
+}
+
+// Replaces the API token. The running server restarts and rejects the
+// requests with the old token.
+export function regenerateApiToken(): string {
+  let token = generateToken()
+  if (!prefSetApiServerToken(token)) {
+    console.error('❌ Failed to save the API token')
+    return prefGetApiServerToken()
+  }
+  return token
+}
+
+// Returns the token the API clients must pass in the Authorization header.
+// The token is generated on first use.
+export function getApiToken(): string {
+  let token = prefGetApiServerToken()
+  if (!token) {
+    token = generateToken()
+    prefSetApiServerToken(token)
+  }
+  return token
+}
+
+export function startApi(): boolean {
+  if (typeof startApiServer === 'undefined') {
+    return false
+  }
+  let started = startApiServer(prefGetApiServerPort(), getApiToken())
+  if (started) {
+    subscribeToEvents()
+    console.log(`✅ API server started on port ${prefGetApiServerPort()}`)
+  } else {
+    console.error(`❌ Failed to start API server on port ${prefGetApiServerPort()}`)
+  }
+  return started
+}
+
+export function stopApi() {
+  if (typeof stopApiServer === 'undefined') {
+    return
+  }
+  stopApiServer()
+}
+
+export function isApiRunning(): boolean {
+  return typeof isApiServerRunning !== 'undefined' && isApiServerRunning()
+}
+
+// Returns false if the API server is enabled but not running.
+export function isApiAlive(): boolean {
+  return typeof startApiServer === 'undefined' || !prefShouldApiServerEnabled() || isApiRunning()
+}
+
+// Starts the API server if it's enabled in the preferences.
+export function startApiIfEnabled() {
+  if (prefShouldApiServerEnabled() && !isApiRunning()) {
+    startApi()
+  }
+}
@@ -12,6 +12,7 @@ import {AppSidebarItem, AppSidebarItemType} from "@/app/AppSidebarItem";
 import {
   AppInfo,
   filterByApp,
+  filterByDetectedKind,
   filterByFavorites,
   filterByTag,
   filterByType,
@@ -19,10 +20,12 @@ import {
   resetFilter
 } from "@/data";
 import {Clip, ClipType} from "@/db";
+import {DetectedKind} from "@/lib/classifier";
 import {AppSidebarTagItem} from "@/app/AppSidebarTagItem";
 import TagDialog from "@/app/TagDialog";
 import {allTags, removeTag, Tag} from "@/tags";
 import {emitter} from "@/actions";
+import {allTagRules, removeTagRule} from "@/rules";
 import {AppSidebarSourceItems} from "@/app/AppSidebarSourceItems";
 
 interface AppSidebarProps {
@@ -87,6 +90,10 @@ export default function AppSidebar(props: AppSidebarProps) {
       handleShowAll()
     }
     removeTag(tag)
+    // The auto-tagging rules of the removed tag aren't needed anymore.
+    for (const rule of allTagRules().filter(rule => rule.tagId === tag.id)) {
+      removeTagRule(rule.id!).catch(error => console.error('❌ Failed to remove tag rule:', error))
+    }
     emitter.emit("DeleteTagById", tag.id)
     emitter.emit("UpdateTags")
   }
@@ -109,6 +116,12 @@ export default function AppSidebar(props: AppSidebarProps) {
     emitter.emit("FilterHistory")
   }
 
+  function handleShowByDetectedKind(kind: DetectedKind, type: AppSidebarItemType) {
+    handleSelectType(type)
+    filterByDetectedKind(kind)
+    emitter.emit("FilterHistory")
+  }
+
   function handleNewTag() {
     setTagToEdit(undefined)
     setItemForTag(undefined)
@@ -161,6 +174,12 @@ export default function AppSidebar(props: AppSidebarProps) {
                 <AppSidebarItem type={"Email"}
                                 selectedType={props.selectedItemType}
                                 onSelect={() => handleShowByType(ClipType.Email, "Email")}/>
+                <AppSidebarItem type={"Code"}
+                                selectedType={props.selectedItemType}
+                                onSelect={() => handleShowByDetectedKind(DetectedKind.Code, "Code")}/>
+                <AppSidebarItem type={"JSON"}
+                                selectedType={props.selectedItemType}
+                                onSelect={() => handleShowByDetectedKind(DetectedKind.JSON, "JSON")}/>
 
               </SidebarMenu>
             </SidebarGroupContent>
@@ -1,6 +1,8 @@
 import {
   ChevronDownIcon,
   ChevronUpIcon,
+  CodeIcon,
+  BracesIcon,
   FileIcon, FilesIcon,
   HistoryIcon,
   ImageIcon,
@@ -13,7 +15,7 @@ import {
 import {SidebarMenuButton, SidebarMenuItem} from "@/components/ui/sidebar";
 import { useTranslation } from 'react-i18next';
 
-export type AppSidebarItemType = "None" | "All" | "Text" | "Image" | "Link" | "Color" | "File" | "Email" | "Favorites" | "NewTag" | "ShowApps" | "HideApps";
+export type AppSidebarItemType = "None" | "All" | "Text" | "Image" | "Link" | "Color" | "File" | "Email" | "Code" | "JSON" | "Favorites" | "NewTag" | "ShowApps" | "HideApps";
 
 interface AppSidebarItemProps {
   type: AppSidebarItemType
@@ -47,6 +49,12 @@ export function AppSidebarItem(props: AppSidebarItemProps) {
     if (props.type === "Email") {
       return <MailIcon className="h-5 w-5"/>
     }
+    if (props.type === "Code") {
+      return <CodeIcon className="h-5 w-5"/>
+    }
+    if (props.type === "JSON") {
+      return <BracesIcon className="h-5 w-5"/>
+    }
     if (props.type === "Favorites") {
       return <StarIcon className="h-5 w-5"/>
     }
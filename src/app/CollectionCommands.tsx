@@ -0,0 +1,109 @@
+import '../app.css';
+
+import * as React from "react"
+import {useEffect, useState} from "react"
+import {VisuallyHidden} from "@radix-ui/react-visually-hidden";
+
+import {
+  CommandDialog,
+  CommandEmpty,
+  CommandGroup,
+  CommandInput,
+  CommandItem,
+  CommandList,
+} from "@/components/ui/command"
+import {DialogTitle} from "@/components/ui/dialog";
+import {SmartCollection} from "@/db";
+import {emitter} from "@/actions";
+import {useTranslation} from "react-i18next";
+import {LibraryIcon, TrashIcon} from "lucide-react";
+import {CollectionAction, listSmartCollections, removeSmartCollection} from "@/collections";
+
+export default function CollectionCommands() {
+  const {t} = useTranslation()
+
+  const [open, setOpen] = useState(false)
+  const [action, setAction] = useState<CollectionAction>(CollectionAction.Show)
+  const [collections, setCollections] = useState<SmartCollection[]>([])
+
+  function handleKeyDown(e: React.KeyboardEvent) {
+    if (e.key === "Escape") {
+      handleOpenChange(false)
+      e.preventDefault()
+    }
+    e.stopPropagation()
+  }
+
+  useEffect(() => {
+    function handleAppWindowDidHide() {
+      handleOpenChange(false)
+    }
+
+    function handleShowCollectionCommands(action: CollectionAction) {
+      setTimeout(() => {
+        handleOpenChange(true, action)
+      }, 200);
+    }
+
+    emitter.on("NotifyAppWindowDidHide", handleAppWindowDidHide)
+    emitter.on("ShowCollectionCommands", handleShowCollectionCommands)
+    return () => {
+      emitter.off("NotifyAppWindowDidHide", handleAppWindowDidHide)
+      emitter.off("ShowCollectionCommands", handleShowCollectionCommands)
+    };
+  }, [])
+
+  function handleOpenChange(open: boolean, action: CollectionAction = CollectionAction.Show) {
+    if (open) {
+      setAction(action)
+      listSmartCollections().then(setCollections).catch(error => {
+        console.error('❌ Failed to load collections:', error)
+      })
+    } else {
+      emitter.emit("FocusSearchInput")
+    }
+    setOpen(open)
+  }
+
+  async function handleSelectCollection(collection: SmartCollection) {
+    handleOpenChange(false)
+    if (action === CollectionAction.Show) {
+      emitter.emit("ShowCollection", collection.id!)
+    }
+    if (action === CollectionAction.Remove) {
+      removeSmartCollection(collection.id!).catch(error => {
+        console.error('❌ Failed to remove collection:', error)
+      })
+    }
+  }
+
+  return (
+      <div className="" onKeyDown={handleKeyDown}>
+        <CommandDialog open={open} onOpenChange={handleOpenChange}>
+          <VisuallyHidden>
+            <DialogTitle></DialogTitle>
+          </VisuallyHidden>
+          <CommandInput placeholder={t("app.collectionCommands.searchPlaceholder")} autoFocus={true}/>
+          <div className="max-h-[70vh] overflow-y-auto mb-1.5">
+            <CommandList>
+              <CommandGroup heading={t("app.collectionCommands." + action)}>
+                {
+                  collections.map(collection => (
+                      <CommandItem key={collection.id} onSelect={() => handleSelectCollection(collection)}>
+                        {
+                          action === CollectionAction.Remove ?
+                              <TrashIcon className="mr-2 h-5 w-5 text-actions-danger"/> :
+                              <LibraryIcon className="mr-2 h-5 w-5"/>
+                        }
+                        <span>{collection.name}<span className="hidden">{collection.query}</span></span>
+                      </CommandItem>
+                  ))
+                }
+              </CommandGroup>
+              <CommandEmpty>{t("app.collectionCommands.noCollections")}</CommandEmpty>
+            </CommandList>
+          </div>
+        </CommandDialog>
+      </div>
+  )
+}
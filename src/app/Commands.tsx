@@ -7,13 +7,13 @@ import {
   ArrowUpLeftIcon,
   CommandIcon,
   CopyIcon, DownloadIcon,
-  Edit3Icon, EyeIcon,
-  GlobeIcon, PenIcon,
+  Edit3Icon, EyeIcon, FileTextIcon, FilePlusIcon, LibraryIcon, PinIcon, PinOffIcon,
+  GlobeIcon, LaptopIcon, ListOrderedIcon, PenIcon, RefreshCwIcon,
   ScanTextIcon,
   SettingsIcon,
   StarIcon,
   StarOffIcon,
-  TrashIcon, TypeIcon, Undo2Icon, UnfoldVerticalIcon, UploadIcon, ZoomIn, ZoomOut
+  ShieldXIcon, TrashIcon, TypeIcon, Undo2Icon, UnfoldVerticalIcon, UploadIcon, UserIcon, ZoomIn, ZoomOut
 } from "lucide-react"
 
 import { VisuallyHidden } from "@radix-ui/react-visually-hidden";
@@ -37,6 +37,7 @@ import {
   prefGetOpenInBrowserShortcut,
   prefGetOpenInDefaultAppShortcut,
   prefGetOpenSettingsShortcut,
+  prefGetPasteNextItemShortcut,
   prefGetPasteSelectedItemToActiveAppShortcut,
   prefGetPasteSelectedObjectToActiveAppShortcut,
   prefGetQuickLookShortcut,
@@ -52,7 +53,10 @@ import {
 } from "@/pref";
 import ShortcutLabel from "@/app/ShortcutLabel";
 import {isShortcutMatch} from "@/lib/shortcuts";
+import {enterGuestMode, exitGuestMode, isGuestMode} from "@/guest";
+import {createPasteSequence} from "@/sequence";
 import {
+  canUndoClearHistory,
   getFirstSelectedHistoryItem,
   getPreviewVisibleState,
   toBase64Icon,
@@ -61,19 +65,33 @@ import {
   isTextItem,
   AppInfo,
   getDefaultApp,
-  getFileOrImagePath, fileExists, getFilterQuery, isFilterActive, FinderIcon
+  getFileOrImagePath, fileExists, getFilterQuery, isFilterActive, FinderIcon,
+  getFilterTagIds, getFilterTypes, isItemPinned,
+  reloadHistory
 } from "@/data";
-import {ClipType, getHTML, getImageText, getRTF} from "@/db";
+import {ClipFilter, ClipType, getHTML, getImageText, getRTF, mergeDatabase, restoreItemsFromBackup} from "@/db";
 import {HidePreviewPaneIcon, ShowPreviewPaneIcon} from "@/app/Icons";
 import {Tooltip, TooltipContent, TooltipTrigger} from "@/components/ui/tooltip";
 import {DialogTitle} from "@/components/ui/dialog";
 import {emitter} from "@/actions";
 import { useTranslation } from 'react-i18next';
+import {ExportFormat, exportFileName, exportHistory} from "@/export";
+import {choosePath, PathKind} from "@/dialogs";
+import {importHistory, ImportSource} from "@/import";
+import {createSnippet, SnippetAction} from "@/snippets";
+import {CollectionAction, createSmartCollection} from "@/collections";
+import {getItemTitle} from "@/picker";
+import {getFullContent} from "@/blobs";
+import {getSyncStatus} from "@/sync";
+import {isSharingStarted} from "@/lanshare";
 
 declare const canZoomIn: () => boolean;
 declare const canZoomOut: () => boolean;
 declare const canResetZoom: () => boolean;
 
+const kHourInMs = 60 * 60 * 1000
+const kDayInMs = 24 * kHourInMs
+
 type CommandsProps = {
   appName: string
   appIcon: string
@@ -193,6 +211,68 @@ export default function Commands(props: CommandsProps) {
     emitter.emit("Merge")
   }
 
+  function handleCreatePasteSequence() {
+    handleOpenChange(false)
+    try {
+      createPasteSequence(getSelectedHistoryItems().map(item => item.id!))
+    } catch (error) {
+      console.error('❌ Failed to create paste sequence:', error)
+    }
+  }
+
+  function handleSaveAsSnippet() {
+    handleOpenChange(false)
+    let item = getFirstSelectedHistoryItem()
+    if (item) {
+      createSnippet(getItemTitle(item), getFullContent(item)).catch(error => {
+        console.error('❌ Failed to save snippet:', error)
+      })
+    }
+  }
+
+  function handleShowSnippetCommands(action: SnippetAction) {
+    handleOpenChange(false)
+    emitter.emit("ShowSnippetCommands", action)
+  }
+
+  // Saves the current search query and filter as a smart collection named
+  // after the query.
+  function handleSaveSearchAsCollection() {
+    handleOpenChange(false)
+    let query = getFilterQuery()
+    createSmartCollection(query, query, {
+      types: getFilterTypes(),
+      tags: getFilterTagIds(),
+    }).catch(error => {
+      console.error('❌ Failed to save collection:', error)
+    })
+  }
+
+  function handleShowCollectionCommands(action: CollectionAction) {
+    handleOpenChange(false)
+    emitter.emit("ShowCollectionCommands", action)
+  }
+
+  function handleShowDeviceCommands() {
+    handleOpenChange(false)
+    emitter.emit("ShowDeviceCommands")
+  }
+
+  function handleShowTrashCommands() {
+    handleOpenChange(false)
+    emitter.emit("ShowTrashCommands")
+  }
+
+  function handlePinItems(duration: number) {
+    handleOpenChange(false)
+    emitter.emit("PinItems", duration)
+  }
+
+  function handleUnpinItems() {
+    handleOpenChange(false)
+    emitter.emit("UnpinItems")
+  }
+
   function handleToggleFavorite() {
     handleOpenChange(false)
     emitter.emit("ToggleFavorite")
@@ -208,6 +288,82 @@ export default function Commands(props: CommandsProps) {
     emitter.emit("OpenSettings")
   }
 
+  function handleSyncNow() {
+    handleOpenChange(false)
+    emitter.emit("SyncNow")
+  }
+
+  function handleToggleGuestMode() {
+    handleOpenChange(false)
+    if (!isGuestMode()) {
+      enterGuestMode()
+      return
+    }
+    exitGuestMode().catch(error => console.error('❌ Failed to exit guest mode:', error))
+  }
+
+  async function handleExportHistory(format: ExportFormat) {
+    handleOpenChange(false)
+    let path = await choosePath(PathKind.Save, exportFileName(format))
+    if (path) {
+      exportHistory(format, {}, path).catch(error => {
+        console.error('❌ Failed to export history:', error)
+      })
+    }
+  }
+
+  async function handleImportHistory(source: ImportSource) {
+    handleOpenChange(false)
+    let path = await choosePath(PathKind.File)
+    if (!path) {
+      return
+    }
+    try {
+      let report = await importHistory(source, path)
+      if (report.imported > 0) {
+        await reloadHistory()
+        emitter.emit("HistoryChanged")
+      }
+    } catch (error) {
+      console.error('❌ Failed to import history:', error)
+    }
+  }
+
+  // Adds the items matching the filter from the backup file that are missing
+  // in the history. The existing items are kept.
+  async function handleRestoreItemsFromBackup(filter: ClipFilter) {
+    handleOpenChange(false)
+    let path = await choosePath(PathKind.File)
+    if (!path) {
+      return
+    }
+    try {
+      if (await restoreItemsFromBackup(path, filter) > 0) {
+        await reloadHistory()
+        emitter.emit("HistoryChanged")
+      }
+    } catch (error) {
+      console.error('❌ Failed to restore items from backup:', error)
+    }
+  }
+
+  async function handleMergeDatabase() {
+    handleOpenChange(false)
+    let path = await choosePath(PathKind.File)
+    if (!path) {
+      return
+    }
+    try {
+      let report = await mergeDatabase(path)
+      if (report.added > 0 || report.merged > 0) {
+        await reloadHistory()
+        emitter.emit("HistoryChanged")
+      }
+    } catch (error) {
+      console.error('❌ Failed to merge database:', error)
+    }
+  }
+
   function handleZoomIn() {
     handleOpenChange(false)
     emitter.emit("ZoomIn")
@@ -233,11 +389,21 @@ export default function Commands(props: CommandsProps) {
     emitter.emit("DeleteItems")
   }
 
+  function handleSecureDeleteItems() {
+    handleOpenChange(false)
+    emitter.emit("SecureDeleteItems")
+  }
+
   function handleDeleteAllItems() {
     handleOpenChange(false)
     emitter.emit("DeleteAllItems")
   }
 
+  function handleUndoClearHistory() {
+    handleOpenChange(false)
+    emitter.emit("UndoClearHistory")
+  }
+
   function handleOpenInBrowser() {
     handleOpenChange(false)
     emitter.emit("OpenInBrowser")
@@ -388,6 +554,10 @@ export default function Commands(props: CommandsProps) {
     return false
   }
 
+  function canShowCreatePasteSequence() {
+    return getSelectedHistoryItemIndices().length > 1
+  }
+
   function canShowOpenInBrowser() {
     if (getSelectedHistoryItemIndices().length === 1) {
       return getFirstSelectedHistoryItem()?.type === ClipType.Link
@@ -469,6 +639,22 @@ export default function Commands(props: CommandsProps) {
     return false
   }
 
+  function canSaveAsSnippet() {
+    return !isGuestMode() && canShowEditContent()
+  }
+
+  function canSendToDevice() {
+    return isSharingStarted() && canShowEditContent()
+  }
+
+  function canPinItems() {
+    return !isGuestMode() && getSelectedHistoryItems().some(item => item && !isItemPinned(item))
+  }
+
+  function canUnpinItems() {
+    return !isGuestMode() && getSelectedHistoryItems().some(item => item && isItemPinned(item))
+  }
+
   function canAddToFavorites() {
     return getSelectedHistoryItems().some(item => {
       return item && !item.favorite
@@ -476,11 +662,11 @@ export default function Commands(props: CommandsProps) {
   }
 
   function canShowDeleteItem() {
-    return getSelectedHistoryItemIndices().length === 1
+    return !isGuestMode() && getSelectedHistoryItemIndices().length === 1
   }
 
   function canShowDeleteItems() {
-    return getSelectedHistoryItemIndices().length > 1
+    return !isGuestMode() && getSelectedHistoryItemIndices().length > 1
   }
 
   function getMultipleItemsIndicator(): string {
@@ -600,6 +786,16 @@ export default function Commands(props: CommandsProps) {
                       <span>{t('commands.mergeItems', {itemsLabel: getMultipleItemsIndicator()})}</span>
                     </CommandItem>
                 }
+                {
+                    canShowCreatePasteSequence() &&
+                    <CommandItem onSelect={handleCreatePasteSequence}>
+                      <ListOrderedIcon className="mr-2 h-5 w-5"/>
+                      <span>{t('commands.createPasteSequence', {itemsLabel: getMultipleItemsIndicator()})}</span>
+                      <CommandShortcut className="flex flex-row">
+                        <ShortcutLabel shortcut={prefGetPasteNextItemShortcut()}/>
+                      </CommandShortcut>
+                    </CommandItem>
+                }
                 {
                     canShowCopyToClipboard() &&
                     <CommandItem onSelect={handleCopyToClipboard}>
@@ -678,6 +874,79 @@ export default function Commands(props: CommandsProps) {
                       </CommandShortcut>
                     </CommandItem>
                 }
+                {
+                    canPinItems() &&
+                    <>
+                      <CommandItem onSelect={() => handlePinItems(0)}>
+                        <PinIcon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.pinToTop')}</span>
+                      </CommandItem>
+                      <CommandItem onSelect={() => handlePinItems(kHourInMs)}>
+                        <PinIcon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.pinToTopForHour')}</span>
+                      </CommandItem>
+                      <CommandItem onSelect={() => handlePinItems(kDayInMs)}>
+                        <PinIcon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.pinToTopForDay')}</span>
+                      </CommandItem>
+                    </>
+                }
+                {
+                    canUnpinItems() &&
+                    <CommandItem onSelect={handleUnpinItems}>
+                      <PinOffIcon className="mr-2 h-5 w-5"/>
+                      <span>{t('commands.unpin')}</span>
+                    </CommandItem>
+                }
+                {
+                    !isGuestMode() && getFilterQuery().length > 0 &&
+                    <CommandItem onSelect={handleSaveSearchAsCollection}>
+                      <LibraryIcon className="mr-2 h-5 w-5"/>
+                      <span>{t('commands.saveSearchAsCollection')}</span>
+                    </CommandItem>
+                }
+                <CommandItem onSelect={() => handleShowCollectionCommands(CollectionAction.Show)}>
+                  <LibraryIcon className="mr-2 h-5 w-5"/>
+                  <span>{t('commands.showCollection')}</span>
+                </CommandItem>
+                {
+                    !isGuestMode() &&
+                    <CommandItem onSelect={() => handleShowCollectionCommands(CollectionAction.Remove)}>
+                      <TrashIcon className="mr-2 h-5 w-5"/>
+                      <span>{t('commands.removeCollection')}</span>
+                    </CommandItem>
+                }
+                <CommandItem onSelect={() => handleShowSnippetCommands(SnippetAction.Paste)}>
+                  <FileTextIcon className="mr-2 h-5 w-5"/>
+                  <span>{t('commands.pasteSnippet')}</span>
+                </CommandItem>
+                {
+                    canSaveAsSnippet() &&
+                    <>
+                      <CommandItem onSelect={handleSaveAsSnippet}>
+                        <FilePlusIcon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.saveAsSnippet')}</span>
+                      </CommandItem>
+                      <CommandItem onSelect={() => handleShowSnippetCommands(SnippetAction.Update)}>
+                        <FileTextIcon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.updateSnippet')}</span>
+                      </CommandItem>
+                    </>
+                }
+                {
+                    !isGuestMode() &&
+                    <CommandItem onSelect={() => handleShowSnippetCommands(SnippetAction.Remove)}>
+                      <TrashIcon className="mr-2 h-5 w-5"/>
+                      <span>{t('commands.removeSnippet')}</span>
+                    </CommandItem>
+                }
+                {
+                    canSendToDevice() &&
+                    <CommandItem onSelect={handleShowDeviceCommands}>
+                      <LaptopIcon className="mr-2 h-5 w-5"/>
+                      <span>{t('commands.sendToDevice')}</span>
+                    </CommandItem>
+                }
                 <CommandItem onSelect={handleToggleFavorite}>
                   {
                     canAddToFavorites() ?
@@ -803,12 +1072,68 @@ export default function Commands(props: CommandsProps) {
                   </CommandShortcut>
                 </CommandItem>
                 <CommandSeparator/>
-                <CommandItem onSelect={handleOpenSettings}>
-                  <SettingsIcon className="mr-2 h-5 w-5"/>
-                  <span>{t('commands.settings')}</span>
-                  <CommandShortcut className="flex flex-row">
-                    <ShortcutLabel shortcut={prefGetOpenSettingsShortcut()}/>
-                  </CommandShortcut>
+                {
+                    !isGuestMode() &&
+                    <>
+                      <CommandItem onSelect={() => handleExportHistory(ExportFormat.JSON)}>
+                        <DownloadIcon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.exportHistoryAs', {format: "JSON"})}</span>
+                      </CommandItem>
+                      <CommandItem onSelect={() => handleExportHistory(ExportFormat.CSV)}>
+                        <DownloadIcon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.exportHistoryAs', {format: "CSV"})}</span>
+                      </CommandItem>
+                      <CommandItem onSelect={() => handleExportHistory(ExportFormat.Markdown)}>
+                        <DownloadIcon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.exportHistoryAs', {format: "Markdown"})}</span>
+                      </CommandItem>
+                      <CommandItem onSelect={() => handleImportHistory(ImportSource.Maccy)}>
+                        <UploadIcon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.importHistoryFrom', {source: "Maccy"})}</span>
+                      </CommandItem>
+                      <CommandItem onSelect={() => handleImportHistory(ImportSource.Clipy)}>
+                        <UploadIcon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.importHistoryFrom', {source: "Clipy"})}</span>
+                      </CommandItem>
+                      <CommandItem onSelect={() => handleImportHistory(ImportSource.JSON)}>
+                        <UploadIcon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.importHistoryFrom', {source: "JSON"})}</span>
+                      </CommandItem>
+                      <CommandItem onSelect={() => handleRestoreItemsFromBackup({})}>
+                        <Undo2Icon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.restoreMissingItemsFromBackup')}</span>
+                      </CommandItem>
+                      <CommandItem onSelect={() => handleRestoreItemsFromBackup({favoritesOnly: true})}>
+                        <Undo2Icon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.restoreFavoritesFromBackup')}</span>
+                      </CommandItem>
+                      <CommandItem onSelect={handleMergeDatabase}>
+                        <UploadIcon className="mr-2 h-5 w-5"/>
+                        <span>{t('commands.mergeHistoryFromBackup')}</span>
+                      </CommandItem>
+                      <CommandSeparator/>
+                    </>
+                }
+                {
+                    !isGuestMode() &&
+                    <CommandItem onSelect={handleOpenSettings}>
+                      <SettingsIcon className="mr-2 h-5 w-5"/>
+                      <span>{t('commands.settings')}</span>
+                      <CommandShortcut className="flex flex-row">
+                        <ShortcutLabel shortcut={prefGetOpenSettingsShortcut()}/>
+                      </CommandShortcut>
+                    </CommandItem>
+                }
+                {
+                    !isGuestMode() && getSyncStatus().configured &&
+                    <CommandItem onSelect={handleSyncNow}>
+                      <RefreshCwIcon className="mr-2 h-5 w-5"/>
+                      <span>{t('commands.syncNow')}</span>
+                    </CommandItem>
+                }
+                <CommandItem onSelect={handleToggleGuestMode}>
+                  <UserIcon className="mr-2 h-5 w-5"/>
+                  <span>{t(isGuestMode() ? 'commands.exitGuestMode' : 'commands.enterGuestMode')}</span>
                 </CommandItem>
                 <CommandSeparator/>
                 {
@@ -833,13 +1158,43 @@ export default function Commands(props: CommandsProps) {
                       </CommandShortcut>
                     </CommandItem>
                 }
-                <CommandItem onSelect={handleDeleteAllItems}>
-                  <TrashIcon className="mr-2 h-5 w-5 text-actions-danger"/>
-                  <span className="text-actions-danger">{t('commands.deleteAllItems')}</span>
-                  <CommandShortcut className="flex flex-row">
-                    <ShortcutLabel shortcut={prefGetClearHistoryShortcut()}/>
-                  </CommandShortcut>
-                </CommandItem>
+                {
+                    (canShowDeleteItem() || canShowDeleteItems()) &&
+                    <CommandItem onSelect={handleSecureDeleteItems}>
+                      <ShieldXIcon className="mr-2 h-5 w-5 text-actions-danger"/>
+                      <span className="text-actions-danger">
+                      {
+                        canShowDeleteItems()
+                            ? t('commands.secureDeleteItems', {itemsLabel: getMultipleItemsIndicator()})
+                            : t('commands.secureDeleteItem')
+                      }
+                    </span>
+                    </CommandItem>
+                }
+                {
+                    !isGuestMode() &&
+                    <CommandItem onSelect={handleDeleteAllItems}>
+                      <TrashIcon className="mr-2 h-5 w-5 text-actions-danger"/>
+                      <span className="text-actions-danger">{t('commands.deleteAllItems')}</span>
+                      <CommandShortcut className="flex flex-row">
+                        <ShortcutLabel shortcut={prefGetClearHistoryShortcut()}/>
+                      </CommandShortcut>
+                    </CommandItem>
+                }
+                {
+                    !isGuestMode() && canUndoClearHistory() &&
+                    <CommandItem onSelect={handleUndoClearHistory}>
+                      <Undo2Icon className="mr-2 h-5 w-5"/>
+                      <span>{t('commands.undoClearHistory')}</span>
+                    </CommandItem>
+                }
+                {
+                    !isGuestMode() &&
+                    <CommandItem onSelect={handleShowTrashCommands}>
+                      <TrashIcon className="mr-2 h-5 w-5"/>
+                      <span>{t('commands.showTrash')}</span>
+                    </CommandItem>
+                }
                 <CommandEmpty>{t('commands.noResultsFound')}</CommandEmpty>
               </CommandList>
             </div>
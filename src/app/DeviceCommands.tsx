@@ -0,0 +1,102 @@
+import '../app.css';
+
+import * as React from "react"
+import {useEffect, useState} from "react"
+import {VisuallyHidden} from "@radix-ui/react-visually-hidden";
+
+import {
+  CommandDialog,
+  CommandEmpty,
+  CommandGroup,
+  CommandInput,
+  CommandItem,
+  CommandList,
+} from "@/components/ui/command"
+import {DialogTitle} from "@/components/ui/dialog";
+import {emitter} from "@/actions";
+import {useTranslation} from "react-i18next";
+import {LaptopIcon} from "lucide-react";
+import {getPeers, sendItemToDevice} from "@/lanshare";
+import {getFirstSelectedHistoryItem} from "@/data";
+
+export default function DeviceCommands() {
+  const {t} = useTranslation()
+
+  const [open, setOpen] = useState(false)
+  const [peers, setPeers] = useState<string[]>([])
+
+  function handleKeyDown(e: React.KeyboardEvent) {
+    if (e.key === "Escape") {
+      handleOpenChange(false)
+      e.preventDefault()
+    }
+    e.stopPropagation()
+  }
+
+  useEffect(() => {
+    function handleAppWindowDidHide() {
+      handleOpenChange(false)
+    }
+
+    function handleShowDeviceCommands() {
+      setTimeout(() => {
+        handleOpenChange(true)
+      }, 200);
+    }
+
+    emitter.on("NotifyAppWindowDidHide", handleAppWindowDidHide)
+    emitter.on("ShowDeviceCommands", handleShowDeviceCommands)
+    return () => {
+      emitter.off("NotifyAppWindowDidHide", handleAppWindowDidHide)
+      emitter.off("ShowDeviceCommands", handleShowDeviceCommands)
+    };
+  }, [])
+
+  function handleOpenChange(open: boolean) {
+    if (open) {
+      setPeers(getPeers())
+    } else {
+      emitter.emit("FocusSearchInput")
+    }
+    setOpen(open)
+  }
+
+  function handleSelectPeer(peer: string) {
+    handleOpenChange(false)
+    let item = getFirstSelectedHistoryItem()
+    if (!item) {
+      return
+    }
+    try {
+      sendItemToDevice(item.id!, peer)
+    } catch (error) {
+      console.error('❌ Failed to send item to device:', error)
+    }
+  }
+
+  return (
+      <div className="" onKeyDown={handleKeyDown}>
+        <CommandDialog open={open} onOpenChange={handleOpenChange}>
+          <VisuallyHidden>
+            <DialogTitle></DialogTitle>
+          </VisuallyHidden>
+          <CommandInput placeholder={t("app.deviceCommands.searchPlaceholder")} autoFocus={true}/>
+          <div className="max-h-[70vh] overflow-y-auto mb-1.5">
+            <CommandList>
+              <CommandGroup heading={t("app.deviceCommands.title")}>
+                {
+                  peers.map(peer => (
+                      <CommandItem key={peer} onSelect={() => handleSelectPeer(peer)}>
+                        <LaptopIcon className="mr-2 h-5 w-5"/>
+                        <span>{peer}</span>
+                      </CommandItem>
+                  ))
+                }
+              </CommandGroup>
+              <CommandEmpty>{t("app.deviceCommands.noDevices")}</CommandEmpty>
+            </CommandList>
+          </div>
+        </CommandDialog>
+      </div>
+  )
+}
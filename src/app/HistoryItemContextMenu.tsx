@@ -5,7 +5,8 @@ import {
   CopyIcon,
   Edit3Icon,
   EyeIcon,
-  GlobeIcon, PenIcon, PlusIcon, ScanTextIcon,
+  EyeOffIcon,
+  GlobeIcon, PenIcon, PlusIcon, QrCodeIcon, ScanTextIcon, Share2Icon,
   StarIcon,
   StarOffIcon, TagsIcon,
   TrashIcon, TypeIcon, UploadIcon
@@ -46,6 +47,8 @@ import TagIcon, {allTags, Tag} from "@/tags";
 import {Checkbox} from "@/components/ui/checkbox";
 import {CheckedState} from "@radix-ui/react-checkbox";
 import {emitter} from "@/actions";
+import {canGenerateQR, showQR} from "@/qrcode";
+import {canShare, share} from "@/share";
 import { useTranslation } from 'react-i18next';
 import {
   ContextMenu,
@@ -177,6 +180,27 @@ const HistoryItemContextMenu = (props: HistoryItemContextMenuProps) => {
     emitter.emit("EditItem", props.item)
   }
 
+  function handleShowQRCode() {
+    try {
+      showQR(props.item.id!)
+    } catch (error) {
+      console.error('❌ Failed to generate QR code:', error)
+    }
+  }
+
+  function handleShare() {
+    try {
+      share(props.item.id!)
+    } catch (error) {
+      console.error('❌ Failed to share item:', error)
+    }
+  }
+
+  function handleToggleSensitive() {
+    props.item.sensitive = !props.item.sensitive
+    emitter.emit("EditItem", props.item)
+  }
+
   function handleEditContent() {
     focusSearchOnClose = false
     emitter.emit("EditContentByIndex", props.index)
@@ -372,6 +396,30 @@ const HistoryItemContextMenu = (props: HistoryItemContextMenuProps) => {
               <ShortcutLabel shortcut={prefGetToggleFavoriteShortcut()}/>
             </CommandShortcut>
           </ContextMenuItem>
+          {
+              props.item.type !== ClipType.Image && props.item.type !== ClipType.File &&
+              <ContextMenuItem onClick={handleToggleSensitive}>
+                {
+                  props.item.sensitive ? <EyeIcon className="mr-2 h-4 w-4"/> :
+                      <EyeOffIcon className="mr-2 h-4 w-4"/>
+                }
+                <span>{props.item.sensitive ? t('historyItemContextMenu.unmarkSensitive') : t('historyItemContextMenu.markSensitive')}</span>
+              </ContextMenuItem>
+          }
+          {
+              canGenerateQR(props.item) &&
+              <ContextMenuItem onClick={handleShowQRCode}>
+                <QrCodeIcon className="mr-2 h-4 w-4"/>
+                <span>{t('historyItemContextMenu.showQRCode')}</span>
+              </ContextMenuItem>
+          }
+          {
+              canShare(props.item) &&
+              <ContextMenuItem onClick={handleShare}>
+                <Share2Icon className="mr-2 h-4 w-4"/>
+                <span>{t('historyItemContextMenu.share')}</span>
+              </ContextMenuItem>
+          }
           <ContextMenuItem onClick={handleRename}>
             <PenIcon className="mr-2 h-4 w-4"/>
             <span className="mr-12">{t('historyItemContextMenu.renameItem')}</span>
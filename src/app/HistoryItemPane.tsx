@@ -4,6 +4,7 @@ import {
   fileExists,
   getFilterQuery,
   getFilterVisibleState,
+  getItemPreview,
   getPasteNextItemIndex,
   updateHistoryItem
 } from "@/data";
@@ -300,7 +301,7 @@ const HistoryItemPane = (props: HistoryItemPaneProps) => {
     if (props.item.type === ClipType.File) {
       return getFileNameFromPath(getFilePath(props.item))
     }
-    let content = props.item.content
+    let content = getItemPreview(props.item)
     if (content.length > 256) {
       content = content.substring(0, 256)
     }
@@ -13,6 +13,7 @@ import {
   clear,
   clearSelection,
   deleteHistoryItem,
+  secureDeleteHistoryItem,
   findItem,
   getDefaultApp,
   getFileOrImagePath,
@@ -21,6 +22,7 @@ import {
   getHistoryItem,
   getHistoryItemIndex,
   getHistoryItems,
+  getHistorySorted,
   getLastSelectedItemIndex,
   getPreviewVisibleState,
   getSelectedHistoryItemIndices,
@@ -31,11 +33,15 @@ import {
   isHistoryItemSelected,
   isTextItem,
   loadHistory,
+  markItemPasted,
   removeSelectedHistoryItemIndex,
   setFilterQuery,
+  filterByItems,
   setPreviewVisibleState,
   setSelectedHistoryItemIndex,
+  SortHistoryType,
   TextFormatOperation,
+  undoClearHistory,
   updateHistoryItem,
   updateHistoryItemTypes,
   setFilterVisibleState,
@@ -46,7 +52,10 @@ import {
   resetFilter,
   setShouldUpdateHistory,
   fileExists,
-  getNextItemIndexForPaste, resetPasteNextItemIndex
+  getNextItemIndexForPaste, resetPasteNextItemIndex,
+  unpinExpiredItems,
+  getHistoryItemsCount,
+  getHistoryItemById
 } from "@/data";
 import {isQuickPasteShortcut, isShortcutMatch} from "@/lib/shortcuts";
 import {
@@ -92,6 +101,7 @@ import {
   prefShouldTreatDigitNumbersAsColor,
   prefShouldUpdateHistoryAfterAction
 } from "@/pref";
+import {hasPasteSequence, pasteSequenceNext} from "@/sequence";
 import {FixedSizeList as List} from "react-window";
 import {Clip, ClipType, getFilePath, getHTML, getImageFileName, getImageText, getRTF} from "@/db";
 import {formatText, getClipType, isUrl} from "@/lib/utils";
@@ -105,6 +115,60 @@ import * as React from "react";
 import AppSidebar from "@/app/AppSidebar";
 import {AppSidebarItemType} from "@/app/AppSidebarItem";
 import {Tag} from "@/tags";
+import {isBackupSchedulerAlive, startBackupScheduler, stopBackupScheduler} from "@/backup";
+import {
+  ClearTrigger,
+  isAutoClearSchedulerAlive,
+  isHistoryClearing,
+  recordHistoryCleared,
+  startAutoClearScheduler,
+  stopAutoClearScheduler,
+  trackHistoryClearing
+} from "@/autoclear";
+import {recordPastedItem} from "@/lineage";
+import {handleApiRequest, isApiAlive, startApiIfEnabled} from "@/api";
+import {isShutdownComplete, shutdownTasks, superviseTask} from "@/supervisor";
+import {handleDeepLink} from "@/deeplinks";
+import {syncManager} from "@/syncmanager";
+import {lanItemSent, receiveLanItem, restartSharing, startSharingIfEnabled} from "@/lanshare";
+import {appLockDisabled, isAppLocked, startAutoLock, userAuthenticated} from "@/applock";
+import {isGuestMode, restoreGuestMode} from "@/guest";
+import {startScreenSharingMonitor} from "@/screensharing";
+import {ItemTooLargeError} from "@/sizelimit";
+import {reloadConfigFile} from "@/config";
+import {loadShortcutBindings, recordShortcutEvent, recordShortcutUse, watchShortcutChanges} from "@/shortcuts";
+import LockScreen from "@/app/LockScreen";
+import {isRetentionCleanupAlive, startRetentionCleanup, stopRetentionCleanup} from "@/retention";
+import {startCapsEnforcement} from "@/caps";
+import {isTrashPurgeAlive, startTrashPurge, stopTrashPurge} from "@/trash";
+import {loadArchive} from "@/archive";
+import {getRegisterItem, loadRegisters} from "@/registers";
+import {cycleProfile} from "@/profiles";
+import {
+  flushMetrics,
+  instrumentCommand,
+  isMetricsPersistenceAlive,
+  startMetricsPersistence,
+  stopMetricsPersistence
+} from "@/metrics";
+import {getFullContent, startBlobGarbageCollector} from "@/blobs";
+import {imageTextRecognized, startImageTextRecognition} from "@/ocr";
+import {startLinkPreviewFetching} from "@/linkpreview";
+import {startTrayStateUpdates, updateTrayRecentItems} from "@/tray";
+import {isPermissionMonitorAlive, startPermissionMonitor, stopPermissionMonitor} from "@/permissions";
+import {shouldRecordFromApp} from "@/apprules";
+import {announceCopy, announcePaste} from "@/accessibility";
+import {handleNotificationAction, notifyCopy} from "@/notifications";
+import {
+  injectClipboardChange,
+  isSimulatedClipboard,
+  readSimulatedClipboard,
+  writeClipboard
+} from "@/clipboard-sim";
+import {cancelReplay, loadReplayScript, replayEvents} from "@/replay";
+import {acknowledgeJournaledCapture, replayJournaledCaptures} from "@/capturejournal";
+import {pathSelected} from "@/dialogs";
+import {getCollectionItems} from "@/collections";
 import {
   emitter,
   FormatTextByIndexArgs,
@@ -116,9 +180,7 @@ declare const pasteItemInFrontApp: (text: string, rtf: string, html: string, ima
 declare const pasteFilesInFrontApp: (filePaths: string) => void;
 declare const pressReturn: () => void;
 declare const pressTab: () => void;
-declare const copyToClipboard: (text: string, rtf: string, html: string, imageFileName: string, filePath: string, ghost: boolean) => void;
 declare const copyToClipboardAfterMerge: (text: string) => void;
-declare const deleteImage: (imageFileName: string) => void;
 declare const clearEntireHistory: () => void;
 declare const openInBrowser: (url: string) => void;
 declare const showInFinder: (filePath: string) => void;
@@ -154,6 +216,7 @@ export default function HistoryPane(props: HistoryPaneProps) {
   const [isTrial, setIsTrial] = useState(isTrialLicense());
   const [trialDaysLeft, setTrialDaysLeft] = useState(getTrialLicenseDaysLeft());
   const [isTrialExpired, setIsTrialExpired] = useState(isTrialLicenseExpired());
+  const [appLocked, setAppLocked] = useState(isAppLocked());
   const [selectedItemType, setSelectedItemType] = useState<AppSidebarItemType>("All")
   const [selectedTag, setSelectedTag] = useState<Tag | undefined>(undefined)
   const [selectedApp, setSelectedApp] = useState<AppInfo | undefined>(undefined)
@@ -166,7 +229,89 @@ export default function HistoryPane(props: HistoryPaneProps) {
   useEffect(() => {
     loadHistory().then(() => {
       setHistory(getHistoryItems())
+      superviseTask({
+        name: "backupScheduler",
+        start: startBackupScheduler,
+        stop: stopBackupScheduler,
+        isAlive: isBackupSchedulerAlive,
+      })
+      superviseTask({
+        name: "autoClearScheduler",
+        start: () => startAutoClearScheduler(clearHistory),
+        stop: stopAutoClearScheduler,
+        isAlive: isAutoClearSchedulerAlive,
+      })
+      superviseTask({
+        name: "retentionCleanup",
+        start: startRetentionCleanup,
+        stop: stopRetentionCleanup,
+        isAlive: isRetentionCleanupAlive,
+      })
+      superviseTask({
+        name: "trashPurge",
+        start: startTrashPurge,
+        stop: stopTrashPurge,
+        isAlive: isTrashPurgeAlive,
+      })
+      superviseTask({
+        name: "permissionMonitor",
+        start: startPermissionMonitor,
+        stop: stopPermissionMonitor,
+        isAlive: isPermissionMonitorAlive,
+      })
+      superviseTask({
+        name: "apiServer",
+        start: startApiIfEnabled,
+        isAlive: isApiAlive,
+      })
+      superviseTask({
+        name: "syncManager",
+        start: () => syncManager.start(),
+        stop: () => syncManager.stop(),
+        isAlive: () => syncManager.isAlive(),
+      })
+      startSharingIfEnabled()
+      startAutoLock()
+      restoreGuestMode()
+      replayJournaledCaptures(addClipboardData).catch(error => {
+        console.error('❌ Failed to replay captures:', error)
+      })
+      startScreenSharingMonitor()
+      startCapsEnforcement()
+      startBlobGarbageCollector()
+      startImageTextRecognition()
+      startLinkPreviewFetching()
+      startTrayStateUpdates()
+      loadArchive().catch(error => {
+        console.error('❌ Failed to load archive:', error)
+      })
+      loadRegisters().catch(error => {
+        console.error('❌ Failed to load registers:', error)
+      })
+      loadShortcutBindings().catch(error => {
+        console.error('❌ Failed to load shortcuts:', error)
+      })
+      watchShortcutChanges()
+      reloadConfigFile()
+      superviseTask({
+        name: "metricsPersistence",
+        start: () => startMetricsPersistence().catch(error => {
+          console.error('❌ Failed to load metrics:', error)
+        }),
+        stop: stopMetricsPersistence,
+        isAlive: isMetricsPersistenceAlive,
+        shutdown: flushMetrics,
+      })
     })
+    // Drop the expired pins so that the items return to their place.
+    const pinExpiryTimer = setInterval(() => {
+      unpinExpiredItems().then(count => {
+        if (count > 0) {
+          handleHistoryChanged()
+        }
+      })
+    }, 60 * 1000)
+    return () => clearInterval(pinExpiryTimer)
   }, []);
 
   async function addClipboardData(content: string,
@@ -183,35 +328,59 @@ export default function HistoryPane(props: HistoryPaneProps) {
                                   fileSizeInBytes: number,
                                   isFolder: boolean,
                                   rtf: string,
-                                  html: string) {
-    
+                                  html: string,
+                                  sourceDevice: string = "",
+                                  journalId: number = 0) {
+    // The capturing is paused in guest mode, but the user may resume it
+    // from the menu bar.
+    if (isGuestMode() || !shouldRecordFromApp(sourceAppPath)) {
+      acknowledgeJournaledCapture(journalId)
+      return
+    }
+
     let item = findItem(content, imageFileName, filePath)
     if (item) {
       // Found duplicate - check if it's the last item
       if (checkIfLastItem(item)) {
+        acknowledgeJournaledCapture(journalId)
         return; // Skip - don't do anything if it's the last item
       }
       // Not the last item - create new duplicate
     }
     
     // Create new item (either no duplicate found or duplicate is not last item)
-    item = await addHistoryItem(
-          content,
-          sourceAppPath,
-          imageFileName,
-          imageThumbFileName,
-          imageWidth,
-          imageHeight,
-          imageSizeInBytes,
-          imageText,
-          filePath,
-          filePathFileName,
-          filePathThumbFileName,
-          fileSizeInBytes,
-          isFolder,
-          rtf,
-          html)
+    try {
+      item = await addHistoryItem(
+            content,
+            sourceAppPath,
+            imageFileName,
+            imageThumbFileName,
+            imageWidth,
+            imageHeight,
+            imageSizeInBytes,
+            imageText,
+            filePath,
+            filePathFileName,
+            filePathThumbFileName,
+            fileSizeInBytes,
+            isFolder,
+            rtf,
+            html,
+            undefined,
+            sourceDevice)
+    } catch (error) {
+      // The user has already been notified that the item is too large.
+      if (error instanceof ItemTooLargeError) {
+        acknowledgeJournaledCapture(journalId)
+        return
+      }
+      // The capture stays in the journal and is replayed on the next launch.
+      throw error
+    }
+    acknowledgeJournaledCapture(journalId)
     setHistory([...getHistoryItems()])
+    announceCopy(item)
+    notifyCopy(item)
 
     // When the history is changed, we need to reset the next item index for paste.
     resetPasteNextItemIndex()
@@ -239,6 +408,9 @@ export default function HistoryPane(props: HistoryPaneProps) {
                                     filePathThumbFileName: string,
                                     fileSizeInBytes: number,
                                     isFolder: boolean) {
+    if (!shouldRecordFromApp(sourceAppPath)) {
+      return
+    }
     if (history.length > 0) {
       // Find the first non-favorite item to merge with.
       let targetItem = history[0]
@@ -292,9 +464,15 @@ export default function HistoryPane(props: HistoryPaneProps) {
         "")
   }
 
-  async function clearHistory() {
-    let keepFavorites = prefGetKeepFavoritesOnClearHistory()
+  async function clearHistory(trigger?: ClearTrigger) {
+    // The automatic clearing at an interval, logout or quit always keeps
+    // favorites.
+    let keepFavorites = trigger !== undefined || prefGetKeepFavoritesOnClearHistory()
+    let itemsCount = getHistoryItemsCount()
     let clips = await clear(keepFavorites)
+    if (trigger) {
+      await recordHistoryCleared(trigger, itemsCount - getHistoryItemsCount())
+    }
     setHistory(getHistoryItems())
     resetPasteNextItemIndex()
     // If the history is not empty, update the preview text to the new active item.
@@ -347,6 +525,10 @@ export default function HistoryPane(props: HistoryPaneProps) {
   }
 
   async function pasteNextItemToActiveApp() {
+    if (hasPasteSequence()) {
+      await pasteSequenceNext()
+      return
+    }
     let index = getNextItemIndexForPaste()
     if (index >= 0) {
       let item = getHistoryItem(index)
@@ -358,6 +540,35 @@ export default function HistoryPane(props: HistoryPaneProps) {
     }
   }
 
+  async function pasteTrayItemToActiveApp(id: number) {
+    let item = getHistoryItemById(id)
+    if (item) {
+      await pasteItem(item, true)
+    }
+  }
+
+  async function pasteRegisterToActiveApp(name: string) {
+    // The global shortcut must not reveal the history while it's locked.
+    if (isAppLocked()) {
+      return
+    }
+    let item = getRegisterItem(name)
+    if (item) {
+      await pasteItem(item, true)
+    }
+  }
+
+  async function pasteRecentItemToActiveApp(number: number) {
+    // The global shortcut must not reveal the history while it's locked.
+    if (isAppLocked()) {
+      return
+    }
+    let item = getHistorySorted(SortHistoryType.TimeOfLastCopy)[number - 1]
+    if (item) {
+      await pasteItem(item, true)
+    }
+  }
+
   function scrollToLastSelectedItem() {
     let selectedItemIndices = getSelectedHistoryItemIndices()
     if (selectedItemIndices.length === 0) {
@@ -374,6 +585,10 @@ export default function HistoryPane(props: HistoryPaneProps) {
     }, 50);
   }
 
+  useEffect(() => {
+    updateTrayRecentItems()
+  }, [history])
+
   useEffect(() => {
     const down = async (e: KeyboardEvent) => {
       if (isTrialExpired) {
@@ -488,6 +703,7 @@ export default function HistoryPane(props: HistoryPaneProps) {
           return // Vim mode handled the key, don't continue to regular shortcuts
         }
       }
+      recordShortcutEvent(e)
       // Select the previous item when the select previous item shortcut is pressed.
       if (isShortcutMatch(prefGetSelectPreviousItemShortcut(), e)) {
         selectPreviousItem()
@@ -751,11 +967,16 @@ export default function HistoryPane(props: HistoryPaneProps) {
     emitter.on("ToggleDetails", handleToggleDetails)
     emitter.on("FocusSearchInput", focusSearchField)
     emitter.on("DeleteTagById", handleDeleteTag)
+    emitter.on("ShowCollection", handleShowCollection)
+    emitter.on("PinItems", handlePinItems)
+    emitter.on("UnpinItems", handleUnpinItems)
     emitter.on("UpdateTagById", handleUpdateTag)
     emitter.on("DeleteItem", handleDeleteItem)
     emitter.on("DeleteItemByIndex", handleDeleteItemByIndex)
     emitter.on("DeleteItems", handleDeleteItems)
+    emitter.on("SecureDeleteItems", handleSecureDeleteItems)
     emitter.on("DeleteAllItems", handleDeleteAllItems)
+    emitter.on("UndoClearHistory", handleUndoClearHistory)
     emitter.on("RenameItem", handleRenameItem)
     emitter.on("RenameItemByIndex", handleRenameItemByIndex)
     emitter.on("FilterHistory", handleFilterHistory)
@@ -791,12 +1012,18 @@ export default function HistoryPane(props: HistoryPaneProps) {
     emitter.on("OpenFileItemWithApp", handleOpenWithApp)
     emitter.on("OpenFileItemWithAppByIndex", handleOpenWithAppByIndex)
     emitter.on("OpenSettings", handleOpenSettings)
+    emitter.on("SyncNow", handleSyncNow)
+    emitter.on("GuestModeChanged", handleGuestModeChanged)
     emitter.on("SaveImageAsFile", handleSaveImageAsFile)
     emitter.on("ShowInFinder", handleShowInFinder)
     emitter.on("ShowInFinderByIndex", handleShowInFinderByIndex)
     emitter.on("ShowInHistory", handleShowInHistory)
     emitter.on("Split", handleSplit)
     emitter.on("Merge", handleMerge)
+    emitter.on("HistoryChanged", handleHistoryChanged)
+    emitter.on("SearchHistory", handleSearchQueryChange)
+    emitter.on("AppLockChanged", setAppLocked)
+    emitter.on("ScreenSharingChanged", handleScreenSharingChanged)
     return () => {
       emitter.off("ToggleFilter", handleToggleFilter)
       emitter.off("ToggleFavorite", handleToggleFavorite)
@@ -804,11 +1031,16 @@ export default function HistoryPane(props: HistoryPaneProps) {
       emitter.off("ToggleDetails", handleToggleDetails)
       emitter.off("FocusSearchInput", focusSearchField)
       emitter.off("DeleteTagById", handleDeleteTag)
+      emitter.off("ShowCollection", handleShowCollection)
+      emitter.off("PinItems", handlePinItems)
+      emitter.off("UnpinItems", handleUnpinItems)
       emitter.off("UpdateTagById", handleUpdateTag)
       emitter.off("DeleteItem", handleDeleteItem)
       emitter.off("DeleteItemByIndex", handleDeleteItemByIndex)
       emitter.off("DeleteItems", handleDeleteItems)
+    emitter.off("SecureDeleteItems", handleSecureDeleteItems)
       emitter.off("DeleteAllItems", handleDeleteAllItems)
+      emitter.off("UndoClearHistory", handleUndoClearHistory)
       emitter.off("RenameItem", handleRenameItem)
       emitter.off("RenameItemByIndex", handleRenameItemByIndex)
       emitter.off("FilterHistory", handleFilterHistory)
@@ -844,15 +1076,38 @@ export default function HistoryPane(props: HistoryPaneProps) {
       emitter.off("OpenFileItemWithApp", handleOpenWithApp)
       emitter.off("OpenFileItemWithAppByIndex", handleOpenWithAppByIndex)
       emitter.off("OpenSettings", handleOpenSettings)
+      emitter.off("SyncNow", handleSyncNow)
+      emitter.off("GuestModeChanged", handleGuestModeChanged)
       emitter.off("SaveImageAsFile", handleSaveImageAsFile)
       emitter.off("ShowInFinder", handleShowInFinder)
       emitter.off("ShowInFinderByIndex", handleShowInFinderByIndex)
       emitter.off("ShowInHistory", handleShowInHistory)
       emitter.off("Split", handleSplit)
       emitter.off("Merge", handleMerge)
+      emitter.off("HistoryChanged", handleHistoryChanged)
+      emitter.off("SearchHistory", handleSearchQueryChange)
+      emitter.off("AppLockChanged", setAppLocked)
+      emitter.off("ScreenSharingChanged", handleScreenSharingChanged)
     };
   }, []);
 
+  function handleHistoryChanged() {
+    setHistory([...getHistoryItems()])
+  }
+
+  async function handleShowCollection(id: number) {
+    try {
+      filterByItems(await getCollectionItems(id))
+      setSelectedItemType("None")
+      setSelectedTag(undefined)
+      setSelectedApp(undefined)
+      updateHistory()
+      focusSearchField()
+    } catch (error) {
+      console.error('❌ Failed to show collection:', error)
+    }
+  }
+
   async function handleDeleteTag(tagId: number) {
     getHistoryItems().forEach((item) => {
       if (item.tags && item.tags.includes(tagId)) {
@@ -951,12 +1206,15 @@ export default function HistoryPane(props: HistoryPaneProps) {
 
   async function pasteItem(item: Clip, keepHistory: boolean = false, pasteObject: boolean = false) {
     item.copyTime = new Date(); item.numberOfCopies += 1
+    markItemPasted(item)
     // Note: We don't need to check prefShouldUpdateHistoryAfterAction since we're always updating copy statistics
     await updateHistoryItem(item.id!, item)
 
     let rtf = pasteObject ? getRTF(item) : ""
     let html = pasteObject ? getHTML(item) : ""
-    pasteItemInFrontApp(item.content, rtf, html, getImageFileName(item), getFilePath(item))
+    pasteItemInFrontApp(getFullContent(item), rtf, html, getImageFileName(item), getFilePath(item))
+    recordPastedItem(item)
+    announcePaste(item)
 
     setHistory([...getHistoryItems()])
 
@@ -1089,7 +1347,7 @@ export default function HistoryPane(props: HistoryPaneProps) {
     if (getSelectedHistoryItemIndices().length === 1) {
       const selectedHistoryItem = getFirstSelectedHistoryItem()
       if (selectedHistoryItem && selectedHistoryItem.type === ClipType.File) {
-        copyToClipboard(selectedHistoryItem.filePath, "", "", "", "", false)
+        writeClipboard(selectedHistoryItem.filePath, "", "", "", "", false)
       }
     }
     focusSearchField()
@@ -1098,7 +1356,7 @@ export default function HistoryPane(props: HistoryPaneProps) {
   async function handleCopyPathToClipboardByIndex(index: number) {
     let item = getHistoryItem(index)
     if (item.type === ClipType.File) {
-      copyToClipboard(item.filePath, "", "", "", "", false)
+      writeClipboard(item.filePath, "", "", "", "", false)
     }
   }
 
@@ -1140,6 +1398,35 @@ export default function HistoryPane(props: HistoryPaneProps) {
     focusSearchField()
   }
 
+  // Pins the selected items for the given duration in milliseconds or until
+  // they're unpinned when the duration is 0.
+  async function handlePinItems(duration: number) {
+    let until = duration > 0 ? new Date(Date.now() + duration) : undefined
+    try {
+      for (const item of getSelectedHistoryItems()) {
+        await pinItem(item.id!, until)
+      }
+    } catch (error) {
+      console.error('❌ Failed to pin items:', error)
+    }
+    setShouldUpdateHistory()
+    updateHistory()
+    focusSearchField()
+  }
+
+  async function handleUnpinItems() {
+    try {
+      for (const item of getSelectedHistoryItems()) {
+        await unpinItem(item.id!)
+      }
+    } catch (error) {
+      console.error('❌ Failed to unpin items:', error)
+    }
+    setShouldUpdateHistory()
+    updateHistory()
+    focusSearchField()
+  }
+
   function handleTogglePreview(): void {
     if (previewPanelRef.current) {
       let visible = previewPanelRef.current.getSize() == 0
@@ -1154,6 +1441,14 @@ export default function HistoryPane(props: HistoryPaneProps) {
     focusSearchField()
   }
 
+  // Hides the preview while the screen is shared without changing the saved
+  // preview visibility, so it's restored when the sharing stops.
+  function handleScreenSharingChanged(sharing: boolean) {
+    let visible = !sharing && getPreviewVisibleState()
+    previewPanelRef.current?.resize(visible ? 50 : 0)
+    setPreviewVisible(visible)
+  }
+
   function handleToggleDetails() {
     let visible = !getDetailsVisibleState()
     setDetailsVisible(visible)
@@ -1299,7 +1594,7 @@ export default function HistoryPane(props: HistoryPaneProps) {
 
     let rtf = pasteObject ? getRTF(item) : ""
     let html = pasteObject ? getHTML(item) : ""
-    copyToClipboard(item.content, rtf, html, getImageFileName(item), getFilePath(item), true)
+    writeClipboard(getFullContent(item), rtf, html, getImageFileName(item), getFilePath(item), true)
 
     setHistory([...getHistoryItems()])
 
@@ -1336,7 +1631,7 @@ export default function HistoryPane(props: HistoryPaneProps) {
   function copyTextFromImage(item: Clip) {
     if (item.type === ClipType.Image || item.type === ClipType.File) {
       let imageText = getImageText(item)
-      copyToClipboard(imageText.length > 0 ? imageText : item.content, "", "", "", "", false)
+      writeClipboard(imageText.length > 0 ? imageText : getFullContent(item), "", "", "", "", false)
     }
   }
 
@@ -1517,20 +1812,28 @@ export default function HistoryPane(props: HistoryPaneProps) {
     quickLook(getHistoryItem(index))
   }
 
+  // The config file changes are applied after the guest mode is exited.
+  function handleGuestModeChanged(enabled: boolean) {
+    if (!enabled) {
+      reloadConfigFile()
+    }
+  }
+
+  function handleSyncNow() {
+    syncManager.syncNow()
+  }
+
   function handleOpenSettings() {
     openSettingsWindow()
     focusSearchField()
   }
 
-  async function deleteItem(item: Clip) {
-    await deleteHistoryItem(item)
-    if (item.type === ClipType.Image) {
-      deleteImage(item.imageFileName)
-      deleteImage(item.imageThumbFileName)
-    }
-    if (item.type === ClipType.File) {
-      deleteImage(item.filePathFileName)
-      deleteImage(item.filePathThumbFileName)
+  async function deleteItem(item: Clip, secure: boolean = false) {
+    if (secure) {
+      await secureDeleteHistoryItem(item)
+    } else {
+      // The image files are deleted when the item is removed from the trash.
+      await deleteHistoryItem(item)
     }
 
     // If the history is not empty, update the preview text to the new active item.
@@ -1574,6 +1877,14 @@ export default function HistoryPane(props: HistoryPaneProps) {
   }
 
   async function handleDeleteItems() {
+    await deleteSelectedItems(false)
+  }
+
+  async function handleSecureDeleteItems() {
+    await deleteSelectedItems(true)
+  }
+
+  async function deleteSelectedItems(secure: boolean) {
     let items = getSelectedHistoryItems()
     if (items.length > 0) {
       let nextSelectedItemIndex = getVisibleHistoryLength() - 1
@@ -1581,7 +1892,7 @@ export default function HistoryPane(props: HistoryPaneProps) {
       for (let item of items) {
         let index = getHistoryItemIndex(item)
         nextSelectedItemIndex = Math.min(index, nextSelectedItemIndex)
-        await deleteItem(item)
+        await deleteItem(item, secure)
       }
 
       let lastIndex = getVisibleHistoryLength() - 1
@@ -1600,6 +1911,15 @@ export default function HistoryPane(props: HistoryPaneProps) {
     clearEntireHistory()
   }
 
+  async function handleUndoClearHistory() {
+    try {
+      await undoClearHistory()
+      setHistory(getHistoryItems())
+    } catch (error) {
+      console.error('❌ Failed to undo clear history:', error)
+    }
+  }
+
   function handleSearchQueryChange(searchQuery: string, skipSelection: boolean = false): void {
     setSearchQuery(searchQuery)
     setFilterQuery(searchQuery)
@@ -1629,7 +1949,10 @@ export default function HistoryPane(props: HistoryPaneProps) {
         hideAppWindow()
       }
     } else {
-      pasteItemInFrontApp(item.content, "", "", getImageFileName(item), getFilePath(item))
+      pasteItemInFrontApp(getFullContent(item), "", "", getImageFileName(item), getFilePath(item))
+      recordPastedItem(item)
+      markItemPasted(item)
+      await updateHistoryItem(item.id!, item)
     }
   }
 
@@ -1665,7 +1988,7 @@ export default function HistoryPane(props: HistoryPaneProps) {
   }
 
   async function handleEditHistoryItem(item: Clip) {
-    await updateHistoryItem(item.id!, item)
+    await updateEditedHistoryItem(item)
     setHistory([...getHistoryItems()])
   }
 
@@ -1700,15 +2023,50 @@ export default function HistoryPane(props: HistoryPaneProps) {
     setSelectedItemIndices(getSelectedHistoryItemIndices())
   }
 
-  (window as any).addClipboardData = addClipboardData;
-  (window as any).mergeClipboardData = mergeClipboardData;
+  (window as any).addClipboardData = instrumentCommand("addClipboardData", addClipboardData);
+  (window as any).mergeClipboardData = instrumentCommand("mergeClipboardData", mergeClipboardData);
   // Only expose copyToClipboardAfterMerge if it's available (native app mode)
   if (typeof copyToClipboardAfterMerge !== 'undefined') {
     (window as any).copyToClipboardAfterMerge = copyToClipboardAfterMerge;
   }
-  (window as any).clearHistory = clearHistory;
-  (window as any).activateApp = activateApp;
-  (window as any).pasteNextItemToActiveApp = pasteNextItemToActiveApp;
+  (window as any).clearHistory = instrumentCommand("clearHistory", (trigger?: ClearTrigger) => {
+    return trackHistoryClearing(clearHistory(trigger))
+  });
+  (window as any).isHistoryClearing = isHistoryClearing;
+  (window as any).startApiIfEnabled = startApiIfEnabled;
+  (window as any).syncNowInBackground = () => syncManager.syncNow();
+  (window as any).restartSharing = restartSharing;
+  (window as any).appLockDisabled = appLockDisabled;
+  (window as any).activateApp = instrumentCommand("activateApp", activateApp);
+  (window as any).pasteNextItemToActiveApp = instrumentCommand("pasteNextItemToActiveApp", pasteNextItemToActiveApp);
+  (window as any).pasteRecentItemToActiveApp = instrumentCommand("pasteRecentItemToActiveApp", pasteRecentItemToActiveApp);
+  (window as any).handleNotificationAction = instrumentCommand("handleNotificationAction", handleNotificationAction);
+  (window as any).pasteTrayItemToActiveApp = instrumentCommand("pasteTrayItemToActiveApp", pasteTrayItemToActiveApp);
+  (window as any).pasteRegisterToActiveApp = instrumentCommand("pasteRegisterToActiveApp", pasteRegisterToActiveApp);
+  (window as any).cycleProfile = instrumentCommand("cycleProfile", cycleProfile);
+  (window as any).shortcutUsed = recordShortcutUse;
+  (window as any).configFileChanged = reloadConfigFile;
+  // Allow test drivers to simulate clipboard changes without the native app.
+  if (isSimulatedClipboard()) {
+    (window as any).injectClipboardChange = injectClipboardChange;
+    (window as any).readSimulatedClipboard = readSimulatedClipboard;
+  }
+  (window as any).replayClipboardEvents = (path: string, speed: number = 1) => replayEvents(loadReplayScript(path), {speed});
+  (window as any).cancelReplay = cancelReplay;
+  (window as any).shutdownApp = shutdownTasks;
+  (window as any).isShutdownComplete = isShutdownComplete;
+  (window as any).handleApiRequest = instrumentCommand("handleApiRequest", handleApiRequest);
+  (window as any).handleDeepLink = instrumentCommand("handleDeepLink", handleDeepLink);
+  (window as any).receiveLanItem = instrumentCommand("receiveLanItem", receiveLanItem);
+  (window as any).addFindPasteboardText = instrumentCommand("addFindPasteboardText", addFindPasteboardText);
+  (window as any).lanItemSent = instrumentCommand("lanItemSent", lanItemSent);
+  (window as any).imageTextRecognized = instrumentCommand("imageTextRecognized", imageTextRecognized);
+  (window as any).userAuthenticated = instrumentCommand("userAuthenticated", userAuthenticated);
+  (window as any).pathSelected = pathSelected;
+
+  if (appLocked) {
+    return <LockScreen/>
+  }
 
   if (isHistoryEmpty()) {
     return (
@@ -1,7 +1,8 @@
 import '../app.css';
 import React, {useEffect, useState} from "react";
 import {Clip, ClipType, getFilePath, getHTML, getRTF} from "@/db";
-import {fileExists, formatDateTime, getHistoryItemById, toBase64Icon} from "@/data";
+import {fileExists, formatDateTime, getHistoryItemById, getItemLineage, toBase64Icon} from "@/data";
+import {getItemTitle} from "@/picker";
 import ItemTags from "@/app/ItemTags";
 import {getTags, Tag} from "@/tags";
 import {emitter} from "@/actions";
@@ -34,6 +35,9 @@ export default function ItemInfoPane(props: ItemInfoPaneProps) {
   const [numberOfCopies, setNumberOfCopies] = useState<number>(props.item.numberOfCopies)
   const [copyTime, setCopyTime] = useState<Date>(props.item.copyTime)
   const [tags, setTags] = useState<Tag[]>(getTags(props.item.tags))
+  const [note, setNote] = useState<string>(props.item.note ?? "")
+  // The items this item was derived from starting from the original one.
+  const [ancestors, setAncestors] = useState<Clip[]>([])
 
   function updateItem(item: Clip) {
     setType(item.type)
@@ -50,6 +54,8 @@ export default function ItemInfoPane(props: ItemInfoPaneProps) {
     setNumberOfCopies(item.numberOfCopies)
     setCopyTime(item.copyTime)
     setTags(getTags(item.tags))
+    setNote(item.note ?? "")
+    setAncestors(getItemLineage(item.id!).slice(0, -1))
   }
 
   useEffect(() => {
@@ -97,6 +103,10 @@ export default function ItemInfoPane(props: ItemInfoPaneProps) {
     return [t("app.itemInfoPane.unknown")]
   }
 
+  function handleShowAncestor(item: Clip) {
+    emitter.emit("ShowInHistory", item)
+  }
+
   function canShowImageSize() {
     return imageWidth > 0 && imageHeight > 0
   }
@@ -178,6 +188,35 @@ export default function ItemInfoPane(props: ItemInfoPaneProps) {
               </div>
             </div>
         }
+        {
+            note.length > 0 &&
+            <div className="flex w-full border-b border-b-preview-infoBorder pb-1">
+              <div className="flex-none text-preview-infoLabel font-semibold mr-4">{t("app.itemInfoPane.note")}</div>
+              <div className="flex-grow"></div>
+              <div className="flex-auto text-foreground text-end whitespace-pre-wrap break-words">{note}</div>
+            </div>
+        }
+        {
+            ancestors.length > 0 &&
+            <div className="flex w-full border-b border-b-preview-infoBorder pb-1">
+              <div className="flex-none text-preview-infoLabel font-semibold mr-4">{t("app.itemInfoPane.derivedFrom")}</div>
+              <div className="flex-grow"></div>
+              <div className="flex-auto text-foreground text-end break-words">
+                {
+                  ancestors.map((item, index) => {
+                    return (
+                        <span key={item.id}>
+                          <span className="cursor-pointer hover:underline"
+                                onClick={() => handleShowAncestor(item)}>{getItemTitle(item)}</span>
+                          {index < ancestors.length - 1 ?
+                              <span className="text-border px-1.5">›</span> : ""}
+                        </span>
+                    )
+                  })
+                }
+              </div>
+            </div>
+        }
         {
             isLink() &&
             <div className="flex w-full border-b border-b-preview-infoBorder pb-1">
@@ -0,0 +1,51 @@
+import '../app.css';
+import React, {useState} from "react";
+import {LockIcon} from "lucide-react";
+import {Button} from "@/components/ui/button";
+import {Input} from "@/components/ui/input";
+import {hasAppLockPassphrase, unlockApp} from "@/applock";
+import {useTranslation} from 'react-i18next';
+
+export default function LockScreen() {
+  const {t} = useTranslation()
+
+  const [passphrase, setPassphrase] = useState("")
+  const [wrongPassphrase, setWrongPassphrase] = useState(false)
+
+  async function handleUnlockWithPassphrase(e: React.FormEvent) {
+    e.preventDefault()
+    let unlocked = await unlockApp(passphrase)
+    setWrongPassphrase(!unlocked)
+    setPassphrase("")
+  }
+
+  return (
+      <div className="flex h-screen draggable">
+        <div className="flex flex-col text-center m-auto w-64 gap-3">
+          <LockIcon className="h-16 w-16 m-auto text-secondary-foreground"/>
+          <p className="text-center pt-4 pb-2 text-xl font-semibold text-foreground">
+            {t('app.lock.title')}
+          </p>
+          <Button variant="primary" onClick={() => unlockApp()}>
+            {t('app.lock.unlockWithTouchId')}
+          </Button>
+          {
+            hasAppLockPassphrase() &&
+              <form className="flex flex-col gap-2" onSubmit={handleUnlockWithPassphrase}>
+                <Input type="password"
+                       placeholder={t('app.lock.passphrase')}
+                       value={passphrase}
+                       onChange={e => setPassphrase(e.target.value)}/>
+                {
+                    wrongPassphrase &&
+                    <p className="text-sm text-destructive">{t('app.lock.wrongPassphrase')}</p>
+                }
+                <Button type="submit" variant="secondary" disabled={passphrase.length === 0}>
+                  {t('app.lock.unlock')}
+                </Button>
+              </form>
+          }
+        </div>
+      </div>
+  )
+}
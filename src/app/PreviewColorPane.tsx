@@ -5,6 +5,7 @@ import namer from 'color-namer';
 import {Clip} from "@/db";
 import {isShortcutMatch} from "@/lib/shortcuts";
 import {prefGetEditHistoryItemShortcut} from "@/pref";
+import {convertColorText, kColorFormats} from "@/lib/colors";
 
 type PreviewColorPaneProps = {
   item: Clip
@@ -78,6 +79,13 @@ export default function PreviewColorPane(props: PreviewColorPaneProps) {
           <div className={`h-48 w-48 rounded-full border-[6px] border-accent`} style={{backgroundColor: cssColor}}></div>
           <div className="mt-6 font-mono">{props.colorText}</div>
           <div className="mt-2 text-secondary-foreground">{colorName}</div>
+          <div className="mt-4 flex flex-col items-center font-mono text-sm text-secondary-foreground select-text">
+            {
+              kColorFormats.map(format => convertColorText(props.colorText, format))
+                  .filter(value => value && value !== props.colorText)
+                  .map(value => <div key={value}>{value}</div>)
+            }
+          </div>
         </div>
       </div>
   )
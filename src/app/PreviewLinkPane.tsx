@@ -1,22 +1,16 @@
 import '../app.css';
 import React, {useEffect, useState} from "react";
-import {
-  Clip,
-  deleteLinkPreviewDetails,
-  getLinkPreviewDetails,
-  LinkPreviewDetails,
-  saveLinkPreviewDetails
-} from "@/db";
+import {Clip, deleteLinkPreviewDetails, LinkPreviewDetails} from "@/db";
 import PreviewLinkCard from "@/app/PreviewLinkCard";
 import {getClipTypeFromText} from "@/lib/utils";
 import {isShortcutMatch} from "@/lib/shortcuts";
 import {prefGetEditHistoryItemShortcut} from "@/pref";
-
-type FetchRequestCallback = {
-  run: (success: boolean, title: string, description: string, imageFileName: string, faviconFileName: string) => void;
-}
-
-declare const fetchLinkPreviewDetails: (url: string, callback: FetchRequestCallback) => void
+import {
+  forgetLinkPreview,
+  getCachedLinkPreview,
+  getLinkPreview,
+  isLinkPreviewFetchingEnabled
+} from "@/linkpreview";
 
 type PreviewLinkPaneProps = {
   item: Clip
@@ -74,6 +68,7 @@ export default function PreviewLinkPane(props: PreviewLinkPaneProps) {
   useEffect(() => {
     if (props.updateLinkPreview) {
       let url = props.item.content
+      forgetLinkPreview(url)
       deleteLinkPreviewDetails(url).then(() => {
         loadLinkPreview()
       })
@@ -86,33 +81,20 @@ export default function PreviewLinkPane(props: PreviewLinkPaneProps) {
 
   function loadLinkPreview() {
     let url = props.item.content
-    getLinkPreviewDetails(url).then(details => {
+    let cached = getCachedLinkPreview(url)
+    if (cached) {
+      updateLinkPreviewDetails(cached)
+      return
+    }
+    setLoading(isLinkPreviewFetchingEnabled())
+    getLinkPreview(url).then(details => {
       if (details) {
         updateLinkPreviewDetails(details)
       } else {
-        setLoading(true)
-        const callbackInstance: FetchRequestCallback = {
-          run: function (success: boolean, title: string, description: string, imageFileName: string, faviconFileName: string) {
-            if (success) {
-              saveLinkPreviewDetails({
-                url: url,
-                title: title,
-                description: description,
-                imageFileName: imageFileName,
-                faviconFileName: faviconFileName
-              }).then(() => {
-                updateLinkPreviewDetails({
-                  url: url,
-                  title: title,
-                  description: description,
-                  imageFileName: imageFileName,
-                  faviconFileName: faviconFileName
-                })
-              });
-            }
-          }
-        }
-        fetchLinkPreviewDetails(url, callbackInstance)
+        setTitle("")
+        setDescription("")
+        setImageFileName("")
+        setLoading(false)
       }
     })
   }
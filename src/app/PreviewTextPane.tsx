@@ -5,7 +5,9 @@ import {getClipTypeFromText} from "@/lib/utils";
 import {isShortcutMatch} from "@/lib/shortcuts";
 import {prefGetEditHistoryItemShortcut} from "@/pref";
 import {emitter} from "@/actions";
-import {getHistoryItemById, TextType} from "@/data";
+import {getHistoryItemById, getItemPreview, isItemSensitive, revealItem, TextType} from "@/data";
+import {Button} from "@/components/ui/button";
+import {useTranslation} from 'react-i18next';
 
 type PreviewTextPaneProps = {
   item: Clip
@@ -15,9 +17,12 @@ type PreviewTextPaneProps = {
 }
 
 export default function PreviewTextPane(props: PreviewTextPaneProps) {
+  const {t} = useTranslation()
+
   // I need this state to keep caret position when editing the content.
   const [content, setContent] = useState(props.item.content)
   const [selectedTextType, setSelectedTextType] = useState<TextType>(TextType.Text)
+  const [revealed, setRevealed] = useState(false)
 
   useEffect(() => {
     if (props.editMode) {
@@ -92,6 +97,24 @@ export default function PreviewTextPane(props: PreviewTextPaneProps) {
   function updateItem() {
     setContent(props.item.content)
     setSelectedTextType(TextType.Text)
+    setRevealed(false)
+  }
+
+  function handleReveal() {
+    setContent(revealItem(props.item.id!))
+    setRevealed(true)
+  }
+
+  // The sensitive content is masked until it's revealed or edited.
+  if (isItemSensitive(props.item) && !revealed && !props.editMode) {
+    return (
+        <div className="flex flex-col h-full px-4 py-2 gap-4 font-mono text-sm">
+          <span>{getItemPreview(props.item)}</span>
+          <Button variant="secondary" className="w-fit" onClick={handleReveal}>
+            {t('preview.sensitive.reveal')}
+          </Button>
+        </div>
+    )
   }
 
   return (
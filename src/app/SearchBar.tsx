@@ -18,7 +18,12 @@ import PasteTransformationCommands from "@/app/PasteTransformationCommands";
 import {isFilterActive, LanguageCode} from "@/data";
 import FormatTextCommands from "@/app/FormatTextCommands";
 import OpenWithCommands from "@/app/OpenWithCommands";
+import SnippetCommands from "@/app/SnippetCommands";
+import CollectionCommands from "@/app/CollectionCommands";
+import DeviceCommands from "@/app/DeviceCommands";
+import TrashCommands from "@/app/TrashCommands";
 import {emitter} from "@/actions";
+import {isGuestMode} from "@/guest";
 import SortMenu from "@/app/SortMenu";
 import {useTranslation} from "react-i18next";
 
@@ -88,7 +93,10 @@ export default function SearchBar(props: SearchBarProps) {
   function handleAlwaysDisplayChange() {
     let display = !alwaysDisplay;
     setAlwaysDisplay(display)
-    prefSetAlwaysDisplay(display)
+    // The preferences stay unchanged in guest mode.
+    if (!isGuestMode()) {
+      prefSetAlwaysDisplay(display)
+    }
   }
 
   function getTrialBadgeText() {
@@ -219,6 +227,10 @@ export default function SearchBar(props: SearchBarProps) {
             <PasteTransformationCommands/>
             <FormatTextCommands/>
             <OpenWithCommands/>
+            <SnippetCommands/>
+            <CollectionCommands/>
+            <DeviceCommands/>
+            <TrashCommands/>
           </div>
           <div className={props.isPreviewVisible ? "hidden" : ""}>
             <Tooltip>
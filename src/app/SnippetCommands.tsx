@@ -0,0 +1,125 @@
+import '../app.css';
+
+import * as React from "react"
+import {useEffect, useState} from "react"
+import {VisuallyHidden} from "@radix-ui/react-visually-hidden";
+
+import {
+  CommandDialog,
+  CommandEmpty,
+  CommandGroup,
+  CommandInput,
+  CommandItem,
+  CommandList,
+} from "@/components/ui/command"
+import {DialogTitle} from "@/components/ui/dialog";
+import {Snippet} from "@/db";
+import {emitter} from "@/actions";
+import {useTranslation} from "react-i18next";
+import {FileTextIcon, RefreshCwIcon, TrashIcon} from "lucide-react";
+import {getFirstSelectedHistoryItem} from "@/data";
+import {getFullContent} from "@/blobs";
+import {editSnippet, listSnippets, pasteSnippet, removeSnippet, SnippetAction} from "@/snippets";
+
+export default function SnippetCommands() {
+  const {t} = useTranslation()
+
+  const [open, setOpen] = useState(false)
+  const [action, setAction] = useState<SnippetAction>(SnippetAction.Paste)
+  const [snippets, setSnippets] = useState<Snippet[]>([])
+
+  function handleKeyDown(e: React.KeyboardEvent) {
+    if (e.key === "Escape") {
+      handleOpenChange(false)
+      e.preventDefault()
+    }
+    e.stopPropagation()
+  }
+
+  useEffect(() => {
+    function handleAppWindowDidHide() {
+      handleOpenChange(false)
+    }
+
+    function handleShowSnippetCommands(action: SnippetAction) {
+      setTimeout(() => {
+        handleOpenChange(true, action)
+      }, 200);
+    }
+
+    emitter.on("NotifyAppWindowDidHide", handleAppWindowDidHide)
+    emitter.on("ShowSnippetCommands", handleShowSnippetCommands)
+    return () => {
+      emitter.off("NotifyAppWindowDidHide", handleAppWindowDidHide)
+      emitter.off("ShowSnippetCommands", handleShowSnippetCommands)
+    };
+  }, [])
+
+  function handleOpenChange(open: boolean, action: SnippetAction = SnippetAction.Paste) {
+    if (open) {
+      setAction(action)
+      listSnippets().then(setSnippets).catch(error => {
+        console.error('❌ Failed to load snippets:', error)
+      })
+    } else {
+      emitter.emit("FocusSearchInput")
+    }
+    setOpen(open)
+  }
+
+  async function handleSelectSnippet(snippet: Snippet) {
+    handleOpenChange(false)
+    try {
+      if (action === SnippetAction.Paste) {
+        await pasteSnippet(snippet.id!)
+      }
+      if (action === SnippetAction.Update) {
+        let item = getFirstSelectedHistoryItem()
+        if (item) {
+          await editSnippet(snippet.id!, snippet.name, getFullContent(item))
+        }
+      }
+      if (action === SnippetAction.Remove) {
+        await removeSnippet(snippet.id!)
+      }
+    } catch (error) {
+      console.error('❌ Failed to ' + action + ' snippet:', error)
+    }
+  }
+
+  function getSnippetIcon() {
+    if (action === SnippetAction.Update) {
+      return <RefreshCwIcon className="mr-2 h-5 w-5"/>
+    }
+    if (action === SnippetAction.Remove) {
+      return <TrashIcon className="mr-2 h-5 w-5 text-actions-danger"/>
+    }
+    return <FileTextIcon className="mr-2 h-5 w-5"/>
+  }
+
+  return (
+      <div className="" onKeyDown={handleKeyDown}>
+        <CommandDialog open={open} onOpenChange={handleOpenChange}>
+          <VisuallyHidden>
+            <DialogTitle></DialogTitle>
+          </VisuallyHidden>
+          <CommandInput placeholder={t("app.snippetCommands.searchPlaceholder")} autoFocus={true}/>
+          <div className="max-h-[70vh] overflow-y-auto mb-1.5">
+            <CommandList>
+              <CommandGroup heading={t("app.snippetCommands." + action)}>
+                {
+                  snippets.map(snippet => (
+                      <CommandItem key={snippet.id} onSelect={() => handleSelectSnippet(snippet)}>
+                        {getSnippetIcon()}
+                        <span>{snippet.name}</span>
+                      </CommandItem>
+                  ))
+                }
+              </CommandGroup>
+              <CommandEmpty>{t("app.snippetCommands.noSnippets")}</CommandEmpty>
+            </CommandList>
+          </div>
+        </CommandDialog>
+      </div>
+  )
+}
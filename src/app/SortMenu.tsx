@@ -87,6 +87,10 @@ export default function SortMenu() {
                                       onCheckedChange={() => handleSortTypeChange(SortHistoryType.Size)}>
               {t('sortMenu.size')}
             </DropdownMenuCheckboxItem>
+            <DropdownMenuCheckboxItem checked={sortType === SortHistoryType.Frecency}
+                                      onCheckedChange={() => handleSortTypeChange(SortHistoryType.Frecency)}>
+              {t('sortMenu.frecency')}
+            </DropdownMenuCheckboxItem>
             <DropdownMenuSeparator/>
             <DropdownMenuCheckboxItem checked={sortOrderReverse}
                                       onCheckedChange={handleSortOrderReverseChange}>
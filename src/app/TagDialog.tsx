@@ -8,11 +8,12 @@ import {
   DialogTitle,
 } from "@/components/ui/dialog"
 import {Button} from "@/components/ui/button";
-import TagIcon, {addTag, Tag, TagColor, updateTag} from "@/tags";
+import TagIcon, {addTag, normalizeTagName, renameTag, Tag, TagColor, updateTag} from "@/tags";
 import {Input} from "@/components/ui/input";
 import {RadioGroup, RadioGroupColorItem} from "@/components/ui/radio-group";
 import {emitter} from "@/actions";
-import {Clip} from "@/db";
+import {Clip, TagRule} from "@/db";
+import {allTagRules, createTagRule, dryRunTagRule, editTagRule, isValidPattern, removeTagRule} from "@/rules";
 import {updateHistoryItem} from "@/data";
 import {useTranslation} from "react-i18next";
 
@@ -29,11 +30,14 @@ export default function TagDialog(props: TagDialogProps) {
   const [visible, setVisible] = useState<boolean>(props.visible)
   const [tagName, setTagName] = useState<string>(props.tag ? props.tag.name : "")
   const [tagColor, setTagColor] = useState<TagColor>(props.tag ? props.tag.color as TagColor : TagColor.Blue)
+  const [pattern, setPattern] = useState<string>("")
+  const [matchCount, setMatchCount] = useState<number | undefined>(undefined)
 
   useEffect(() => {
     if (props.visible) {
       setTagName(props.tag ? props.tag.name : "")
       setTagColor(props.tag ? props.tag.color as TagColor : TagColor.Blue)
+      setPattern(findTagRule()?.pattern ?? "")
       // Find the input element by ID, focus it and select its content.
       setTimeout(() => {
         let input = document.getElementById("tagName") as HTMLInputElement
@@ -46,17 +50,64 @@ export default function TagDialog(props: TagDialogProps) {
     setVisible(props.visible)
   }, [props.visible, props.tag])
 
+  // Shows how many history items the auto-tagging pattern would tag.
+  useEffect(() => {
+    setMatchCount(undefined)
+    if (pattern.length === 0 || !isValidPattern(pattern, "i")) {
+      return
+    }
+    let canceled = false
+    let timer = setTimeout(() => {
+      dryRunTagRule(new TagRule("", pattern, "i", 0)).then(clips => {
+        if (!canceled) {
+          setMatchCount(clips.length)
+        }
+      }).catch(error => console.error('❌ Failed to test the tag rule:', error))
+    }, 300)
+    return () => {
+      canceled = true
+      clearTimeout(timer)
+    }
+  }, [pattern])
+
+  function findTagRule(): TagRule | undefined {
+    return props.tag ? allTagRules().find(rule => rule.tagId === props.tag!.id) : undefined
+  }
+
+  async function saveTagRule(tag: Tag) {
+    let rule = props.tag ? findTagRule() : undefined
+    if (rule && pattern.length === 0) {
+      await removeTagRule(rule.id!)
+    } else if (rule) {
+      await editTagRule(rule.id!, {name: tag.name, pattern})
+    } else if (pattern.length > 0) {
+      await createTagRule(tag.name, pattern, "i", tag.id)
+    }
+  }
+
+  function isPatternValid() {
+    return pattern.length === 0 || isValidPattern(pattern, "i")
+  }
+
   function handleCancel() {
     props.onClose()
   }
 
   async function handleSave() {
+    let name = normalizeTagName(tagName)
+    if (name.length === 0 || !isPatternValid()) {
+      return
+    }
     if (props.tag) {
-      updateTag(props.tag.id, tagName, tagColor)
+      // Renaming the namespace moves the nested tags as well.
+      renameTag(props.tag.id, name)
+      updateTag(props.tag.id, name, tagColor)
+      await saveTagRule(props.tag)
       emitter.emit("UpdateTagById", props.tag.id)
     } else {
-      let tag = new Tag(tagName, tagColor)
+      let tag = new Tag(name, tagColor)
       addTag(tag)
+      await saveTagRule(tag)
       if (props.item) {
         if (props.item.tags) {
           props.item.tags = [...props.item.tags, tag.id]
@@ -77,7 +128,7 @@ export default function TagDialog(props: TagDialogProps) {
 
   async function handleKeyDown(e: React.KeyboardEvent) {
     e.stopPropagation()
-    if (e.key === "Enter" && tagName.length > 0) {
+    if (e.key === "Enter" && tagName.length > 0 && isPatternValid()) {
       await handleSave()
     }
     if (e.key === "Escape") {
@@ -85,6 +136,20 @@ export default function TagDialog(props: TagDialogProps) {
     }
   }
 
+  function handlePatternChange(e: React.ChangeEvent<HTMLInputElement>) {
+    setPattern(e.target.value)
+  }
+
+  function getPatternHint(): string {
+    if (!isPatternValid()) {
+      return t('tagDialog.invalidPattern')
+    }
+    if (matchCount !== undefined) {
+      return t('tagDialog.matchingItems', {count: matchCount})
+    }
+    return t('tagDialog.patternDescription')
+  }
+
   function handleColorChange(value: TagColor) {
     setTagColor(value)
   }
@@ -128,9 +193,20 @@ export default function TagDialog(props: TagDialogProps) {
             <RadioGroupColorItem value={TagColor.Purple} id="r9" className="w-5 h-5 rounded-full"
                                  style={{backgroundColor: TagColor.Purple}}/>
           </RadioGroup>
+          <div className="flex flex-col -mt-2 mb-4 space-y-1">
+            <Input id="tagPattern"
+                   value={pattern}
+                   onChange={handlePatternChange}
+                   onKeyDown={handleKeyDown}
+                   placeholder={t('tagDialog.pattern')}
+                   className="text-base font-mono placeholder:text-settings-inputPlaceholder border-none bg-secondary-solid"/>
+            <span className={`text-xs px-1 ${isPatternValid() ? "text-secondary-foreground" : "text-actions-danger"}`}>
+              {getPatternHint()}
+            </span>
+          </div>
           <DialogFooter className="mx-auto">
             <Button variant="secondary" onClick={handleCancel}>{t('tagDialog.cancel')}</Button>
-            <Button variant="primary" onClick={handleSave} disabled={tagName.length === 0}>
+            <Button variant="primary" onClick={handleSave} disabled={tagName.length === 0 || !isPatternValid()}>
               {
                 props.tag ? t('tagDialog.save') : t('tagDialog.create')
               }
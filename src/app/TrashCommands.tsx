@@ -0,0 +1,133 @@
+import '../app.css';
+
+import * as React from "react"
+import {useEffect, useState} from "react"
+import {VisuallyHidden} from "@radix-ui/react-visually-hidden";
+
+import {
+  CommandDialog,
+  CommandEmpty,
+  CommandGroup,
+  CommandInput,
+  CommandItem,
+  CommandList,
+  CommandSeparator,
+} from "@/components/ui/command"
+import {DialogTitle} from "@/components/ui/dialog";
+import {Clip, ClipType} from "@/db";
+import {emitter} from "@/actions";
+import {useTranslation} from "react-i18next";
+import {TrashIcon, Undo2Icon} from "lucide-react";
+import {getItemPreview, getTrashItems} from "@/data";
+import {emptyTrash, restoreItem} from "@/trash";
+
+// The number of characters of the item content shown in the list.
+const kMaxLabelLength = 100
+
+export default function TrashCommands() {
+  const {t} = useTranslation()
+
+  const [open, setOpen] = useState(false)
+  const [items, setItems] = useState<Clip[]>([])
+
+  function handleKeyDown(e: React.KeyboardEvent) {
+    if (e.key === "Escape") {
+      handleOpenChange(false)
+      e.preventDefault()
+    }
+    e.stopPropagation()
+  }
+
+  useEffect(() => {
+    function handleAppWindowDidHide() {
+      handleOpenChange(false)
+    }
+
+    function handleShowTrashCommands() {
+      setTimeout(() => {
+        handleOpenChange(true)
+      }, 200);
+    }
+
+    emitter.on("NotifyAppWindowDidHide", handleAppWindowDidHide)
+    emitter.on("ShowTrashCommands", handleShowTrashCommands)
+    return () => {
+      emitter.off("NotifyAppWindowDidHide", handleAppWindowDidHide)
+      emitter.off("ShowTrashCommands", handleShowTrashCommands)
+    };
+  }, [])
+
+  function handleOpenChange(open: boolean) {
+    if (open) {
+      setItems(getTrashItems())
+    } else {
+      emitter.emit("FocusSearchInput")
+    }
+    setOpen(open)
+  }
+
+  function getItemLabel(item: Clip): string {
+    let label = item.name || getItemPreview(item).trim()
+    if (!label) {
+      return t("app.itemInfoPane." + ClipType[item.type].toLowerCase())
+    }
+    return label.length > kMaxLabelLength ? label.substring(0, kMaxLabelLength) + "…" : label
+  }
+
+  function handleRestoreItem(item: Clip) {
+    handleOpenChange(false)
+    restoreItem(item.id!).catch(error => {
+      console.error('❌ Failed to restore item:', error)
+    })
+  }
+
+  function handleEmptyTrash() {
+    handleOpenChange(false)
+    emptyTrash().then(count => {
+      if (count > 0) {
+        emitter.emit("HistoryChanged")
+      }
+    }).catch(error => {
+      console.error('❌ Failed to empty trash:', error)
+    })
+  }
+
+  return (
+      <div className="" onKeyDown={handleKeyDown}>
+        <CommandDialog open={open} onOpenChange={handleOpenChange}>
+          <VisuallyHidden>
+            <DialogTitle></DialogTitle>
+          </VisuallyHidden>
+          <CommandInput placeholder={t("app.trashCommands.searchPlaceholder")} autoFocus={true}/>
+          <div className="max-h-[70vh] overflow-y-auto mb-1.5">
+            <CommandList>
+              <CommandGroup heading={t("app.trashCommands.title")}>
+                {
+                  items.map(item => (
+                      <CommandItem key={item.id} value={item.id + " " + getItemLabel(item)}
+                                   onSelect={() => handleRestoreItem(item)}>
+                        <Undo2Icon className="mr-2 h-5 w-5 shrink-0"/>
+                        <span className="truncate">{getItemLabel(item)}</span>
+                      </CommandItem>
+                  ))
+                }
+              </CommandGroup>
+              {
+                  items.length > 0 &&
+                  <>
+                    <CommandSeparator/>
+                    <CommandGroup>
+                      <CommandItem onSelect={handleEmptyTrash}>
+                        <TrashIcon className="mr-2 h-5 w-5 text-actions-danger"/>
+                        <span className="text-actions-danger">{t("app.trashCommands.emptyTrash")}</span>
+                      </CommandItem>
+                    </CommandGroup>
+                  </>
+              }
+              <CommandEmpty>{t("app.trashCommands.noItems")}</CommandEmpty>
+            </CommandList>
+          </div>
+        </CommandDialog>
+      </div>
+  )
+}
@@ -12,6 +12,9 @@ import {
 import './index.css'
 import './i18n'
 import {ThemeProvider} from "@/app/ThemeProvider";
+import {installLogger} from "@/logger";
+
+installLogger()
 
 const router = createBrowserRouter([
   {
@@ -0,0 +1,127 @@
+import i18n from "i18next";
+import {emitter} from "@/actions";
+import {hashPassphrase, PassphraseHash, verifyPassphrase} from "@/lib/crypto";
+import {prefGetAutoLockMinutes, prefShouldAppLockEnabled} from "@/pref";
+import {systemManager} from "@/system";
+
+declare const authenticateUser: (reason: string) => void;
+
+export class AppLockedError extends Error {
+  constructor() {
+    super('ClipBook is locked')
+    this.name = "AppLockedError"
+  }
+}
+
+// The app starts locked if the lock is enabled.
+let locked = prefShouldAppLockEnabled()
+let autoLockStarted = false
+let pendingAuthentication: ((success: boolean) => void) | undefined
+
+export function isAppLocked(): boolean {
+  return locked
+}
+
+// Throws an error if the history must not be accessed until the app is
+// unlocked.
+export function requireUnlocked() {
+  if (isAppLocked()) {
+    throw new AppLockedError()
+  }
+}
+
+function setLocked(value: boolean) {
+  if (locked !== value) {
+    locked = value
+    emitter.emit("AppLockChanged", value)
+  }
+}
+
+export function lockApp() {
+  if (prefShouldAppLockEnabled()) {
+    setLocked(true)
+  }
+}
+
+// Called by the app when the lock is turned off in the settings after the
+// user has authenticated there.
+export function appLockDisabled() {
+  if (!prefShouldAppLockEnabled()) {
+    setLocked(false)
+  }
+}
+
+function getPassphraseHash(): PassphraseHash | undefined {
+  let value = localStorage.getItem("appLockPassphrase")
+  return value ? JSON.parse(value) : undefined
+}
+
+export function hasAppLockPassphrase(): boolean {
+  return getPassphraseHash() !== undefined
+}
+
+export async function setAppLockPassphrase(passphrase: string) {
+  if (passphrase.length === 0) {
+    localStorage.removeItem("appLockPassphrase")
+    return
+  }
+  localStorage.setItem("appLockPassphrase", JSON.stringify(await hashPassphrase(passphrase)))
+}
+
+function authenticateWithSystem(): Promise<boolean> {
+  if (typeof authenticateUser === 'undefined') {
+    return Promise.resolve(false)
+  }
+  // Only one authentication prompt can be shown at a time.
+  pendingAuthentication?.(false)
+  return new Promise(resolve => {
+    pendingAuthentication = resolve
+    authenticateUser(i18n.t("app.appLock.authenticationReason"))
+  })
+}
+
+// Called by the app when the Touch ID or system password prompt is closed.
+export function userAuthenticated(success: boolean) {
+  let resolve = pendingAuthentication
+  pendingAuthentication = undefined
+  resolve?.(success)
+}
+
+// Checks the passphrase or, if no passphrase is given, asks the user to
+// authenticate with Touch ID or the system password.
+export async function verifyUser(passphrase?: string): Promise<boolean> {
+  if (passphrase !== undefined) {
+    let stored = getPassphraseHash()
+    return stored !== undefined && await verifyPassphrase(passphrase, stored)
+  }
+  return authenticateWithSystem()
+}
+
+// Unlocks the app with the passphrase or, if no passphrase is given, with
+// Touch ID or the system password.
+export async function unlockApp(passphrase?: string): Promise<boolean> {
+  if (!isAppLocked()) {
+    return true
+  }
+  let unlocked = await verifyUser(passphrase)
+  if (unlocked) {
+    systemManager.recordActivity()
+    setLocked(false)
+  }
+  return unlocked
+}
+
+// Locks the app after `app_lock.idle_minutes` minutes without user activity.
+export function startAutoLock() {
+  if (autoLockStarted) {
+    return
+  }
+  autoLockStarted = true
+  systemManager.start()
+  systemManager.onIdle(idleMs => {
+    let minutes = prefGetAutoLockMinutes()
+    if (minutes > 0 && idleMs >= minutes * 60 * 1000) {
+      lockApp()
+    }
+  })
+}
@@ -0,0 +1,93 @@
+import {
+  addAppRule,
+  AppRule,
+  AppRuleAction,
+  Clip,
+  deleteAppRule,
+  getAllAppRules,
+  getSourceAppBundleId,
+  updateAppRule
+} from "@/db";
+import {findTagById} from "@/tags";
+
+let rules: AppRule[] = []
+
+export async function loadAppRules() {
+  rules = await getAllAppRules()
+}
+
+export function allAppRules(): AppRule[] {
+  return rules
+}
+
+function validate(rule: AppRule) {
+  if (rule.bundleId.trim().length === 0) {
+    throw new Error('The bundle identifier of the app rule cannot be empty')
+  }
+  if (!Object.values(AppRuleAction).includes(rule.action)) {
+    throw new Error(`Unknown app rule action: ${rule.action}`)
+  }
+  if (rule.action === AppRuleAction.Tag && (rule.tagId === undefined || !findTagById(rule.tagId))) {
+    throw new Error(`Tag not found: ${rule.tagId}`)
+  }
+}
+
+export async function createAppRule(bundleId: string, action: AppRuleAction, tagId?: number): Promise<AppRule> {
+  let rule = new AppRule(bundleId, action, action === AppRuleAction.Tag ? tagId : undefined)
+  validate(rule)
+  rule.id = await addAppRule(rule)
+  rules.push(rule)
+  return rule
+}
+
+export async function editAppRule(id: number, changes: Partial<AppRule>) {
+  let rule = rules.find(rule => rule.id === id)
+  if (!rule) {
+    return
+  }
+  validate({...rule, ...changes})
+  Object.assign(rule, changes)
+  await updateAppRule(id, changes)
+}
+
+export async function setAppRuleEnabled(id: number, enabled: boolean) {
+  await editAppRule(id, {enabled})
+}
+
+export async function removeAppRule(id: number) {
+  rules = rules.filter(rule => rule.id !== id)
+  await deleteAppRule(id)
+}
+
+function findRules(bundleId: string | undefined, action: AppRuleAction): AppRule[] {
+  if (!bundleId) {
+    return []
+  }
+  return rules.filter(rule => rule.enabled && rule.bundleId === bundleId && rule.action === action)
+}
+
+// Returns false if the content copied in the app must not be saved to
+// the history.
+export function shouldRecordFromApp(sourceAppPath: string): boolean {
+  return findRules(getSourceAppBundleId(sourceAppPath), AppRuleAction.Ignore).length === 0
+}
+
+// Applies the rules of the source app to the captured item before it's saved.
+export function applyAppRules(clip: Clip) {
+  let bundleId = clip.sourceAppBundleId
+  if (findRules(bundleId, AppRuleAction.PlainText).length > 0) {
+    clip.rtf = ""
+    clip.html = ""
+  }
+  if (findRules(bundleId, AppRuleAction.Favorite).length > 0) {
+    clip.favorite = true
+  }
+  for (const rule of findRules(bundleId, AppRuleAction.Tag)) {
+    if (!clip.tags) {
+      clip.tags = []
+    }
+    if (rule.tagId !== undefined && !clip.tags.includes(rule.tagId)) {
+      clip.tags.push(rule.tagId)
+    }
+  }
+}
@@ -0,0 +1,69 @@
+import {addArchivedClips, Clip, deleteArchivedClip, getArchivedClips} from "@/db";
+import {isItemPinned, itemHasText, removeHistoryItem, returnHistoryItem, searchHistory} from "@/data";
+import {emitter} from "@/actions";
+
+const kDayMs = 24 * 60 * 60 * 1000
+
+// The archived items are kept in memory, so they can be searched as quickly
+// as the history.
+let archive: Clip[] = []
+let archiving = false
+
+export async function loadArchive() {
+  archive = await getArchivedClips()
+}
+
+// Moves the items copied more than the given number of days ago to the
+// archive. The favorite and pinned items stay in the history. Returns the
+// number of archived items.
+export async function archiveItemsOlderThan(days: number): Promise<number> {
+  if (archiving) {
+    throw new Error('Archiving is already in progress')
+  }
+  archiving = true
+  try {
+    let now = new Date()
+    let cutoff = now.getTime() - Math.max(0, days) * kDayMs
+    let items = searchHistory("").filter(item => !item.favorite && !isItemPinned(item, now) &&
+        new Date(item.copyTime).getTime() < cutoff)
+    if (items.length === 0) {
+      return 0
+    }
+    let archived = items.map(item => ({...item, archivedAt: now}))
+    await addArchivedClips(archived)
+    for (const item of items) {
+      await removeHistoryItem(item)
+    }
+    archive = archive.concat(archived)
+    emitter.emit("HistoryChanged")
+    console.log(`✅ Archived ${items.length} items older than ${days} days`)
+    return items.length
+  } finally {
+    archiving = false
+  }
+}
+
+// Returns the archived items matching the query, the most recently copied
+// first.
+export function searchArchive(query: string): Clip[] {
+  let items = query.length > 0 ? archive.filter(item => itemHasText(query, item)) : [...archive]
+  return items.sort((a, b) => new Date(b.copyTime).getTime() - new Date(a.copyTime).getTime())
+}
+
+export function getArchivedItemsCount(): number {
+  return archive.length
+}
+
+// Moves the archived item back to the history.
+export async function unarchiveItem(id: number): Promise<Clip> {
+  let index = archive.findIndex(item => item.id === id)
+  if (index === -1) {
+    throw new Error(`Archived item not found: ${id}`)
+  }
+  let item = archive[index]
+  item.archivedAt = undefined
+  await returnHistoryItem(item)
+  await deleteArchivedClip(id)
+  archive.splice(index, 1)
+  return item
+}
@@ -0,0 +1,82 @@
+import {addAuditLogEntry, AuditLogEntry} from "@/db";
+import {prefGetAutoClearIntervalHours} from "@/pref";
+
+// The events that clear the history automatically.
+export enum ClearTrigger {
+  Interval = "interval",
+  Logout = "logout",
+  Quit = "quit",
+}
+
+// How often to check whether the history should be cleared.
+const kCheckIntervalMs = 60 * 1000
+
+let schedulerTimer: ReturnType<typeof setInterval> | undefined
+let lastCheckTime = 0
+let pendingClears = 0
+
+function getLastClearTime(): number {
+  return parseInt(localStorage.getItem("lastAutoClearTime") || "0")
+}
+
+function isClearDue(): boolean {
+  let intervalHours = prefGetAutoClearIntervalHours()
+  if (intervalHours <= 0) {
+    return false
+  }
+  return Date.now() - getLastClearTime() >= intervalHours * 60 * 60 * 1000
+}
+
+// Records the automatic clearing of the history in the audit log.
+export async function recordHistoryCleared(trigger: ClearTrigger, removedItems: number) {
+  localStorage.setItem("lastAutoClearTime", Date.now().toString())
+  try {
+    await addAuditLogEntry(new AuditLogEntry("history.clear",
+        JSON.stringify({trigger, removedItems})))
+  } catch (error) {
+    console.error('❌ Failed to write audit log entry:', error);
+  }
+}
+
+// Tracks the clearing of the history, so that the app can wait until it's
+// done before it quits or the user logs out.
+export function trackHistoryClearing(clearing: Promise<void>): Promise<void> {
+  pendingClears++
+  return clearing.finally(() => pendingClears--)
+}
+
+export function isHistoryClearing(): boolean {
+  return pendingClears > 0
+}
+
+// Starts the scheduler that clears the non-favorite history every
+// `auto_clear.interval_hours` hours.
+export function startAutoClearScheduler(clearHistory: (trigger: ClearTrigger) => Promise<void>) {
+  if (schedulerTimer) {
+    return
+  }
+  // Start counting the interval from the first launch.
+  if (!localStorage.getItem("lastAutoClearTime")) {
+    localStorage.setItem("lastAutoClearTime", Date.now().toString())
+  }
+  lastCheckTime = Date.now()
+  schedulerTimer = setInterval(() => {
+    lastCheckTime = Date.now()
+    if (isClearDue()) {
+      clearHistory(ClearTrigger.Interval).catch(() => {})
+    }
+  }, kCheckIntervalMs)
+}
+
+// Returns false if the scheduler has stopped checking whether the history
+// should be cleared.
+export function isAutoClearSchedulerAlive(): boolean {
+  return schedulerTimer !== undefined && Date.now() - lastCheckTime < 2 * kCheckIntervalMs
+}
+
+export function stopAutoClearScheduler() {
+  if (schedulerTimer) {
+    clearInterval(schedulerTimer)
+    schedulerTimer = undefined
+  }
+}
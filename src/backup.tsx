@@ -0,0 +1,234 @@
+import {BackupData, Clip, exportAllData, kSchemaVersion} from "@/db";
+import {hashString} from "@/lib/hash";
+import {emitter} from "@/actions";
+import {prefGetBackupIntervalHours, prefGetMaxBackups} from "@/pref";
+import {Capability, requireCapability} from "@/capabilities";
+
+declare const getBackupsDir: () => string;
+declare const getBackupFiles: () => string;
+declare const deleteBackupFile: (fileName: string) => void;
+declare const writeTextFile: (filePath: string, content: string) => boolean;
+declare const readTextFile: (filePath: string) => string;
+declare const secureDeleteFile: (filePath: string) => void;
+
+// How often the scheduler checks whether a backup is due.
+const kCheckIntervalMs = 10 * 60 * 1000
+// The number of clips to serialize before emitting a progress event.
+const kProgressStep = 500
+
+// The manifest written next to every backup file.
+export interface BackupManifest {
+  schemaVersion: number;
+  backupVersion: string;
+  createdAt: string;
+  incremental: boolean;
+  rowCounts: {
+    clips: number;
+    linkPreviews: number;
+  };
+  // The hash of the content of the backup file.
+  contentHash: string;
+}
+
+let schedulerTimer: ReturnType<typeof setInterval> | undefined
+let lastCheckTime = 0
+let backupInProgress = false
+
+function getLastBackupTime(): Date | undefined {
+  let value = localStorage.getItem("lastBackupTime")
+  return value ? new Date(value) : undefined
+}
+
+function setLastBackupTime(time: Date) {
+  localStorage.setItem("lastBackupTime", time.toISOString())
+}
+
+export function getManifestFileName(backupFileName: string): string {
+  return backupFileName.replace(/\.json$/, "") + ".manifest.json"
+}
+
+function isManifestFile(fileName: string): boolean {
+  return fileName.endsWith(".manifest.json")
+}
+
+export function getBackupFileNames(): string[] {
+  if (typeof getBackupFiles === 'undefined') return []
+  let files = getBackupFiles()
+  return files ? files.split("*").filter(fileName => !isManifestFile(fileName)) : []
+}
+
+function isFullBackup(fileName: string): boolean {
+  return fileName.endsWith("-full.json")
+}
+
+// The backups created by the scheduler. The backups made before the database
+// migrations are kept until the user deletes them.
+function getScheduledBackupFileNames(): string[] {
+  return getBackupFileNames().filter(fileName => isFullBackup(fileName) || fileName.endsWith("-incremental.json"))
+}
+
+// An incremental backup requires a full backup to restore from. Make sure
+// at least one full backup survives the rotation.
+function shouldCreateFullBackup(maxBackups: number): boolean {
+  if (!getLastBackupTime() || maxBackups <= 1) {
+    return true
+  }
+  let survivors = getScheduledBackupFileNames().slice(-(maxBackups - 1))
+  return !survivors.some(isFullBackup)
+}
+
+function rotateBackups(maxBackups: number) {
+  let fileNames = getScheduledBackupFileNames()
+  for (let i = 0; i < fileNames.length - maxBackups; i++) {
+    deleteBackupFile(fileNames[i])
+    deleteBackupFile(getManifestFileName(fileNames[i]))
+  }
+}
+
+function serializeBackup(data: BackupData): string {
+  let total = data.clips.length
+  let clips: string[] = []
+  for (let i = 0; i < total; i++) {
+    clips.push(JSON.stringify(data.clips[i]))
+    if ((i + 1) % kProgressStep === 0) {
+      emitter.emit("BackupProgress", {processed: i + 1, total})
+    }
+  }
+  emitter.emit("BackupProgress", {processed: total, total})
+  let json = JSON.stringify({...data, clips: undefined})
+  return json.slice(0, -1) + `,"clips":[${clips.join(",")}]}`
+}
+
+// Creates a backup in the backups directory. Only the clips copied or changed
+// after the last backup are included unless a full backup is required.
+export async function createBackup(): Promise<string> {
+  requireCapability(Capability.Backups)
+  if (backupInProgress) {
+    throw new Error('Backup is already in progress')
+  }
+  backupInProgress = true
+  try {
+    const maxBackups = Math.max(1, prefGetMaxBackups())
+    const full = shouldCreateFullBackup(maxBackups)
+    const lastBackupTime = getLastBackupTime()
+    const backupTime = new Date()
+
+    let data = await exportAllData()
+    if (!full && lastBackupTime) {
+      data.clips = data.clips.filter((clip: Clip) => new Date(clip.copyTime) > lastBackupTime ||
+          (clip.modifiedAt !== undefined && new Date(clip.modifiedAt) > lastBackupTime))
+      data.since = lastBackupTime.toISOString()
+    }
+
+    const timestamp = backupTime.toISOString().replace(/[:.]/g, '-').slice(0, -5)
+    const fileName = `clipbook-backup-${timestamp}-${full ? "full" : "incremental"}.json`
+    const content = serializeBackup(data)
+    if (!writeTextFile(getBackupsDir() + "/" + fileName, content)) {
+      throw new Error(`Failed to write backup file: ${fileName}`)
+    }
+    const manifest: BackupManifest = {
+      schemaVersion: kSchemaVersion,
+      backupVersion: data.version,
+      createdAt: backupTime.toISOString(),
+      incremental: !full,
+      rowCounts: {clips: data.clips.length, linkPreviews: data.linkPreviews.length},
+      contentHash: hashString(content),
+    }
+    const manifestFileName = getManifestFileName(fileName)
+    if (!writeTextFile(getBackupsDir() + "/" + manifestFileName, JSON.stringify(manifest, null, 2))) {
+      throw new Error(`Failed to write backup manifest: ${manifestFileName}`)
+    }
+    setLastBackupTime(backupTime)
+    rotateBackups(maxBackups)
+
+    emitter.emit("BackupCompleted", {fileName, incremental: !full, clips: data.clips.length})
+    console.log(`✅ Created ${full ? "full" : "incremental"} backup ${fileName} with ${data.clips.length} clips`)
+    return fileName
+  } catch (error) {
+    console.error('❌ Backup failed:', error)
+    throw error
+  } finally {
+    backupInProgress = false
+  }
+}
+
+// Removes the clip from all the backups including the ones made before the
+// database migrations, so a securely deleted item can't be restored. The old
+// backup file is overwritten before the new content is written.
+export function purgeClipFromBackups(id: number) {
+  if (typeof getBackupsDir === 'undefined' || typeof readTextFile === 'undefined') {
+    return
+  }
+  for (const fileName of getBackupFileNames()) {
+    const filePath = getBackupsDir() + "/" + fileName
+    let data: BackupData
+    try {
+      data = JSON.parse(readTextFile(filePath))
+    } catch (error) {
+      console.error(`❌ Failed to read backup file ${fileName}:`, error)
+      continue
+    }
+    if (!Array.isArray(data.clips) || !data.clips.some((clip: Clip) => clip.id === id)) {
+      continue
+    }
+    data.clips = data.clips.filter((clip: Clip) => clip.id !== id)
+    const content = JSON.stringify(data)
+    secureDeleteFile(filePath)
+    if (!writeTextFile(filePath, content)) {
+      console.error(`❌ Failed to write backup file: ${fileName}`)
+      continue
+    }
+    const manifestPath = getBackupsDir() + "/" + getManifestFileName(fileName)
+    try {
+      const manifest: BackupManifest = JSON.parse(readTextFile(manifestPath))
+      manifest.rowCounts.clips = data.clips.length
+      manifest.contentHash = hashString(content)
+      writeTextFile(manifestPath, JSON.stringify(manifest, null, 2))
+    } catch (error) {
+      // The backups made before the database migrations have no manifest.
+    }
+  }
+}
+
+function isBackupDue(): boolean {
+  const intervalHours = prefGetBackupIntervalHours()
+  if (intervalHours <= 0) {
+    return false
+  }
+  const lastBackupTime = getLastBackupTime()
+  if (!lastBackupTime) {
+    return true
+  }
+  return Date.now() - lastBackupTime.getTime() >= intervalHours * 60 * 60 * 1000
+}
+
+function checkBackup() {
+  lastCheckTime = Date.now()
+  if (!backupInProgress && isBackupDue()) {
+    createBackup().catch(() => {})
+  }
+}
+
+// Starts the scheduler that creates backups every `backup.interval_hours`.
+export function startBackupScheduler() {
+  if (schedulerTimer || typeof getBackupsDir === 'undefined') {
+    return
+  }
+  checkBackup()
+  schedulerTimer = setInterval(checkBackup, kCheckIntervalMs)
+}
+
+// Returns false if the scheduler has stopped checking whether a backup is due.
+export function isBackupSchedulerAlive(): boolean {
+  if (typeof getBackupsDir === 'undefined') {
+    return true
+  }
+  return schedulerTimer !== undefined && Date.now() - lastCheckTime < 2 * kCheckIntervalMs
+}
+
+export function stopBackupScheduler() {
+  if (schedulerTimer) {
+    clearInterval(schedulerTimer)
+    schedulerTimer = undefined
+  }
+}
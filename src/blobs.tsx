@@ -0,0 +1,242 @@
+import {Clip, flushPendingClips, getHistoryStore} from "@/db";
+import {emitter} from "@/actions";
+
+declare const getBlobsDir: () => string;
+declare const readTextFile: (filePath: string) => string;
+declare const isFileExists: (filePath: string) => boolean;
+declare const writeTextFile: (filePath: string, content: string) => boolean;
+declare const getFilesInDir: (dirPath: string, extension: string) => string;
+declare const deleteFile: (filePath: string) => void;
+declare const secureDeleteFile: (filePath: string) => void;
+
+// The text fields larger than this are stored in the blob store instead of
+// the database.
+const kBlobThreshold = 256 * 1024
+const kBlobExtension = ".blob"
+// How long to wait after the items are deleted before deleting their blobs.
+const kGarbageCollectionDelayMs = 60 * 1000
+
+// The text fields of a clip that can be moved to the blob store and the
+// fields that keep the references to their blobs.
+const kBlobFields = [
+  {field: "content", ref: "contentBlob"},
+  {field: "rtf", ref: "rtfBlob"},
+  {field: "html", ref: "htmlBlob"},
+] as const
+
+// The full content blobs of the truncated items that aren't stored in the
+// database yet.
+let unstoredBlobs = new Set<string>()
+let blobLock: Promise<unknown> = Promise.resolve()
+
+function isSupported(): boolean {
+  return typeof getBlobsDir !== 'undefined'
+}
+
+async function sha256(text: string): Promise<string> {
+  let digest = await crypto.subtle.digest("SHA-256", new TextEncoder().encode(text))
+  return Array.from(new Uint8Array(digest), byte => byte.toString(16).padStart(2, "0")).join("")
+}
+
+function blobPath(hash: string): string {
+  return getBlobsDir() + "/" + hash + kBlobExtension
+}
+
+// Stores the text in a file named by the hash of its content, so the same
+// content is stored only once. Returns the hash.
+async function putBlob(text: string): Promise<string> {
+  let hash = await sha256(text)
+  let path = blobPath(hash)
+  if (!isFileExists(path) && !writeTextFile(path, text)) {
+    throw new Error(`Failed to write blob ${hash}`)
+  }
+  return hash
+}
+
+function getBlob(hash: string): string {
+  return readTextFile(blobPath(hash))
+}
+
+// Runs the function after the previously started ones complete. The clips
+// are written together with their blobs under the lock, so the garbage
+// collector never sees a blob before the clip that references it.
+export function withBlobLock<T>(fn: () => Promise<T>): Promise<T> {
+  let result = blobLock.then(fn)
+  blobLock = result.catch(() => undefined)
+  return result
+}
+
+// Stores the full text of the truncated item. Returns the hash of the blob.
+// The blob is kept by the garbage collector until the item is stored.
+export async function putFullContent(text: string): Promise<string> {
+  if (!isSupported()) {
+    throw new Error("The blob store is not available")
+  }
+  return withBlobLock(async () => {
+    let hash = await putBlob(text)
+    unstoredBlobs.add(hash)
+    return hash
+  })
+}
+
+// Called after the clips are stored, so their full content blobs are
+// referenced by the database.
+export function markBlobsStored(clips: Clip[]) {
+  for (const clip of clips) {
+    if (clip.fullContentBlob) {
+      unstoredBlobs.delete(clip.fullContentBlob)
+    }
+  }
+}
+
+// Returns the full text of the item, which is loaded from the blob store if
+// the content was truncated.
+export function getFullContent(item: Clip): string {
+  if (item.fullContentBlob && isSupported()) {
+    return getBlob(item.fullContentBlob)
+  }
+  return item.content
+}
+
+// Returns the copy of the clip changes with the large text fields replaced
+// by the references to the blobs.
+export async function offloadBlobs<T extends Partial<Clip>>(clip: T): Promise<T> {
+  if (!isSupported()) {
+    return clip
+  }
+  let result: any = {...clip}
+  for (const {field, ref} of kBlobFields) {
+    let value = result[field]
+    if (typeof value !== "string") {
+      continue
+    }
+    if (value.length > kBlobThreshold) {
+      result[ref] = await putBlob(value)
+      result[field] = ""
+    } else {
+      // The field was replaced with the small value, so the blob is no
+      // longer referenced.
+      result[ref] = undefined
+    }
+  }
+  return result
+}
+
+// Loads the content of the blobs referenced by the clip.
+export function loadBlobs(clip: Clip): Clip {
+  if (!isSupported()) {
+    return clip
+  }
+  let fields: any = clip
+  for (const {field, ref} of kBlobFields) {
+    if (fields[ref]) {
+      fields[field] = getBlob(fields[ref])
+      delete fields[ref]
+    }
+  }
+  return clip
+}
+
+// Overwrites and deletes the blobs of the deleted clip that aren't shared
+// with any of the stored clips. Must be called after the clip is deleted
+// from the database.
+export async function secureDeleteBlobs(clip: Clip) {
+  if (!isSupported() || typeof secureDeleteFile === 'undefined') {
+    return
+  }
+  // The loaded clip no longer keeps the references to its blobs, but the
+  // blobs are named by the hash of their content.
+  let fields: any = clip
+  let hashes = new Set<string>()
+  for (const {field, ref} of kBlobFields) {
+    let value = fields[field]
+    if (fields[ref]) {
+      hashes.add(fields[ref])
+    } else if (typeof value === "string" && value.length > kBlobThreshold) {
+      hashes.add(await sha256(value))
+    }
+  }
+  if (clip.fullContentBlob) {
+    hashes.add(clip.fullContentBlob)
+  }
+  if (hashes.size === 0) {
+    return
+  }
+  // The clips with the same content share the blobs.
+  await flushPendingClips()
+  await withBlobLock(async () => {
+    for (const stored of await getHistoryStore().getAllClips() as any[]) {
+      for (const {ref} of kBlobFields) {
+        hashes.delete(stored[ref])
+      }
+      hashes.delete(stored.fullContentBlob)
+    }
+    for (const hash of hashes) {
+      if (!unstoredBlobs.has(hash)) {
+        secureDeleteFile(blobPath(hash))
+      }
+    }
+  })
+}
+
+let garbageCollectorStarted = false
+let garbageCollectionTimer: ReturnType<typeof setTimeout> | undefined
+
+// Deletes the blobs that aren't referenced by any of the stored clips.
+// Returns the number of deleted blobs.
+export async function collectGarbageBlobs(): Promise<number> {
+  if (!isSupported()) {
+    return 0
+  }
+  // The buffered clips get their blobs when they're written.
+  await flushPendingClips()
+  return withBlobLock(async () => {
+    // The stored clips keep the references to the blobs.
+    let storedClips = await getHistoryStore().getAllClips()
+    let referenced = new Set<string>(unstoredBlobs)
+    for (const clip of storedClips as any[]) {
+      for (const {ref} of kBlobFields) {
+        if (clip[ref]) {
+          referenced.add(clip[ref])
+        }
+      }
+      if (clip.fullContentBlob) {
+        referenced.add(clip.fullContentBlob)
+      }
+    }
+    let dir = getBlobsDir()
+    let deleted = 0
+    for (const fileName of getFilesInDir(dir, kBlobExtension).split("*")) {
+      let hash = fileName.substring(0, fileName.length - kBlobExtension.length)
+      if (fileName.length > 0 && !referenced.has(hash)) {
+        deleteFile(dir + "/" + fileName)
+        deleted++
+      }
+    }
+    return deleted
+  })
+}
+
+function scheduleGarbageCollection() {
+  if (garbageCollectionTimer) {
+    clearTimeout(garbageCollectionTimer)
+  }
+  garbageCollectionTimer = setTimeout(() => {
+    garbageCollectionTimer = undefined
+    collectGarbageBlobs().catch(error => {
+      console.error('❌ Failed to delete unreferenced blobs:', error)
+    })
+  }, kGarbageCollectionDelayMs)
+}
+
+// Deletes the unreferenced blobs shortly after the items are deleted.
+export function startBlobGarbageCollector() {
+  if (garbageCollectorStarted) {
+    return
+  }
+  garbageCollectorStarted = true
+  emitter.on("HistoryItemDeleted", scheduleGarbageCollection)
+  emitter.on("HistoryItemUpdated", scheduleGarbageCollection)
+  emitter.on("HistoryCleared", scheduleGarbageCollection)
+  scheduleGarbageCollection()
+}
@@ -0,0 +1,71 @@
+declare const copyToClipboard: (text: string, rtf: string, html: string, imageFileName: string, filePath: string, ghost: boolean) => void;
+declare const pasteItemInFrontApp: (text: string, rtf: string, html: string, imageFileName: string, filePath: string) => void;
+declare const isAccessibilityAccessGranted: () => boolean;
+declare const readTextFile: (filePath: string) => string;
+declare const writeTextFile: (filePath: string, content: string) => boolean;
+declare const getBackupsDir: () => string;
+declare const readMaccyHistory: (dbPath: string) => string;
+declare const announce: (message: string) => void;
+
+export enum Capability {
+  Clipboard = "clipboard",
+  Paste = "paste",
+  Accessibility = "accessibility",
+  FileSystem = "fileSystem",
+  Backups = "backups",
+  MaccyImport = "maccyImport",
+  Announcements = "announcements",
+}
+
+export type SystemCapabilities = Record<Capability, boolean>
+
+// Thrown by the commands that depend on a capability that is not available
+// at runtime, so that the UI can hide or explain the disabled feature.
+export class CapabilityUnavailableError extends Error {
+  capability: Capability
+
+  constructor(capability: Capability) {
+    super(`The "${capability}" capability is not available`)
+    this.name = 'CapabilityUnavailableError'
+    this.capability = capability
+  }
+}
+
+function isAvailable(capability: Capability): boolean {
+  switch (capability) {
+    case Capability.Clipboard:
+      return typeof copyToClipboard !== 'undefined'
+    case Capability.Paste:
+      return typeof pasteItemInFrontApp !== 'undefined'
+    case Capability.Accessibility:
+      return typeof isAccessibilityAccessGranted !== 'undefined' && isAccessibilityAccessGranted()
+    case Capability.FileSystem:
+      return typeof readTextFile !== 'undefined' && typeof writeTextFile !== 'undefined'
+    case Capability.Backups:
+      return typeof getBackupsDir !== 'undefined'
+    case Capability.MaccyImport:
+      return typeof readMaccyHistory !== 'undefined'
+    case Capability.Announcements:
+      return typeof announce !== 'undefined'
+  }
+}
+
+// Detects the capabilities available at runtime. Accessibility access can be
+// granted or revoked while the app is running, so the result is not cached.
+export function detectCapabilities(): SystemCapabilities {
+  let capabilities = {} as SystemCapabilities
+  for (const capability of Object.values(Capability)) {
+    capabilities[capability] = isAvailable(capability)
+  }
+  return capabilities
+}
+
+export function hasCapability(capability: Capability): boolean {
+  return isAvailable(capability)
+}
+
+export function requireCapability(capability: Capability) {
+  if (!isAvailable(capability)) {
+    throw new CapabilityUnavailableError(capability)
+  }
+}
@@ -0,0 +1,132 @@
+import {Clip, ClipType} from "@/db";
+import {isItemPinned, purgeHistoryItem, searchHistory} from "@/data";
+import {prefGetMaxHistoryItems, prefGetMaxHistoryMegabytes} from "@/pref";
+import {emitter} from "@/actions";
+
+export interface PruneResult {
+  removedItems: number;
+  reclaimedBytes: number;
+}
+
+// The totals of the pruned items since the first launch.
+export interface CapsMetrics extends PruneResult {
+  lastPruneTime?: Date;
+}
+
+let enforcementStarted = false
+let pruning = false
+
+const encoder = new TextEncoder()
+
+// The sizes of the text fields of the items, so the text isn't encoded on
+// every new item. The entry is recomputed when any of the fields changes.
+let textSizes = new WeakMap<Clip, { fields: (string | undefined)[], size: number }>()
+
+function getTextSizeInBytes(item: Clip): number {
+  let fields = [item.content, item.rtf, item.html, item.imageText]
+  let cached = textSizes.get(item)
+  if (cached && cached.fields.every((field, index) => field === fields[index])) {
+    return cached.size
+  }
+  let size = fields.reduce((total, field) => total + (field ? encoder.encode(field).length : 0), 0)
+  textSizes.set(item, {fields, size})
+  return size
+}
+
+// Returns the approximate disk space used by the item including its image.
+// The text is stored as UTF-8 in the database and the blob files.
+export function getItemSizeInBytes(item: Clip): number {
+  let size = getTextSizeInBytes(item)
+  if (item.type === ClipType.Image) {
+    size += item.imageSizeInBytes ?? 0
+  }
+  return size
+}
+
+function isProtected(item: Clip): boolean {
+  return item.favorite || (item.tags !== undefined && item.tags.length > 0) || isItemPinned(item)
+}
+
+// Returns the oldest non-favorite items that must be removed to fit the
+// history into the given limits. Zero means no limit.
+export function findItemsToPrune(items: Clip[], maxItems: number, maxBytes: number): Clip[] {
+  let count = items.length
+  let bytes = maxBytes > 0 ? items.reduce((total, item) => total + getItemSizeInBytes(item), 0) : 0
+  let candidates = items.filter(item => !isProtected(item))
+      .sort((a, b) => new Date(a.copyTime).getTime() - new Date(b.copyTime).getTime())
+  let result: Clip[] = []
+  for (const item of candidates) {
+    let overCount = maxItems > 0 && count > maxItems
+    let overBytes = maxBytes > 0 && bytes > maxBytes
+    if (!overCount && !overBytes) {
+      break
+    }
+    result.push(item)
+    count--
+    bytes -= getItemSizeInBytes(item)
+  }
+  return result
+}
+
+export function getCapsMetrics(): CapsMetrics {
+  let value = localStorage.getItem("capsMetrics")
+  let metrics: CapsMetrics = value ? JSON.parse(value) : {removedItems: 0, reclaimedBytes: 0}
+  if (metrics.lastPruneTime) {
+    metrics.lastPruneTime = new Date(metrics.lastPruneTime)
+  }
+  return metrics
+}
+
+function recordPrune(result: PruneResult) {
+  let metrics = getCapsMetrics()
+  metrics.removedItems += result.removedItems
+  metrics.reclaimedBytes += result.reclaimedBytes
+  metrics.lastPruneTime = new Date()
+  localStorage.setItem("capsMetrics", JSON.stringify(metrics))
+}
+
+// Removes the oldest non-favorite items while the history exceeds the
+// `history.max_items` or `history.max_megabytes` limits.
+export async function enforceHistoryCaps(): Promise<PruneResult> {
+  let result: PruneResult = {removedItems: 0, reclaimedBytes: 0}
+  if (pruning) {
+    return result
+  }
+  let maxItems = prefGetMaxHistoryItems()
+  let maxBytes = prefGetMaxHistoryMegabytes() * 1024 * 1024
+  if (maxItems <= 0 && maxBytes <= 0) {
+    return result
+  }
+  pruning = true
+  try {
+    for (const item of findItemsToPrune(searchHistory(""), maxItems, maxBytes)) {
+      await purgeHistoryItem(item)
+      result.removedItems++
+      result.reclaimedBytes += getItemSizeInBytes(item)
+    }
+  } finally {
+    pruning = false
+  }
+  if (result.removedItems > 0) {
+    recordPrune(result)
+    emitter.emit("HistoryChanged")
+    console.log(`✅ Pruned ${result.removedItems} history items, reclaimed ${result.reclaimedBytes} bytes`)
+  }
+  return result
+}
+
+// Enforces the history limits after each new item.
+export function startCapsEnforcement() {
+  if (enforcementStarted) {
+    return
+  }
+  enforcementStarted = true
+  emitter.on("HistoryItemAdded", () => {
+    enforceHistoryCaps().catch(error => {
+      console.error('❌ Failed to enforce history limits:', error)
+    })
+  })
+  enforceHistoryCaps().catch(error => {
+    console.error('❌ Failed to enforce history limits:', error)
+  })
+}
@@ -0,0 +1,72 @@
+import {
+  prefGetCaptureAllowedTypes,
+  prefGetCaptureIgnoredTypes,
+  prefSetCaptureAllowedTypes,
+  prefSetCaptureIgnoredTypes
+} from "@/pref";
+import {requireNotGuest} from "@/guest";
+
+declare const pauseApp: (durationSecs: number) => void;
+declare const resumeApp: () => void;
+declare const isAppPaused: () => boolean;
+declare const getPausedUntil: () => number;
+declare const getRecentPasteboardTypes: () => string;
+
+export type CaptureState = {
+  paused: boolean
+  // The time when the capturing is resumed automatically.
+  pausedUntil?: Date
+}
+
+// Stops adding the copied items to the history. The monitor keeps running
+// and drops the clipboard changes. If the duration is given, the capturing
+// is resumed automatically when it expires.
+export function pauseCapture(durationSecs?: number) {
+  if (typeof pauseApp === 'undefined') return
+  pauseApp(durationSecs && durationSecs > 0 ? Math.round(durationSecs) : 0)
+}
+
+export function resumeCapture() {
+  if (typeof resumeApp === 'undefined') return
+  resumeApp()
+}
+
+export function getCaptureState(): CaptureState {
+  if (typeof isAppPaused === 'undefined' || !isAppPaused()) {
+    return {paused: false}
+  }
+  let pausedUntil = typeof getPausedUntil === 'undefined' ? 0 : getPausedUntil()
+  return {paused: true, pausedUntil: pausedUntil > 0 ? new Date(pausedUntil) : undefined}
+}
+
+export type CaptureFilters = {
+  // The pasteboard types (UTIs) that are read. Empty if all are read.
+  allowedTypes: string[]
+  // The pasteboard types (UTIs) that make the copied data ignored.
+  ignoredTypes: string[]
+}
+
+export function getCaptureFilters(): CaptureFilters {
+  return {
+    allowedTypes: prefGetCaptureAllowedTypes(),
+    ignoredTypes: prefGetCaptureIgnoredTypes(),
+  }
+}
+
+export function setCaptureFilters(filters: Partial<CaptureFilters>) {
+  requireNotGuest()
+  if (filters.allowedTypes) {
+    prefSetCaptureAllowedTypes(filters.allowedTypes)
+  }
+  if (filters.ignoredTypes) {
+    prefSetCaptureIgnoredTypes(filters.ignoredTypes)
+  }
+}
+
+// Returns the pasteboard types (UTIs) of the recently copied data, the most
+// recent first, so they can be added to the capture filters.
+export function listRecentPasteboardTypes(): string[] {
+  if (typeof getRecentPasteboardTypes === 'undefined') return []
+  let types = getRecentPasteboardTypes()
+  return types === "" ? [] : types.split(",")
+}
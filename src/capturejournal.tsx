@@ -0,0 +1,104 @@
+declare const readCaptureJournal: () => string;
+declare const acknowledgeCapture: (id: number) => void;
+declare const compactCaptureJournal: () => void;
+declare const clearCaptureJournal: () => void;
+
+// The clipboard data the monitor passes to addClipboardData().
+export type JournaledCapture = {
+  id: number
+  data: {
+    text: string
+    appPath: string
+    imageFileName: string
+    imageThumbFileName: string
+    imageWidth: number
+    imageHeight: number
+    imageSizeInBytes: number
+    imageText: string
+    filePath: string
+    filePathFileName: string
+    filePathThumbFileName: string
+    fileSizeInBytes: number
+    folder: boolean
+    rtf: string
+    html: string
+    sourceDevice: string
+  }
+}
+
+type CaptureHandler = (content: string,
+                       sourceAppPath: string,
+                       imageFileName: string,
+                       imageThumbFileName: string,
+                       imageWidth: number,
+                       imageHeight: number,
+                       imageSizeInBytes: number,
+                       imageText: string,
+                       filePath: string,
+                       filePathFileName: string,
+                       filePathThumbFileName: string,
+                       fileSizeInBytes: number,
+                       isFolder: boolean,
+                       rtf: string,
+                       html: string,
+                       sourceDevice: string,
+                       journalId: number) => Promise<void>
+
+// Tells the monitor that the capture has been handled, so it's not replayed
+// on the next launch.
+export function acknowledgeJournaledCapture(journalId: number) {
+  if (journalId > 0 && typeof acknowledgeCapture !== 'undefined') {
+    acknowledgeCapture(journalId)
+  }
+}
+
+// Removes the data of the saved captures from the journal, e.g. after an
+// item is deleted securely.
+export function compactJournal() {
+  if (typeof compactCaptureJournal !== 'undefined') {
+    compactCaptureJournal()
+  }
+}
+
+// Drops the captures left from the previous launch, so the cleared items
+// aren't replayed.
+export function clearJournal() {
+  if (typeof clearCaptureJournal !== 'undefined') {
+    clearCaptureJournal()
+  }
+}
+
+export function getJournaledCaptures(): JournaledCapture[] {
+  if (typeof readCaptureJournal === 'undefined') {
+    return []
+  }
+  try {
+    return JSON.parse(readCaptureJournal())
+  } catch (error) {
+    console.error('❌ Failed to read the capture journal:', error)
+    return []
+  }
+}
+
+// Adds the captures that weren't saved because the app quit or crashed
+// before they reached the database. The duplicates of the last item are
+// skipped by the handler, so a capture saved right before the crash isn't
+// added twice. A capture that fails to be saved stays in the journal and is
+// dropped after a few launches.
+export async function replayJournaledCaptures(handler: CaptureHandler) {
+  let captures = getJournaledCaptures()
+  for (const capture of captures) {
+    let data = capture.data
+    try {
+      await handler(data.text, data.appPath, data.imageFileName, data.imageThumbFileName,
+          data.imageWidth, data.imageHeight, data.imageSizeInBytes, data.imageText,
+          data.filePath, data.filePathFileName, data.filePathThumbFileName,
+          data.fileSizeInBytes, data.folder, data.rtf, data.html, data.sourceDevice, capture.id)
+    } catch (error) {
+      console.error(`❌ Failed to replay capture ${capture.id}:`, error)
+    }
+  }
+  if (captures.length > 0) {
+    console.log(`✅ Replayed ${captures.length} journaled captures`)
+  }
+}
@@ -0,0 +1,85 @@
+import {getFileNameFromPath} from "@/lib/utils";
+
+declare const copyToClipboard: (text: string, rtf: string, html: string, imageFileName: string, filePath: string, ghost: boolean) => void;
+
+// A virtual pasteboard used instead of the system one when ClipBook runs
+// without the native app (in a web browser or a headless test runner) or when
+// the VITE_SIMULATED_CLIPBOARD environment variable is set to "true".
+export interface SimulatedClipboardData {
+  text: string;
+  rtf: string;
+  html: string;
+  imageFileName: string;
+  filePath: string;
+  sourceAppPath: string;
+}
+
+let pasteboard: SimulatedClipboardData = {
+  text: "",
+  rtf: "",
+  html: "",
+  imageFileName: "",
+  filePath: "",
+  sourceAppPath: "",
+}
+let changeCount = 0
+
+export function isSimulatedClipboard(): boolean {
+  return import.meta.env.VITE_SIMULATED_CLIPBOARD === "true" || typeof copyToClipboard === 'undefined'
+}
+
+export function readSimulatedClipboard(): SimulatedClipboardData {
+  return {...pasteboard}
+}
+
+// Returns the number of changes made to the simulated pasteboard. Works the
+// same way as NSPasteboard.changeCount.
+export function getSimulatedClipboardChangeCount(): number {
+  return changeCount
+}
+
+// Writes the data to the simulated pasteboard. Like the native implementation,
+// the data copied by ClipBook itself is not added to the history.
+export function writeSimulatedClipboard(data: Partial<SimulatedClipboardData>) {
+  pasteboard = {...readSimulatedClipboard(), ...data}
+  changeCount++
+}
+
+// Copies the data to the system pasteboard or, if the pasteboard is simulated,
+// to the simulated one.
+export function writeClipboard(text: string, rtf: string, html: string, imageFileName: string,
+                               filePath: string, ghost: boolean) {
+  if (isSimulatedClipboard()) {
+    writeSimulatedClipboard({text, rtf, html, imageFileName, filePath})
+    return
+  }
+  copyToClipboard(text, rtf, html, imageFileName, filePath, ghost)
+}
+
+// Simulates a clipboard change made by another app and passes it to the
+// clipboard monitor the same way the native clipboard reader does.
+export async function injectClipboardChange(data: Partial<SimulatedClipboardData>) {
+  pasteboard = {
+    text: data.text || "",
+    rtf: data.rtf || "",
+    html: data.html || "",
+    imageFileName: data.imageFileName || "",
+    filePath: data.filePath || "",
+    sourceAppPath: data.sourceAppPath || "",
+  }
+  changeCount++
+  const addClipboardData = (window as any).addClipboardData
+  if (addClipboardData) {
+    await addClipboardData(pasteboard.text, pasteboard.sourceAppPath,
+        pasteboard.imageFileName, "", 0, 0, 0, "",
+        pasteboard.filePath, getFileNameFromPath(pasteboard.filePath), "", 0, false,
+        pasteboard.rtf, pasteboard.html)
+  }
+}
+
+// Injects the given text items one by one in the given order.
+export async function injectClipboardChanges(texts: string[], sourceAppPath: string = "") {
+  for (const text of texts) {
+    await injectClipboardChange({text, sourceAppPath})
+  }
+}
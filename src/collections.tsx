@@ -0,0 +1,77 @@
+import {
+  addSmartCollection,
+  Clip,
+  ClipFilter,
+  ClipType,
+  deleteSmartCollection,
+  getAllSmartCollections,
+  getSmartCollection,
+  matchesClipFilter,
+  SmartCollection,
+  updateSmartCollection
+} from "@/db";
+import {searchHistory} from "@/data";
+
+const kDayInMs = 24 * 60 * 60 * 1000
+
+// The actions the collection commands can perform on the selected collection.
+export enum CollectionAction {
+  Show = "show",
+  Remove = "remove",
+}
+
+export interface SmartCollectionOptions {
+  types?: ClipType[];
+  tags?: number[];
+  fromDate?: Date;
+  toDate?: Date;
+  lastDays?: number;
+}
+
+function toClipFilter(collection: SmartCollection, now: Date): ClipFilter {
+  let fromDate = collection.fromDate
+  if (collection.lastDays && collection.lastDays > 0) {
+    let since = new Date(now.getTime() - collection.lastDays * kDayInMs)
+    if (!fromDate || since > fromDate) {
+      fromDate = since
+    }
+  }
+  return {
+    fromDate,
+    toDate: collection.toDate,
+    types: collection.types,
+    tags: collection.tags,
+  }
+}
+
+export async function createSmartCollection(name: string, query: string,
+                                            options: SmartCollectionOptions = {}): Promise<SmartCollection> {
+  let collection = new SmartCollection(name, query)
+  Object.assign(collection, options)
+  collection.id = await addSmartCollection(collection)
+  return collection
+}
+
+export async function listSmartCollections(): Promise<SmartCollection[]> {
+  let collections = await getAllSmartCollections()
+  return collections.sort((a, b) => a.name.localeCompare(b.name))
+}
+
+export async function editSmartCollection(id: number, changes: Partial<SmartCollection>) {
+  await updateSmartCollection(id, changes)
+}
+
+export async function removeSmartCollection(id: number) {
+  await deleteSmartCollection(id)
+}
+
+// Returns the history items matching the query and the filters of the
+// collection with the given id.
+export async function getCollectionItems(id: number): Promise<Clip[]> {
+  let collection = await getSmartCollection(id)
+  if (!collection) {
+    throw new Error(`Smart collection not found: ${id}`)
+  }
+  let filter = toClipFilter(collection, new Date())
+  return searchHistory(collection.query).filter(item => matchesClipFilter(item, filter))
+}
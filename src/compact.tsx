@@ -0,0 +1,87 @@
+import {Clip, clipHash} from "@/db";
+import {isTextItem, purgeHistoryItem, searchHistory, updateHistoryItem} from "@/data";
+import {emitter} from "@/actions";
+
+export interface CompactResult {
+  // The number of the duplicate items merged into other items and deleted.
+  mergedItems: number;
+}
+
+let compacting = false
+
+// The text items that differ only in whitespaces are considered duplicates.
+function duplicateKey(item: Clip): string {
+  return clipHash({...item, content: item.content.trim().replace(/\s+/g, " ")})
+}
+
+function getTime(date?: Date): number {
+  return date ? new Date(date).getTime() : 0
+}
+
+// Merges the duplicate into the item keeping the earliest copy time, the
+// summed copy and paste counts, and the favorite state and tags of both.
+function mergeDuplicate(item: Clip, duplicate: Clip) {
+  if (getTime(duplicate.copyTime) < getTime(item.copyTime)) {
+    item.copyTime = duplicate.copyTime
+  }
+  item.numberOfCopies = (item.numberOfCopies || 1) + (duplicate.numberOfCopies || 1)
+  if (duplicate.pasteCount) {
+    item.pasteCount = (item.pasteCount ?? 0) + duplicate.pasteCount
+  }
+  if (getTime(duplicate.lastPastedAt) > getTime(item.lastPastedAt)) {
+    item.lastPastedAt = duplicate.lastPastedAt
+  }
+  item.favorite = item.favorite || duplicate.favorite
+  item.pinned = item.pinned || duplicate.pinned
+  item.sensitive = item.sensitive || duplicate.sensitive
+  let tags = item.tags ?? []
+  item.tags = tags.concat((duplicate.tags ?? []).filter(tagId => !tags.includes(tagId)))
+  if (!item.name && duplicate.name) {
+    item.name = duplicate.name
+  }
+}
+
+// Finds the text items with the same content or the content that differs
+// only in whitespaces, and merges each group into its oldest item.
+export async function compactHistory(): Promise<CompactResult> {
+  let result: CompactResult = {mergedItems: 0}
+  if (compacting) {
+    return result
+  }
+  compacting = true
+  try {
+    let groups = new Map<string, Clip[]>()
+    for (const item of searchHistory("")) {
+      if (!isTextItem(item)) {
+        continue
+      }
+      let key = duplicateKey(item)
+      let group = groups.get(key)
+      if (group) {
+        group.push(item)
+      } else {
+        groups.set(key, [item])
+      }
+    }
+    for (const group of groups.values()) {
+      if (group.length < 2) {
+        continue
+      }
+      group.sort((a, b) => a.id! - b.id!)
+      let [item, ...duplicates] = group
+      for (const duplicate of duplicates) {
+        mergeDuplicate(item, duplicate)
+        await purgeHistoryItem(duplicate)
+        result.mergedItems++
+      }
+      await updateHistoryItem(item.id!, item)
+    }
+  } finally {
+    compacting = false
+  }
+  if (result.mergedItems > 0) {
+    emitter.emit("HistoryChanged")
+  }
+  console.log(`✅ Compacted history, merged ${result.mergedItems} duplicate items`)
+  return result
+}
@@ -0,0 +1,61 @@
+import {parseToml, TomlTable, TomlValue} from "@/lib/toml";
+import {emitter} from "@/actions";
+import {isGuestMode} from "@/guest";
+
+declare const readConfigFile: () => string;
+declare const applyPreferences: (json: string) => number;
+
+// The preferences from the last successfully parsed config file.
+let lastConfig = new Map<string, string | number | boolean>()
+
+// Converts the nested tables to the preference names, e.g. the
+// "theme" key of the "[app]" table becomes "app.theme". The arrays are not
+// supported by the preferences, so they are skipped.
+function flatten(table: TomlTable, prefix: string, result: Map<string, string | number | boolean>): Map<string, string | number | boolean> {
+  for (const [key, value] of Object.entries(table)) {
+    let name = prefix + key
+    if (isTable(value)) {
+      flatten(value, name + ".", result)
+    } else if (!Array.isArray(value)) {
+      result.set(name, value)
+    }
+  }
+  return result
+}
+
+function isTable(value: TomlValue): value is TomlTable {
+  return typeof value === "object" && !Array.isArray(value)
+}
+
+// Reads the optional clipbook.toml config file and saves the preferences
+// that have changed since the last read. The preferences set in the config
+// file override the ones changed in the settings. Emits the
+// "PreferencesChanged" event with the names of the changed preferences, so
+// the subsystems re-read the settings. The config file is applied after the
+// guest mode is exited.
+export function loadConfigFile(): string[] {
+  if (typeof readConfigFile === 'undefined' || isGuestMode()) {
+    return []
+  }
+  let config = flatten(parseToml(readConfigFile()), "", new Map())
+  let changed = [...config.entries()]
+      .filter(([name, value]) => lastConfig.get(name) !== value)
+  lastConfig = config
+  if (changed.length === 0) {
+    return []
+  }
+  applyPreferences(JSON.stringify(Object.fromEntries(changed)))
+  let names = changed.map(([name]) => name)
+  console.log(`✅ Applied ${names.length} preferences from the config file`)
+  emitter.emit("PreferencesChanged", names)
+  return names
+}
+
+// Keeps the previously applied preferences if the config file is invalid.
+export function reloadConfigFile() {
+  try {
+    loadConfigFile()
+  } catch (error) {
+    console.error('❌ Failed to load the config file:', error)
+  }
+}
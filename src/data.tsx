@@ -2,27 +2,54 @@ import {
   addClip,
   Clip,
   ClipType,
+  DatabaseIncompatibleError,
   deleteAllClips,
+  detectColor,
+  detectKind,
   deleteClip,
   getAllClips,
   getFilePath, getHTML,
   getImageFileName,
-  getImageText, getRTF,
+  getImageText, getPendingClips, getRTF,
+  getSourceAppBundleId,
+  openDatabase,
+  restoreClips,
+  secureDeleteClip,
   updateClip
 } from "@/db";
-import {prefGetClearHistoryOnMacReboot, prefGetLanguage} from "@/pref";
-import {getClipType} from "@/lib/utils";
-import {loadTags, Tag} from "@/tags";
+import {prefGetClearHistoryOnMacReboot, prefGetLanguage, prefShouldSecureDelete} from "@/pref";
+import {getClipType, getClipTypeFromText} from "@/lib/utils";
+import {DetectedKind} from "@/lib/classifier";
+import {getTagAndDescendantIds, loadTags, Tag} from "@/tags";
+import {getNewItemProfileId, hasMultipleProfiles, isInCurrentProfile} from "@/profiles";
+import {applyTagRules, loadTagRules} from "@/rules";
+import {applyAppRules, loadAppRules} from "@/apprules";
+import {loadSnippets} from "@/snippets";
+import {findParentItemId} from "@/lineage";
+import {CopySession, getSessionId, groupSessions} from "@/sessions";
+import {scheduleVaultExport} from "@/vault";
 import {emitter} from "@/actions";
+import {containsSecret, maskText} from "@/lib/secrets";
+import {ColorFormat, convertColorText} from "@/lib/colors";
+import {requireUnlocked} from "@/applock";
+import {requireNotGuest} from "@/guest";
+import {applySizeLimit} from "@/sizelimit";
+import {getFullContent, secureDeleteBlobs} from "@/blobs";
+import {clearJournal, compactJournal} from "@/capturejournal";
+import {purgeClipFromBackups} from "@/backup";
+import {normalizeForSearch} from "@/lib/normalize";
 
 declare const getImagesDir: () => string;
 declare const isAfterSystemReboot: () => boolean;
 // Returns a string that contains the app name, path, and icon separated by '|'.
 declare const getAppInfo: (appPath: string) => string;
+declare const getAppIconFile: (appPath: string) => string;
 declare const getDefaultAppInfo: (filePath: string) => string;
 declare const getRecommendedAppsInfo: (filePath: string) => string;
 declare const getAllAppsInfo: () => string;
 declare const isFileExists: (filePath: string) => boolean;
+declare const deleteImage: (imageFileName: string) => void;
+declare const secureDeleteImage: (imageFileName: string) => void;
 
 export let FinderIcon = "iVBORw0KGgoAAAANSUhEUgAAAEAAAABACAYAAACqaXHeAAAAAXNSR0IArs4c6QAAAHhlWElmTU0AKgAAAAgABAEaAAUAAAABAAAAPgEbAAUAAAABAAAARgEoAAMAAAABAAIAAIdpAAQAAAABAAAATgAAAAAAAACQAAAAAQAAAJAAAAABAAOgAQADAAAAAQABAACgAgAEAAAAAQAAAECgAwAEAAAAAQAAAEAAAAAAlNz6EQAAAAlwSFlzAAAWJQAAFiUBSVIk8AAAE7tJREFUeAHtW3+MXcV1Pve9t7tvf3vttWOv7cU2BhwMgTUBm+IAEYnSQCiISAS1xf8QRUkrov5Q1ShVEuKSpgE1IWmpRJCDKpQUCPmhVkrcJkRAkRswBbdAG0hSAoYYG9sbvOvdfb/7fd/Mue++t/sWx02rSvWs587MmTNnzvnOmblz7302O5VOIXAKgVMI/D9GIDlJ20923ElOd8LDGifMGRl/GUPIy5wbe+C5scbKte9Jij0XWyN3huUaq9C1hH0oiyh7E2vgL4j3upfoTxNp9WrZdo7O2I7VRZst16xWq6k/yeUDX6NuSSJZDZS/YL3RaBxH+Xq9Xn8J9cenp6e/Pz4+vg8D6sgE4oTACBqGaRa7wjDLL//bfed0rdn46aSneGWjYfkklzGyjnoO4jg9uVl6am+DLiOSoGPP8SnbuyUBaomVKjWDKEAH+ILRaFBASJxCCbSUJ9ar1epTBw8e3Llp06bvgqeK/KZANCUHsQtdydM1tvvAH3RtOPvxpLvnahpPxkY9Ggxj5W032ssoLaFF8CITlWaC1wJI1ar9+mDZhrq7rQoWeDT0kSeY2OSHDI8qyYPh4oEsyisUClvWrFnzbYBw18033zyAgdTdIZOc9kuMsXZy2qaA7lXfO/KXyeCSP4a8vDUSS6j8AiVtY19wbCzBJ16K1BjyeB2VUsluGa/bWE/eypWqvE9jkuj1NAo4PtrC4Uku+E6AIlJkpcY0rLu7+/zt27dvPXTo0Lf37dtX0dAOl8XQkedXfufVj+eHV3yyEcNV4UhnshellgEjoS1RMY5JYDSuYBQzirhsOL5WtzXVX9hjF/cBh5rNlavi9SjRMgEYORiriMEQLQGGvIwm4hGITNtBm546du+qVas+hGFlZGo9L4XR88gCND/65R9syw+95U9oQFKDERQRs4c1l0EwkVxQiKEewz2dEm2Nx6qUIexnvVqzq4dr1qg1rAowvI9lkBaAdeOppujsjzyueks7zj8wOHTj3r17rwJPAXm+l0BcDIDu7nUTn8QenaenlBpAPq5nKk3vyqMAgW0aLBq9HvcHp1NxAuP9lJdUSnbNWBfEYxau/QyU5G3Uw91AU8JgphYwQMvuGYEhyon86zec/mnQu5EXtHUhIpHKj37m3rOsp/9yekphTIOpSTSSpVDHRKSTRzSCwcmjsSl/9IoDkuBWtzE3Z5uGitr8CEJ7ckCc7m3Kz4LCftclSyetp6dn8+7du7eBhfsdbWtJnQAo5NddeCVcEvpjANAYz26wgyKjCQ7BiMtFNAxhSZoygSKtUrGrlwZDcPuSQfI6wWN/1DUrI3SEpUJ6O08Ww+y409affgXGLrgMSGxP1LCQDC69SJ7XRJhKioVJZTyYuPq5K8lA1H1SCYQd4gOEooPV+xNEU6NcsitWhvB377cb1KoYBHBJ+KaniErgj7AhUhsmyYCuvoGyHBrouxBdvxwAuXzXOoUvBTPcUYSdNxgCc8Nk1IlrPu740iIaTQ4LyxhknubYETw4ho35nCWDOvmF9R8ONgITSntKQQONxnkP6/RJejAiIJAf+EMUOQhd3T3jkHdCAFA+c95yhVFtfhFxgpCZXZPxzoaYxwUTEgSWJHG/iMaKgM1TmsaNLsGmetlIDH/U6UUBI2ZKgwpxzyCpBQRFYmCk8eojqJiPGijplhj7QM/n86OgYxJZQPsiY0BFY9ouGJbrr+sWRwPDSFglg2lzKoE0tDW57KB89ssMdASvKOypMXjrlTm7ZCnCF4Aw/IOBYZzkZIyXMMqD4ZSYehw0rXnw8pwABmdVyZZHAPr70CQA81KnPSDXqCW5pIBJBQJKzJxQsRjSKdoCIyifBUKbo6ZD6KPUcwLvKISuXLFLVvamR9+sVhG2LCmtUw6NTueOXic4NDabAqigMBpy+R7WYs6yLYwKOSAzr1sgZ6SHqyhhAOstGU0aW/n6Z6364U1WfeDPwyEneoy8OhO48Wiv767YSBHHC9wKU0UhR8uA45C9LjLbSHd84fP2a1vfrtI3Q+/nGM+MLKlNMS4vACA52cuCYQEGAJrvTRlphAAMAgUAOqUoAUGq/t1fWb5assp37gwE0uMBSrdGep4A4pa3ZQAKIpJ0AMooSaU9tcwHIufadfddODKX7J6v7AI+ATyOcSOlDwUgMjx5n7fbyyZnew8NkMIoUfcI0K2NfaRlj8dYHgMDeACDgerDpiheymCKY3j/P3c4rH96iilVnHUGeNNrLXXyc44KZKR8WhBxTBzr0dMOoiZru3QGgIoH/VqHREPSPm+Dq69/IBjjNB+PUidASiqVbctot7yvx9/odVeWyjO5gWqwDVCYfA5vu7HZSFC9DUgNXuDSGQBndmM8GhyYLB31/sFhW71uvfUODYfIYT+XQBzn0dBTq9hbl/WG8PcIcM/FOR0MgeAeRjkI2ZxjaHhJCpDzcGgKSqx728sovqXoDACVpxEZg9PdlyK8Dx7jGh96/8esZ3iZLbnuY2EC9teieN09QEC5rreOV0kNAZAaGr27kOeovBvwux/9fRseWWa/c/PvieZ9XnJ8jeeROIalZEb5QbHWa+u9A8sa3V3IS1ffP3uglTW00tsN7QF7+lqMQOCW40YplHkAYmKBfm5cVw0dsS9eNmLTs2WbqxClk0+cL9UHYjR7pDndeVYsW4L3lnYUmS9I4sLrfBBaeP1rJEykoUIV3uG5AG2JpFidDFEqBQOTKvgJLV54nNYfvELPMFFOUx5FBdntpe/sNNLHcDw3Rr0wke8oj3gH4ZTBlDpFrdbLQgehwNHmHD/vsxMqiMeVbKLFsAiTBiHxCpLG18q2cajQco+mYZTG7MZJ4Ri2NJZtHsJEJ0AUSzqy6xA0arbJwqSxYUQgtF0XBUBT8Z4qu4KodLwUiobF12Xk8OSKsS0TuDYrDRvtzclrnW6BPj4rK8iIsrnUJNPbESAoyecBhYA8z37kCKTLbS8XBUBhhxCTMoCYUzFFH6guWiCnfeR3XhFxURtnhOV9iAD+UR6Vy4Q8ed2raV0jg7EE1ZcOJQpkzBWh0FiqwpiiHD0jUNAiaVEAqLQLb10CnLRpdTA3csILabi2Tcy7xdLeAjZDjICCepaPHhIY4PfShzY1WKAvjiVv0CaCArpHoJcur73sCACVbU0IPZCyCnl/lpYFzfvTErt+MR+XAMXHZcR+9yZnIAhsexIoDO9swlglD3s08ASrqHdgKYE5+wSpMZlLRwD8KZC8LR7ODJ5Hxx1AkcJpM3XnoyG9XYnNlNrAhRHCQ4ZzHYclwnFK0XgBE2kEXf1xf+JyxdOBziT8pNbkJVcTzIz6qnYEgKe4sKmE+7sb4Uq5AqLHTTBhaLticVLvFwtkPn/guI0OFGxyctLK5bI2RGqCjxk2ODhoXV08hoSUKg5gPKU0EFSXYBiYiRpFDOavkwY+vy26jGzZEQB5EhugQhHzhw8jqoR12rJ5Zd7GMDQz4cvJ0m0q320f/ubP7PoNdRttHLXq7DE8O+FjCBQdGRmxs895mxWLRYFCm+XFlnkCzQ0QuBFopxEqLLLgCOHCveZkIoB2cGBGEZ+EU4SXpKGf4d6Ipz5NRS2QBJrwCArkCn22f2653fYvB81msRk2BptHCPJ/70n7j89cYaVqTh9JKUO3Sy6RKDNIolqh5iCJFzxc74oAAMdtLDgwDiZTW+oYAXx6841IxtKrNJQTB8mpqOBhTMJNTW+O4BuGn0dCnD9Juizft9ySrkE8Xlcghg8aEMszAkK5cXzSenu68KYIj7vRYsrQcEVW60YYDCUtbJwqqR/GtN6JTgIAKsWZgyKoB9NbjJKR+EQOq2mH+DUOVY+eQEcYkEXRABCKWOckZTc3KF3D+wV6MLtrh/kxB3kJMAXGOqveT8cILPIAPD8eE8d8/JBK/vbUMQKooBLn5rRSljXUYx8fhPS0CII8T3oOl9gvpcgT2yopBy8CFEm6hhq9RgOZaECkqh2JKOgIJEVHrLOZ0l0upqTKnBDz+alTY9sunQGo1RmfhXSaWEm461JPGdKw2Xuu0abTs+MbeI7EJ7gQ1fK4zhKMjmiY5uZjNhPIIcogT23QPZICJXoXfAQnQ+MboY/cdKMou+69X6UiAXwsnV/ACEwfPb+EGh1SrT7X4jkqTkOoP3M0pF6atq7J5638/Z3NdwcU6Xw+hsAwk46kV2wOBgnsQ9sfkWUQ6UiKCBoiz5vddecX7ac/+bHNzMyIRg/TWHqdJflIY9vHh9r8aycAeO8IyQ1hy41gHXS+Exy69ku29nT8TOiZ+6y850tNw91YL9vlxLbeK5KHCbQKPpmH5RaNiQbJMNQfuO9r9vX7vmr46mufuvVzKShapgQ7RpvAQt1BCxPMv3YCIHC6kgxB1H2Dc3AovDa62d6Y+CN76znnWf2xz1vpB38KoNwijIFPmDwsfWyYIHhXdc6FhN9IpUbIGBqE5cZX6Hfecbvdcfuf2VmbN9sHP/JRO3PT2Rrj3mfDI0D1GDWLgdB5D6AN6Q9oqB1NUYxRdjAMa5a3r+mVl9vBrqJt6ftre2rPLpt58RErvvc2y6+ckELk11hWMsnBodJMBHhqtmJL+6EWZUe0fvTcM/bZnZ9Q2G/d/g77wA032tZt+IGalkRzf3DRLi+0eT+Ka8EZMmVHAJJGrdaohxNeq8AwmjTtslCaExwb2WYvbl5uW4bvsdeeedT233uNFTa+y7rP32HJ2ndg01t4qhbZMHq6hFMi3q7zjPDk3ifswfu/Zv/08EO2enytXXfDb9p177/B1q3f0GISgWCEuSz3OGnhIJXMZGxuqbbDxzYP40tWf+rw00m+OEaxwdhQtoxGw9F1HgBnK4/uthUHd9t/PvuUHXh1vzWKI9az4Z2WH5uwZOlZlhsYE82KtBQyylN4WTJl9UP/br912o/t8Ms/socfedQmjx6xlWNr7NyJCbvs8ncjQ0Y+Dct2VdT2peYgkFirVo+ce+b4ZlQnkd/0nSDjpZGUS68nPT1jqKdG6r4PhXXE5W2QdfTyhMgaOnDN289H3meHhi+3FSv32CXHnrD60Rds/4tP2cv/+CDFLZruhtfWnrbeNp37NsMPG2zi/AvsgrdfaH19/L4Z58tImOcgLYsmA/UrleYOa6iUbPaxtnBcwrR6aWZ/rmv4PLHTQBjM+3p4TKat8UGJthMEJAHDFrCoJn12YOjd9vPhd1lx1WFbcvpPbKL0svVWDlp3+SjOSzOWr81qXA1f4WpJr5ULQ3b2+FK7dGKjrVw9boPDI+rn3I24u6c20AHc5cUBvdhGkkNUCTqTZ65c2Q+SNGVXNrUDIO+DoV6fPvRkrv8t7wvM9DYFNjcU3b7QGU6AVCAuAj4SEzDsHwIJ4MzlRu21Pnyi79umjU5fiuPurp3ejSlN2dYzjtn2i5fbGzMVm8U7RC2xaHxqHOb1s34aAZEn6BuAoFs45sjh159GVfcXkZwJZYAtQ4gM1eMvfPMh2IHfrWIynihQ+g+cvNQwP8ygX7z0CcfEkvVs1trkGPJEuTpUkQ+b4KuTNZ3j07UsSZQWokxzkjW2WToIrHsmn+8DTz+x52E0HQBUm6kTAJWph//ixdrU64/y1qQnQ+72yFKcJdDhBOr3kr/94VNkhs95aCzpninHeVMaVuT+SfAx0pA5Ns3RYKpOmspII47Op47IQ9rU1LFnbvnEx/8NpJbNz/k6AUC0Zqf/9W9ux3fsshtEhTU5vYXPXv51mCXpqSHRwykvjY0vQj0aJBNiXKZ4sRR+eigYxydCjwKVCHHyiI/jCEKkoZkm74+ExkP/8N3PoT6HzJNNEB47WSx6Tym99Mh075lXFvK9q/A7O1oNz+i1F4yVLChEKj0ey0hRO7IEYLgzRr7QiaurwxIZ0q00N2PXTuStrzuPD8k4CrWtbR9LDTirG+xguWbse2X/Sw/e9NvX70J1CpkRQCNa0kIRQAaqxAFTB79y6Z2VIy/cp90fBnBCGsw2xcl4lopD3BkY6m1ZG13kIb8y+UnL/MaA9Bz+u8Hzr8ziGR51ugdR4Uai1UzufW6gzEjiA53OOXrk8D9/aMcHdoJM40vImG1+WjQC4qDG9NNffrw4fmmpMDB2AZQsMPqEv8KQWDE0AQjXLqZRXYaqS3CSxiBAJ/+hZJuCOA7/AIjG1Uq2dnDGLjpjAD8lqul3RGQXn/hpbJgr0GgX4w7yBESjsf/ln33r+t94zx/iZ/OH0DmNvGD4g77oEpCa4OF+UDv+7FefbVSOPdK1bPOKXM/AODqzL27AEg1STSoGzKGrPB1eAKAR+kiWT2CU6pHO387kKm/YtRctwbtB/PaIT4eUDROZeA31oJ4MJw3GY8N7fvfff+uWD+644e65ubkjIHvoh8EgtCefu52ebRNyHo97kQeQB/s3XjXed95N7ywMbTg/1zs8jp8TLcdZv5jkC/3w7IIyg6JuRNagZh2ycdwoW3HuFfvhrWvsOH5Cf2xu/iMtzvflWq1aKpdKk7OzswcOvnbgub0/3PPYF2679UmIoNHHkHn+93X/3wIAcnRe4HJxIPpRZ+b5lP9HiHQeqmJ8ovY/l4LrQ2QytLm+aexxZIY7j5ekedh3NB48HY/C7MumsNCakxJZTsLM3+D9XwCAIND4N/U6eNK0YLimvQtX6GVGAz3uhmu/Rpt9/xvJo4COoadpNPMJeR18aToZADiY45izIX+ysijvZJKHtoORLU9Y3q9K6V+VnBNWPMPoQGRIp6onjMB/AUz+r5rlodoQAAAAAElFTkSuQmCC"
 
@@ -34,6 +61,8 @@ export enum TextType {
 
 type FilterOptions = {
   types: ClipType[]
+  kinds: DetectedKind[]
+  itemIds: number[]
   favorites: boolean
   tags: Tag[]
   apps: AppInfo[]
@@ -43,7 +72,8 @@ export enum SortHistoryType {
   TimeOfFirstCopy,
   TimeOfLastCopy,
   NumberOfCopies,
-  Size
+  Size,
+  Frecency
 }
 
 export enum TextFormatOperation {
@@ -60,6 +90,16 @@ export type AppInfo = {
   name: string
   path: string
   icon: string
+  bundleId?: string
+}
+
+// The source app with the number of history items copied from it.
+export type SourceAppUsage = {
+  name: string
+  path: string
+  bundleId?: string
+  iconFile?: string
+  count: number
 }
 
 let unknownAppInfo: AppInfo = {
@@ -102,12 +142,16 @@ export type TagCheckedState = {
 }
 
 let history: Clip[] = [];
+// The deleted items that can be restored until the trash is emptied.
+let trash: Clip[] = [];
 let filteredHistory: Clip[] = [];
 let filterQuery = "";
 let filterHistory = false;
 let filterOptionsUpdated = false;
 let filterOptions: FilterOptions = {
   types: [],
+  kinds: [],
+  itemIds: [],
   favorites: false,
   tags: [],
   apps: []
@@ -124,6 +168,14 @@ let sortOrderReverse = false;
 let sourceApps: AppInfo[] = [];
 let pasteNextItemIndex = -1;
 
+// Splits the stored clips of the current profile into the history and the
+// trash.
+function setClips(clips: Clip[]) {
+  clips = clips.filter(clip => isInCurrentProfile(clip.profileId))
+  history = clips.filter(clip => !clip.deletedAt)
+  trash = clips.filter(clip => clip.deletedAt)
+}
+
 function sleep(ms: number) {
   return new Promise(resolve => setTimeout(resolve, ms));
 }
@@ -164,17 +216,31 @@ async function loadHistoryForPromo() {
   await sleep(500)
   await addClip(new Clip(ClipType.Text, "Standard clipboard stores only one entry and overwrites the previous one. It is easy to accidentally overwrite. It is inconvenient and wastes a lot of time because of such a limitation. If you copy a lot, if you are annoyed by wasting time searching for information that was copied just a couple of minutes or hours ago, if you are tired of constantly switching applications for copying and pasting, then the clipboard history app is for you. Once you try it, you will no longer be able to imagine working on a Mac without this application.", "/System/Applications/Notes.app"))
   await addClip(new Clip(ClipType.Text, "Clipboard history app for your Mac", "/System/Applications/Notes.app"))
-  history = await getAllClips()
+  setClips(await getAllClips())
 }
 
 export async function loadHistory() {
-  // Load all tags.
+  // Open the database and migrate it if needed.
+  try {
+    await openDatabase()
+  } catch (error) {
+    if (error instanceof DatabaseIncompatibleError) {
+      emitter.emit("DatabaseIncompatible", error.message)
+    }
+    throw error
+  }
+
+  // Load all tags, auto-tagging rules, per-app capture rules, and snippets.
   loadTags()
+  await loadTagRules()
+  await loadAppRules()
+  await loadSnippets()
 
   // Clear history on Mac reboot.
   if (prefGetClearHistoryOnMacReboot() && isAfterSystemReboot()) {
     await deleteAllClips()
     history = []
+    trash = []
     return
   }
 
@@ -183,7 +249,7 @@ export async function loadHistory() {
   //   history[i] = new Clip(ClipType.Text, "Standard clipboard stores only one entry and overwrites the previous one. It is easy to accidentally overwrite. It is inconvenient and wastes a lot of time because of such a limitation. If you copy a lot, if you are annoyed by wasting time searching for information that was copied just a couple of minutes or hours ago, if you are tired of constantly switching applications for copying and pasting, then the clipboard history app is for you. Once you try it, you will no longer be able to imagine working on a Mac without this application.", "/Applications/Safari.app")
   // }
 
-  history = await getAllClips()
+  setClips(await getAllClips())
   
   // Migrate existing items to ensure they have the new timestamp fields
   let needsMigration = false
@@ -209,9 +275,29 @@ export async function loadHistory() {
   requestHistoryUpdate()
 }
 
+// Reloads the history from the database after it was changed in the
+// background, e.g. by sync.
+export async function reloadHistory() {
+  setClips(await getAllClips())
+  sortHistory(sortType, history)
+  requestHistoryUpdate()
+}
+
 export function requestHistoryUpdate() {
   shouldUpdateHistory = true
   loadSourceApps(history)
+  scheduleVaultExport()
+}
+
+// Reloads the UI preferences after they were replaced by the preferences of
+// another profile.
+export function reloadSettings() {
+  previewVisible = true
+  filterVisible = false
+  infoVisible = true
+  sortType = SortHistoryType.TimeOfLastCopy
+  sortOrderReverse = false
+  loadSettings()
 }
 
 function loadSettings() {
@@ -269,6 +355,56 @@ export function getSourceApps(): AppInfo[] {
   return sourceApps
 }
 
+// Returns the distinct source apps of the history items with the number of
+// items copied from each app, the most used apps first. The apps are
+// identified by the bundle identifier, so the copies from the same app at
+// different paths are counted together.
+export function getSourceAppUsage(): SourceAppUsage[] {
+  let apps = new Map<string, SourceAppUsage>()
+  for (const item of history) {
+    let key = item.sourceAppBundleId || item.sourceApp || ""
+    let app = apps.get(key)
+    if (app) {
+      app.count++
+      continue
+    }
+    let appInfo = item.sourceApp ? getAppInfoByPath(item.sourceApp) : undefined
+    apps.set(key, {
+      name: appInfo?.name ?? unknownAppInfo.name,
+      path: item.sourceApp ?? "",
+      bundleId: item.sourceAppBundleId,
+      iconFile: item.sourceApp && typeof getAppIconFile !== 'undefined' ? getAppIconFile(item.sourceApp) || undefined : undefined,
+      count: 1
+    })
+  }
+  return [...apps.values()].sort((a, b) => b.count - a.count)
+}
+
+// Returns the copy sessions, the most recent sessions first.
+export function getSessions(): CopySession[] {
+  return groupSessions(history)
+}
+
+export function getSessionItems(sessionId: number): Clip[] {
+  return history.filter(item => item.sessionId === sessionId)
+}
+
+// Returns the items received from the other devices with Universal
+// Clipboard, the most recent first.
+export function getRemoteItems(): Clip[] {
+  return history
+      .filter(item => item.sourceDevice !== undefined)
+      .sort((a, b) => new Date(b.copyTime).getTime() - new Date(a.copyTime).getTime())
+}
+
+export function getSourceAppInfo(appPath: string): AppInfo | undefined {
+  let appInfo = getAppInfoByPath(appPath)
+  if (!appInfo && typeof getAppInfo !== 'undefined') {
+    appInfo = parseAppInfo(getAppInfo(appPath))
+  }
+  return appInfo
+}
+
 function hasItem(item: Clip): number {
   for (let i = 0; i < history.length; i++) {
     if (history[i].id === item.id) {
@@ -371,18 +507,52 @@ export function checkIfSameContentAsLatest(item: Clip): boolean {
   return item.content === lastItem.content && item.type === lastItem.type
 }
 
-async function deleteItem(item: Clip) {
+async function deleteItem(item: Clip, secure: boolean = false) {
   let index = hasItem(item);
   if (index !== -1) {
     history.splice(index, 1)
-    await deleteClip(item.id!)
+    if (secure) {
+      await secureDeleteClip(item.id!)
+    } else {
+      await deleteClip(item.id!)
+    }
     requestHistoryUpdate()
+    emitter.emit("HistoryItemDeleted", item)
+  }
+}
+
+// Returns true if the name or the content of the item contains the query
+// ignoring the case and the diacritics.
+export function itemHasText(query: string, item: Clip): boolean {
+  return hasText(normalizeForSearch(query), item)
+}
+
+// The normalized fields of the items, so the items aren't normalized on
+// every key press in the search field. The entry is recomputed when the
+// field changes.
+let normalizedFields = new WeakMap<Clip, Map<string, { source: string, normalized: string }>>()
+
+function normalizedField(item: Clip, field: string, source: string): string {
+  let fields = normalizedFields.get(item)
+  if (!fields) {
+    fields = new Map()
+    normalizedFields.set(item, fields)
+  }
+  let entry = fields.get(field)
+  if (!entry || entry.source !== source) {
+    entry = {source, normalized: normalizeForSearch(source)}
+    fields.set(field, entry)
   }
+  return entry.normalized
 }
 
 function hasText(searchString: string, item: Clip) {
   // Search in name.
-  if (item.name && item.name.toLowerCase().includes(searchString)) {
+  if (item.name && normalizedField(item, "name", item.name).includes(searchString)) {
+    return true
+  }
+  // Search in note.
+  if (item.note && normalizedField(item, "note", item.note).includes(searchString)) {
     return true
   }
   // Search in image title.
@@ -393,17 +563,17 @@ function hasText(searchString: string, item: Clip) {
     }
   }
   // Search in text from image.
-  if (getImageText(item).toLowerCase().includes(searchString)) {
+  if (normalizedField(item, "imageText", getImageText(item)).includes(searchString)) {
     return true
   }
   // Search in file path.
   if (item.type === ClipType.File) {
-    if (item.filePathFileName.toLowerCase().includes(searchString)) {
+    if (normalizedField(item, "fileName", item.filePathFileName).includes(searchString)) {
       return true
     }
   }
   // Search in content.
-  return item.content.toLowerCase().includes(searchString)
+  return normalizedField(item, "content", item.content).includes(searchString)
 }
 
 export function getHistoryItems(): Clip[] {
@@ -412,10 +582,11 @@ export function getHistoryItems(): Clip[] {
       return filteredHistory
     }
     filterHistory = false
+    let searchString = normalizeForSearch(filterQuery)
     filteredHistory = Array.from(history.filter(item => {
       let keep = filter(item)
       if (filterQuery.length > 0 && keep) {
-        return hasText(filterQuery.toLowerCase(), item)
+        return hasText(searchString, item)
       }
       return keep
     }));
@@ -432,10 +603,22 @@ export function getHistoryItems(): Clip[] {
   return history
 }
 
+// Returns the items matching the query regardless of the current filter.
+export function searchHistory(query: string): Clip[] {
+  let searchString = normalizeForSearch(query)
+  let items = searchString.length > 0 ? history.filter(item => hasText(searchString, item)) : [...history]
+  sortHistory(sortType, items)
+  return items
+}
+
 export function isHistoryEmpty() {
   return history.length === 0
 }
 
+export function getHistoryItemsCount(): number {
+  return history.length
+}
+
 export async function addHistoryItem(content: string,
                                      sourceAppPath: string,
                                      imageFileName: string,
@@ -450,9 +633,12 @@ export async function addHistoryItem(content: string,
                                      fileSizeInBytes: number,
                                      isFolder: boolean,
                                      rtf: string,
-                                     html: string): Promise<Clip> {
+                                     html: string,
+                                     pasteboard?: string,
+                                     sourceDevice?: string): Promise<Clip> {
   let type = getClipType(content, imageFileName, filePath)
   let item = new Clip(type, content, sourceAppPath)
+  item.sourceAppBundleId = getSourceAppBundleId(sourceAppPath)
   item.content = content
   item.rtf = rtf
   item.html = html
@@ -467,19 +653,201 @@ export async function addHistoryItem(content: string,
   item.imageThumbFileName = imageThumbFileName
   item.imageText = imageText
   item.fileFolder = isFolder
+  item.pasteboard = pasteboard
+  item.sourceDevice = sourceDevice || undefined
+  item.detectedKind = detectKind(item)
+  item.color = detectColor(item)
+  item.parentItemId = findParentItemId(item)
+  item.sessionId = getSessionId(item, getLastCapturedItem())
+  item.profileId = getNewItemProfileId()
+  item.sensitive = type !== ClipType.Image && type !== ClipType.File && containsSecret(content)
+  applyTagRules(item)
+  applyAppRules(item)
+  await applySizeLimit(item)
   await addClip(item)
   history.push(item)
   requestHistoryUpdate()
+  emitter.emit("HistoryItemAdded", item)
   return item
 }
 
+// Returns the most recently added item. The history is sorted by the sort
+// type selected in the UI, so the item with the greatest id is looked up.
+// The clips that aren't written yet have no ids, but they are newer than
+// the written ones.
+export function getLastCapturedItem(): Clip | undefined {
+  let pending = getPendingClips()
+  if (pending.length > 0) {
+    return pending[pending.length - 1]
+  }
+  let last: Clip | undefined
+  for (const item of history) {
+    if (item.id !== undefined && (!last || item.id > last.id!)) {
+      last = item
+    }
+  }
+  return last
+}
+
+// Moves the item to the trash, so it can be restored later. If secure
+// deletion is enabled, the item is deleted permanently instead, because the
+// trash would keep its content.
 export async function deleteHistoryItem(item: Clip) {
+  requireNotGuest()
+  if (prefShouldSecureDelete()) {
+    await secureDeleteHistoryItem(item)
+    return
+  }
+  let index = hasItem(item)
+  if (index === -1) {
+    return
+  }
+  history.splice(index, 1)
+  item.deletedAt = new Date()
+  await updateClip(item.id!, {deletedAt: item.deletedAt})
+  trash.push(item)
+  requestHistoryUpdate()
+  emitter.emit("HistoryItemDeleted", item)
+}
+
+// Deletes the item and its image files permanently bypassing the trash.
+export async function purgeHistoryItem(item: Clip) {
+  let index = trash.indexOf(item)
+  if (index !== -1) {
+    trash.splice(index, 1)
+    await deleteClip(item.id!)
+  } else {
+    await deleteItem(item)
+  }
+  await deleteItemFiles(item, prefShouldSecureDelete())
+}
+
+// Deletes the image files of the item. When the deletion is secure, the
+// image files and the blobs of the item are overwritten before they're
+// deleted.
+async function deleteItemFiles(item: Clip, secure: boolean) {
+  if (secure) {
+    await secureDeleteBlobs(item)
+  }
+  if (typeof deleteImage === 'undefined') {
+    return
+  }
+  let deleteFile = secure && typeof secureDeleteImage !== 'undefined' ? secureDeleteImage : deleteImage
+  if (item.type === ClipType.Image) {
+    deleteFile(item.imageFileName)
+    deleteFile(item.imageThumbFileName)
+  }
+  if (item.type === ClipType.File) {
+    deleteFile(item.filePathFileName)
+    deleteFile(item.filePathThumbFileName)
+  }
+}
+
+// Returns the deleted items, the most recently deleted first.
+export function getTrashItems(): Clip[] {
+  return [...trash].sort((a, b) => new Date(b.deletedAt!).getTime() - new Date(a.deletedAt!).getTime())
+}
+
+// Moves the deleted item back to the history.
+export async function restoreHistoryItem(id: number): Promise<Clip> {
+  requireNotGuest()
+  let index = trash.findIndex(item => item.id === id)
+  if (index === -1) {
+    throw new Error(`Deleted item not found: ${id}`)
+  }
+  let item = trash[index]
+  trash.splice(index, 1)
+  item.deletedAt = undefined
+  // The deletion might be already synced to the other devices, so the
+  // restored item is synced as a new one.
+  item.syncId = undefined
+  item.syncClock = undefined
+  item.syncHash = undefined
+  await updateClip(id, {deletedAt: undefined, syncId: undefined, syncClock: undefined, syncHash: undefined})
+  history.push(item)
+  sortHistory(sortType, history)
+  requestHistoryUpdate()
+  emitter.emit("HistoryItemUpdated", item)
+  return item
+}
+
+// Removes the item from the history keeping its files, e.g. when it's moved
+// to the archive.
+export async function removeHistoryItem(item: Clip) {
   await deleteItem(item)
 }
 
+// Adds the item removed by removeHistoryItem back to the history keeping
+// its id.
+export async function returnHistoryItem(item: Clip) {
+  await addClip(item)
+  history.push(item)
+  sortHistory(sortType, history)
+  requestHistoryUpdate()
+  emitter.emit("HistoryItemUpdated", item)
+}
+
+// Deletes the item permanently bypassing the trash and overwrites its
+// content, image files and blobs regardless of the secure deletion
+// preference. The item is also removed from the backups, the capture
+// journal and the snapshot for undoing the clearing of the history.
+export async function secureDeleteHistoryItem(item: Clip) {
+  requireNotGuest()
+  let index = trash.indexOf(item)
+  if (index !== -1) {
+    trash.splice(index, 1)
+    await secureDeleteClip(item.id!)
+  } else {
+    await deleteItem(item, true)
+  }
+  await deleteItemFiles(item, true)
+  compactJournal()
+  purgeClipFromBackups(item.id!)
+  if (clearSnapshot) {
+    clearSnapshot.clips = clearSnapshot.clips.filter(clip => clip.id !== item.id)
+  }
+}
+
 export async function updateHistoryItem(id: number, item: Clip) {
   await updateClip(id, item)
   requestHistoryUpdate()
+  emitter.emit("HistoryItemUpdated", item)
+}
+
+// Replaces the text content of the history item and updates the properties
+// derived from it. The rich text formats are dropped because they no longer
+// match the edited content.
+export async function updateItemContent(id: number, content: string): Promise<Clip> {
+  requireNotGuest()
+  let item = getHistoryItemById(id)
+  if (!item) {
+    throw new Error(`History item not found: ${id}`)
+  }
+  if (item.type === ClipType.Image || item.type === ClipType.File) {
+    throw new Error(`The content of image and file items cannot be edited: ${id}`)
+  }
+  if (content.trim().length === 0) {
+    throw new Error('The content of the history item cannot be empty')
+  }
+  item.content = content
+  item.fullContentBlob = undefined
+  item.rtf = ""
+  item.html = ""
+  item.type = getClipTypeFromText(content)
+  await updateEditedHistoryItem(item)
+  return item
+}
+
+// Saves the history item whose content was edited in place and updates the
+// properties derived from the content.
+export async function updateEditedHistoryItem(item: Clip) {
+  item.detectedKind = detectKind(item)
+  item.color = detectColor(item)
+  // An item marked as sensitive by the user stays sensitive after editing.
+  item.sensitive = item.sensitive === true ||
+      (item.type !== ClipType.Image && item.type !== ClipType.File && containsSecret(item.content))
+  item.editedAt = new Date()
+  await updateHistoryItem(item.id!, item)
 }
 
 function isFavoriteOrTagged(item: Clip) {
@@ -490,10 +858,25 @@ function getFavoriteItems(): Clip[] {
   return history.filter(item => isFavoriteOrTagged(item))
 }
 
+// How long the clearing of the history can be undone.
+const kUndoClearGracePeriodMs = 5 * 60 * 1000
+
+// The snapshot of the items removed by the last clearing of the history.
+let clearSnapshot: { clips: Clip[], time: number } | undefined
+
+// Remembers the items that are about to be cleared, so the clearing can be
+// undone. The securely deleted items are never kept.
+function takeClearSnapshot(clips: Clip[]) {
+  clearSnapshot = prefShouldSecureDelete() ? undefined : {clips, time: Date.now()}
+}
+
 export async function clear(keepFavorites: boolean): Promise<Clip[]> {
+  requireNotGuest()
+  clearJournal()
   if (keepFavorites) {
     let favorites = getFavoriteItems()
     if (favorites.length > 0) {
+      takeClearSnapshot(history.filter(clip => !isFavoriteOrTagged(clip)))
       for (const clip of history) {
         if (!isFavoriteOrTagged(clip)) {
           await deleteClip(clip.id!)
@@ -501,15 +884,84 @@ export async function clear(keepFavorites: boolean): Promise<Clip[]> {
       }
       history = favorites
       requestHistoryUpdate()
+      emitter.emit("HistoryCleared")
       return getHistoryItems()
     }
   }
+  let cleared = history.concat(trash)
+  takeClearSnapshot(cleared)
   history = []
+  trash = []
   requestHistoryUpdate()
-  await deleteAllClips()
+  if (hasMultipleProfiles()) {
+    // Keep the items of the other profiles.
+    for (const clip of cleared) {
+      await deleteClip(clip.id!)
+    }
+  } else {
+    await deleteAllClips()
+  }
+  emitter.emit("HistoryCleared")
   return getHistoryItems()
 }
 
+export function canUndoClearHistory(): boolean {
+  return clearSnapshot !== undefined && Date.now() - clearSnapshot.time < kUndoClearGracePeriodMs
+}
+
+// Restores the items removed by the last clearing of the history if it
+// happened within the grace period. Returns the number of restored items.
+export async function undoClearHistory(): Promise<number> {
+  requireNotGuest()
+  if (!canUndoClearHistory()) {
+    throw new Error("There is no cleared history to restore")
+  }
+  let clips = clearSnapshot!.clips
+  clearSnapshot = undefined
+  await restoreClips(clips)
+  for (const clip of clips) {
+    if (clip.deletedAt) {
+      trash.push(clip)
+    } else {
+      history.push(clip)
+    }
+  }
+  sortHistory(sortType, history)
+  requestHistoryUpdate()
+  emitter.emit("HistoryChanged")
+  console.log(`✅ Restored ${clips.length} cleared history items`)
+  return clips.length
+}
+
+// The number of days in which the weight of the item usage halves.
+const kFrecencyHalfLifeDays = 7
+
+// Returns the score of the item that combines how often and how recently it
+// was copied and pasted, so the frequently used items surface first while
+// the items used a lot a long time ago gradually sink.
+export function getFrecency(item: Clip, now: number = Date.now()): number {
+  let lastUsed = Math.max(new Date(item.copyTime).getTime(),
+      item.lastPastedAt ? new Date(item.lastPastedAt).getTime() : 0)
+  let ageDays = Math.max(0, now - lastUsed) / (24 * 60 * 60 * 1000)
+  // The pastes from ClipBook count more than the copies.
+  let uses = (item.numberOfCopies || 1) + 2 * (item.pasteCount ?? 0)
+  return uses * Math.pow(0.5, ageDays / kFrecencyHalfLifeDays)
+}
+
+// Returns all the history items sorted by the given type regardless of the
+// sort type selected in the UI.
+export function getHistorySorted(type: SortHistoryType): Clip[] {
+  let items = [...history]
+  sortHistory(type, items)
+  return items
+}
+
+// Updates the paste statistics of the item. The caller saves the item.
+export function markItemPasted(item: Clip) {
+  item.pasteCount = (item.pasteCount ?? 0) + 1
+  item.lastPastedAt = new Date()
+}
+
 export function sortHistory(type: SortHistoryType, history: Clip[]) {
   switch (type) {
     case SortHistoryType.TimeOfFirstCopy:
@@ -530,6 +982,11 @@ export function sortHistory(type: SortHistoryType, history: Clip[]) {
     case SortHistoryType.Size:
       history.sort((a, b) => compareItemsSize(a, b))
       break
+    case SortHistoryType.Frecency: {
+      const now = Date.now()
+      history.sort((a, b) => getFrecency(b, now) - getFrecency(a, now))
+      break
+    }
   }
   // Move pinned items to the top.
   history.sort((a, b) => {
@@ -545,6 +1002,112 @@ export function sortHistory(type: SortHistoryType, history: Clip[]) {
   if (sortOrderReverse) {
     history.reverse()
   }
+  // Keep the items pinned with pinItem() above everything else.
+  const now = new Date()
+  history.sort((a, b) => {
+    return Number(isItemPinned(b, now)) - Number(isItemPinned(a, now))
+  })
+}
+
+export function isItemPinned(item: Clip, now: Date = new Date()): boolean {
+  if (!item.pinned) {
+    return false
+  }
+  return !item.pinnedUntil || new Date(item.pinnedUntil) > now
+}
+
+// Pins the item to the top of the history list until the given time or
+// forever if the time is not specified.
+export async function pinItem(id: number, until?: Date) {
+  requireNotGuest()
+  let item = getHistoryItemById(id)
+  if (!item) {
+    throw new Error(`History item not found: ${id}`)
+  }
+  item.pinned = true
+  item.pinnedUntil = until
+  await updateHistoryItem(id, item)
+}
+
+export async function unpinItem(id: number) {
+  requireNotGuest()
+  let item = getHistoryItemById(id)
+  if (!item) {
+    throw new Error(`History item not found: ${id}`)
+  }
+  item.pinned = false
+  item.pinnedUntil = undefined
+  await updateHistoryItem(id, item)
+}
+
+export function isItemSensitive(item: Clip): boolean {
+  return item.sensitive === true
+}
+
+export async function markItemSensitive(id: number, sensitive: boolean = true) {
+  requireNotGuest()
+  let item = getHistoryItemById(id)
+  if (!item) {
+    throw new Error(`History item not found: ${id}`)
+  }
+  item.sensitive = sensitive
+  await updateHistoryItem(id, item)
+}
+
+// Attaches the note to the item. An empty note removes it.
+export async function setItemNote(id: number, note: string) {
+  requireNotGuest()
+  let item = getHistoryItemById(id)
+  if (!item) {
+    throw new Error(`History item not found: ${id}`)
+  }
+  item.note = note.trim() || undefined
+  await updateHistoryItem(id, item)
+  emitter.emit("UpdateItemById", id)
+}
+
+// Returns the text to display instead of the content of the item. The
+// content of the sensitive items is masked.
+export function getItemPreview(item: Clip): string {
+  return isItemSensitive(item) ? maskText(item.content) : item.content
+}
+
+// Returns the full content of the item including the sensitive one.
+export function revealItem(id: number): string {
+  requireUnlocked()
+  let item = getHistoryItemById(id)
+  if (!item) {
+    throw new Error(`History item not found: ${id}`)
+  }
+  return getFullContent(item)
+}
+
+// Returns the color of the history item in the given format.
+export function convertColor(id: number, format: ColorFormat): string {
+  let item = getHistoryItemById(id)
+  if (!item) {
+    throw new Error(`History item not found: ${id}`)
+  }
+  let color = convertColorText(item.color ?? item.content, format)
+  if (!color) {
+    throw new Error(`The history item is not a color: ${id}`)
+  }
+  return color
+}
+
+// Unpins the items whose pin has expired. Returns the number of unpinned items.
+export async function unpinExpiredItems(): Promise<number> {
+  const now = new Date()
+  let expired = history.filter(item => item.pinned && !isItemPinned(item, now))
+  for (const item of expired) {
+    item.pinned = false
+    item.pinnedUntil = undefined
+    await updateClip(item.id!, item)
+  }
+  if (expired.length > 0) {
+    requestHistoryUpdate()
+  }
+  return expired.length
 }
 
 function itemSize(item: Clip): number {
@@ -568,12 +1131,19 @@ function filter(item: Clip) {
   if (filterOptions.types.length > 0) {
     return filterOptions.types.includes(item.type)
   }
+  if (filterOptions.kinds.length > 0) {
+    return item.detectedKind !== undefined && filterOptions.kinds.includes(item.detectedKind)
+  }
+  if (filterOptions.itemIds.length > 0) {
+    return filterOptions.itemIds.includes(item.id!)
+  }
   if (filterOptions.apps.length > 0) {
     if (!item.sourceApp) {
       return false
     }
     for (let i = 0; i < filterOptions.apps.length; i++) {
-      if (item.sourceApp === filterOptions.apps[i].path) {
+      let app = filterOptions.apps[i]
+      if (item.sourceApp === app.path || (app.bundleId && item.sourceAppBundleId === app.bundleId)) {
         return true
       }
     }
@@ -583,8 +1153,10 @@ function filter(item: Clip) {
     if (!item.tags) {
       return false
     }
+    // The tag matches the items tagged with the tags nested in its namespace.
     for (let i = 0; i < filterOptions.tags.length; i++) {
-      if (!item.tags.includes(filterOptions.tags[i].id)) {
+      let tagIds = getTagAndDescendantIds(filterOptions.tags[i])
+      if (!item.tags.some(tagId => tagIds.includes(tagId))) {
         return false
       }
     }
@@ -662,6 +1234,18 @@ export function getHistoryItem(index: number): Clip {
   return getHistoryItems()[index]
 }
 
+// Returns the chain of items the item was derived from starting from the
+// original one and ending with the item itself.
+export function getItemLineage(id: number): Clip[] {
+  let lineage: Clip[] = []
+  let item = getHistoryItemById(id)
+  while (item && !lineage.includes(item)) {
+    lineage.unshift(item)
+    item = item.parentItemId ? getHistoryItemById(item.parentItemId) : undefined
+  }
+  return lineage
+}
+
 export function getHistoryItemById(id: number): Clip | undefined {
   for (let i = 0; i < history.length; i++) {
     if (history[i].id === id) {
@@ -765,7 +1349,8 @@ function parseAppInfo(appInfo: string) {
   return {
     name: parts[0],
     path: parts[1],
-    icon: parts[2]
+    icon: parts[2],
+    bundleId: parts[3] || undefined
   }
 }
 
@@ -804,6 +1389,8 @@ export function setShouldUpdateHistory() {
 
 export function resetFilter() {
   filterOptions.types = []
+  filterOptions.kinds = []
+  filterOptions.itemIds = []
   filterOptions.tags = []
   filterOptions.apps = []
   filterOptions.favorites = false
@@ -817,6 +1404,18 @@ export function filterByType(type: ClipType) {
   filterOptions.types = [type]
 }
 
+export function filterByDetectedKind(kind: DetectedKind) {
+  resetFilter()
+  filterOptions.kinds = [kind]
+}
+
+// Shows only the given items, e.g. the items of a smart collection.
+export function filterByItems(items: Clip[]) {
+  resetFilter()
+  // The -1 id keeps the filter active when there are no items.
+  filterOptions.itemIds = items.length > 0 ? items.map(item => item.id!) : [-1]
+}
+
 export function filterByTag(tag: Tag) {
   resetFilter()
   filterOptions.tags = [tag]
@@ -832,8 +1431,16 @@ export function filterByApp(app: AppInfo) {
   filterOptions.apps = [app]
 }
 
+export function getFilterTypes(): ClipType[] {
+  return [...filterOptions.types]
+}
+
+export function getFilterTagIds(): number[] {
+  return filterOptions.tags.map(tag => tag.id)
+}
+
 export function isFilterActive(): boolean {
-  return filterOptions.types.length > 0 || filterOptions.favorites || filterOptions.tags.length > 0 || filterOptions.apps.length > 0
+  return filterOptions.types.length > 0 || filterOptions.kinds.length > 0 || filterOptions.itemIds.length > 0 || filterOptions.favorites || filterOptions.tags.length > 0 || filterOptions.apps.length > 0
 }
 
 export function getSelectedItemTextTypes(item: Clip | undefined): TextType[] {
@@ -1,4 +1,37 @@
 import Dexie, {Table} from 'dexie';
+import {hashString} from "@/lib/hash";
+import {allTags, findTagByName, loadTags} from "@/tags";
+import {
+  HistorySnapshot,
+  HistoryStore,
+  kDefaultRetryConfig,
+  RetryingHistoryStore,
+  RetryMetrics,
+  RetryPolicy
+} from "@/store";
+import {classify, DetectedKind} from "@/lib/classifier";
+import {normalizeColor} from "@/lib/colors";
+import {Capability, requireCapability} from "@/capabilities";
+import {prefGetDatabaseMaxAttempts, prefShouldSecureDelete} from "@/pref";
+import {loadBlobs, markBlobsStored, offloadBlobs, withBlobLock} from "@/blobs";
+import {getSessionId} from "@/sessions";
+import {isRetryableError} from "@/lib/errors";
+
+declare const readTextFile: (filePath: string) => string;
+declare const writeTextFile: (filePath: string, content: string) => boolean;
+declare const getBackupsDir: () => string;
+declare const getVersion: () => string;
+declare const getAppBundleId: (appPath: string) => string;
+
+const kDatabaseName = 'ClipBookDB'
+const kArchiveDatabaseName = 'ClipBookArchiveDB'
+// The current version of the database schema. Increase it when adding a new
+// version of the schema below, and set kSchemaMinAppVersion to the version of
+// the app that ships the new schema.
+export const kSchemaVersion = 13
+// The minimum app version that understands the current database schema. The
+// older apps refuse to open the database instead of corrupting it.
+const kSchemaMinAppVersion = "1.30.0"
 
 export enum ClipType {
   Text,
@@ -26,16 +59,146 @@ export class LinkPreviewDetails {
   }
 }
 
+export class Snippet {
+  id?: number;
+  name: string = "";
+  content: string = "";
+  createdAt: Date = new Date();
+  updatedAt: Date = new Date();
+
+  constructor(name: string, content: string) {
+    this.name = name
+    this.content = content
+  }
+}
+
+export class TagRule {
+  id?: number;
+  name: string = "";
+  pattern: string = "";
+  flags: string = "";
+  tagId: number = 0;
+  enabled: boolean = true;
+
+  constructor(name: string, pattern: string, flags: string, tagId: number) {
+    this.name = name
+    this.pattern = pattern
+    this.flags = flags
+    this.tagId = tagId
+  }
+}
+
+// What to do with the content copied in the app.
+export enum AppRuleAction {
+  // Don't save the content to the history.
+  Ignore = "ignore",
+  // Save only the plain text dropping the RTF and HTML formats.
+  PlainText = "plainText",
+  // Add the tag of the rule to the saved item.
+  Tag = "tag",
+  // Add the saved item to favorites.
+  Favorite = "favorite",
+}
+
+export class AppRule {
+  id?: number;
+  bundleId: string = "";
+  action: AppRuleAction = AppRuleAction.Ignore;
+  // The tag to add for the Tag action.
+  tagId?: number;
+  enabled: boolean = true;
+
+  constructor(bundleId: string, action: AppRuleAction, tagId?: number) {
+    this.bundleId = bundleId
+    this.action = action
+    this.tagId = tagId
+  }
+}
+
+export class SmartCollection {
+  id?: number;
+  name: string = "";
+  query: string = "";
+  types: ClipType[] = [];
+  tags: number[] = [];
+  fromDate?: Date;
+  toDate?: Date;
+  // Limits the items to the ones copied within the last N days.
+  lastDays?: number;
+  createdAt: Date = new Date();
+
+  constructor(name: string, query: string) {
+    this.name = name
+    this.query = query
+  }
+}
+
+// The duration of a single command invocation kept for the metrics.
+export interface MetricSample {
+  id?: number;
+  name: string;
+  time: Date;
+  durationMs: number;
+  failed: boolean;
+}
+
+// The last known status of the system permission.
+export interface PermissionStatusRow {
+  kind: string;
+  status: string;
+  checkedAt: Date;
+  changedAt: Date;
+}
+
+// The named clipboard that keeps a history item.
+export interface RegisterRow {
+  name: string;
+  itemId: number;
+  // The global shortcut that pastes the item of the register.
+  shortcut?: string;
+  updatedAt: Date;
+}
+
+// The key combination of the shortcut action and how often it's used.
+export interface ShortcutRow {
+  id: string;
+  shortcut: string;
+  useCount: number;
+  lastUsed?: Date;
+}
+
+export class AuditLogEntry {
+  id?: number;
+  time: Date = new Date();
+  action: string = "";
+  details: string = "";
+
+  constructor(action: string, details: string) {
+    this.action = action
+    this.details = details
+  }
+}
+
 export class Clip {
   id?: number;
   name: string = "";
   content: string = "";
   type: ClipType = ClipType.Text;
   sourceApp: string = "";
+  // The bundle identifier of the source app, which doesn't change when the
+  // app is moved or updated.
+  sourceAppBundleId?: string;
   favorite: boolean = false;
+  pinned?: boolean;
+  // The time the pin expires. The item stays pinned forever if not set.
+  pinnedUntil?: Date;
   tags?: number[] = [];
   copyTime: Date = new Date();
   numberOfCopies: number = 1;
+  // How many times the item was pasted from ClipBook and when it was pasted
+  // last time.
+  pasteCount?: number;
+  lastPastedAt?: Date;
   imageFileName: string = "";
   imageThumbFileName: string = "";
   imageWidth: number = 0;
@@ -49,6 +212,51 @@ export class Clip {
   fileFolder: boolean = false;
   rtf: string = "";
   html: string = "";
+  detectedKind?: string;
+  // The detected color in the #rrggbb or #rrggbbaa notation.
+  color?: string;
+  // The last time the content was edited by the user.
+  editedAt?: Date;
+  // The last time any of the item properties, e.g. the tags, was changed.
+  modifiedAt?: Date;
+  // The id of the item this one was derived from by pasting it from ClipBook
+  // and copying it again in another app.
+  parentItemId?: number;
+  // Whether the content is masked until it's explicitly revealed.
+  sensitive?: boolean;
+  // The free-text note the user attached to the item.
+  note?: string;
+  // Whether the text recognition has been run on the image.
+  ocrProcessed?: boolean;
+  // The copy session the item was captured in.
+  sessionId?: number;
+  // The profile the item was captured in. Not set for the default profile.
+  profileId?: string;
+  // The secondary pasteboard the item was captured from, e.g. "find". Not
+  // set for the general pasteboard.
+  pasteboard?: string;
+  // The device the item was copied on if it came with Universal Clipboard
+  // (Handoff). Not set for the items copied on this Mac.
+  sourceDevice?: string;
+  // The time the item was moved to the trash.
+  deletedAt?: Date;
+  // The time the item was moved to the archive.
+  archivedAt?: Date;
+  // The hash of the blob that keeps the full text of the item whose content
+  // was truncated because it exceeded the size limit.
+  fullContentBlob?: string;
+  // The hashes of the blobs that keep the large text fields.
+  contentBlob?: string;
+  rtfBlob?: string;
+  htmlBlob?: string;
+  // The id of the item shared by all synced devices.
+  syncId?: string;
+  syncClock?: Record<string, number>;
+  syncModifiedAt?: number;
+  // The id of the device that made the last synced change.
+  syncModifiedBy?: string;
+  // The hash of the synced properties at the time of the last sync.
+  syncHash?: string;
 
   constructor(type: ClipType, content: string, sourceApp: string) {
     this.type = type;
@@ -57,52 +265,574 @@ export class Clip {
   }
 }
 
+// Returns the detected kind of the text content. Images and files are not
+// classified.
+export function detectKind(clip: Clip): string | undefined {
+  if (clip.type === ClipType.Image || clip.type === ClipType.File) {
+    return undefined
+  }
+  return classify(clip.content)
+}
+
+// Returns the bundle identifier of the app at the given path.
+export function getSourceAppBundleId(appPath: string): string | undefined {
+  if (!appPath || typeof getAppBundleId === 'undefined') {
+    return undefined
+  }
+  return getAppBundleId(appPath) || undefined
+}
+
+// Returns the normalized color if the clip content is a color.
+export function detectColor(clip: Clip): string | undefined {
+  return clip.detectedKind === DetectedKind.Color ? normalizeColor(clip.content) : undefined
+}
+
 class AppDatabase extends Dexie {
   public history!: Table<Clip, number>;
   public linkPreviews!: Table<LinkPreviewDetails, number>;
+  public snippets!: Table<Snippet, number>;
+  public tagRules!: Table<TagRule, number>;
+  public smartCollections!: Table<SmartCollection, number>;
+  public appRules!: Table<AppRule, number>;
+  public auditLog!: Table<AuditLogEntry, number>;
+  public metricSamples!: Table<MetricSample, number>;
+  public permissionStatus!: Table<PermissionStatusRow, string>;
+  public registers!: Table<RegisterRow, string>;
+  public shortcuts!: Table<ShortcutRow, string>;
 
   constructor() {
-    super('ClipBookDB');
+    super(kDatabaseName);
     this.version(1).stores({
       history: '++id, title, content, type, sourceApp, favorite, copyTime, numberOfCopies, imageFileName, imageThumbFileName, imageWidth, imageHeight, imageSizeInBytes, imageText, filePath, filePathFileName, filePathThumbFileName, fileSizeInBytes, fileFolder, rtf, html',
       linkPreviews: '++id, url, title, description, imageFileName, faviconFileName'
     });
+    this.version(2).stores({
+      snippets: '++id, name, createdAt, updatedAt'
+    });
+    this.version(3).stores({
+      history: '++id, title, content, type, sourceApp, favorite, copyTime, numberOfCopies, imageFileName, imageThumbFileName, imageWidth, imageHeight, imageSizeInBytes, imageText, filePath, filePathFileName, filePathThumbFileName, fileSizeInBytes, fileFolder, rtf, html, detectedKind'
+    }).upgrade(tx => {
+      return tx.table('history').toCollection().modify((clip: Clip) => {
+        clip.detectedKind = detectKind(clip)
+      })
+    });
+    this.version(4).stores({
+      tagRules: '++id, name, tagId, enabled'
+    });
+    this.version(5).stores({
+      smartCollections: '++id, name, createdAt'
+    });
+    this.version(6).stores({
+      auditLog: '++id, time, action'
+    });
+    this.version(7).stores({
+      history: '++id, title, content, type, sourceApp, favorite, copyTime, numberOfCopies, imageFileName, imageThumbFileName, imageWidth, imageHeight, imageSizeInBytes, imageText, filePath, filePathFileName, filePathThumbFileName, fileSizeInBytes, fileFolder, rtf, html, detectedKind, sourceAppBundleId'
+    }).upgrade(tx => {
+      return tx.table('history').toCollection().modify((clip: Clip) => {
+        clip.sourceAppBundleId = getSourceAppBundleId(clip.sourceApp)
+      })
+    });
+    this.version(8).stores({
+      appRules: '++id, bundleId, action, enabled'
+    });
+    this.version(9).stores({
+      history: '++id, title, content, type, sourceApp, favorite, copyTime, numberOfCopies, imageFileName, imageThumbFileName, imageWidth, imageHeight, imageSizeInBytes, imageText, filePath, filePathFileName, filePathThumbFileName, fileSizeInBytes, fileFolder, rtf, html, detectedKind, sourceAppBundleId, sessionId'
+    }).upgrade(tx => {
+      // The clips are visited in the order they were added.
+      let previous: Clip | undefined
+      return tx.table('history').toCollection().modify((clip: Clip) => {
+        clip.sessionId = getSessionId(clip, previous)
+        previous = clip
+      })
+    });
+    this.version(10).stores({
+      metricSamples: '++id, name, time'
+    });
+    this.version(11).stores({
+      permissionStatus: 'kind'
+    });
+    this.version(12).stores({
+      registers: 'name, itemId'
+    });
+    this.version(13).stores({
+      shortcuts: 'id'
+    });
   }
 }
 
 const db = new AppDatabase();
 
+// The separate database the old clips are moved to, so they don't slow down
+// loading and searching the history.
+class ArchiveDatabase extends Dexie {
+  public clips!: Table<Clip, number>;
+
+  constructor() {
+    super(kArchiveDatabaseName);
+    this.version(1).stores({
+      clips: 'id, type, copyTime, archivedAt'
+    });
+  }
+}
+
+const archiveDb = new ArchiveDatabase();
+
+// The values written over the content of the securely deleted clips.
+const kErasedContent: Partial<Clip> = {
+  name: "",
+  content: "",
+  rtf: "",
+  html: "",
+  imageText: "",
+  filePath: "",
+  filePathFileName: "",
+  sourceApp: "",
+}
+
+class DexieHistoryStore implements HistoryStore {
+  async open() {
+    await db.open()
+  }
+
+  async getAllClips(): Promise<Clip[]> {
+    return db.history.toArray()
+  }
+
+  async addClip(clip: Clip): Promise<number> {
+    return db.history.add(clip)
+  }
+
+  async addClips(clips: Clip[]): Promise<number[]> {
+    return db.transaction('rw', db.history, async () => {
+      return await db.history.bulkAdd(clips, {allKeys: true}) as number[]
+    })
+  }
+
+  async updateClip(id: number, changes: Partial<Clip>) {
+    await db.history.update(id, changes)
+  }
+
+  async deleteClip(id: number) {
+    await db.history.delete(id)
+  }
+
+  async deleteAllClips() {
+    await db.history.clear()
+  }
+
+  async secureDeleteClip(id: number) {
+    await db.transaction('rw', db.history, async () => {
+      await db.history.update(id, kErasedContent)
+      await db.history.delete(id)
+    })
+  }
+
+  async secureDeleteAllClips() {
+    await db.transaction('rw', db.history, async () => {
+      await db.history.toCollection().modify(kErasedContent)
+      await db.history.clear()
+    })
+  }
+
+  async getAllLinkPreviews(): Promise<LinkPreviewDetails[]> {
+    return db.linkPreviews.toArray()
+  }
+
+  async getLinkPreview(url: string): Promise<LinkPreviewDetails | undefined> {
+    return db.linkPreviews.where('url').equals(url).first()
+  }
+
+  async addLinkPreviews(previews: LinkPreviewDetails[]) {
+    await db.linkPreviews.bulkAdd(previews)
+  }
+
+  async deleteLinkPreview(url: string) {
+    await db.linkPreviews.where('url').equals(url).delete()
+  }
+
+  async deleteAllLinkPreviews() {
+    await db.linkPreviews.clear()
+  }
+
+  // Reads both tables in a single read-only transaction, so the snapshot
+  // doesn't include partial changes made while it's being taken.
+  async snapshot(): Promise<HistorySnapshot> {
+    return db.transaction('r', db.history, db.linkPreviews, async () => {
+      return {
+        clips: await db.history.toArray(),
+        linkPreviews: await db.linkPreviews.toArray()
+      }
+    })
+  }
+}
+
+// Retries the database operations that fail because the database is busy.
+const retryPolicy = new RetryPolicy({...kDefaultRetryConfig, maxAttempts: prefGetDatabaseMaxAttempts()},
+    isRetryableError)
+
+let store: HistoryStore = new RetryingHistoryStore(new DexieHistoryStore(), retryPolicy)
+
+// Replaces the storage backend of the history. Must be called before the
+// history is loaded.
+export function setHistoryStore(historyStore: HistoryStore) {
+  store = new RetryingHistoryStore(historyStore, retryPolicy)
+}
+
+export function getDatabaseRetryMetrics(): RetryMetrics {
+  return retryPolicy.getMetrics()
+}
+
+export function getHistoryStore(): HistoryStore {
+  return store
+}
+
+export class DatabaseIncompatibleError extends Error {
+  constructor(message: string) {
+    super(message)
+    this.name = "DatabaseIncompatibleError"
+  }
+}
+
+function compareVersions(a: string, b: string): number {
+  const partsA = a.split(".").map(part => parseInt(part) || 0)
+  const partsB = b.split(".").map(part => parseInt(part) || 0)
+  for (let i = 0; i < Math.max(partsA.length, partsB.length); i++) {
+    const diff = (partsA[i] || 0) - (partsB[i] || 0)
+    if (diff !== 0) {
+      return diff
+    }
+  }
+  return 0
+}
+
+// Saves a snapshot of the database before migrating it to the new schema.
+async function backupBeforeMigration(database: Dexie) {
+  if (typeof getBackupsDir === 'undefined') {
+    return
+  }
+  const history = database.table('history')
+  const linkPreviews = database.table('linkPreviews')
+  const data: BackupData = await database.transaction('r', history, linkPreviews, async () => {
+    return {
+      version: "1.0.0",
+      timestamp: new Date().toISOString(),
+      clips: await history.toArray(),
+      linkPreviews: await linkPreviews.toArray()
+    }
+  })
+  const timestamp = data.timestamp.replace(/[:.]/g, '-').slice(0, -5)
+  const fileName = `clipbook-backup-${timestamp}-premigration-v${database.verno}.json`
+  if (!writeTextFile(getBackupsDir() + "/" + fileName, JSON.stringify(data))) {
+    throw new Error(`Failed to write pre-migration backup: ${fileName}`)
+  }
+  console.log(`✅ Created pre-migration backup ${fileName}`)
+}
+
+// Opens the database. If the database schema is outdated, a backup is created
+// before migrating it. Throws DatabaseIncompatibleError if the database was
+// created by a newer version of the app.
+export async function openDatabase() {
+  if (!(store instanceof DexieHistoryStore)) {
+    await store.open()
+    return
+  }
+  if (await Dexie.exists(kDatabaseName)) {
+    const existing = new Dexie(kDatabaseName)
+    await existing.open()
+    try {
+      if (existing.verno > kSchemaVersion) {
+        throw new DatabaseIncompatibleError(
+            `The database schema version ${existing.verno} is newer than the supported version ${kSchemaVersion}`)
+      }
+      const minAppVersion = localStorage.getItem("schemaMinAppVersion")
+      if (minAppVersion && typeof getVersion !== 'undefined' && compareVersions(getVersion(), minAppVersion) < 0) {
+        throw new DatabaseIncompatibleError(
+            `The database requires ClipBook ${minAppVersion} or newer`)
+      }
+      if (existing.verno < kSchemaVersion) {
+        await backupBeforeMigration(existing)
+      }
+    } finally {
+      existing.close()
+    }
+  }
+  await store.open()
+  const minAppVersion = localStorage.getItem("schemaMinAppVersion")
+  if (!minAppVersion || compareVersions(kSchemaMinAppVersion, minAppVersion) > 0) {
+    localStorage.setItem("schemaMinAppVersion", kSchemaMinAppVersion)
+  }
+}
+
 export async function getAllClips(): Promise<Clip[]> {
-  return db.history.toArray()
+  return (await store.getAllClips()).map(loadBlobs)
+}
+
+// Adds the clip to the database. The large text fields are stored in the
+// blob store, while the given clip keeps the full content.
+// The new clips are written in batches, so a burst of copies is stored in a
+// single transaction instead of one transaction per clip.
+const kWriteBatchSize = 20
+const kWriteBatchDelayMs = 50
+
+type PendingClip = {
+  clip: Clip
+  resolve: () => void
+  reject: (error: unknown) => void
+}
+
+let pendingClips: PendingClip[] = []
+// The batches that are being written.
+let writingBatches = new Set<PendingClip[]>()
+let writeBatchTimer: ReturnType<typeof setTimeout> | undefined
+
+// Writes the buffered clips and assigns their ids.
+export async function flushPendingClips() {
+  if (writeBatchTimer) {
+    clearTimeout(writeBatchTimer)
+    writeBatchTimer = undefined
+  }
+  let batch = pendingClips
+  pendingClips = []
+  if (batch.length === 0) {
+    return
+  }
+  writingBatches.add(batch)
+  try {
+    let ids = await addClipsWithBlobs(batch.map(pending => pending.clip))
+    batch.forEach((pending, index) => {
+      pending.clip.id = ids[index]
+      pending.resolve()
+    })
+  } catch (error) {
+    // A single clip that can't be written fails the whole transaction, so
+    // the clips are written one by one to fail only that clip.
+    console.error(`❌ Failed to write ${batch.length} clips, retrying one by one:`, error)
+    for (const pending of batch) {
+      try {
+        pending.clip.id = (await addClipsWithBlobs([pending.clip]))[0]
+        pending.resolve()
+      } catch (clipError) {
+        console.error('❌ Failed to write clip:', clipError)
+        pending.reject(clipError)
+      }
+    }
+  } finally {
+    writingBatches.delete(batch)
+  }
 }
 
-export async function addClip(clip: Clip) {
-  await db.history.add(clip)
+// Returns the clips that are added but not written yet, the oldest first.
+export function getPendingClips(): Clip[] {
+  return [...writingBatches, pendingClips].flat().map(pending => pending.clip)
+}
+
+// Moves the large text fields of the clips to the blob store and adds the
+// clips to the database. Returns the ids of the added clips.
+function addClipsWithBlobs(clips: Clip[]): Promise<number[]> {
+  return withBlobLock(async () => {
+    let ids = await store.addClips(await Promise.all(clips.map(clip => offloadBlobs(clip))))
+    markBlobsStored(clips)
+    return ids
+  })
+}
+
+// Adds the clip to the write buffer. Resolves when the clip is stored and
+// its id is assigned.
+export function addClip(clip: Clip): Promise<void> {
+  return new Promise((resolve, reject) => {
+    pendingClips.push({clip, resolve, reject})
+    if (pendingClips.length >= kWriteBatchSize) {
+      void flushPendingClips()
+    } else if (!writeBatchTimer) {
+      writeBatchTimer = setTimeout(() => void flushPendingClips(), kWriteBatchDelayMs)
+    }
+  })
+}
+
+// Adds the previously deleted clips back to the database keeping their ids.
+export async function restoreClips(clips: Clip[]) {
+  await addClipsWithBlobs(clips)
 }
 
 export async function updateClip(id: number, clip: Partial<Clip>) {
-  await db.history.update(id, clip)
+  await withBlobLock(async () => {
+    await store.updateClip(id, await offloadBlobs({...clip, modifiedAt: new Date()}))
+  })
 }
 
+// Deletes the clip. If secure deletion is enabled in the preferences, the
+// content of the clip is overwritten before it's deleted.
 export async function deleteClip(id: number) {
-  await db.history.delete(id)
+  if (prefShouldSecureDelete()) {
+    await store.secureDeleteClip(id)
+  } else {
+    await store.deleteClip(id)
+  }
+}
+
+export async function secureDeleteClip(id: number) {
+  await store.secureDeleteClip(id)
 }
 
 export async function deleteAllClips() {
-  await db.history.clear()
+  await flushPendingClips()
+  if (prefShouldSecureDelete()) {
+    await store.secureDeleteAllClips()
+  } else {
+    await store.deleteAllClips()
+  }
+}
+
+// The archived clips keep their ids and the full content, because the blobs
+// that are no longer referenced by the history are deleted.
+export async function addArchivedClips(clips: Clip[]) {
+  await archiveDb.clips.bulkPut(clips)
+}
+
+export async function getArchivedClips(): Promise<Clip[]> {
+  return archiveDb.clips.toArray()
+}
+
+export async function deleteArchivedClip(id: number) {
+  await archiveDb.clips.delete(id)
 }
 
 export async function saveLinkPreviewDetails(details: LinkPreviewDetails) {
-  await db.linkPreviews.where('url').equals(details.url).delete()
-  await db.linkPreviews.add(details)
+  await store.deleteLinkPreview(details.url)
+  await store.addLinkPreviews([details])
 }
 
 export async function deleteLinkPreviewDetails(url: string) {
-  await db.linkPreviews.where('url').equals(url).delete()
+  await store.deleteLinkPreview(url)
 }
 
 export async function getLinkPreviewDetails(url: string): Promise<LinkPreviewDetails | undefined> {
-  return db.linkPreviews.where('url').equals(url).first()
+  return store.getLinkPreview(url)
+}
+
+export async function getAllLinkPreviews(): Promise<LinkPreviewDetails[]> {
+  return store.getAllLinkPreviews()
+}
+
+export async function getAllSnippets(): Promise<Snippet[]> {
+  return retryPolicy.run(() => db.snippets.toArray())
+}
+
+export async function getSnippet(id: number): Promise<Snippet | undefined> {
+  return retryPolicy.run(() => db.snippets.get(id))
+}
+
+export async function addSnippet(snippet: Snippet): Promise<number> {
+  return retryPolicy.run(() => db.snippets.add(snippet))
+}
+
+export async function updateSnippet(id: number, snippet: Partial<Snippet>) {
+  await retryPolicy.run(() => db.snippets.update(id, snippet))
+}
+
+export async function deleteSnippet(id: number) {
+  await retryPolicy.run(() => db.snippets.delete(id))
+}
+
+export async function getAllTagRules(): Promise<TagRule[]> {
+  return retryPolicy.run(() => db.tagRules.toArray())
+}
+
+export async function addTagRule(rule: TagRule): Promise<number> {
+  return retryPolicy.run(() => db.tagRules.add(rule))
+}
+
+export async function updateTagRule(id: number, rule: Partial<TagRule>) {
+  await retryPolicy.run(() => db.tagRules.update(id, rule))
+}
+
+export async function deleteTagRule(id: number) {
+  await retryPolicy.run(() => db.tagRules.delete(id))
+}
+
+export async function getAllAppRules(): Promise<AppRule[]> {
+  return retryPolicy.run(() => db.appRules.toArray())
+}
+
+export async function addAppRule(rule: AppRule): Promise<number> {
+  return retryPolicy.run(() => db.appRules.add(rule))
+}
+
+export async function updateAppRule(id: number, rule: Partial<AppRule>) {
+  await retryPolicy.run(() => db.appRules.update(id, rule))
+}
+
+export async function deleteAppRule(id: number) {
+  await retryPolicy.run(() => db.appRules.delete(id))
+}
+
+export async function getAllSmartCollections(): Promise<SmartCollection[]> {
+  return retryPolicy.run(() => db.smartCollections.toArray())
+}
+
+export async function getSmartCollection(id: number): Promise<SmartCollection | undefined> {
+  return retryPolicy.run(() => db.smartCollections.get(id))
+}
+
+export async function addSmartCollection(collection: SmartCollection): Promise<number> {
+  return retryPolicy.run(() => db.smartCollections.add(collection))
+}
+
+export async function updateSmartCollection(id: number, collection: Partial<SmartCollection>) {
+  await retryPolicy.run(() => db.smartCollections.update(id, collection))
+}
+
+export async function deleteSmartCollection(id: number) {
+  await retryPolicy.run(() => db.smartCollections.delete(id))
+}
+
+export async function addAuditLogEntry(entry: AuditLogEntry): Promise<number> {
+  return retryPolicy.run(() => db.auditLog.add(entry))
+}
+
+// Returns the audit log entries starting from the most recent one.
+export async function getAuditLog(limit: number = 100): Promise<AuditLogEntry[]> {
+  return retryPolicy.run(() => db.auditLog.orderBy('time').reverse().limit(limit).toArray())
+}
+
+export async function addMetricSamples(samples: MetricSample[]) {
+  await retryPolicy.run(() => db.metricSamples.bulkAdd(samples))
+}
+
+export async function getMetricSamples(since: Date): Promise<MetricSample[]> {
+  return retryPolicy.run(() => db.metricSamples.where('time').aboveOrEqual(since).toArray())
+}
+
+export async function deleteMetricSamples(before: Date) {
+  await retryPolicy.run(() => db.metricSamples.where('time').below(before).delete())
+}
+
+export async function getPermissionStatusRows(): Promise<PermissionStatusRow[]> {
+  return retryPolicy.run(() => db.permissionStatus.toArray())
+}
+
+export async function savePermissionStatusRow(row: PermissionStatusRow) {
+  await retryPolicy.run(() => db.permissionStatus.put(row))
+}
+
+export async function getRegisterRows(): Promise<RegisterRow[]> {
+  return retryPolicy.run(() => db.registers.toArray())
+}
+
+export async function saveRegisterRow(row: RegisterRow) {
+  await retryPolicy.run(() => db.registers.put(row))
+}
+
+export async function deleteRegisterRow(name: string) {
+  await retryPolicy.run(() => db.registers.delete(name))
+}
+
+export async function getShortcutRows(): Promise<ShortcutRow[]> {
+  return retryPolicy.run(() => db.shortcuts.toArray())
+}
+
+export async function saveShortcutRow(row: ShortcutRow) {
+  await retryPolicy.run(() => db.shortcuts.put(row))
 }
 
 export function getImageText(item: Clip): string {
@@ -131,18 +861,26 @@ export interface BackupData {
   timestamp: string;
   clips: Clip[];
   linkPreviews: LinkPreviewDetails[];
+  // The names of the tags the clips refer to by id.
+  tags?: {id: number, name: string}[];
+  // The time since which the clips are included in an incremental backup.
+  since?: string;
 }
 
 // Export all data for backup
 export async function exportAllData(): Promise<BackupData> {
-  const clips = await getAllClips();
-  const linkPreviews = await db.linkPreviews.toArray();
-  
+  const snapshot = await store.snapshot();
+  const clips = snapshot.clips.map(loadBlobs);
+  const linkPreviews = snapshot.linkPreviews;
+  loadTags();
+  const tags = allTags().map(tag => ({id: tag.id, name: tag.name}));
+
   return {
     version: "1.0.0",
     timestamp: new Date().toISOString(),
     clips,
-    linkPreviews
+    linkPreviews,
+    tags
   };
 }
 
@@ -156,8 +894,8 @@ export async function importData(data: BackupData, options: { merge?: boolean }
 
     // Clear existing data if not merging
     if (!options.merge) {
-      await db.history.clear();
-      await db.linkPreviews.clear();
+      await store.deleteAllClips();
+      await store.deleteAllLinkPreviews();
     }
 
     // Import clips
@@ -169,7 +907,7 @@ export async function importData(data: BackupData, options: { merge?: boolean }
         id: options.merge ? undefined : clip.id // Let DB assign new IDs when merging
       }));
 
-      await db.history.bulkAdd(processedClips);
+      await addClipsWithBlobs(processedClips);
     }
 
     // Import link previews
@@ -179,7 +917,7 @@ export async function importData(data: BackupData, options: { merge?: boolean }
         id: options.merge ? undefined : preview.id // Let DB assign new IDs when merging
       }));
 
-      await db.linkPreviews.bulkAdd(processedPreviews);
+      await store.addLinkPreviews(processedPreviews);
     }
 
     console.log(`✅ Successfully imported ${data.clips.length} clips and ${data.linkPreviews?.length || 0} link previews`);
@@ -188,3 +926,165 @@ export async function importData(data: BackupData, options: { merge?: boolean }
     throw error;
   }
 }
+
+// Deletes all the clips and link previews before a backup is restored.
+export async function clearAllData() {
+  await flushPendingClips()
+  await store.deleteAllClips()
+  await store.deleteAllLinkPreviews()
+}
+
+// Adds the clips from a backup keeping their ids.
+export async function addBackupClips(clips: Clip[]) {
+  const processedClips = clips.map(clip => ({...clip, copyTime: new Date(clip.copyTime)}))
+  await addClipsWithBlobs(processedClips)
+}
+
+export async function addBackupLinkPreviews(previews: LinkPreviewDetails[]) {
+  await store.addLinkPreviews(previews)
+}
+
+// Filter used to select a subset of clips for export and restore
+export interface ClipFilter {
+  fromDate?: Date;
+  toDate?: Date;
+  tags?: number[];
+  types?: ClipType[];
+  favoritesOnly?: boolean;
+}
+
+export function matchesClipFilter(clip: Clip, filter: ClipFilter): boolean {
+  const copyTime = new Date(clip.copyTime)
+  if (filter.fromDate && copyTime < filter.fromDate) {
+    return false
+  }
+  if (filter.toDate && copyTime > filter.toDate) {
+    return false
+  }
+  if (filter.types && filter.types.length > 0 && !filter.types.includes(clip.type)) {
+    return false
+  }
+  if (filter.tags && filter.tags.length > 0) {
+    if (!clip.tags || !filter.tags.some(tagId => clip.tags!.includes(tagId))) {
+      return false
+    }
+  }
+  if (filter.favoritesOnly && !clip.favorite) {
+    return false
+  }
+  return true
+}
+
+export function readBackupFile(path: string): BackupData {
+  requireCapability(Capability.FileSystem)
+  const text = readTextFile(path)
+  if (!text) {
+    throw new Error(`Failed to read backup file: ${path}`)
+  }
+  const data: BackupData = JSON.parse(text)
+  if (!data.clips || !Array.isArray(data.clips)) {
+    throw new Error('Invalid backup data: clips array is required')
+  }
+  return data
+}
+
+function clipKey(clip: Clip): string {
+  return `${clip.type}:${new Date(clip.copyTime).getTime()}:${clip.content}`
+}
+
+// Restores the clips matching the given filter from the backup file at the
+// given path. The existing data is kept and the clips that are already in the
+// history are skipped. Returns the number of restored clips.
+export async function restoreItemsFromBackup(path: string, filter: ClipFilter): Promise<number> {
+  try {
+    const data = readBackupFile(path)
+    const existing = new Set((await getAllClips()).map(clipKey))
+    const clips = data.clips
+        .map(clip => ({...clip, copyTime: new Date(clip.copyTime), id: undefined}))
+        .filter(clip => matchesClipFilter(clip, filter) && !existing.has(clipKey(clip)))
+    if (clips.length > 0) {
+      await addClipsWithBlobs(clips)
+    }
+
+    // Restore the link previews of the restored links.
+    const urls = new Set(clips.filter(clip => clip.type === ClipType.Link).map(clip => clip.content))
+    for (const preview of data.linkPreviews || []) {
+      if (urls.has(preview.url) && !(await getLinkPreviewDetails(preview.url))) {
+        await store.addLinkPreviews([{...preview, id: undefined}])
+      }
+    }
+
+    console.log(`✅ Restored ${clips.length} clips from ${path}`)
+    return clips.length
+  } catch (error) {
+    console.error('❌ Restore failed:', error)
+    throw error
+  }
+}
+
+export interface MergeReport {
+  added: number;
+  merged: number;
+  skipped: number;
+}
+
+// Returns the hash of the clip data that identifies the same clip across
+// different databases.
+export function clipHash(clip: Clip): string {
+  return hashString([clip.type, clip.content, clip.imageFileName || "", clip.filePath || ""].join("\u0000"))
+}
+
+// Merges the history from the backup file of another ClipBook database into
+// the current one. Identical clips are merged by combining their favorite
+// state and tags. The tag ids differ between the databases, so the tags are
+// matched by name, and the tags that don't exist in this database are ignored.
+export async function mergeDatabase(path: string): Promise<MergeReport> {
+  try {
+    const data = readBackupFile(path)
+    loadTags()
+    let tagIds = new Map<number, number>()
+    for (const tag of data.tags || []) {
+      const localTag = findTagByName(tag.name)
+      if (localTag) {
+        tagIds.set(tag.id, localTag.id)
+      }
+    }
+    let clipsByHash = new Map<string, Clip>()
+    for (const clip of await getAllClips()) {
+      clipsByHash.set(clipHash(clip), clip)
+    }
+
+    let report: MergeReport = {added: 0, merged: 0, skipped: 0}
+    for (const other of data.clips) {
+      const tags = (other.tags || [])
+          .map(tagId => tagIds.get(tagId))
+          .filter((tagId): tagId is number => tagId !== undefined)
+      const hash = clipHash(other)
+      const clip = clipsByHash.get(hash)
+      if (!clip) {
+        const newClip: Clip = {...other, tags, copyTime: new Date(other.copyTime), id: undefined}
+        newClip.id = (await addClipsWithBlobs([newClip]))[0]
+        clipsByHash.set(hash, newClip)
+        report.added++
+        continue
+      }
+      const clipTags = clip.tags || []
+      const newTags = tags.filter(tagId => !clipTags.includes(tagId))
+      const favorite = clip.favorite || other.favorite
+      if (newTags.length === 0 && favorite === clip.favorite) {
+        report.skipped++
+        continue
+      }
+      clip.tags = clipTags.concat(newTags)
+      clip.favorite = favorite
+      await updateClip(clip.id!, {tags: clip.tags, favorite: clip.favorite})
+      report.merged++
+    }
+
+    console.log(`✅ Merged database: ${report.added} added, ${report.merged} merged, ${report.skipped} skipped`)
+    return report
+  } catch (error) {
+    console.error('❌ Merge failed:', error)
+    throw error
+  }
+}
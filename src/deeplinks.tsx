@@ -0,0 +1,67 @@
+import {emitter} from "@/actions";
+import {executePickerAction, PickerAction} from "@/picker";
+import {isAppLocked} from "@/applock";
+import {getHistoryItemById} from "@/data";
+
+declare const showAppWindow: () => void;
+declare const hideAppWindow: () => void;
+declare const isAppWindowVisible: () => boolean;
+
+// The clipbook:// scheme is used by the app to load its own pages and
+// images, so the deep links use a separate scheme.
+const kScheme = "clipbook-link:"
+
+// Performs the command of the clipbook-link:// URL opened from another app:
+//   clipbook-link://paste/<id>   pastes the history item to the active app
+//   clipbook-link://search?q=... opens the app window and searches the history
+//   clipbook-link://toggle       shows or hides the app window
+export async function handleDeepLink(link: string) {
+  let url: URL
+  try {
+    url = new URL(link)
+  } catch (e) {
+    console.error('❌ Invalid deep link:', link)
+    return
+  }
+  if (url.protocol !== kScheme) {
+    return
+  }
+  // In clipbook-link://paste/42 the command is the host and the id is the path.
+  let command = url.hostname
+  let args = url.pathname.split("/").filter(arg => arg.length > 0)
+  switch (command) {
+    case "paste": {
+      let id = parseInt(args[0])
+      if (isNaN(id)) {
+        console.error('❌ Invalid item id in deep link:', link)
+        return
+      }
+      // Any app can open the URL, so paste without confirmation only when
+      // the user is already working with the unlocked app window. Otherwise,
+      // select the item in the app window and let the user paste it.
+      if (isAppLocked() || !isAppWindowVisible()) {
+        showAppWindow()
+        let item = isAppLocked() ? undefined : getHistoryItemById(id)
+        if (item) {
+          emitter.emit("ShowInHistory", item)
+        }
+        return
+      }
+      await executePickerAction(id, url.searchParams.has("plain") ? PickerAction.PastePlain : PickerAction.Paste)
+      break
+    }
+    case "search":
+      showAppWindow()
+      emitter.emit("SearchHistory", url.searchParams.get("q") ?? "")
+      break
+    case "toggle":
+      if (isAppWindowVisible()) {
+        hideAppWindow()
+      } else {
+        showAppWindow()
+      }
+      break
+    default:
+      console.error('❌ Unknown deep link command:', command)
+  }
+}
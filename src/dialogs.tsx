@@ -0,0 +1,31 @@
+declare const selectPath: (requestId: number, kind: string, defaultName: string) => void;
+
+export enum PathKind {
+  File = "file",
+  Folder = "folder",
+  // The path of a new file to save to.
+  Save = "save",
+}
+
+let nextRequestId = 1
+const pendingRequests = new Map<number, (path: string) => void>()
+
+// Asks the user to choose a path in the system dialog. Resolves with an empty
+// string if the dialog is canceled or not available.
+export function choosePath(kind: PathKind, defaultName: string = ""): Promise<string> {
+  if (typeof selectPath === 'undefined') {
+    return Promise.resolve("")
+  }
+  let requestId = nextRequestId++
+  return new Promise(resolve => {
+    pendingRequests.set(requestId, resolve)
+    selectPath(requestId, kind, defaultName)
+  })
+}
+
+// Called by the app when the dialog shown by choosePath() is closed.
+export function pathSelected(requestId: number, path: string) {
+  let resolve = pendingRequests.get(requestId)
+  pendingRequests.delete(requestId)
+  resolve?.(path)
+}
@@ -0,0 +1,177 @@
+import {Clip, ClipFilter, ClipType, getAllClips, matchesClipFilter} from "@/db";
+import {getTags, loadTags} from "@/tags";
+import {emitter} from "@/actions";
+
+declare const writeTextFile: (filePath: string, content: string) => boolean;
+
+export enum ExportFormat {
+  JSON = "json",
+  CSV = "csv",
+  Markdown = "md",
+}
+
+// The number of items to process before emitting a progress event.
+const kProgressStep = 500
+
+export function typeName(type: ClipType): string {
+  return ClipType[type]
+}
+
+export function tagNames(clip: Clip): string[] {
+  return getTags(clip.tags).map(tag => tag.name)
+}
+
+export function clipContent(clip: Clip): string {
+  if (clip.type === ClipType.Image) {
+    return clip.imageText
+  }
+  if (clip.type === ClipType.File) {
+    return clip.filePath
+  }
+  return clip.content
+}
+
+function escapeCSV(value: string): string {
+  if (/[",\r\n]/.test(value)) {
+    return '"' + value.replace(/"/g, '""') + '"'
+  }
+  return value
+}
+
+function toJSONEntry(clip: Clip) {
+  return {
+    id: clip.id,
+    name: clip.name,
+    type: typeName(clip.type),
+    content: clipContent(clip),
+    sourceApp: clip.sourceApp,
+    favorite: clip.favorite,
+    tags: tagNames(clip),
+    copyTime: new Date(clip.copyTime).toISOString(),
+    numberOfCopies: clip.numberOfCopies,
+  }
+}
+
+function toCSVRow(clip: Clip): string {
+  return [
+    String(clip.id ?? ""),
+    typeName(clip.type),
+    clip.name,
+    clipContent(clip),
+    clip.sourceApp,
+    clip.favorite ? "true" : "false",
+    tagNames(clip).join(";"),
+    new Date(clip.copyTime).toISOString(),
+    String(clip.numberOfCopies),
+  ].map(escapeCSV).join(",")
+}
+
+function toMarkdownEntry(clip: Clip): string {
+  let title = clip.name ? clip.name : typeName(clip.type)
+  let lines = [`## ${title}`, ""]
+  lines.push(`- Type: ${typeName(clip.type)}`)
+  lines.push(`- Copied: ${new Date(clip.copyTime).toISOString()}`)
+  if (clip.sourceApp) {
+    lines.push(`- Source: ${clip.sourceApp}`)
+  }
+  if (clip.favorite) {
+    lines.push(`- Favorite: yes`)
+  }
+  let tags = tagNames(clip)
+  if (tags.length > 0) {
+    lines.push(`- Tags: ${tags.join(", ")}`)
+  }
+  // Use a fence longer than any backtick run in the content.
+  let content = clipContent(clip)
+  let longestRun = Math.max(0, ...(content.match(/`+/g) || []).map(run => run.length))
+  let fence = "`".repeat(Math.max(3, longestRun + 1))
+  lines.push("", fence, content, fence, "")
+  return lines.join("\n")
+}
+
+function emitProgress(processed: number, total: number) {
+  emitter.emit("ExportProgress", {processed, total})
+}
+
+export function exportFileName(format: ExportFormat): string {
+  const timestamp = new Date().toISOString().replace(/[:.]/g, '-').slice(0, -5)
+  return `clipbook-history-${timestamp}.${format}`
+}
+
+export function formatHistory(clips: Clip[], format: ExportFormat): string {
+  let parts: string[] = []
+  if (format === ExportFormat.CSV) {
+    parts.push("id,type,name,content,sourceApp,favorite,tags,copyTime,numberOfCopies")
+  }
+  if (format === ExportFormat.Markdown) {
+    parts.push("# ClipBook History\n")
+  }
+  let entries: any[] = []
+  for (let i = 0; i < clips.length; i++) {
+    let clip = clips[i]
+    if (format === ExportFormat.JSON) {
+      entries.push(toJSONEntry(clip))
+    } else if (format === ExportFormat.CSV) {
+      parts.push(toCSVRow(clip))
+    } else {
+      parts.push(toMarkdownEntry(clip))
+    }
+    if ((i + 1) % kProgressStep === 0) {
+      emitProgress(i + 1, clips.length)
+    }
+  }
+  emitProgress(clips.length, clips.length)
+  if (format === ExportFormat.JSON) {
+    return JSON.stringify(entries, null, 2)
+  }
+  return parts.join("\n")
+}
+
+// Saves the given content to the file at the given path or, when the path is
+// not specified or the native API is unavailable, downloads it as a file.
+export function saveTextFile(content: string, fileName: string, mimeType: string, path?: string) {
+  if (path && typeof writeTextFile !== 'undefined') {
+    if (!writeTextFile(path, content)) {
+      throw new Error(`Failed to write file: ${path}`)
+    }
+    return
+  }
+  const blob = new Blob([content], {type: mimeType})
+  const url = URL.createObjectURL(blob)
+  const a = document.createElement('a')
+  a.href = url
+  a.download = fileName
+  document.body.appendChild(a)
+  a.click()
+  document.body.removeChild(a)
+  URL.revokeObjectURL(url)
+}
+
+function mimeTypeFor(format: ExportFormat): string {
+  switch (format) {
+    case ExportFormat.JSON:
+      return "application/json"
+    case ExportFormat.CSV:
+      return "text/csv"
+    case ExportFormat.Markdown:
+      return "text/markdown"
+  }
+}
+
+// Exports the history items matching the given filter and returns the number
+// of exported items.
+export async function exportHistory(format: ExportFormat,
+                                    filter: ClipFilter = {},
+                                    path?: string): Promise<number> {
+  try {
+    loadTags()
+    const clips = (await getAllClips()).filter(clip => matchesClipFilter(clip, filter))
+    const content = formatHistory(clips, format)
+    saveTextFile(content, exportFileName(format), mimeTypeFor(format), path)
+    console.log(`✅ Exported ${clips.length} clips as ${format}`)
+    return clips.length
+  } catch (error) {
+    console.error('❌ Export failed:', error)
+    throw error
+  }
+}
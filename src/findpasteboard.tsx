@@ -0,0 +1,38 @@
+import {addHistoryItem} from "@/data";
+import {isGuestMode} from "@/guest";
+import {shouldRecordFromApp} from "@/apprules";
+import {emitter} from "@/actions";
+
+declare const writeToFindPasteboard: (text: string) => void;
+
+// The name of the pasteboard the apps share for the search terms.
+export const kFindPasteboard = "find"
+
+let lastFindText = ""
+
+// Adds the text placed on the find pasteboard (e.g. with ⌘E) to the
+// history. The same search term isn't added twice in a row.
+export async function addFindPasteboardText(text: string, sourceAppPath: string) {
+  if (isGuestMode() || !shouldRecordFromApp(sourceAppPath)) {
+    return
+  }
+  if (text === lastFindText) {
+    return
+  }
+  lastFindText = text
+  try {
+    await addHistoryItem(text, sourceAppPath, "", "", 0, 0, 0, "", "", "", "", 0, false, "", "", kFindPasteboard)
+    emitter.emit("HistoryChanged")
+  } catch (error) {
+    console.error('❌ Failed to add the search term to history:', error)
+  }
+}
+
+// Puts the text to the find pasteboard, so ⌘G searches for it in the
+// other apps.
+export function setFindPasteboardText(text: string) {
+  if (typeof writeToFindPasteboard === 'undefined') {
+    throw new Error("The find pasteboard is not supported")
+  }
+  writeToFindPasteboard(text)
+}
@@ -0,0 +1,84 @@
+import {emitter} from "@/actions";
+import {verifyUser} from "@/applock";
+import {getCaptureState, pauseCapture, resumeCapture} from "@/capture";
+
+declare const setGuestMode: (enabled: boolean) => void;
+
+export class GuestModeError extends Error {
+  constructor() {
+    super('ClipBook is in guest mode')
+    this.name = "GuestModeError"
+  }
+}
+
+type GuestModeState = {
+  // Whether the capturing was already paused when the guest mode was entered,
+  // so it stays paused when the guest mode is exited.
+  capturePaused: boolean
+}
+
+// Lets the app block the settings window while the guest mode is on.
+function notifyApp(enabled: boolean) {
+  if (typeof setGuestMode === 'undefined') return
+  setGuestMode(enabled)
+}
+
+function getState(): GuestModeState | undefined {
+  let value = localStorage.getItem("guestMode")
+  return value ? JSON.parse(value) : undefined
+}
+
+export function isGuestMode(): boolean {
+  return getState() !== undefined
+}
+
+// Throws an error if the history, the tags or the preferences must not be
+// modified because the guest mode is on.
+export function requireNotGuest() {
+  if (isGuestMode()) {
+    throw new GuestModeError()
+  }
+}
+
+// Stops capturing the clipboard and makes the history read-only. The items
+// can still be copied and pasted.
+export function enterGuestMode() {
+  if (isGuestMode()) {
+    return
+  }
+  let state: GuestModeState = {capturePaused: getCaptureState().paused}
+  localStorage.setItem("guestMode", JSON.stringify(state))
+  pauseCapture()
+  notifyApp(true)
+  emitter.emit("GuestModeChanged", true)
+  console.log('✅ Guest mode entered')
+}
+
+// Exits the guest mode after the user is authenticated with the app lock
+// passphrase or, if no passphrase is given, with Touch ID or the system
+// password.
+export async function exitGuestMode(passphrase?: string): Promise<boolean> {
+  let state = getState()
+  if (!state) {
+    return true
+  }
+  if (!await verifyUser(passphrase)) {
+    return false
+  }
+  localStorage.removeItem("guestMode")
+  if (!state.capturePaused) {
+    resumeCapture()
+  }
+  notifyApp(false)
+  emitter.emit("GuestModeChanged", false)
+  console.log('✅ Guest mode exited')
+  return true
+}
+
+// Keeps the capturing paused after the app restarts in guest mode.
+export function restoreGuestMode() {
+  if (isGuestMode()) {
+    pauseCapture()
+    notifyApp(true)
+  }
+}
@@ -0,0 +1,133 @@
+import {getHistoryItemsCount, getLastCapturedItem} from "@/data";
+import {getLastIntegrityReport} from "@/integrity";
+import {getCaptureState} from "@/capture";
+import {getDoubleTapShortcut, getShortcuts} from "@/shortcuts";
+import {DoubleTapModifier} from "@/pref";
+import {getTrayStatus, TrayStatus} from "@/tray";
+import {syncManager} from "@/syncmanager";
+import {SyncStatus} from "@/sync";
+import {getTasksHealth, TaskHealth} from "@/supervisor";
+import {getPendingImageTextRecognitions} from "@/ocr";
+import {getRestoreJob, JobStatus} from "@/restore";
+
+declare const isAppPaused: () => boolean;
+
+export enum HealthStatus {
+  Ok = "ok",
+  Degraded = "degraded",
+}
+
+export type DatabaseHealth = {
+  items: number
+  lastIntegrityCheck?: Date
+  integrityProblems?: number
+}
+
+export type MonitorHealth = {
+  running: boolean
+  paused: boolean
+  // The time in milliseconds since the last item was captured.
+  lastEventAgeMs?: number
+}
+
+export type ShortcutsHealth = {
+  registered: number
+  doubleTapEnabled: boolean
+  doubleTapActive: boolean
+}
+
+export type SyncHealth = SyncStatus & {
+  running: boolean
+}
+
+export type BackgroundJobs = {
+  tasks: TaskHealth[]
+  imageTextRecognitions: number
+  restoreInProgress: boolean
+}
+
+export type AppHealth = {
+  status: HealthStatus
+  // The subsystems that have problems.
+  problems: string[]
+  database: DatabaseHealth
+  monitor: MonitorHealth
+  shortcuts: ShortcutsHealth
+  tray: TrayStatus
+  sync: SyncHealth
+  jobs: BackgroundJobs
+}
+
+function getDatabaseHealth(): DatabaseHealth {
+  let report = getLastIntegrityReport()
+  return {
+    items: getHistoryItemsCount(),
+    lastIntegrityCheck: report?.checkedAt,
+    integrityProblems: report?.problems.length,
+  }
+}
+
+function getMonitorHealth(): MonitorHealth {
+  let paused = getCaptureState().paused
+  let last = getLastCapturedItem()
+  return {
+    running: typeof isAppPaused !== 'undefined' && !paused,
+    paused,
+    lastEventAgeMs: last ? Date.now() - new Date(last.copyTime).getTime() : undefined,
+  }
+}
+
+function getShortcutsHealth(): ShortcutsHealth {
+  let doubleTap = getDoubleTapShortcut()
+  return {
+    registered: getShortcuts().filter(shortcut => shortcut.shortcut).length,
+    doubleTapEnabled: doubleTap.modifier !== DoubleTapModifier.NONE,
+    doubleTapActive: doubleTap.active,
+  }
+}
+
+function getBackgroundJobs(): BackgroundJobs {
+  return {
+    tasks: getTasksHealth(),
+    imageTextRecognitions: getPendingImageTextRecognitions(),
+    restoreInProgress: getRestoreJob()?.status === JobStatus.Running,
+  }
+}
+
+function findProblems(health: Omit<AppHealth, "status" | "problems">): string[] {
+  let problems: string[] = []
+  if (health.database.integrityProblems) {
+    problems.push("database")
+  }
+  if (health.shortcuts.doubleTapEnabled && !health.shortcuts.doubleTapActive) {
+    problems.push("shortcuts")
+  }
+  if (health.sync.configured && (!health.sync.running || health.sync.lastError)) {
+    problems.push("sync")
+  }
+  for (const task of health.jobs.tasks) {
+    if (!task.alive) {
+      problems.push(`task:${task.name}`)
+    }
+  }
+  return problems
+}
+
+// Collects the state of all the subsystems into one report for the
+// diagnostics.
+export function getAppHealth(): AppHealth {
+  let health = {
+    database: getDatabaseHealth(),
+    monitor: getMonitorHealth(),
+    shortcuts: getShortcutsHealth(),
+    tray: getTrayStatus(),
+    sync: {...syncManager.status, running: syncManager.isAlive()},
+    jobs: getBackgroundJobs(),
+  }
+  let problems = findProblems(health)
+  return {
+    status: problems.length > 0 ? HealthStatus.Degraded : HealthStatus.Ok,
+    problems,
+    ...health,
+  }
+}
@@ -0,0 +1,42 @@
+import {emitter} from "@/actions";
+import {LruCache} from "@/lib/lru";
+import {recordCacheLookup} from "@/metrics";
+
+// The name of the cache in the metrics.
+const kCacheName = "history_page"
+// The number of the cached pages, e.g. the first page for every sort type
+// and limit requested by the clients.
+const kMaxCachedPages = 16
+
+const cache = new LruCache<string, unknown[]>(kMaxCachedPages)
+let subscribed = false
+
+function invalidate() {
+  cache.clear()
+}
+
+function subscribe() {
+  if (subscribed) {
+    return
+  }
+  subscribed = true
+  emitter.on("HistoryChanged", invalidate)
+  emitter.on("HistoryItemAdded", invalidate)
+  emitter.on("HistoryItemUpdated", invalidate)
+  emitter.on("HistoryItemDeleted", invalidate)
+  emitter.on("HistoryCleared", invalidate)
+  emitter.on("UpdateItemById", invalidate)
+}
+
+// Returns the cached page of the history or loads and caches it. The cache
+// is invalidated on every change of the history.
+export function getHistoryPage<T>(key: string, load: () => T[]): T[] {
+  subscribe()
+  let page = cache.get(key)
+  recordCacheLookup(kCacheName, page !== undefined)
+  if (page === undefined) {
+    page = load()
+    cache.set(key, page)
+  }
+  return page as T[]
+}
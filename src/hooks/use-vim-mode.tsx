@@ -1,5 +1,6 @@
 import { useState, useCallback, useEffect } from 'react'
 import { prefGetVimModeEnabled, prefSetVimModeEnabled, prefGetVimShowStatusLine, prefGetVimPanelNavigation } from '@/pref'
+import { isGuestMode } from '@/guest'
 
 // Vim mode types
 export type VimMode = 'normal' | 'visual' | 'visual-line'
@@ -31,7 +32,9 @@ export const useVimMode = () => {
 
   // Enable/disable vim mode
   const setEnabled = useCallback((enabled: boolean) => {
-    prefSetVimModeEnabled(enabled) // Save to preferences
+    if (!isGuestMode()) {
+      prefSetVimModeEnabled(enabled) // Save to preferences
+    }
     setState(prev => ({
       ...prev,
       enabled,
@@ -0,0 +1,121 @@
+import {addClip, Clip, ClipType, getAllClips} from "@/db";
+import {getClipTypeFromText} from "@/lib/utils";
+import {Capability, requireCapability} from "@/capabilities";
+
+declare const readTextFile: (filePath: string) => string;
+declare const readMaccyHistory: (dbPath: string) => string;
+
+export enum ImportSource {
+  Maccy = "maccy",
+  Clipy = "clipy",
+  JSON = "json",
+}
+
+export interface ImportReport {
+  imported: number;
+  skipped: number;
+}
+
+// An entry parsed from a third-party clipboard manager history.
+interface ImportEntry {
+  content: string;
+  name?: string;
+  copyTime?: number | string;
+  numberOfCopies?: number;
+  favorite?: boolean;
+  sourceApp?: string;
+}
+
+function parseMaccy(path: string): ImportEntry[] {
+  requireCapability(Capability.MaccyImport)
+  const json = readMaccyHistory(path)
+  if (!json) {
+    throw new Error(`Failed to read Maccy database: ${path}`)
+  }
+  return JSON.parse(json)
+}
+
+// Parses the Clipy snippets XML file exported via "Snippets > Export".
+export function parseClipy(xml: string): ImportEntry[] {
+  const doc = new DOMParser().parseFromString(xml, "application/xml")
+  if (doc.getElementsByTagName("parsererror").length > 0) {
+    throw new Error('Invalid Clipy snippets file')
+  }
+  let entries: ImportEntry[] = []
+  const snippets = doc.getElementsByTagName("snippet")
+  for (let i = 0; i < snippets.length; i++) {
+    const snippet = snippets[i]
+    const title = snippet.getElementsByTagName("title")[0]?.textContent || ""
+    const content = snippet.getElementsByTagName("content")[0]?.textContent || ""
+    entries.push({content, name: title !== content ? title : ""})
+  }
+  return entries
+}
+
+// Parses a JSON array of entries. Both the ClipBook history export format and
+// the backup format with the "clips" property are supported.
+export function parseJSON(json: string): ImportEntry[] {
+  const data = JSON.parse(json)
+  const entries = Array.isArray(data) ? data : data.clips
+  if (!Array.isArray(entries)) {
+    throw new Error('Invalid JSON: an array of entries is required')
+  }
+  return entries
+}
+
+function readEntries(source: ImportSource, path: string): ImportEntry[] {
+  if (source === ImportSource.Maccy) {
+    return parseMaccy(path)
+  }
+  requireCapability(Capability.FileSystem)
+  const text = readTextFile(path)
+  if (!text) {
+    throw new Error(`Failed to read file: ${path}`)
+  }
+  return source === ImportSource.Clipy ? parseClipy(text) : parseJSON(text)
+}
+
+function toClip(entry: ImportEntry): Clip {
+  let clip = new Clip(getClipTypeFromText(entry.content), entry.content, entry.sourceApp || "")
+  clip.name = entry.name || ""
+  clip.favorite = !!entry.favorite
+  clip.numberOfCopies = entry.numberOfCopies && entry.numberOfCopies > 0 ? entry.numberOfCopies : 1
+  if (entry.copyTime) {
+    let copyTime = new Date(entry.copyTime)
+    if (!isNaN(copyTime.getTime())) {
+      clip.copyTime = copyTime
+    }
+  }
+  return clip
+}
+
+// Imports text items from another clipboard manager. Items whose content
+// already exists in the history are skipped.
+export async function importHistory(source: ImportSource, path: string): Promise<ImportReport> {
+  try {
+    const entries = readEntries(source, path)
+    let existing = new Set<string>()
+    for (const clip of await getAllClips()) {
+      if (clip.type !== ClipType.Image && clip.type !== ClipType.File) {
+        existing.add(clip.content)
+      }
+    }
+
+    let report: ImportReport = {imported: 0, skipped: 0}
+    for (const entry of entries) {
+      if (!entry || typeof entry.content !== 'string' || entry.content.trim().length === 0
+          || existing.has(entry.content)) {
+        report.skipped++
+        continue
+      }
+      await addClip(toClip(entry))
+      existing.add(entry.content)
+      report.imported++
+    }
+    console.log(`✅ Imported ${report.imported} items from ${source}, skipped ${report.skipped}`)
+    return report
+  } catch (error) {
+    console.error('❌ Import failed:', error)
+    throw error
+  }
+}
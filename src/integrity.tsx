@@ -0,0 +1,175 @@
+import {addAuditLogEntry, AuditLogEntry, BackupData, Clip, ClipType, exportAllData, importData, LinkPreviewDetails} from "@/db";
+import {reloadHistory} from "@/data";
+import {emitter} from "@/actions";
+import {createBackup} from "@/backup";
+import {Capability, hasCapability} from "@/capabilities";
+
+export interface IntegrityProblem {
+  table: "history" | "linkPreviews";
+  id?: number;
+  problem: string;
+  // False if the row can't be salvaged and is dropped by the repair.
+  repairable: boolean;
+}
+
+export interface IntegrityReport {
+  checkedAt: Date;
+  quick: boolean;
+  checkedRows: number;
+  problems: IntegrityProblem[];
+  repair?: RepairResult;
+}
+
+export interface RepairResult {
+  backupFileName?: string;
+  fixedRows: number;
+  droppedRows: number;
+}
+
+export interface IntegrityCheckOptions {
+  // Checks only that all rows can be read and have valid ids.
+  quick?: boolean;
+  // Fixes the repairable rows, drops the others and replaces the database
+  // content with the salvaged rows. A backup is created first.
+  repair?: boolean;
+}
+
+let checkInProgress = false
+let lastReport: IntegrityReport | undefined
+
+function isValidDate(value: any): boolean {
+  return value !== undefined && value !== null && !isNaN(new Date(value).getTime())
+}
+
+function checkIds(table: IntegrityProblem["table"], rows: { id?: number }[], problems: IntegrityProblem[]) {
+  for (const row of rows) {
+    if (!Number.isInteger(row.id) || row.id! <= 0) {
+      problems.push({table, id: row.id, problem: "invalid id", repairable: false})
+    }
+  }
+}
+
+function checkClip(clip: Clip, problems: IntegrityProblem[]) {
+  let report = (problem: string, repairable: boolean) => {
+    problems.push({table: "history", id: clip.id, problem, repairable})
+  }
+  if (ClipType[clip.type] === undefined) {
+    report(`unknown type ${clip.type}`, false)
+  }
+  if (typeof clip.content !== "string") {
+    report("content is not a string", clip.type === ClipType.Image || clip.type === ClipType.File)
+  }
+  if (clip.type === ClipType.Image && !clip.imageFileName) {
+    report("image without image file", false)
+  }
+  if (clip.type === ClipType.File && !clip.filePath) {
+    report("file without file path", false)
+  }
+  if (!isValidDate(clip.copyTime)) {
+    report("invalid copy time", true)
+  }
+  if (typeof clip.numberOfCopies !== "number" || clip.numberOfCopies < 1) {
+    report("invalid number of copies", true)
+  }
+  if (clip.tags !== undefined && (!Array.isArray(clip.tags) || !clip.tags.every(Number.isInteger))) {
+    report("invalid tags", true)
+  }
+  if (clip.deletedAt !== undefined && !isValidDate(clip.deletedAt)) {
+    report("invalid deletion time", true)
+  }
+}
+
+function checkLinkPreview(preview: LinkPreviewDetails, problems: IntegrityProblem[]) {
+  if (typeof preview.url !== "string" || !preview.url) {
+    problems.push({table: "linkPreviews", id: preview.id, problem: "missing url", repairable: false})
+  }
+}
+
+// Fixes the repairable fields of the clip in place.
+function fixClip(clip: Clip) {
+  if (typeof clip.content !== "string") {
+    clip.content = ""
+  }
+  if (!isValidDate(clip.copyTime)) {
+    clip.copyTime = new Date()
+  }
+  if (typeof clip.numberOfCopies !== "number" || clip.numberOfCopies < 1) {
+    clip.numberOfCopies = 1
+  }
+  if (clip.tags !== undefined && (!Array.isArray(clip.tags) || !clip.tags.every(Number.isInteger))) {
+    clip.tags = Array.isArray(clip.tags) ? clip.tags.filter(Number.isInteger) : []
+  }
+  if (clip.deletedAt !== undefined && !isValidDate(clip.deletedAt)) {
+    clip.deletedAt = new Date()
+  }
+}
+
+export function findIntegrityProblems(data: BackupData, quick: boolean): IntegrityProblem[] {
+  let problems: IntegrityProblem[] = []
+  checkIds("history", data.clips, problems)
+  checkIds("linkPreviews", data.linkPreviews, problems)
+  if (!quick) {
+    data.clips.forEach(clip => checkClip(clip, problems))
+    data.linkPreviews.forEach(preview => checkLinkPreview(preview, problems))
+  }
+  return problems
+}
+
+// Writes the salvageable rows to the emptied database. Every row with an
+// unrepairable problem is dropped, the other problematic rows are fixed.
+async function repair(data: BackupData, problems: IntegrityProblem[]): Promise<RepairResult> {
+  let backupFileName = hasCapability(Capability.Backups) ? await createBackup() : undefined
+  let dropped = {
+    history: new Set(problems.filter(p => p.table === "history" && !p.repairable).map(p => p.id)),
+    linkPreviews: new Set(problems.filter(p => p.table === "linkPreviews" && !p.repairable).map(p => p.id)),
+  }
+  let fixed = new Set(problems.filter(p => p.table === "history" && p.repairable).map(p => p.id))
+  let clips = data.clips.filter(clip => !dropped.history.has(clip.id))
+  clips.filter(clip => fixed.has(clip.id)).forEach(fixClip)
+  let linkPreviews = data.linkPreviews.filter(preview => !dropped.linkPreviews.has(preview.id))
+  await importData({...data, clips, linkPreviews})
+  await reloadHistory()
+  emitter.emit("HistoryChanged")
+  let result: RepairResult = {
+    backupFileName,
+    fixedRows: [...fixed].filter(id => !dropped.history.has(id)).length,
+    droppedRows: data.clips.length - clips.length + data.linkPreviews.length - linkPreviews.length,
+  }
+  await addAuditLogEntry(new AuditLogEntry("database.repair", JSON.stringify(result)))
+  return result
+}
+
+// Verifies that all the rows of the database can be read and contain valid
+// data, and optionally repairs the database.
+export async function checkDatabaseIntegrity(options: IntegrityCheckOptions = {}): Promise<IntegrityReport> {
+  if (checkInProgress) {
+    throw new Error('Integrity check is already in progress')
+  }
+  checkInProgress = true
+  try {
+    let quick = !!options.quick && !options.repair
+    let data = await exportAllData()
+    let problems = findIntegrityProblems(data, quick)
+    let report: IntegrityReport = {
+      checkedAt: new Date(),
+      quick,
+      checkedRows: data.clips.length + data.linkPreviews.length,
+      problems,
+    }
+    if (options.repair && problems.length > 0) {
+      report.repair = await repair(data, problems)
+      console.log(`✅ Repaired database, fixed ${report.repair.fixedRows} and dropped ${report.repair.droppedRows} rows`)
+    }
+    if (problems.length > 0) {
+      console.error(`❌ Database integrity check found ${problems.length} problems`)
+    }
+    lastReport = report
+    return report
+  } finally {
+    checkInProgress = false
+  }
+}
+
+export function getLastIntegrityReport(): IntegrityReport | undefined {
+  return lastReport
+}
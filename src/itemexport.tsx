@@ -0,0 +1,70 @@
+import {Clip, ClipType, getHTML, getImageFileName} from "@/db";
+import {getHistoryItemById} from "@/data";
+import {getFullContent} from "@/blobs";
+import {requireUnlocked} from "@/applock";
+
+declare const getImagesDir: () => string;
+declare const isFileExists: (filePath: string) => boolean;
+declare const writeTextFile: (filePath: string, content: string) => boolean;
+declare const copyFile: (sourcePath: string, targetPath: string) => boolean;
+
+const kMaxFileNameLength = 50
+const kMarkdownRegex = /^(#{1,6} |[-*+] |\d+\. |> |```)|\[[^\]]+]\([^)]+\)|\*\*[^*]+\*\*/m
+
+function getFileExtension(item: Clip, content: string): string {
+  if (item.type === ClipType.Image) {
+    return "png"
+  }
+  if (getHTML(item)) {
+    return "html"
+  }
+  return kMarkdownRegex.test(content) ? "md" : "txt"
+}
+
+// Makes the file name from the item name or the first line of the text
+// without the characters that are not allowed in the file names.
+function getBaseFileName(item: Clip, content: string): string {
+  let text = item.name || (item.type === ClipType.Image ? "" : content.split("\n").find(line => line.trim()) ?? "")
+  let name = text
+      .replace(/[/\\:*?"<>|\x00-\x1f]/g, " ")
+      .replace(/\s+/g, " ")
+      .trim()
+      .replace(/^\.+/, "")
+      .slice(0, kMaxFileNameLength)
+      .trim()
+  return name || `ClipBook Item ${item.id}`
+}
+
+function getUniqueFilePath(dir: string, name: string, extension: string): string {
+  let path = `${dir}/${name}.${extension}`
+  for (let index = 2; isFileExists(path); index++) {
+    path = `${dir}/${name} ${index}.${extension}`
+  }
+  return path
+}
+
+// Writes the history item to a new file in the given directory: the images
+// as PNG, the HTML as .html, and the text as .md or .txt. Returns the path of
+// the created file, so it can be dragged to another app or moved with
+// "Save as…".
+export function exportItemToFile(id: number, dir: string): string {
+  requireUnlocked()
+  let item = getHistoryItemById(id)
+  if (!item) {
+    throw new Error(`History item not found: ${id}`)
+  }
+  if (item.type === ClipType.File) {
+    throw new Error(`Cannot export the file history item: ${id}`)
+  }
+  let content = item.type === ClipType.Image ? "" : getFullContent(item)
+  let extension = getFileExtension(item, content)
+  let path = getUniqueFilePath(dir.replace(/\/+$/, ""), getBaseFileName(item, content), extension)
+  let written = item.type === ClipType.Image ?
+      copyFile(getImagesDir() + "/" + getImageFileName(item), path) :
+      writeTextFile(path, extension === "html" ? getHTML(item) : content)
+  if (!written) {
+    throw new Error(`Failed to write file: ${path}`)
+  }
+  console.log(`✅ Exported history item ${id} to ${path}`)
+  return path
+}
@@ -0,0 +1,136 @@
+import {Clip, ClipType, getHTML, getRTF} from "@/db";
+import {addHistoryItem, getHistoryItemById} from "@/data";
+import {emitter} from "@/actions";
+import {prefGetLanSharingKey, prefShouldLanSharingEnabled} from "@/pref";
+import {requireUnlocked} from "@/applock";
+
+declare const startLanSharing: (deviceName: string, key: string) => boolean;
+declare const stopLanSharing: () => void;
+declare const getLanPeers: () => string;
+declare const sendToLanPeer: (peer: string, payload: string) => void;
+
+// The length of the generated sharing key in bytes.
+const kSharingKeyLength = 16
+
+// The format of the item sent to a peer.
+const kPayloadVersion = 1
+
+interface LanItem {
+  version: number;
+  content: string;
+  rtf: string;
+  html: string;
+}
+
+let sharingStarted = false
+
+function isSupported(): boolean {
+  return typeof startLanSharing !== 'undefined'
+}
+
+// Advertises this instance on the local network and starts discovering the
+// other instances. The instances must use the same sharing key.
+export function startSharing(): boolean {
+  if (!isSupported()) {
+    return false
+  }
+  let key = prefGetLanSharingKey()
+  if (!key) {
+    console.error('❌ LAN sharing key is not set')
+    return false
+  }
+  sharingStarted = startLanSharing("", key)
+  if (!sharingStarted) {
+    console.error('❌ Failed to start LAN sharing')
+  }
+  return sharingStarted
+}
+
+export function stopSharing() {
+  if (isSupported()) {
+    stopLanSharing()
+  }
+  sharingStarted = false
+}
+
+export function isSharingStarted(): boolean {
+  return sharingStarted
+}
+
+export function startSharingIfEnabled() {
+  if (prefShouldLanSharingEnabled() && !sharingStarted) {
+    startSharing()
+  }
+}
+
+// Applies the changed sharing preferences, e.g. a new sharing key.
+export function restartSharing() {
+  if (sharingStarted) {
+    stopSharing()
+  }
+  startSharingIfEnabled()
+}
+
+// Returns the names of the instances discovered on the local network.
+export function getPeers(): string[] {
+  if (!isSupported()) {
+    return []
+  }
+  return getLanPeers().split("*").filter(name => name.length > 0)
+}
+
+// Returns a random key to be entered on all devices that share the items.
+export function generateSharingKey(): string {
+  let bytes = crypto.getRandomValues(new Uint8Array(kSharingKeyLength))
+  return Array.from(bytes, byte => byte.toString(16).padStart(2, "0")).join("")
+}
+
+function toLanItem(item: Clip): LanItem {
+  return {
+    version: kPayloadVersion,
+    content: item.content,
+    rtf: getRTF(item),
+    html: getHTML(item),
+  }
+}
+
+// Sends the history item to the peer. The result is reported with the
+// "LanItemSent" event.
+export function sendItemToDevice(itemId: number, peer: string) {
+  if (!sharingStarted) {
+    throw new Error('LAN sharing is not started')
+  }
+  requireUnlocked()
+  let item = getHistoryItemById(itemId)
+  if (!item) {
+    throw new Error(`History item not found: ${itemId}`)
+  }
+  // Images and files refer to local files, so only text items can be sent.
+  if (item.type === ClipType.Image || item.type === ClipType.File) {
+    throw new Error(`Image and file items cannot be sent: ${itemId}`)
+  }
+  sendToLanPeer(peer, JSON.stringify(toLanItem(item)))
+}
+
+// Adds the item received from a peer to the history.
+export async function receiveLanItem(payload: string) {
+  try {
+    let item: LanItem = JSON.parse(payload)
+    if (item.version !== kPayloadVersion || typeof item.content !== "string" || !item.content) {
+      console.error('❌ Unsupported item received from LAN peer')
+      return
+    }
+    await addHistoryItem(item.content, "", "", "", 0, 0, 0, "", "", "", "", 0, false,
+        item.rtf ?? "", item.html ?? "")
+    emitter.emit("HistoryChanged")
+  } catch (error) {
+    console.error('❌ Failed to receive item from LAN peer:', error)
+  }
+}
+
+export function lanItemSent(peer: string, success: boolean) {
+  if (!success) {
+    console.error(`❌ Failed to send item to ${peer}`)
+  }
+  emitter.emit("LanItemSent", {peer, success})
+}
@@ -0,0 +1,89 @@
+import {parseColor} from "@/lib/colors";
+
+export enum DetectedKind {
+  Url = "url",
+  Email = "email",
+  PhoneNumber = "phone",
+  Color = "color",
+  FilePath = "path",
+  JSON = "json",
+  Code = "code",
+  IPAddress = "ip",
+  Text = "text",
+}
+
+const kUrlRegex = /^(https?|ftp):\/\/[^\s/$.?#].\S*$/i
+const kEmailRegex = /^[^\s@]+@[^\s@]+\.[^\s@]+$/
+const kPhoneRegex = /^\+?[\d\s().-]{7,20}$/
+const kFilePathRegex = /^(~|\/|\.{1,2}\/)[^\0\n]*$/
+const kIPv4Regex = /^(25[0-5]|2[0-4]\d|1?\d?\d)(\.(25[0-5]|2[0-4]\d|1?\d?\d)){3}(:\d{1,5})?$/
+const kIPv6Regex = /^(?:[0-9a-f]{1,4}:){2,7}[0-9a-f]{0,4}$/i
+const kCodeRegexes = [
+  /^\s*(import|export|package|using|#include)\b/m,
+  /^\s*(function|def|fn|func|class|struct|interface|enum)\s+\w+/m,
+  /^\s*(const|let|var|val)\s+\w+\s*=/m,
+  /[;{}]\s*$/m,
+  /=>|->|::|\+\+|&&|\|\|/,
+]
+
+function isJSON(text: string): boolean {
+  if (!/^[{[]/.test(text)) {
+    return false
+  }
+  try {
+    JSON.parse(text)
+    return true
+  } catch (e) {
+    return false
+  }
+}
+
+function isPhoneNumber(text: string): boolean {
+  if (!kPhoneRegex.test(text)) {
+    return false
+  }
+  const digits = text.replace(/\D/g, "")
+  return digits.length >= 7 && digits.length <= 15
+}
+
+function isCode(text: string): boolean {
+  let matches = kCodeRegexes.filter(regex => regex.test(text)).length
+  // A single line needs stronger evidence than a multiline snippet.
+  return text.includes("\n") ? matches >= 2 : matches >= 3
+}
+
+// Detects the kind of the given text content.
+export function classify(content: string): DetectedKind {
+  const text = content.trim()
+  if (text.length === 0) {
+    return DetectedKind.Text
+  }
+  // The #hex, rgb(), and hsl() colors.
+  if (parseColor(text)) {
+    return DetectedKind.Color
+  }
+  if (!/\s/.test(text)) {
+    if (kUrlRegex.test(text)) {
+      return DetectedKind.Url
+    }
+    if (kEmailRegex.test(text)) {
+      return DetectedKind.Email
+    }
+    if (kIPv4Regex.test(text) || kIPv6Regex.test(text)) {
+      return DetectedKind.IPAddress
+    }
+  }
+  if (isPhoneNumber(text)) {
+    return DetectedKind.PhoneNumber
+  }
+  if (!text.includes("\n") && kFilePathRegex.test(text)) {
+    return DetectedKind.FilePath
+  }
+  if (isJSON(text)) {
+    return DetectedKind.JSON
+  }
+  if (isCode(text)) {
+    return DetectedKind.Code
+  }
+  return DetectedKind.Text
+}
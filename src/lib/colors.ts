@@ -0,0 +1,124 @@
+export type ColorFormat = "hex" | "rgb" | "hsl"
+
+export const kColorFormats: ColorFormat[] = ["hex", "rgb", "hsl"]
+
+// The color channels: red, green, and blue are 0-255, alpha is 0-1.
+export interface RGBA {
+  r: number;
+  g: number;
+  b: number;
+  a: number;
+}
+
+const kHexRegex = /^#([0-9a-f]{3}|[0-9a-f]{4}|[0-9a-f]{6}|[0-9a-f]{8})$/i
+// Both the legacy comma-separated and the modern space-separated syntax,
+// e.g. rgb(255, 100, 3), rgba(255, 100, 3, 0.8), rgb(255 100 3 / 80%).
+const kRgbRegex = /^rgba?\(\s*([\d.]+%?)\s*[,\s]\s*([\d.]+%?)\s*[,\s]\s*([\d.]+%?)\s*(?:[,/]\s*([\d.]+%?)\s*)?\)$/i
+const kHslRegex = /^hsla?\(\s*([\d.]+)(?:deg)?\s*[,\s]\s*([\d.]+)%\s*[,\s]\s*([\d.]+)%\s*(?:[,/]\s*([\d.]+%?)\s*)?\)$/i
+
+function clamp(value: number, min: number, max: number): number {
+  return Math.min(max, Math.max(min, value))
+}
+
+function parseChannel(value: string): number {
+  return value.endsWith("%") ? parseFloat(value) * 2.55 : parseFloat(value)
+}
+
+function parseAlpha(value: string | undefined): number {
+  if (value === undefined) {
+    return 1
+  }
+  return clamp(value.endsWith("%") ? parseFloat(value) / 100 : parseFloat(value), 0, 1)
+}
+
+function parseHex(hex: string): RGBA {
+  let digits = hex.substring(1)
+  if (digits.length <= 4) {
+    digits = digits.split("").map(digit => digit + digit).join("")
+  }
+  let channel = (index: number) => parseInt(digits.substring(index, index + 2), 16)
+  return {r: channel(0), g: channel(2), b: channel(4), a: digits.length === 8 ? channel(6) / 255 : 1}
+}
+
+function hslToRgb(h: number, s: number, l: number): [number, number, number] {
+  h = ((h % 360) + 360) % 360
+  s = clamp(s, 0, 100) / 100
+  l = clamp(l, 0, 100) / 100
+  let c = (1 - Math.abs(2 * l - 1)) * s
+  let x = c * (1 - Math.abs((h / 60) % 2 - 1))
+  let m = l - c / 2
+  let [r, g, b] = h < 60 ? [c, x, 0] : h < 120 ? [x, c, 0] : h < 180 ? [0, c, x] :
+      h < 240 ? [0, x, c] : h < 300 ? [x, 0, c] : [c, 0, x]
+  return [(r + m) * 255, (g + m) * 255, (b + m) * 255]
+}
+
+function rgbToHsl(color: RGBA): [number, number, number] {
+  let r = color.r / 255
+  let g = color.g / 255
+  let b = color.b / 255
+  let max = Math.max(r, g, b)
+  let min = Math.min(r, g, b)
+  let l = (max + min) / 2
+  if (max === min) {
+    return [0, 0, l * 100]
+  }
+  let d = max - min
+  let s = l > 0.5 ? d / (2 - max - min) : d / (max + min)
+  let h = max === r ? (g - b) / d + (g < b ? 6 : 0) : max === g ? (b - r) / d + 2 : (r - g) / d + 4
+  return [h * 60, s * 100, l * 100]
+}
+
+// Parses the color in the #hex, rgb(), or hsl() notation. Returns undefined
+// if the text isn't a color.
+export function parseColor(text: string): RGBA | undefined {
+  let value = text.trim()
+  if (kHexRegex.test(value)) {
+    return parseHex(value)
+  }
+  let match = value.match(kRgbRegex)
+  if (match) {
+    let [r, g, b] = [match[1], match[2], match[3]].map(channel => Math.round(clamp(parseChannel(channel), 0, 255)))
+    return {r, g, b, a: parseAlpha(match[4])}
+  }
+  match = value.match(kHslRegex)
+  if (match) {
+    let [r, g, b] = hslToRgb(parseFloat(match[1]), parseFloat(match[2]), parseFloat(match[3]))
+    return {r: Math.round(r), g: Math.round(g), b: Math.round(b), a: parseAlpha(match[4])}
+  }
+  return undefined
+}
+
+function toHex(value: number): string {
+  return Math.round(value).toString(16).padStart(2, "0")
+}
+
+function formatAlpha(alpha: number): string {
+  return parseFloat(alpha.toFixed(3)).toString()
+}
+
+export function formatColor(color: RGBA, format: ColorFormat): string {
+  let opaque = color.a >= 1
+  switch (format) {
+    case "hex":
+      return "#" + toHex(color.r) + toHex(color.g) + toHex(color.b) + (opaque ? "" : toHex(color.a * 255))
+    case "rgb":
+      return opaque ? `rgb(${color.r}, ${color.g}, ${color.b})` :
+          `rgba(${color.r}, ${color.g}, ${color.b}, ${formatAlpha(color.a)})`
+    case "hsl": {
+      let [h, s, l] = rgbToHsl(color).map(Math.round)
+      return opaque ? `hsl(${h}, ${s}%, ${l}%)` : `hsla(${h}, ${s}%, ${l}%, ${formatAlpha(color.a)})`
+    }
+  }
+}
+
+// Returns the color normalized to the lowercase #rrggbb or #rrggbbaa hex
+// notation, or undefined if the text isn't a color.
+export function normalizeColor(text: string): string | undefined {
+  let color = parseColor(text)
+  return color ? formatColor(color, "hex") : undefined
+}
+
+export function convertColorText(text: string, format: ColorFormat): string | undefined {
+  let color = parseColor(text)
+  return color ? formatColor(color, format) : undefined
+}
@@ -0,0 +1,93 @@
+// The number of PBKDF2 iterations used to derive the key from the passphrase.
+const kKeyIterations = 210000
+
+// The encrypted payload. The binary values are base64-encoded.
+export interface EncryptedData {
+  version: number;
+  salt: string;
+  iv: string;
+  data: string;
+}
+
+function toBase64(bytes: Uint8Array): string {
+  let binary = ""
+  for (let i = 0; i < bytes.length; i++) {
+    binary += String.fromCharCode(bytes[i])
+  }
+  return btoa(binary)
+}
+
+function fromBase64(base64: string): Uint8Array {
+  let binary = atob(base64)
+  let bytes = new Uint8Array(binary.length)
+  for (let i = 0; i < binary.length; i++) {
+    bytes[i] = binary.charCodeAt(i)
+  }
+  return bytes
+}
+
+async function deriveKey(passphrase: string, salt: Uint8Array): Promise<CryptoKey> {
+  let material = await crypto.subtle.importKey("raw", new TextEncoder().encode(passphrase),
+      "PBKDF2", false, ["deriveKey"])
+  return crypto.subtle.deriveKey({name: "PBKDF2", salt, iterations: kKeyIterations, hash: "SHA-256"},
+      material, {name: "AES-GCM", length: 256}, false, ["encrypt", "decrypt"])
+}
+
+// Encrypts the text with AES-GCM using a key derived from the passphrase.
+export async function encryptText(text: string, passphrase: string): Promise<EncryptedData> {
+  let salt = crypto.getRandomValues(new Uint8Array(16))
+  let iv = crypto.getRandomValues(new Uint8Array(12))
+  let key = await deriveKey(passphrase, salt)
+  let data = await crypto.subtle.encrypt({name: "AES-GCM", iv}, key, new TextEncoder().encode(text))
+  return {
+    version: 1,
+    salt: toBase64(salt),
+    iv: toBase64(iv),
+    data: toBase64(new Uint8Array(data)),
+  }
+}
+
+// Decrypts the data encrypted with encryptText(). Throws an error if the
+// passphrase is wrong or the data has been tampered with.
+export async function decryptText(encrypted: EncryptedData, passphrase: string): Promise<string> {
+  let key = await deriveKey(passphrase, fromBase64(encrypted.salt))
+  let data = await crypto.subtle.decrypt({name: "AES-GCM", iv: fromBase64(encrypted.iv)}, key,
+      fromBase64(encrypted.data))
+  return new TextDecoder().decode(data)
+}
+
+// The passphrase hash stored instead of the passphrase itself.
+export interface PassphraseHash {
+  salt: string;
+  hash: string;
+}
+
+async function derivePassphraseBits(passphrase: string, salt: Uint8Array): Promise<Uint8Array> {
+  let material = await crypto.subtle.importKey("raw", new TextEncoder().encode(passphrase),
+      "PBKDF2", false, ["deriveBits"])
+  let bits = await crypto.subtle.deriveBits({name: "PBKDF2", salt, iterations: kKeyIterations, hash: "SHA-256"},
+      material, 256)
+  return new Uint8Array(bits)
+}
+
+export async function hashPassphrase(passphrase: string): Promise<PassphraseHash> {
+  let salt = crypto.getRandomValues(new Uint8Array(16))
+  return {
+    salt: toBase64(salt),
+    hash: toBase64(await derivePassphraseBits(passphrase, salt)),
+  }
+}
+
+export async function verifyPassphrase(passphrase: string, stored: PassphraseHash): Promise<boolean> {
+  let hash = await derivePassphraseBits(passphrase, fromBase64(stored.salt))
+  let expected = fromBase64(stored.hash)
+  if (hash.length !== expected.length) {
+    return false
+  }
+  // Compare in constant time.
+  let diff = 0
+  for (let i = 0; i < hash.length; i++) {
+    diff |= hash[i] ^ expected[i]
+  }
+  return diff === 0
+}
@@ -0,0 +1,100 @@
+export enum ErrorCode {
+  // The user hasn't granted the permission required by the command.
+  PermissionDenied = "permission_denied",
+  // The app is locked and must be unlocked first.
+  Locked = "locked",
+  // The guest mode is on and the history can't be modified.
+  ReadOnly = "read_only",
+  // The feature isn't available in this version of the app or on this system.
+  Unavailable = "unavailable",
+  NotFound = "not_found",
+  InvalidArgument = "invalid_argument",
+  Conflict = "conflict",
+  // The database is used by another operation, e.g. a long transaction.
+  DatabaseBusy = "database_busy",
+  // The database was created by a newer version of the app.
+  DatabaseIncompatible = "database_incompatible",
+  // There's no space left to store the data.
+  StorageFull = "storage_full",
+  Storage = "storage_error",
+  Clipboard = "clipboard_error",
+  Internal = "internal_error",
+}
+
+// The error codes of the failures that may succeed if the command is
+// repeated later.
+const kRetryableCodes = new Set([ErrorCode.DatabaseBusy, ErrorCode.Storage])
+
+// The error that carries the code the UI and the API clients can rely on
+// instead of parsing the message.
+export class ClipBookError extends Error {
+  code: ErrorCode
+  retryable: boolean
+
+  constructor(code: ErrorCode, message: string, retryable: boolean = kRetryableCodes.has(code)) {
+    super(message)
+    this.name = "ClipBookError"
+    this.code = code
+    this.retryable = retryable
+  }
+}
+
+export type SerializedError = {
+  code: ErrorCode
+  message: string
+  retryable: boolean
+}
+
+// The error codes of the app errors and the errors thrown by the database
+// and the browser APIs by the error name.
+const kErrorCodesByName: Record<string, ErrorCode> = {
+  AppLockedError: ErrorCode.Locked,
+  GuestModeError: ErrorCode.ReadOnly,
+  CapabilityUnavailableError: ErrorCode.Unavailable,
+  DatabaseIncompatibleError: ErrorCode.DatabaseIncompatible,
+  ShortcutConflict: ErrorCode.Conflict,
+  NotAllowedError: ErrorCode.PermissionDenied,
+  SecurityError: ErrorCode.PermissionDenied,
+  QuotaExceededError: ErrorCode.StorageFull,
+  TimeoutError: ErrorCode.DatabaseBusy,
+  TransactionInactiveError: ErrorCode.DatabaseBusy,
+  DatabaseClosedError: ErrorCode.DatabaseBusy,
+  AbortError: ErrorCode.DatabaseBusy,
+  OpenFailedError: ErrorCode.Storage,
+  UnknownError: ErrorCode.Storage,
+  ReadOnlyError: ErrorCode.Storage,
+  ConstraintError: ErrorCode.Conflict,
+  DataError: ErrorCode.InvalidArgument,
+  InvalidArgumentError: ErrorCode.InvalidArgument,
+  ItemTooLargeError: ErrorCode.InvalidArgument,
+  RegexTimeoutError: ErrorCode.InvalidArgument,
+  NotFoundError: ErrorCode.NotFound,
+}
+
+function getErrorCode(error: Error): ErrorCode {
+  // Dexie wraps the IndexedDB errors and keeps the original one as `inner`.
+  let inner = (error as any).inner
+  if (!kErrorCodesByName[error.name] && inner instanceof Error) {
+    return getErrorCode(inner)
+  }
+  return kErrorCodesByName[error.name] ?? ErrorCode.Internal
+}
+
+export function toClipBookError(error: unknown): ClipBookError {
+  if (error instanceof ClipBookError) {
+    return error
+  }
+  if (error instanceof Error) {
+    return new ClipBookError(getErrorCode(error), error.message)
+  }
+  return new ClipBookError(ErrorCode.Internal, String(error))
+}
+
+export function isRetryableError(error: unknown): boolean {
+  return toClipBookError(error).retryable
+}
+
+export function serializeError(error: unknown): SerializedError {
+  let result = toClipBookError(error)
+  return {code: result.code, message: result.message, retryable: result.retryable}
+}
@@ -0,0 +1,74 @@
+import {foldForMatching} from "@/lib/normalize";
+
+// The bonuses for the matched characters that make the intuitive matches
+// rank higher, e.g. "gh" for "GitHub" or "ml" for "main_loop".
+const kConsecutiveBonus = 4
+const kWordStartBonus = 6
+const kFirstCharBonus = 8
+
+// The Chinese and Japanese text has no spaces between the words, so the
+// change of the script, e.g. from kanji to hiragana, is the best guess of
+// the word boundary.
+const kCJKScripts = [/\p{Script=Han}/u, /\p{Script=Hiragana}/u, /[\p{Script=Katakana}\u30fc]/u, /\p{Script=Hangul}/u]
+
+function cjkScript(char: string): number {
+  return kCJKScripts.findIndex(script => script.test(char))
+}
+
+function isWordStart(text: string, index: number): boolean {
+  if (index === 0) {
+    return true
+  }
+  let prev = text[index - 1]
+  let char = text[index]
+  if (!/[\p{L}\p{N}]/u.test(prev)) {
+    return true
+  }
+  let script = cjkScript(char)
+  if (script >= 0) {
+    return script !== cjkScript(prev)
+  }
+  return prev === prev.toLowerCase() && char !== char.toLowerCase()
+}
+
+// Returns the score of the fuzzy match of the query characters in the text
+// in the same order, or -1 if the text doesn't contain all the characters.
+// The higher the score, the better the match.
+export function fuzzyScore(query: string, text: string): number {
+  if (query.length === 0) {
+    return 0
+  }
+  let lowerQuery = foldForMatching(query)
+  let lowerText = foldForMatching(text)
+  // The exact substring match is always better than the scattered one.
+  let substringIndex = lowerText.indexOf(lowerQuery)
+  if (substringIndex >= 0) {
+    let score = lowerQuery.length * (1 + kConsecutiveBonus)
+    if (substringIndex === 0) {
+      score += kFirstCharBonus
+    } else if (isWordStart(text, substringIndex)) {
+      score += kWordStartBonus
+    }
+    return score
+  }
+  let score = 0
+  let queryIndex = 0
+  let prevMatch = -2
+  for (let i = 0; i < lowerText.length && queryIndex < lowerQuery.length; i++) {
+    if (lowerText[i] !== lowerQuery[queryIndex]) {
+      continue
+    }
+    score += 1
+    if (i === prevMatch + 1) {
+      score += kConsecutiveBonus
+    }
+    if (i === 0) {
+      score += kFirstCharBonus
+    } else if (isWordStart(text, i)) {
+      score += kWordStartBonus
+    }
+    prevMatch = i
+    queryIndex++
+  }
+  return queryIndex === lowerQuery.length ? score : -1
+}
@@ -0,0 +1,34 @@
+// Keeps the most recently used values up to the given capacity. The Map
+// iterates in the insertion order, so the first key is the least recently
+// used one.
+export class LruCache<K, V> {
+  private entries = new Map<K, V>()
+
+  constructor(private capacity: number) {
+  }
+
+  get(key: K): V | undefined {
+    let value = this.entries.get(key)
+    if (value !== undefined) {
+      this.entries.delete(key)
+      this.entries.set(key, value)
+    }
+    return value
+  }
+
+  set(key: K, value: V) {
+    this.entries.delete(key)
+    this.entries.set(key, value)
+    if (this.entries.size > this.capacity) {
+      this.entries.delete(this.entries.keys().next().value as K)
+    }
+  }
+
+  clear() {
+    this.entries.clear()
+  }
+
+  get size(): number {
+    return this.entries.size
+  }
+}
@@ -0,0 +1,53 @@
+// The diacritics left after the decomposition, e.g. the acute accent of "é"
+// decomposed to "e" + U+0301. Only the Latin, Greek, and Cyrillic diacritics
+// are removed, because the marks of the other scripts, e.g. the Japanese
+// dakuten, change the meaning of the character.
+const kCombiningMarks = /[\u0300-\u036f]/g
+
+// The case foldings that differ from the lower case.
+const kCaseFoldings: Record<string, string> = {
+  "ß": "ss",
+  "ς": "σ",
+  "ſ": "s",
+}
+const kCaseFoldingChars = new RegExp(`[${Object.keys(kCaseFoldings).join("")}]`, "g")
+
+// Converts the text to the form used for matching the search query: the
+// compatibility characters are replaced with their equivalents (NFKC),
+// the case is folded, and the diacritics are removed, so "Café" and
+// "ｃａｆｅ" both match "cafe".
+export function normalizeForSearch(text: string): string {
+  return text
+      .normalize("NFKD")
+      .replace(kCombiningMarks, "")
+      .normalize("NFKC")
+      .toLowerCase()
+      .replace(kCaseFoldingChars, char => kCaseFoldings[char])
+}
+
+let foldedChars = new Map<string, string>()
+
+function foldChar(char: string): string {
+  let folded = foldedChars.get(char)
+  if (folded === undefined) {
+    folded = normalizeForSearch(char)
+    // Keep the character if folding changes the length, so the positions
+    // in the folded text match the original text.
+    if (folded.length !== char.length) {
+      folded = char.toLowerCase().length === char.length ? char.toLowerCase() : char
+    }
+    foldedChars.set(char, folded)
+  }
+  return folded
+}
+
+// Like normalizeForSearch, but keeps the length of the text, so the match
+// positions can be mapped back to the original text.
+export function foldForMatching(text: string): string {
+  let result = ""
+  for (let i = 0; i < text.length; i++) {
+    let code = text.charCodeAt(i)
+    result += code < 0x80 ? text[i].toLowerCase() : foldChar(text[i])
+  }
+  return result
+}
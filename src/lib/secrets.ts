@@ -0,0 +1,73 @@
+// The number of trailing characters left visible in the masked text.
+const kVisibleChars = 4
+const kMaskChar = "•"
+
+const kSecretRegexes = [
+  // AWS access key id.
+  /\b(AKIA|ASIA)[0-9A-Z]{16}\b/,
+  // GitHub tokens.
+  /\bgh[pousr]_[A-Za-z0-9]{36,}\b/,
+  /\bgithub_pat_[A-Za-z0-9_]{60,}\b/,
+  // Slack tokens.
+  /\bxox[abposr]-[A-Za-z0-9-]{10,}\b/,
+  // Stripe secret and restricted keys.
+  /\b[sr]k_(live|test)_[A-Za-z0-9]{16,}\b/,
+  // Google API key.
+  /\bAIza[0-9A-Za-z_-]{35}\b/,
+  // OpenAI and Anthropic style API keys.
+  /\bsk-[A-Za-z0-9_-]{20,}\b/,
+  // JSON Web Token.
+  /\beyJ[A-Za-z0-9_-]{10,}\.eyJ[A-Za-z0-9_-]{10,}\.[A-Za-z0-9_-]{10,}\b/,
+  // PEM private key.
+  /-----BEGIN ([A-Z]+ )?PRIVATE KEY-----/,
+]
+
+// Card numbers are 13-19 digits optionally grouped with spaces or dashes.
+const kCardNumberRegex = /\b\d(?:[ -]?\d){12,18}\b/g
+
+// Checks the number with the Luhn algorithm used by the payment cards.
+export function isLuhnValid(digits: string): boolean {
+  let sum = 0
+  let double = false
+  for (let i = digits.length - 1; i >= 0; i--) {
+    let digit = digits.charCodeAt(i) - 48
+    if (digit < 0 || digit > 9) {
+      return false
+    }
+    if (double) {
+      digit *= 2
+      if (digit > 9) {
+        digit -= 9
+      }
+    }
+    sum += digit
+    double = !double
+  }
+  return digits.length > 0 && sum % 10 === 0
+}
+
+function containsCardNumber(text: string): boolean {
+  for (const match of text.matchAll(kCardNumberRegex)) {
+    let digits = match[0].replace(/\D/g, "")
+    if (digits.length >= 13 && digits.length <= 19 && isLuhnValid(digits)) {
+      return true
+    }
+  }
+  return false
+}
+
+// Returns true if the text looks like it contains an API key, a token,
+// a private key, or a payment card number.
+export function containsSecret(text: string): boolean {
+  return kSecretRegexes.some(regex => regex.test(text)) || containsCardNumber(text)
+}
+
+// Replaces the text with the mask characters leaving only its last few
+// characters visible.
+export function maskText(text: string): string {
+  let trimmed = text.trim()
+  if (trimmed.length <= kVisibleChars * 2) {
+    return kMaskChar.repeat(8)
+  }
+  return kMaskChar.repeat(8) + trimmed.substring(trimmed.length - kVisibleChars)
+}
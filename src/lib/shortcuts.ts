@@ -172,6 +172,15 @@ export const isShortcutMatch = (shortcut: string, event: KeyboardEvent): boolean
   return allModifiersMatch && event.code === key;
 };
 
+// Returns the shortcut in the form that doesn't depend on the order of the
+// modifiers and on whether the left or the right modifier key is used, so the
+// shortcuts can be compared.
+export function normalizeShortcut(shortcut: string): string {
+  const {key, modifiers} = parseShortcut(shortcut);
+  const kinds = new Set(modifiers.map(modifier => modifier.replace(/(Left|Right)$/, '')));
+  return ['Control', 'Alt', 'Shift', 'Meta'].filter(kind => kinds.has(kind)).concat(key).join(' + ');
+}
+
 export const isModifierKey = (key: string): boolean => {
   return ['MetaLeft', 'MetaRight', 'AltLeft', 'AltRight', 'ControlLeft', 'ControlRight', 'ShiftLeft', 'ShiftRight'].includes(key);
 }
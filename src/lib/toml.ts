@@ -0,0 +1,300 @@
+// A parser of the TOML subset used by the config file: the tables, the
+// dotted keys, the strings, the numbers, the booleans, the arrays, and the
+// inline tables. The multi-line strings, the dates, and the arrays of
+// tables are not supported.
+
+export type TomlValue = string | number | boolean | TomlValue[] | TomlTable
+export type TomlTable = { [key: string]: TomlValue }
+
+export class TomlError extends Error {
+  line: number
+
+  constructor(message: string, line: number) {
+    super(`${message} at line ${line}`)
+    this.name = "TomlError"
+    this.line = line
+  }
+}
+
+const kBareKeyChar = /[A-Za-z0-9_-]/
+const kEscapes: Record<string, string> = {
+  "b": "\b",
+  "t": "\t",
+  "n": "\n",
+  "f": "\f",
+  "r": "\r",
+  "\"": "\"",
+  "\\": "\\",
+}
+
+function isTable(value: TomlValue | undefined): value is TomlTable {
+  return typeof value === "object" && !Array.isArray(value)
+}
+
+class Parser {
+  private pos = 0
+  private line = 1
+
+  constructor(private readonly text: string) {
+  }
+
+  parse(): TomlTable {
+    let root: TomlTable = {}
+    let table = root
+    while (true) {
+      this.skipWhitespaceAndComments(true)
+      if (this.pos >= this.text.length) {
+        return root
+      }
+      if (this.peek() === "[") {
+        table = this.parseTableHeader(root)
+      } else {
+        this.parseKeyValue(table)
+      }
+      this.expectLineEnd()
+    }
+  }
+
+  private error(message: string): TomlError {
+    return new TomlError(message, this.line)
+  }
+
+  private peek(): string {
+    return this.text[this.pos]
+  }
+
+  private next(): string {
+    let char = this.text[this.pos++]
+    if (char === "\n") {
+      this.line++
+    }
+    return char
+  }
+
+  private skipWhitespaceAndComments(newlines: boolean) {
+    while (this.pos < this.text.length) {
+      let char = this.peek()
+      if (char === " " || char === "\t" || char === "\r" || (newlines && char === "\n")) {
+        this.next()
+      } else if (char === "#") {
+        while (this.pos < this.text.length && this.peek() !== "\n") {
+          this.next()
+        }
+      } else {
+        return
+      }
+    }
+  }
+
+  private expectLineEnd() {
+    this.skipWhitespaceAndComments(false)
+    if (this.pos < this.text.length && this.next() !== "\n") {
+      throw this.error("Expected the end of line")
+    }
+  }
+
+  private parseTableHeader(root: TomlTable): TomlTable {
+    this.next()
+    if (this.peek() === "[") {
+      throw this.error("Arrays of tables are not supported")
+    }
+    let keys = this.parseKey()
+    this.skipWhitespaceAndComments(false)
+    if (this.next() !== "]") {
+      throw this.error("Expected ]")
+    }
+    let table = root
+    for (const key of keys) {
+      let value = table[key]
+      if (value === undefined) {
+        value = table[key] = {}
+      } else if (!isTable(value)) {
+        throw this.error(`The key is already defined: ${key}`)
+      }
+      table = value
+    }
+    return table
+  }
+
+  private parseKey(): string[] {
+    let keys: string[] = []
+    while (true) {
+      this.skipWhitespaceAndComments(false)
+      let char = this.peek()
+      if (char === "\"") {
+        keys.push(this.parseBasicString())
+      } else if (char === "'") {
+        keys.push(this.parseLiteralString())
+      } else {
+        let start = this.pos
+        while (this.pos < this.text.length && kBareKeyChar.test(this.peek())) {
+          this.next()
+        }
+        if (start === this.pos) {
+          throw this.error("Expected a key")
+        }
+        keys.push(this.text.slice(start, this.pos))
+      }
+      this.skipWhitespaceAndComments(false)
+      if (this.peek() !== ".") {
+        return keys
+      }
+      this.next()
+    }
+  }
+
+  private parseKeyValue(table: TomlTable) {
+    let keys = this.parseKey()
+    if (this.next() !== "=") {
+      throw this.error("Expected =")
+    }
+    this.skipWhitespaceAndComments(false)
+    let value = this.parseValue()
+    let target = table
+    for (const key of keys.slice(0, -1)) {
+      let nested = target[key]
+      if (nested === undefined) {
+        nested = target[key] = {}
+      } else if (!isTable(nested)) {
+        throw this.error(`The key is already defined: ${key}`)
+      }
+      target = nested
+    }
+    let key = keys[keys.length - 1]
+    if (target[key] !== undefined) {
+      throw this.error(`The key is already defined: ${key}`)
+    }
+    target[key] = value
+  }
+
+  private parseValue(): TomlValue {
+    let char = this.peek()
+    if (char === "\"") {
+      return this.parseBasicString()
+    }
+    if (char === "'") {
+      return this.parseLiteralString()
+    }
+    if (char === "[") {
+      return this.parseArray()
+    }
+    if (char === "{") {
+      return this.parseInlineTable()
+    }
+    let start = this.pos
+    while (this.pos < this.text.length && /[A-Za-z0-9_.+-]/.test(this.peek())) {
+      this.next()
+    }
+    let token = this.text.slice(start, this.pos)
+    if (token === "true") {
+      return true
+    }
+    if (token === "false") {
+      return false
+    }
+    if (/^0x[0-9A-Fa-f_]+$/.test(token)) {
+      return parseInt(token.slice(2).replace(/_/g, ""), 16)
+    }
+    if (/^[+-]?\d[\d_]*$/.test(token)) {
+      return parseInt(token.replace(/_/g, ""), 10)
+    }
+    if (/^[+-]?\d[\d_]*(\.\d[\d_]*)?([eE][+-]?\d+)?$/.test(token)) {
+      return parseFloat(token.replace(/_/g, ""))
+    }
+    throw this.error(`Invalid value: ${token || char}`)
+  }
+
+  private parseBasicString(): string {
+    this.next()
+    if (this.text.startsWith("\"\"", this.pos)) {
+      throw this.error("Multi-line strings are not supported")
+    }
+    let result = ""
+    while (true) {
+      if (this.pos >= this.text.length || this.peek() === "\n") {
+        throw this.error("Unterminated string")
+      }
+      let char = this.next()
+      if (char === "\"") {
+        return result
+      }
+      if (char !== "\\") {
+        result += char
+        continue
+      }
+      let escape = this.next()
+      if (escape === "u" || escape === "U") {
+        let length = escape === "u" ? 4 : 8
+        let hex = this.text.slice(this.pos, this.pos + length)
+        if (!/^[0-9A-Fa-f]+$/.test(hex) || hex.length !== length) {
+          throw this.error("Invalid unicode escape")
+        }
+        this.pos += length
+        result += String.fromCodePoint(parseInt(hex, 16))
+      } else if (escape in kEscapes) {
+        result += kEscapes[escape]
+      } else {
+        throw this.error(`Invalid escape: \\${escape}`)
+      }
+    }
+  }
+
+  private parseLiteralString(): string {
+    this.next()
+    let end = this.text.indexOf("'", this.pos)
+    let newline = this.text.indexOf("\n", this.pos)
+    if (end < 0 || (newline >= 0 && newline < end)) {
+      throw this.error("Unterminated string")
+    }
+    let result = this.text.slice(this.pos, end)
+    this.pos = end + 1
+    return result
+  }
+
+  private parseArray(): TomlValue[] {
+    this.next()
+    let result: TomlValue[] = []
+    while (true) {
+      this.skipWhitespaceAndComments(true)
+      if (this.peek() === "]") {
+        this.next()
+        return result
+      }
+      result.push(this.parseValue())
+      this.skipWhitespaceAndComments(true)
+      let char = this.next()
+      if (char === "]") {
+        return result
+      }
+      if (char !== ",") {
+        throw this.error("Expected , or ]")
+      }
+    }
+  }
+
+  private parseInlineTable(): TomlTable {
+    this.next()
+    let result: TomlTable = {}
+    this.skipWhitespaceAndComments(false)
+    if (this.peek() === "}") {
+      this.next()
+      return result
+    }
+    while (true) {
+      this.parseKeyValue(result)
+      this.skipWhitespaceAndComments(false)
+      let char = this.next()
+      if (char === "}") {
+        return result
+      }
+      if (char !== ",") {
+        throw this.error("Expected , or }")
+      }
+    }
+  }
+}
+
+// Throws TomlError with the line number if the text is not valid.
+export function parseToml(text: string): TomlTable {
+  return new Parser(text).parse()
+}
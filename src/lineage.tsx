@@ -0,0 +1,68 @@
+import {Clip, ClipType} from "@/db";
+
+// How long after pasting a re-copied item is considered derived from it.
+const kMaxLineageDelayMs = 30 * 60 * 1000
+// The minimum similarity of a re-copied text to its ancestor.
+const kMinSimilarity = 0.5
+
+interface PastedItem {
+  id: number;
+  content: string;
+  time: number;
+}
+
+let lastPastedItem: PastedItem | undefined
+
+function bigrams(text: string): Map<string, number> {
+  let result = new Map<string, number>()
+  for (let i = 0; i < text.length - 1; i++) {
+    let bigram = text.substring(i, i + 2)
+    result.set(bigram, (result.get(bigram) || 0) + 1)
+  }
+  return result
+}
+
+// Returns the Sørensen–Dice coefficient of the two texts in the range [0, 1].
+export function textSimilarity(a: string, b: string): number {
+  if (a === b) {
+    return 1
+  }
+  if (a.length < 2 || b.length < 2) {
+    return 0
+  }
+  let aBigrams = bigrams(a)
+  let bBigrams = bigrams(b)
+  let intersection = 0
+  aBigrams.forEach((count, bigram) => {
+    intersection += Math.min(count, bBigrams.get(bigram) || 0)
+  })
+  return (2 * intersection) / (a.length + b.length - 2)
+}
+
+// Remembers the item pasted from ClipBook, so that its copy made in another
+// app can be linked to it.
+export function recordPastedItem(item: Clip) {
+  if (item.type === ClipType.Image || item.type === ClipType.File) {
+    return
+  }
+  lastPastedItem = {id: item.id!, content: item.content, time: Date.now()}
+}
+
+// Returns the id of the item the captured clip was derived from, or undefined
+// if the clip was not copied after pasting a similar item from ClipBook.
+export function findParentItemId(clip: Clip): number | undefined {
+  if (!lastPastedItem || clip.type === ClipType.Image || clip.type === ClipType.File) {
+    return undefined
+  }
+  if (Date.now() - lastPastedItem.time > kMaxLineageDelayMs) {
+    lastPastedItem = undefined
+    return undefined
+  }
+  if (clip.id === lastPastedItem.id) {
+    return undefined
+  }
+  if (textSimilarity(clip.content, lastPastedItem.content) < kMinSimilarity) {
+    return undefined
+  }
+  return lastPastedItem.id
+}
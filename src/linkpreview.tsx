@@ -0,0 +1,92 @@
+import {
+  Clip,
+  ClipType,
+  getAllLinkPreviews,
+  getLinkPreviewDetails,
+  LinkPreviewDetails,
+  saveLinkPreviewDetails
+} from "@/db";
+import {prefShouldFetchLinkPreviews} from "@/pref";
+import {emitter} from "@/actions";
+
+type FetchRequestCallback = {
+  run: (success: boolean, title: string, description: string, imageFileName: string, faviconFileName: string) => void;
+}
+
+declare const fetchLinkPreviewDetails: (url: string, callback: FetchRequestCallback) => void
+
+// The fetched link previews by URL, so they can be returned together with
+// the history items without reading the database.
+let previews = new Map<string, LinkPreviewDetails>()
+// The URLs being fetched, so the same page isn't requested twice.
+let pending = new Map<string, Promise<LinkPreviewDetails | undefined>>()
+let prefetchStarted = false
+
+export function isLinkPreviewFetchingEnabled(): boolean {
+  return typeof fetchLinkPreviewDetails !== 'undefined' && prefShouldFetchLinkPreviews()
+}
+
+export function getCachedLinkPreview(url: string): LinkPreviewDetails | undefined {
+  return previews.get(url)
+}
+
+function fetchDetails(url: string): Promise<LinkPreviewDetails | undefined> {
+  return new Promise(resolve => {
+    fetchLinkPreviewDetails(url, {
+      run: (success, title, description, imageFileName, faviconFileName) => {
+        resolve(success ? {url, title, description, imageFileName, faviconFileName} : undefined)
+      }
+    })
+  })
+}
+
+// Returns the stored link preview or fetches the page title, description,
+// and images if fetching is allowed in the privacy settings.
+export async function getLinkPreview(url: string): Promise<LinkPreviewDetails | undefined> {
+  let details = await getLinkPreviewDetails(url)
+  if (details) {
+    previews.set(url, details)
+    return details
+  }
+  if (!isLinkPreviewFetchingEnabled()) {
+    return undefined
+  }
+  let request = pending.get(url)
+  if (!request) {
+    request = fetchDetails(url).then(async details => {
+      if (details) {
+        await saveLinkPreviewDetails(details)
+        previews.set(url, details)
+      }
+      return details
+    }).finally(() => pending.delete(url))
+    pending.set(url, request)
+  }
+  return request
+}
+
+export function forgetLinkPreview(url: string) {
+  previews.delete(url)
+}
+
+function prefetch(item: Clip) {
+  if (item.type !== ClipType.Link || !isLinkPreviewFetchingEnabled()) {
+    return
+  }
+  getLinkPreview(item.content).catch(error => {
+    console.error('❌ Failed to fetch link preview:', error)
+  })
+}
+
+// Loads the stored link previews and fetches the previews of the new links
+// in the background.
+export async function startLinkPreviewFetching() {
+  if (prefetchStarted) {
+    return
+  }
+  prefetchStarted = true
+  for (const details of await getAllLinkPreviews()) {
+    previews.set(details.url, details)
+  }
+  emitter.on("HistoryItemAdded", prefetch)
+}
@@ -0,0 +1,120 @@
+import {LogLevel, prefGetLogLevel, prefSetLogLevel} from "@/pref";
+import {emitter} from "@/actions";
+import {requireNotGuest} from "@/guest";
+
+declare const writeLogLine: (line: string) => boolean;
+declare const readRecentLogLines: (count: number) => string;
+
+// The number of the latest entries kept in memory when the log files are not
+// available, e.g. in the browser.
+const kMaxMemoryEntries = 500
+
+const kLevelOrder = [LogLevel.DEBUG, LogLevel.INFO, LogLevel.WARN, LogLevel.ERROR]
+
+export interface LogEntry {
+  time: string;
+  level: LogLevel;
+  message: string;
+}
+
+let logLevel = LogLevel.INFO
+let memoryEntries: LogEntry[] = []
+let installed = false
+
+function formatArg(arg: unknown): string {
+  if (typeof arg === "string") {
+    return arg
+  }
+  if (arg instanceof Error) {
+    return arg.stack || `${arg.name}: ${arg.message}`
+  }
+  try {
+    return JSON.stringify(arg)
+  } catch {
+    return String(arg)
+  }
+}
+
+function log(level: LogLevel, args: unknown[]) {
+  if (kLevelOrder.indexOf(level) < kLevelOrder.indexOf(logLevel)) {
+    return
+  }
+  let entry: LogEntry = {
+    time: new Date().toISOString(),
+    level,
+    message: args.map(formatArg).join(" "),
+  }
+  if (typeof writeLogLine !== 'undefined') {
+    writeLogLine(JSON.stringify(entry))
+    return
+  }
+  memoryEntries.push(entry)
+  if (memoryEntries.length > kMaxMemoryEntries) {
+    memoryEntries.shift()
+  }
+}
+
+// Writes the console messages to the rotating log files in the app data
+// directory as JSON lines, so the problems can be investigated after the
+// fact. The messages are still printed to the console.
+export function installLogger() {
+  if (installed) {
+    return
+  }
+  installed = true
+  logLevel = prefGetLogLevel()
+  // The level may be changed in the config file.
+  emitter.on("PreferencesChanged", names => {
+    if (names.includes("app.log_level")) {
+      logLevel = prefGetLogLevel()
+    }
+  })
+  let methods: [string, LogLevel][] = [
+    ["debug", LogLevel.DEBUG],
+    ["log", LogLevel.INFO],
+    ["info", LogLevel.INFO],
+    ["warn", LogLevel.WARN],
+    ["error", LogLevel.ERROR],
+  ]
+  let target: any = console
+  for (const [method, level] of methods) {
+    let original = target[method].bind(console)
+    target[method] = (...args: unknown[]) => {
+      original(...args)
+      try {
+        log(level, args)
+      } catch {
+        // Logging must never break the app.
+      }
+    }
+  }
+}
+
+export function getLogLevel(): LogLevel {
+  return logLevel
+}
+
+export function setLogLevel(level: LogLevel) {
+  requireNotGuest()
+  logLevel = level
+  prefSetLogLevel(level)
+}
+
+// Returns up to the given number of the most recent log entries, the oldest
+// entry first.
+export function getRecentLogs(count: number): LogEntry[] {
+  if (typeof readRecentLogLines === 'undefined') {
+    return memoryEntries.slice(-count)
+  }
+  let entries: LogEntry[] = []
+  for (const line of readRecentLogLines(count).split("\n")) {
+    try {
+      if (line) {
+        entries.push(JSON.parse(line))
+      }
+    } catch {
+      // Skip the line truncated by a crash.
+    }
+  }
+  return entries
+}
@@ -0,0 +1,338 @@
+import {addMetricSamples, deleteMetricSamples, getMetricSamples, MetricSample} from "@/db";
+
+declare const getMemoryUsage: () => string;
+
+// The upper bounds of the latency histogram buckets in milliseconds. The
+// last bucket counts everything slower.
+const kBucketBoundsMs = [1, 2, 5, 10, 20, 50, 100, 200, 500, 1000, 2000, 5000, 10000, Infinity]
+// The number of the latest samples kept in memory for every command.
+const kMaxSamples = 1000
+// How long the samples are kept in the database.
+const kRetentionMs = 24 * 60 * 60 * 1000
+// How often the new samples are saved to the database.
+const kFlushIntervalMs = 60 * 1000
+// The maximum number of the samples waiting to be saved.
+const kMaxPendingSamples = 10000
+// The number of the latest memory usage samples kept in memory. The memory
+// is sampled every time the samples are saved, so it's the last hour.
+const kMaxMemorySamples = 60
+
+export type MetricsFormat = "json" | "prometheus"
+
+export interface CommandMetrics {
+  count: number;
+  errors: number;
+  totalMs: number;
+  maxMs: number;
+  p50Ms: number;
+  p95Ms: number;
+  p99Ms: number;
+}
+
+// The cumulative latency histogram since the launch.
+class LatencyHistogram {
+  buckets: number[] = kBucketBoundsMs.map(() => 0)
+  count = 0
+  errors = 0
+  totalMs = 0
+
+  record(durationMs: number, failed: boolean) {
+    this.buckets[kBucketBoundsMs.findIndex(bound => durationMs <= bound)]++
+    this.count++
+    this.totalMs += durationMs
+    if (failed) {
+      this.errors++
+    }
+  }
+}
+
+// Keeps the latest samples of the command in a ring buffer.
+class TimeSeries {
+  private samples: MetricSample[] = []
+  private next = 0
+
+  add(sample: MetricSample) {
+    if (this.samples.length < kMaxSamples) {
+      this.samples.push(sample)
+    } else {
+      this.samples[this.next] = sample
+      this.next = (this.next + 1) % kMaxSamples
+    }
+  }
+
+  getSamples(since: number): MetricSample[] {
+    return this.samples.filter(sample => sample.time.getTime() >= since)
+  }
+}
+
+type CommandStats = {
+  histogram: LatencyHistogram
+  series: TimeSeries
+}
+
+// The number of the cache lookups since the launch.
+export interface CacheMetrics {
+  hits: number;
+  misses: number;
+}
+
+// The memory used by the app process in bytes.
+export interface MemoryUsage {
+  residentSize: number;
+  peakResidentSize: number;
+  footprint: number;
+  allocatedSize: number;
+  allocatedBlocks: number;
+}
+
+export interface MemoryMetrics {
+  current?: MemoryUsage;
+  samples: (MemoryUsage & { time: Date })[];
+}
+
+let stats = new Map<string, CommandStats>()
+let cacheStats = new Map<string, CacheMetrics>()
+// The samples that haven't been saved to the database yet.
+let pendingSamples: MetricSample[] = []
+let memorySamples: (MemoryUsage & { time: Date })[] = []
+let flushTimer: ReturnType<typeof setInterval> | undefined
+let lastFlushTime = 0
+let restored = false
+
+function getStats(name: string): CommandStats {
+  let result = stats.get(name)
+  if (!result) {
+    result = {histogram: new LatencyHistogram(), series: new TimeSeries()}
+    stats.set(name, result)
+  }
+  return result
+}
+
+export function recordCommandLatency(name: string, durationMs: number, failed: boolean = false) {
+  let sample: MetricSample = {name, time: new Date(), durationMs, failed}
+  let commandStats = getStats(name)
+  commandStats.histogram.record(durationMs, failed)
+  commandStats.series.add(sample)
+  pendingSamples.push(sample)
+  if (pendingSamples.length > kMaxPendingSamples) {
+    pendingSamples.shift()
+  }
+}
+
+export function recordCacheLookup(name: string, hit: boolean) {
+  let cacheMetrics = cacheStats.get(name)
+  if (!cacheMetrics) {
+    cacheMetrics = {hits: 0, misses: 0}
+    cacheStats.set(name, cacheMetrics)
+  }
+  if (hit) {
+    cacheMetrics.hits++
+  } else {
+    cacheMetrics.misses++
+  }
+}
+
+export function getCacheMetrics(): Record<string, CacheMetrics> {
+  let result: Record<string, CacheMetrics> = {}
+  for (const [name, cacheMetrics] of cacheStats) {
+    result[name] = {...cacheMetrics}
+  }
+  return result
+}
+
+// Reads the memory usage of the app process with task_info(), so it's cheap
+// enough to be called on every request.
+function readMemoryUsage(): MemoryUsage | undefined {
+  if (typeof getMemoryUsage === 'undefined') {
+    return undefined
+  }
+  return JSON.parse(getMemoryUsage())
+}
+
+function sampleMemoryUsage() {
+  let usage = readMemoryUsage()
+  if (!usage) {
+    return
+  }
+  memorySamples.push({...usage, time: new Date()})
+  if (memorySamples.length > kMaxMemorySamples) {
+    memorySamples.shift()
+  }
+}
+
+export function getMemoryMetrics(): MemoryMetrics {
+  return {current: readMemoryUsage(), samples: [...memorySamples]}
+}
+
+// Wraps the command invoked by the app, so the time of every invocation is
+// recorded. The time of the async commands is measured until they settle.
+export function instrumentCommand<A extends any[], R>(name: string, command: (...args: A) => R): (...args: A) => R {
+  return (...args: A): R => {
+    let start = performance.now()
+    let result: R
+    try {
+      result = command(...args)
+    } catch (error) {
+      recordCommandLatency(name, performance.now() - start, true)
+      throw error
+    }
+    if (result instanceof Promise) {
+      result.then(
+          () => recordCommandLatency(name, performance.now() - start),
+          () => recordCommandLatency(name, performance.now() - start, true))
+    } else {
+      recordCommandLatency(name, performance.now() - start)
+    }
+    return result
+  }
+}
+
+// Returns the value below which the given fraction of the sorted values
+// falls using the nearest-rank method.
+export function percentile(sortedValues: number[], p: number): number {
+  if (sortedValues.length === 0) {
+    return 0
+  }
+  let rank = Math.max(1, Math.ceil(p * sortedValues.length))
+  return sortedValues[rank - 1]
+}
+
+function computeMetrics(samples: MetricSample[]): CommandMetrics {
+  let durations = samples.map(sample => sample.durationMs).sort((a, b) => a - b)
+  return {
+    count: samples.length,
+    errors: samples.filter(sample => sample.failed).length,
+    totalMs: durations.reduce((total, duration) => total + duration, 0),
+    maxMs: durations.length > 0 ? durations[durations.length - 1] : 0,
+    p50Ms: percentile(durations, 0.5),
+    p95Ms: percentile(durations, 0.95),
+    p99Ms: percentile(durations, 0.99),
+  }
+}
+
+// Returns the latency percentiles of every command computed from the latest
+// samples of the last 24 hours.
+export function getPerformanceMetrics(): Record<string, CommandMetrics> {
+  let since = Date.now() - kRetentionMs
+  let result: Record<string, CommandMetrics> = {}
+  for (const [name, commandStats] of stats) {
+    let samples = commandStats.series.getSamples(since)
+    if (samples.length > 0) {
+      result[name] = computeMetrics(samples)
+    }
+  }
+  return result
+}
+
+function escapeLabel(value: string): string {
+  return value.replace(/\\/g, "\\\\").replace(/"/g, '\\"').replace(/\n/g, "\\n")
+}
+
+// Formats the metrics in the Prometheus text exposition format.
+function formatPrometheus(): string {
+  let lines = [
+    "# HELP clipbook_command_duration_milliseconds The duration of the commands.",
+    "# TYPE clipbook_command_duration_milliseconds histogram",
+  ]
+  for (const [name, {histogram}] of stats) {
+    let command = escapeLabel(name)
+    let cumulative = 0
+    for (let i = 0; i < kBucketBoundsMs.length; i++) {
+      cumulative += histogram.buckets[i]
+      let bound = isFinite(kBucketBoundsMs[i]) ? String(kBucketBoundsMs[i]) : "+Inf"
+      lines.push(`clipbook_command_duration_milliseconds_bucket{command="${command}",le="${bound}"} ${cumulative}`)
+    }
+    lines.push(`clipbook_command_duration_milliseconds_sum{command="${command}"} ${histogram.totalMs}`)
+    lines.push(`clipbook_command_duration_milliseconds_count{command="${command}"} ${histogram.count}`)
+  }
+  lines.push("# HELP clipbook_command_errors_total The number of the failed commands.")
+  lines.push("# TYPE clipbook_command_errors_total counter")
+  for (const [name, {histogram}] of stats) {
+    lines.push(`clipbook_command_errors_total{command="${escapeLabel(name)}"} ${histogram.errors}`)
+  }
+  lines.push("# HELP clipbook_cache_lookups_total The number of the cache lookups.")
+  lines.push("# TYPE clipbook_cache_lookups_total counter")
+  for (const [name, {hits, misses}] of cacheStats) {
+    let cache = escapeLabel(name)
+    lines.push(`clipbook_cache_lookups_total{cache="${cache}",result="hit"} ${hits}`)
+    lines.push(`clipbook_cache_lookups_total{cache="${cache}",result="miss"} ${misses}`)
+  }
+  let memory = readMemoryUsage()
+  if (memory) {
+    lines.push("# HELP clipbook_memory_bytes The memory used by the app process.")
+    lines.push("# TYPE clipbook_memory_bytes gauge")
+    lines.push(`clipbook_memory_bytes{kind="resident"} ${memory.residentSize}`)
+    lines.push(`clipbook_memory_bytes{kind="peak_resident"} ${memory.peakResidentSize}`)
+    lines.push(`clipbook_memory_bytes{kind="footprint"} ${memory.footprint}`)
+    lines.push(`clipbook_memory_bytes{kind="allocated"} ${memory.allocatedSize}`)
+    lines.push("# HELP clipbook_memory_allocated_blocks The number of the allocated memory blocks.")
+    lines.push("# TYPE clipbook_memory_allocated_blocks gauge")
+    lines.push(`clipbook_memory_allocated_blocks ${memory.allocatedBlocks}`)
+  }
+  return lines.join("\n") + "\n"
+}
+
+export function exportMetrics(format: MetricsFormat): string {
+  switch (format) {
+    case "json":
+      return JSON.stringify(getPerformanceMetrics())
+    case "prometheus":
+      return formatPrometheus()
+  }
+}
+
+async function flushSamples() {
+  lastFlushTime = Date.now()
+  let samples = pendingSamples
+  pendingSamples = []
+  if (samples.length > 0) {
+    await addMetricSamples(samples)
+  }
+  await deleteMetricSamples(new Date(Date.now() - kRetentionMs))
+}
+
+// Restores the samples of the last 24 hours and starts saving the new samples
+// to the database, so the metrics survive the restart of the app.
+export async function startMetricsPersistence() {
+  if (flushTimer) {
+    return
+  }
+  sampleMemoryUsage()
+  flushTimer = setInterval(() => {
+    sampleMemoryUsage()
+    flushSamples().catch(error => {
+      console.error('❌ Failed to save metrics:', error)
+    })
+  }, kFlushIntervalMs)
+  lastFlushTime = Date.now()
+  if (restored) {
+    return
+  }
+  restored = true
+  let stored = await getMetricSamples(new Date(Date.now() - kRetentionMs))
+  stored.sort((a, b) => new Date(a.time).getTime() - new Date(b.time).getTime())
+  // The stored samples are older than the ones recorded since the launch.
+  let recorded = [...stats.values()].flatMap(commandStats => commandStats.series.getSamples(0))
+  for (const commandStats of stats.values()) {
+    commandStats.series = new TimeSeries()
+  }
+  for (const sample of stored.concat(recorded)) {
+    getStats(sample.name).series.add({...sample, time: new Date(sample.time)})
+  }
+}
+
+export function isMetricsPersistenceAlive(): boolean {
+  return flushTimer !== undefined && Date.now() - lastFlushTime < 2 * kFlushIntervalMs
+}
+
+export function stopMetricsPersistence() {
+  if (flushTimer) {
+    clearInterval(flushTimer)
+    flushTimer = undefined
+  }
+}
+
+// Saves the samples recorded since the last flush, e.g. before the app exits.
+export async function flushMetrics() {
+  await flushSamples()
+}
@@ -0,0 +1,46 @@
+import i18n from "i18next";
+import {Clip} from "@/db";
+import {getSourceAppInfo} from "@/data";
+import {executePickerAction, PickerAction} from "@/picker";
+import {prefShouldShowCopyNotification} from "@/pref";
+import {getTrayItemTitle} from "@/tray";
+
+declare const showCopyNotification: (itemId: number, title: string, body: string) => void;
+declare const showNotification: (title: string, body: string) => void;
+
+// Shows the notification with the "Paste" and "Favorite" buttons about the
+// item that has just been copied.
+export function notifyCopy(item: Clip) {
+  if (typeof showCopyNotification === 'undefined' || !prefShouldShowCopyNotification()) {
+    return
+  }
+  let appInfo = item.sourceApp ? getSourceAppInfo(item.sourceApp) : undefined
+  let title = appInfo
+      ? i18n.t("app.notifications.copiedFrom", {app: appInfo.name})
+      : i18n.t("app.notifications.copied")
+  showCopyNotification(item.id!, title, getTrayItemTitle(item))
+}
+
+// Tells the user that the copied text wasn't added to the history because
+// it exceeds the size limit.
+export function notifyItemSkipped(sizeInBytes: number) {
+  if (typeof showNotification === 'undefined') {
+    return
+  }
+  showNotification(i18n.t("app.notifications.itemSkipped.title"),
+      i18n.t("app.notifications.itemSkipped.body", {size: Math.ceil(sizeInBytes / 1024)}))
+}
+
+// Invoked when the user clicks a button in the copy notification.
+export async function handleNotificationAction(action: string, itemId: number) {
+  switch (action) {
+    case "paste":
+      await executePickerAction(itemId, PickerAction.Paste)
+      break
+    case "favorite":
+      await executePickerAction(itemId, PickerAction.Favorite)
+      break
+    default:
+      console.error(`❌ Unknown notification action: ${action}`)
+  }
+}
@@ -0,0 +1,99 @@
+import {Clip, ClipType} from "@/db";
+import {getHistoryItemById, searchHistory, updateHistoryItem} from "@/data";
+import {emitter} from "@/actions";
+
+declare const recognizeImageText: (imageFileName: string) => void;
+
+// How long to wait for the text recognition of a single image.
+const kRecognitionTimeoutMs = 60 * 1000
+
+// The ids of the image items waiting for the text recognition.
+let queue: number[] = []
+let current: {id: number, imageFileName: string} | undefined
+let currentTimer: ReturnType<typeof setTimeout> | undefined
+let recognitionStarted = false
+
+function isSupported(): boolean {
+  return typeof recognizeImageText !== 'undefined'
+}
+
+function needsRecognition(item: Clip): boolean {
+  return item.type === ClipType.Image && !item.ocrProcessed && item.imageFileName.length > 0
+}
+
+function enqueue(item: Clip) {
+  if (item.id && needsRecognition(item) && !queue.includes(item.id) && current?.id !== item.id) {
+    queue.push(item.id)
+  }
+}
+
+// Recognizes the text in the next image in the queue. Only one image is
+// processed at a time.
+function processNext() {
+  if (current) {
+    return
+  }
+  while (queue.length > 0) {
+    let item = getHistoryItemById(queue.shift()!)
+    if (item && needsRecognition(item)) {
+      current = {id: item.id!, imageFileName: item.imageFileName}
+      currentTimer = setTimeout(() => {
+        console.error(`❌ Text recognition timed out for ${current?.imageFileName}`)
+        finish()
+      }, kRecognitionTimeoutMs)
+      recognizeImageText(item.imageFileName)
+      return
+    }
+  }
+}
+
+function finish() {
+  if (currentTimer) {
+    clearTimeout(currentTimer)
+    currentTimer = undefined
+  }
+  current = undefined
+  processNext()
+}
+
+// Returns the number of the images waiting for the text recognition
+// including the one being processed.
+export function getPendingImageTextRecognitions(): number {
+  return queue.length + (current ? 1 : 0)
+}
+
+// Called by the app when the text in the image has been recognized.
+export async function imageTextRecognized(imageFileName: string, text: string) {
+  if (!current || current.imageFileName !== imageFileName) {
+    return
+  }
+  let item = getHistoryItemById(current.id)
+  try {
+    if (item) {
+      if (text.length > 0) {
+        item.imageText = text
+      }
+      item.ocrProcessed = true
+      await updateHistoryItem(item.id!, item)
+    }
+  } catch (error) {
+    console.error('❌ Failed to save the recognized image text:', error)
+  } finally {
+    finish()
+  }
+}
+
+// Recognizes the text in the new images and in the images that haven't been
+// processed yet, so the images can be found by the text they contain.
+export function startImageTextRecognition() {
+  if (recognitionStarted || !isSupported()) {
+    return
+  }
+  recognitionStarted = true
+  emitter.on("HistoryItemAdded", item => {
+    enqueue(item)
+    processNext()
+  })
+  searchHistory("").forEach(enqueue)
+  processNext()
+}
@@ -0,0 +1,134 @@
+import {getPermissionStatusRows, PermissionStatusRow, savePermissionStatusRow} from "@/db";
+import {emitter} from "@/actions";
+
+declare const getPermissionStatus: (kind: string) => string;
+declare const requestPermission: (kind: string) => void;
+
+export enum PermissionKind {
+  Accessibility = "accessibility",
+  InputMonitoring = "inputMonitoring",
+  FullDiskAccess = "fullDiskAccess",
+  Notifications = "notifications",
+}
+
+export enum PermissionState {
+  Granted = "granted",
+  Denied = "denied",
+  NotDetermined = "notDetermined",
+  Unknown = "unknown",
+}
+
+export type PermissionStatus = {
+  kind: PermissionKind
+  state: PermissionState
+  checkedAt: Date
+  // The time when the state was changed last time.
+  changedAt: Date
+}
+
+export type PermissionChange = {
+  kind: PermissionKind
+  state: PermissionState
+  previousState?: PermissionState
+}
+
+// How often to check whether the user has granted or revoked a permission.
+const kCheckIntervalMs = 10 * 1000
+
+let statuses = new Map<PermissionKind, PermissionStatus>()
+let monitorTimer: ReturnType<typeof setInterval> | undefined
+let lastCheckTime = 0
+let loaded = false
+
+export function isPermissionKind(kind: string): kind is PermissionKind {
+  return Object.values(PermissionKind).includes(kind as PermissionKind)
+}
+
+function toState(status: string): PermissionState {
+  return Object.values(PermissionState).includes(status as PermissionState)
+      ? status as PermissionState
+      : PermissionState.Unknown
+}
+
+export function checkPermission(kind: PermissionKind): PermissionState {
+  if (typeof getPermissionStatus === 'undefined') {
+    return PermissionState.Unknown
+  }
+  return toState(getPermissionStatus(kind))
+}
+
+// Asks the system to grant the permission and opens the pane of System
+// Settings where the user can grant it. The change is detected by the
+// permission monitor.
+export function requestSystemPermission(kind: PermissionKind) {
+  if (typeof requestPermission === 'undefined') return
+  requestPermission(kind)
+}
+
+async function loadStatuses() {
+  for (const row of await getPermissionStatusRows()) {
+    if (isPermissionKind(row.kind)) {
+      statuses.set(row.kind, {
+        kind: row.kind,
+        state: toState(row.status),
+        checkedAt: new Date(row.checkedAt),
+        changedAt: new Date(row.changedAt),
+      })
+    }
+  }
+  loaded = true
+}
+
+// Checks all the permissions, saves the changed statuses, and emits the
+// "PermissionChanged" event for every permission whose state has changed
+// since the previous check, including the changes made while the app
+// wasn't running.
+export async function checkPermissions(): Promise<PermissionStatus[]> {
+  if (!loaded) {
+    await loadStatuses()
+  }
+  let now = new Date()
+  for (const kind of Object.values(PermissionKind)) {
+    let state = checkPermission(kind)
+    let previous = statuses.get(kind)
+    let changed = !previous || previous.state !== state
+    let status: PermissionStatus = {kind, state, checkedAt: now, changedAt: changed ? now : previous!.changedAt}
+    statuses.set(kind, status)
+    if (changed) {
+      let row: PermissionStatusRow = {kind, status: state, checkedAt: now, changedAt: now}
+      await savePermissionStatusRow(row)
+      emitter.emit("PermissionChanged", {kind, state, previousState: previous?.state})
+    }
+  }
+  return getPermissionStatuses()
+}
+
+export function getPermissionStatuses(): PermissionStatus[] {
+  return [...statuses.values()].map(status => ({...status}))
+}
+
+function runCheck() {
+  lastCheckTime = Date.now()
+  checkPermissions().catch(error => {
+    console.error('❌ Failed to check permissions:', error)
+  })
+}
+
+export function startPermissionMonitor() {
+  if (monitorTimer) {
+    return
+  }
+  runCheck()
+  monitorTimer = setInterval(runCheck, kCheckIntervalMs)
+}
+
+export function isPermissionMonitorAlive(): boolean {
+  return monitorTimer !== undefined && Date.now() - lastCheckTime < 2 * kCheckIntervalMs
+}
+
+export function stopPermissionMonitor() {
+  if (monitorTimer) {
+    clearInterval(monitorTimer)
+    monitorTimer = undefined
+  }
+}
@@ -0,0 +1,226 @@
+import {Clip, ClipType, getFilePath, getHTML, getImageFileName, getRTF} from "@/db";
+import {
+  deleteHistoryItem,
+  getHistoryItemById,
+  getItemPreview,
+  getSourceAppInfo,
+  isItemPinned,
+  markItemPasted,
+  searchHistory,
+  updateHistoryItem
+} from "@/data";
+import {prefGetQuickPasteShortcuts} from "@/pref";
+import {shortcutToDisplayShortcut} from "@/lib/shortcuts";
+import {emitter} from "@/actions";
+import {Capability, requireCapability} from "@/capabilities";
+import {recordPastedItem} from "@/lineage";
+import {requireUnlocked} from "@/applock";
+import {getFullContent} from "@/blobs";
+import {fuzzyScore} from "@/lib/fuzzy";
+import {getCachedSnippets, pasteSnippet} from "@/snippets";
+
+declare const pasteItemInFrontApp: (text: string, rtf: string, html: string, imageFileName: string, filePath: string) => void;
+
+const kMaxTitleLength = 80
+// Only the beginning of the long items is matched, so the picker stays
+// responsive with the large history.
+const kMaxMatchLength = 256
+// The age in hours at which the recency boost of the item halves.
+const kRecencyHalfLifeHours = 24
+// The bonus of the pinned items and the snippets over the regular items.
+const kPinnedBonus = 2
+const kSnippetBonus = 1
+const kSnippetGlyph = "✂"
+
+export enum PickerAction {
+  Paste = "paste",
+  PastePlain = "paste-plain",
+  Favorite = "favorite",
+  Delete = "delete",
+}
+
+// A pre-formatted row of the quick picker.
+export interface PickerItem {
+  id: number;
+  title: string;
+  typeGlyph: string;
+  appPath: string;
+  appIcon: string;
+  shortcutHint: string;
+  favorite: boolean;
+}
+
+const kTypeGlyphs: Record<ClipType, string> = {
+  [ClipType.Text]: "¶",
+  [ClipType.Link]: "🔗",
+  [ClipType.Email]: "@",
+  [ClipType.Color]: "●",
+  [ClipType.Image]: "🖼",
+  [ClipType.File]: "📄",
+}
+
+export function getItemTitle(item: Clip): string {
+  if (item.name) {
+    return item.name
+  }
+  if (item.type === ClipType.Image) {
+    return "Image (" + item.imageWidth + "x" + item.imageHeight + ")"
+  }
+  if (item.type === ClipType.File) {
+    return item.filePathFileName
+  }
+  let title = getItemPreview(item).trim().split("\n")[0]
+  if (title.length > kMaxTitleLength) {
+    title = title.substring(0, kMaxTitleLength) + "…"
+  }
+  return title
+}
+
+export function getPickerItems(query: string, limit: number = 20): PickerItem[] {
+  requireUnlocked()
+  let shortcuts = prefGetQuickPasteShortcuts()
+  return searchHistory(query).slice(0, limit).map((item, index) => {
+    let appInfo = item.sourceApp ? getSourceAppInfo(item.sourceApp) : undefined
+    return {
+      id: item.id!,
+      title: getItemTitle(item),
+      typeGlyph: kTypeGlyphs[item.type],
+      appPath: item.sourceApp,
+      appIcon: appInfo ? appInfo.icon : "",
+      shortcutHint: index < shortcuts.length ? shortcutToDisplayShortcut(shortcuts[index]) : "",
+      favorite: item.favorite,
+    }
+  })
+}
+
+export enum QuickPickerItemKind {
+  History = "history",
+  Snippet = "snippet",
+}
+
+// A row of the Spotlight-style quick picker that can be a history item or
+// a snippet.
+export interface QuickPickerItem extends PickerItem {
+  kind: QuickPickerItemKind;
+  pinned: boolean;
+  score: number;
+}
+
+// Returns the boost of the item based on how recently and how often it was
+// copied. Between 1 and about 3 for the frequently copied recent items.
+function getUsageBoost(item: Clip, now: number): number {
+  let ageHours = Math.max(0, now - new Date(item.copyTime).getTime()) / (60 * 60 * 1000)
+  let recency = Math.pow(0.5, ageHours / kRecencyHalfLifeHours)
+  let frequency = Math.log2(1 + item.numberOfCopies) / 4
+  return 1 + recency + Math.min(frequency, 1)
+}
+
+function getMatchText(item: Clip): string {
+  let text = (item.name ? item.name + " " : "") +
+      (item.type === ClipType.Image ? item.imageText ?? "" : getItemPreview(item))
+  return text.length > kMaxMatchLength ? text.substring(0, kMaxMatchLength) : text
+}
+
+// Returns the history items, the pinned items, and the snippets matching the
+// query ranked by the match quality, recency, and frequency. Everything is
+// matched in memory, so the picker popup can query it on every key stroke.
+export function getQuickPickerItems(query: string, limit: number = 20): QuickPickerItem[] {
+  requireUnlocked()
+  let now = Date.now()
+  let results: QuickPickerItem[] = []
+  for (const item of searchHistory("")) {
+    let match = fuzzyScore(query, getMatchText(item))
+    if (match < 0) {
+      continue
+    }
+    let pinned = isItemPinned(item)
+    let score = (match + 1) * getUsageBoost(item, now) * (pinned ? kPinnedBonus : 1)
+    let appInfo = item.sourceApp ? getSourceAppInfo(item.sourceApp) : undefined
+    results.push({
+      kind: QuickPickerItemKind.History,
+      id: item.id!,
+      title: getItemTitle(item),
+      typeGlyph: kTypeGlyphs[item.type],
+      appPath: item.sourceApp,
+      appIcon: appInfo ? appInfo.icon : "",
+      shortcutHint: "",
+      favorite: item.favorite,
+      pinned,
+      score,
+    })
+  }
+  for (const snippet of getCachedSnippets()) {
+    let match = fuzzyScore(query, snippet.name)
+    if (query.length === 0 || match < 0) {
+      continue
+    }
+    results.push({
+      kind: QuickPickerItemKind.Snippet,
+      id: snippet.id!,
+      title: snippet.name,
+      typeGlyph: kSnippetGlyph,
+      appPath: "",
+      appIcon: "",
+      shortcutHint: "",
+      favorite: false,
+      pinned: false,
+      score: (match + 1) * (1 + kSnippetBonus),
+    })
+  }
+  let shortcuts = prefGetQuickPasteShortcuts()
+  let items = results.sort((a, b) => b.score - a.score).slice(0, limit)
+  items.forEach((item, index) => {
+    item.shortcutHint = index < shortcuts.length ? shortcutToDisplayShortcut(shortcuts[index]) : ""
+  })
+  return items
+}
+
+// Pastes the history item or the snippet selected in the quick picker.
+export async function pasteQuickPickerItem(kind: QuickPickerItemKind, id: number) {
+  requireUnlocked()
+  if (kind === QuickPickerItemKind.Snippet) {
+    requireCapability(Capability.Paste)
+    await pasteSnippet(id)
+    return
+  }
+  await executePickerAction(id, PickerAction.Paste)
+}
+
+async function pasteItem(item: Clip, plain: boolean) {
+  requireCapability(Capability.Paste)
+  item.copyTime = new Date()
+  item.numberOfCopies += 1
+  markItemPasted(item)
+  await updateHistoryItem(item.id!, item)
+  if (plain) {
+    pasteItemInFrontApp(getFullContent(item), "", "", "", "")
+  } else {
+    pasteItemInFrontApp(getFullContent(item), getRTF(item), getHTML(item), getImageFileName(item), getFilePath(item))
+  }
+  recordPastedItem(item)
+}
+
+// Performs the action on the history item with the given id.
+export async function executePickerAction(itemId: number, action: PickerAction) {
+  requireUnlocked()
+  let item = getHistoryItemById(itemId)
+  if (!item) {
+    throw new Error(`History item not found: ${itemId}`)
+  }
+  switch (action) {
+    case PickerAction.Paste:
+      await pasteItem(item, false)
+      break
+    case PickerAction.PastePlain:
+      await pasteItem(item, true)
+      break
+    case PickerAction.Favorite:
+      item.favorite = !item.favorite
+      await updateHistoryItem(item.id!, item)
+      break
+    case PickerAction.Delete:
+      await deleteHistoryItem(item)
+      break
+  }
+  emitter.emit("HistoryChanged")
+}
@@ -99,6 +99,127 @@ declare const setCopyOnNumberAction: (copy: boolean) => void;
 declare const shouldCopyOnNumberAction: () => boolean;
 declare const isCopyOnNumberActionManaged: () => boolean;
 
+declare const saveBackupIntervalHours: (hours: number) => void;
+declare const getBackupIntervalHours: () => number;
+
+declare const saveMaxBackups: (count: number) => void;
+declare const getMaxBackups: () => number;
+
+declare const saveAnnounceClipboardChanges: (announce: boolean) => void;
+declare const shouldAnnounceClipboardChanges: () => boolean;
+declare const isAnnounceClipboardChangesManaged: () => boolean;
+
+declare const saveClearHistoryOnLogout: (clear: boolean) => void;
+declare const shouldClearHistoryOnLogout: () => boolean;
+declare const isClearHistoryOnLogoutManaged: () => boolean;
+
+declare const saveAutoClearIntervalHours: (hours: number) => void;
+declare const getAutoClearIntervalHours: () => number;
+
+declare const saveVaultDirectory: (dir: string) => void;
+declare const getVaultDirectory: () => string;
+
+declare const saveApiServerEnabled: (enabled: boolean) => void;
+declare const shouldApiServerEnabled: () => boolean;
+declare const isApiServerEnabledManaged: () => boolean;
+
+declare const saveApiServerPort: (port: number) => void;
+declare const getApiServerPort: () => number;
+
+declare const saveApiServerToken: (token: string) => boolean;
+declare const getApiServerToken: () => string;
+
+declare const saveLanSharingEnabled: (enabled: boolean) => void;
+declare const shouldLanSharingEnabled: () => boolean;
+declare const isLanSharingEnabledManaged: () => boolean;
+
+declare const saveLanSharingKey: (key: string) => void;
+declare const getLanSharingKey: () => string;
+
+declare const saveAppLockEnabled: (enabled: boolean) => void;
+declare const shouldAppLockEnabled: () => boolean;
+declare const isAppLockEnabledManaged: () => boolean;
+
+declare const saveAutoLockMinutes: (minutes: number) => void;
+declare const getAutoLockMinutes: () => number;
+
+declare const saveSecureDelete: (enabled: boolean) => void;
+declare const shouldSecureDelete: () => boolean;
+declare const isSecureDeleteManaged: () => boolean;
+
+declare const saveRetentionDays: (type: string, days: number) => void;
+declare const getRetentionDays: (type: string) => number;
+declare const saveRetentionKeepFavorites: (keep: boolean) => void;
+declare const shouldRetentionKeepFavorites: () => boolean;
+
+declare const saveMaxHistoryItems: (count: number) => void;
+declare const getMaxHistoryItems: () => number;
+
+declare const saveMaxHistoryMegabytes: (megabytes: number) => void;
+declare const getMaxHistoryMegabytes: () => number;
+
+declare const saveFetchLinkPreviews: (fetch: boolean) => void;
+declare const shouldFetchLinkPreviews: () => boolean;
+declare const isFetchLinkPreviewsManaged: () => boolean;
+
+declare const saveUsePasteRecentShortcuts: (enabled: boolean) => void;
+declare const shouldUsePasteRecentShortcuts: () => boolean;
+declare const isUsePasteRecentShortcutsManaged: () => boolean;
+
+declare const saveTrashRetentionDays: (days: number) => void;
+declare const getTrashRetentionDays: () => number;
+
+declare const saveDatabaseMaxAttempts: (attempts: number) => void;
+declare const getDatabaseMaxAttempts: () => number;
+
+declare const saveIgnoreOwnChanges: (ignore: boolean) => void;
+declare const shouldIgnoreOwnChanges: () => boolean;
+declare const isIgnoreOwnChangesManaged: () => boolean;
+
+declare const saveShowTodayCountInMenuBar: (show: boolean) => void;
+declare const shouldShowTodayCountInMenuBar: () => boolean;
+declare const isShowTodayCountInMenuBarManaged: () => boolean;
+
+declare const saveShowCopyNotification: (show: boolean) => void;
+declare const shouldShowCopyNotification: () => boolean;
+declare const isShowCopyNotificationManaged: () => boolean;
+
+declare const saveCycleProfileShortcut: (shortcut: string) => void;
+declare const getCycleProfileShortcut: () => string;
+
+declare const savePauseWhenScreenSharing: (pause: boolean) => void;
+declare const shouldPauseWhenScreenSharing: () => boolean;
+declare const isPauseWhenScreenSharingManaged: () => boolean;
+
+declare const saveMaxItemKilobytes: (kilobytes: number) => void;
+declare const getMaxItemKilobytes: () => number;
+
+declare const saveLargeItemPolicy: (policy: string) => void;
+declare const getLargeItemPolicy: () => string;
+
+declare const saveDoubleTapModifier: (modifier: string) => void;
+declare const getDoubleTapModifier: () => string;
+
+declare const saveDoubleTapAction: (action: string) => void;
+declare const getDoubleTapAction: () => string;
+
+declare const saveDeduplicateSimilarImages: (deduplicate: boolean) => void;
+declare const shouldDeduplicateSimilarImages: () => boolean;
+declare const isDeduplicateSimilarImagesManaged: () => boolean;
+
+declare const saveCaptureAllowedTypes: (types: string) => void;
+declare const getCaptureAllowedTypes: () => string;
+
+declare const saveCaptureIgnoredTypes: (types: string) => void;
+declare const getCaptureIgnoredTypes: () => string;
+
+declare const saveMonitorFindPasteboard: (monitor: boolean) => void;
+declare const shouldMonitorFindPasteboard: () => boolean;
+declare const isMonitorFindPasteboardManaged: () => boolean;
+
+declare const saveLogLevel: (level: string) => void;
+declare const getLogLevel: () => string;
+
 declare const saveVimModeEnabled: (enabled: boolean) => void;
 declare const shouldVimModeEnabled: () => boolean;
 declare const isVimModeEnabledManaged: () => boolean;
@@ -702,6 +823,12 @@ export function prefGetQuickPasteShortcuts(): string[] {
   return shortcuts
 }
 
+// The global shortcut that pastes the Nth most recent item, where the number
+// is from 1 to 9.
+export function prefGetPasteRecentShortcut(number: number) {
+  return `ControlLeft + AltLeft + Digit${number}`
+}
+
 export function prefGetClearHistoryOnQuit() {
   if (typeof shouldClearHistoryOnQuit === 'undefined') return false
   return shouldClearHistoryOnQuit()
@@ -1013,6 +1140,502 @@ export function prefShouldCopyOnNumberAction() {
   return shouldCopyOnNumberAction()
 }
 
+export function prefGetBackupIntervalHours(): number {
+  if (typeof getBackupIntervalHours === 'undefined') return 0
+  return getBackupIntervalHours()
+}
+
+export function prefSetBackupIntervalHours(hours: number) {
+  if (typeof saveBackupIntervalHours === 'undefined') return
+  saveBackupIntervalHours(hours)
+}
+
+export function prefGetMaxBackups(): number {
+  if (typeof getMaxBackups === 'undefined') return 10
+  return getMaxBackups()
+}
+
+export function prefSetMaxBackups(count: number) {
+  if (typeof saveMaxBackups === 'undefined') return
+  saveMaxBackups(count)
+}
+
+export function prefSetAnnounceClipboardChanges(announce: boolean) {
+  if (typeof saveAnnounceClipboardChanges === 'undefined') return
+  saveAnnounceClipboardChanges(announce)
+}
+
+export function prefShouldAnnounceClipboardChanges() {
+  if (typeof shouldAnnounceClipboardChanges === 'undefined') return false
+  return shouldAnnounceClipboardChanges()
+}
+
+export function prefIsAnnounceClipboardChangesManaged() {
+  if (typeof isAnnounceClipboardChangesManaged === 'undefined') return false
+  return isAnnounceClipboardChangesManaged()
+}
+
+export function prefSetClearHistoryOnLogout(clear: boolean) {
+  if (typeof saveClearHistoryOnLogout === 'undefined') return
+  saveClearHistoryOnLogout(clear)
+}
+
+export function prefGetClearHistoryOnLogout() {
+  if (typeof shouldClearHistoryOnLogout === 'undefined') return false
+  return shouldClearHistoryOnLogout()
+}
+
+export function prefIsClearHistoryOnLogoutManaged() {
+  if (typeof isClearHistoryOnLogoutManaged === 'undefined') return false
+  return isClearHistoryOnLogoutManaged()
+}
+
+export function prefGetAutoClearIntervalHours(): number {
+  if (typeof getAutoClearIntervalHours === 'undefined') return 0
+  return getAutoClearIntervalHours()
+}
+
+export function prefSetAutoClearIntervalHours(hours: number) {
+  if (typeof saveAutoClearIntervalHours === 'undefined') return
+  saveAutoClearIntervalHours(hours)
+}
+
+export function prefGetVaultDirectory(): string {
+  if (typeof getVaultDirectory === 'undefined') return ""
+  return getVaultDirectory()
+}
+
+export function prefSetVaultDirectory(dir: string) {
+  if (typeof saveVaultDirectory === 'undefined') return
+  saveVaultDirectory(dir)
+}
+
+export function prefSetApiServerEnabled(enabled: boolean) {
+  if (typeof saveApiServerEnabled === 'undefined') return
+  saveApiServerEnabled(enabled)
+}
+
+export function prefShouldApiServerEnabled() {
+  if (typeof shouldApiServerEnabled === 'undefined') return false
+  return shouldApiServerEnabled()
+}
+
+export function prefIsApiServerEnabledManaged() {
+  if (typeof isApiServerEnabledManaged === 'undefined') return false
+  return isApiServerEnabledManaged()
+}
+
+export function prefGetApiServerPort(): number {
+  if (typeof getApiServerPort === 'undefined') return 8765
+  return getApiServerPort()
+}
+
+export function prefSetApiServerPort(port: number) {
+  if (typeof saveApiServerPort === 'undefined') return
+  saveApiServerPort(port)
+}
+
+export function prefGetApiServerToken(): string {
+  if (typeof getApiServerToken === 'undefined') return ""
+  return getApiServerToken()
+}
+
+export function prefSetApiServerToken(token: string): boolean {
+  if (typeof saveApiServerToken === 'undefined') return false
+  return saveApiServerToken(token)
+}
+
+export function prefSetLanSharingEnabled(enabled: boolean) {
+  if (typeof saveLanSharingEnabled === 'undefined') return
+  saveLanSharingEnabled(enabled)
+}
+
+export function prefShouldLanSharingEnabled() {
+  if (typeof shouldLanSharingEnabled === 'undefined') return false
+  return shouldLanSharingEnabled()
+}
+
+export function prefIsLanSharingEnabledManaged() {
+  if (typeof isLanSharingEnabledManaged === 'undefined') return false
+  return isLanSharingEnabledManaged()
+}
+
+export function prefGetLanSharingKey(): string {
+  if (typeof getLanSharingKey === 'undefined') return ""
+  return getLanSharingKey()
+}
+
+export function prefSetLanSharingKey(key: string) {
+  if (typeof saveLanSharingKey === 'undefined') return
+  saveLanSharingKey(key)
+}
+
+export function prefSetAppLockEnabled(enabled: boolean) {
+  if (typeof saveAppLockEnabled === 'undefined') return
+  saveAppLockEnabled(enabled)
+}
+
+export function prefShouldAppLockEnabled() {
+  if (typeof shouldAppLockEnabled === 'undefined') return false
+  return shouldAppLockEnabled()
+}
+
+export function prefIsAppLockEnabledManaged() {
+  if (typeof isAppLockEnabledManaged === 'undefined') return false
+  return isAppLockEnabledManaged()
+}
+
+export function prefGetAutoLockMinutes(): number {
+  if (typeof getAutoLockMinutes === 'undefined') return 5
+  return getAutoLockMinutes()
+}
+
+export function prefSetAutoLockMinutes(minutes: number) {
+  if (typeof saveAutoLockMinutes === 'undefined') return
+  saveAutoLockMinutes(minutes)
+}
+
+export function prefSetSecureDelete(enabled: boolean) {
+  if (typeof saveSecureDelete === 'undefined') return
+  saveSecureDelete(enabled)
+}
+
+export function prefShouldSecureDelete() {
+  if (typeof shouldSecureDelete === 'undefined') return false
+  return shouldSecureDelete()
+}
+
+export function prefIsSecureDeleteManaged() {
+  if (typeof isSecureDeleteManaged === 'undefined') return false
+  return isSecureDeleteManaged()
+}
+
+export function prefGetRetentionDays(type: string): number {
+  if (typeof getRetentionDays === 'undefined') return 0
+  return getRetentionDays(type)
+}
+
+export function prefSetRetentionDays(type: string, days: number) {
+  if (typeof saveRetentionDays === 'undefined') return
+  saveRetentionDays(type, days)
+}
+
+export function prefShouldRetentionKeepFavorites(): boolean {
+  if (typeof shouldRetentionKeepFavorites === 'undefined') return true
+  return shouldRetentionKeepFavorites()
+}
+
+export function prefSetRetentionKeepFavorites(keep: boolean) {
+  if (typeof saveRetentionKeepFavorites === 'undefined') return
+  saveRetentionKeepFavorites(keep)
+}
+
+export function prefGetMaxHistoryItems(): number {
+  if (typeof getMaxHistoryItems === 'undefined') return 0
+  return getMaxHistoryItems()
+}
+
+export function prefSetMaxHistoryItems(count: number) {
+  if (typeof saveMaxHistoryItems === 'undefined') return
+  saveMaxHistoryItems(count)
+}
+
+export function prefGetMaxHistoryMegabytes(): number {
+  if (typeof getMaxHistoryMegabytes === 'undefined') return 0
+  return getMaxHistoryMegabytes()
+}
+
+export function prefSetMaxHistoryMegabytes(megabytes: number) {
+  if (typeof saveMaxHistoryMegabytes === 'undefined') return
+  saveMaxHistoryMegabytes(megabytes)
+}
+
+export function prefSetFetchLinkPreviews(fetch: boolean) {
+  if (typeof saveFetchLinkPreviews === 'undefined') return
+  saveFetchLinkPreviews(fetch)
+}
+
+export function prefShouldFetchLinkPreviews() {
+  if (typeof shouldFetchLinkPreviews === 'undefined') return true
+  return shouldFetchLinkPreviews()
+}
+
+export function prefIsFetchLinkPreviewsManaged() {
+  if (typeof isFetchLinkPreviewsManaged === 'undefined') return false
+  return isFetchLinkPreviewsManaged()
+}
+
+export function prefSetUsePasteRecentShortcuts(enabled: boolean) {
+  if (typeof saveUsePasteRecentShortcuts === 'undefined') return
+  saveUsePasteRecentShortcuts(enabled)
+}
+
+export function prefShouldUsePasteRecentShortcuts() {
+  if (typeof shouldUsePasteRecentShortcuts === 'undefined') return false
+  return shouldUsePasteRecentShortcuts()
+}
+
+export function prefIsUsePasteRecentShortcutsManaged() {
+  if (typeof isUsePasteRecentShortcutsManaged === 'undefined') return false
+  return isUsePasteRecentShortcutsManaged()
+}
+
+export function prefGetTrashRetentionDays(): number {
+  if (typeof getTrashRetentionDays === 'undefined') return 30
+  return getTrashRetentionDays()
+}
+
+export function prefSetTrashRetentionDays(days: number) {
+  if (typeof saveTrashRetentionDays === 'undefined') return
+  saveTrashRetentionDays(days)
+}
+
+export function prefGetDatabaseMaxAttempts(): number {
+  if (typeof getDatabaseMaxAttempts === 'undefined') return 3
+  return getDatabaseMaxAttempts()
+}
+
+export function prefSetDatabaseMaxAttempts(attempts: number) {
+  if (typeof saveDatabaseMaxAttempts === 'undefined') return
+  saveDatabaseMaxAttempts(attempts)
+}
+
+export function prefSetIgnoreOwnChanges(ignore: boolean) {
+  if (typeof saveIgnoreOwnChanges === 'undefined') return
+  saveIgnoreOwnChanges(ignore)
+}
+
+export function prefShouldIgnoreOwnChanges() {
+  if (typeof shouldIgnoreOwnChanges === 'undefined') return false
+  return shouldIgnoreOwnChanges()
+}
+
+export function prefIsIgnoreOwnChangesManaged() {
+  if (typeof isIgnoreOwnChangesManaged === 'undefined') return false
+  return isIgnoreOwnChangesManaged()
+}
+
+export function prefSetShowTodayCountInMenuBar(show: boolean) {
+  if (typeof saveShowTodayCountInMenuBar === 'undefined') return
+  saveShowTodayCountInMenuBar(show)
+}
+
+export function prefShouldShowTodayCountInMenuBar() {
+  if (typeof shouldShowTodayCountInMenuBar === 'undefined') return false
+  return shouldShowTodayCountInMenuBar()
+}
+
+export function prefIsShowTodayCountInMenuBarManaged() {
+  if (typeof isShowTodayCountInMenuBarManaged === 'undefined') return false
+  return isShowTodayCountInMenuBarManaged()
+}
+
+export function prefSetShowCopyNotification(show: boolean) {
+  if (typeof saveShowCopyNotification === 'undefined') return
+  saveShowCopyNotification(show)
+}
+
+export function prefShouldShowCopyNotification() {
+  if (typeof shouldShowCopyNotification === 'undefined') return false
+  return shouldShowCopyNotification()
+}
+
+export function prefIsShowCopyNotificationManaged() {
+  if (typeof isShowCopyNotificationManaged === 'undefined') return false
+  return isShowCopyNotificationManaged()
+}
+
+export function prefGetCycleProfileShortcut(): string {
+  if (typeof getCycleProfileShortcut === 'undefined') return ""
+  return getCycleProfileShortcut()
+}
+
+export function prefSetCycleProfileShortcut(shortcut: string) {
+  if (typeof saveCycleProfileShortcut === 'undefined') return
+  saveCycleProfileShortcut(shortcut)
+}
+
+export function prefSetPauseWhenScreenSharing(pause: boolean) {
+  if (typeof savePauseWhenScreenSharing === 'undefined') return
+  savePauseWhenScreenSharing(pause)
+}
+
+export function prefShouldPauseWhenScreenSharing() {
+  if (typeof shouldPauseWhenScreenSharing === 'undefined') return false
+  return shouldPauseWhenScreenSharing()
+}
+
+export function prefIsPauseWhenScreenSharingManaged() {
+  if (typeof isPauseWhenScreenSharingManaged === 'undefined') return false
+  return isPauseWhenScreenSharingManaged()
+}
+
+export function prefGetMaxItemKilobytes(): number {
+  if (typeof getMaxItemKilobytes === 'undefined') return 1024
+  return getMaxItemKilobytes()
+}
+
+export function prefSetMaxItemKilobytes(kilobytes: number) {
+  if (typeof saveMaxItemKilobytes === 'undefined') return
+  saveMaxItemKilobytes(kilobytes)
+}
+
+// What to do with the copied text that exceeds `app.max_item_kilobytes`.
+export enum LargeItemPolicy {
+  // Store the full content.
+  KEEP = "keep",
+  // Store the truncated preview and keep the full content in the blob store.
+  TRUNCATE = "truncate",
+  // Don't add the item to the history and notify the user.
+  SKIP = "skip",
+}
+
+export function prefGetLargeItemPolicy(): LargeItemPolicy {
+  if (typeof getLargeItemPolicy === 'undefined') return LargeItemPolicy.TRUNCATE
+  let policy = getLargeItemPolicy()
+  if (Object.values(LargeItemPolicy).includes(policy as LargeItemPolicy)) {
+    return policy as LargeItemPolicy
+  }
+  return LargeItemPolicy.TRUNCATE
+}
+
+export function prefSetLargeItemPolicy(policy: LargeItemPolicy) {
+  if (typeof saveLargeItemPolicy === 'undefined') return
+  saveLargeItemPolicy(policy)
+}
+
+// The modifier key that triggers the double-tap action when pressed twice.
+export enum DoubleTapModifier {
+  NONE = "",
+  COMMAND = "command",
+  SHIFT = "shift",
+}
+
+// The action performed when the double-tap modifier is pressed twice.
+export enum DoubleTapAction {
+  OPEN_APP = "openApp",
+  PASTE_NEXT_ITEM = "pasteNextItem",
+  PAUSE_RESUME = "pauseResume",
+  CYCLE_PROFILE = "cycleProfile",
+}
+
+export function prefGetDoubleTapModifier(): DoubleTapModifier {
+  if (typeof getDoubleTapModifier === 'undefined') return DoubleTapModifier.NONE
+  let modifier = getDoubleTapModifier()
+  if (Object.values(DoubleTapModifier).includes(modifier as DoubleTapModifier)) {
+    return modifier as DoubleTapModifier
+  }
+  return DoubleTapModifier.NONE
+}
+
+export function prefSetDoubleTapModifier(modifier: DoubleTapModifier) {
+  if (typeof saveDoubleTapModifier === 'undefined') return
+  saveDoubleTapModifier(modifier)
+}
+
+export function prefGetDoubleTapAction(): DoubleTapAction {
+  if (typeof getDoubleTapAction === 'undefined') return DoubleTapAction.OPEN_APP
+  let action = getDoubleTapAction()
+  if (Object.values(DoubleTapAction).includes(action as DoubleTapAction)) {
+    return action as DoubleTapAction
+  }
+  return DoubleTapAction.OPEN_APP
+}
+
+export function prefSetDoubleTapAction(action: DoubleTapAction) {
+  if (typeof saveDoubleTapAction === 'undefined') return
+  saveDoubleTapAction(action)
+}
+
+export function prefSetDeduplicateSimilarImages(deduplicate: boolean) {
+  if (typeof saveDeduplicateSimilarImages === 'undefined') return
+  saveDeduplicateSimilarImages(deduplicate)
+}
+
+export function prefShouldDeduplicateSimilarImages() {
+  if (typeof shouldDeduplicateSimilarImages === 'undefined') return false
+  return shouldDeduplicateSimilarImages()
+}
+
+export function prefIsDeduplicateSimilarImagesManaged() {
+  if (typeof isDeduplicateSimilarImagesManaged === 'undefined') return false
+  return isDeduplicateSimilarImagesManaged()
+}
+
+// The pasteboard types of the password managers and the apps that put
+// temporary data to the clipboard.
+const kDefaultCaptureIgnoredTypes = [
+  "de.petermaurer.TransientPasteboardType",
+  "com.typeit4me.clipping",
+  "Pasteboard generator type",
+  "com.agilebits.onepassword",
+  "net.antelle.keeweb",
+]
+
+function parseTypes(types: string): string[] {
+  return types.split(",").map(type => type.trim()).filter(type => type.length > 0)
+}
+
+// Returns the pasteboard types (UTIs) that are captured. An empty list means
+// that all the supported types are captured.
+export function prefGetCaptureAllowedTypes(): string[] {
+  if (typeof getCaptureAllowedTypes === 'undefined') return []
+  return parseTypes(getCaptureAllowedTypes())
+}
+
+export function prefSetCaptureAllowedTypes(types: string[]) {
+  if (typeof saveCaptureAllowedTypes === 'undefined') return
+  saveCaptureAllowedTypes(types.join(","))
+}
+
+// Returns the pasteboard types (UTIs) that make the copied data ignored.
+export function prefGetCaptureIgnoredTypes(): string[] {
+  if (typeof getCaptureIgnoredTypes === 'undefined') return kDefaultCaptureIgnoredTypes
+  return parseTypes(getCaptureIgnoredTypes())
+}
+
+export function prefSetCaptureIgnoredTypes(types: string[]) {
+  if (typeof saveCaptureIgnoredTypes === 'undefined') return
+  saveCaptureIgnoredTypes(types.join(","))
+}
+
+export function prefSetMonitorFindPasteboard(monitor: boolean) {
+  if (typeof saveMonitorFindPasteboard === 'undefined') return
+  saveMonitorFindPasteboard(monitor)
+}
+
+export function prefShouldMonitorFindPasteboard() {
+  if (typeof shouldMonitorFindPasteboard === 'undefined') return false
+  return shouldMonitorFindPasteboard()
+}
+
+export function prefIsMonitorFindPasteboardManaged() {
+  if (typeof isMonitorFindPasteboardManaged === 'undefined') return false
+  return isMonitorFindPasteboardManaged()
+}
+
+// The minimum level of the messages written to the log files.
+export enum LogLevel {
+  DEBUG = "debug",
+  INFO = "info",
+  WARN = "warn",
+  ERROR = "error",
+}
+
+export function prefGetLogLevel(): LogLevel {
+  if (typeof getLogLevel === 'undefined') return LogLevel.INFO
+  let level = getLogLevel()
+  if (Object.values(LogLevel).includes(level as LogLevel)) {
+    return level as LogLevel
+  }
+  return LogLevel.INFO
+}
+
+export function prefSetLogLevel(level: LogLevel) {
+  if (typeof saveLogLevel === 'undefined') return
+  saveLogLevel(level)
+}
+
 // Vim Mode Preferences
 export function prefGetVimModeEnabled() {
   // Fallback for web development mode when native functions aren't available
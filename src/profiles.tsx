@@ -0,0 +1,122 @@
+import {reloadHistory, reloadSettings} from "@/data";
+import {emitter} from "@/actions";
+
+// The profile of the items captured before the profiles were introduced.
+export const kDefaultProfileId = "default"
+
+// The UI preferences every profile keeps separately.
+const kProfilePreferenceKeys = ["sortType", "sortOrderReverse", "previewVisible", "filterVisible", "infoVisible"]
+
+export type Profile = {
+  id: string
+  name: string
+  createdAt: Date
+  // The values of the profile preferences saved when switching to another
+  // profile.
+  preferences: Record<string, string>
+}
+
+let profiles: Profile[] | undefined
+
+function loadProfiles(): Profile[] {
+  if (!profiles) {
+    let value = localStorage.getItem("profiles")
+    profiles = value ? JSON.parse(value) : [{
+      id: kDefaultProfileId,
+      name: "Default",
+      createdAt: new Date(),
+      preferences: {},
+    }]
+  }
+  return profiles!
+}
+
+function saveProfiles() {
+  localStorage.setItem("profiles", JSON.stringify(profiles))
+}
+
+export function listProfiles(): Profile[] {
+  return loadProfiles().map(profile => ({...profile}))
+}
+
+export function hasMultipleProfiles(): boolean {
+  return loadProfiles().length > 1
+}
+
+export function getCurrentProfileId(): string {
+  return localStorage.getItem("currentProfile") || kDefaultProfileId
+}
+
+export function findProfileById(id: string): Profile | undefined {
+  return loadProfiles().find(profile => profile.id === id)
+}
+
+// Returns true if the item belongs to the current profile. The items without
+// a profile belong to the default profile.
+export function isInCurrentProfile(profileId: string | undefined): boolean {
+  return (profileId ?? kDefaultProfileId) === getCurrentProfileId()
+}
+
+// Returns the profile id to store with the new item. The items of the
+// default profile keep no profile id.
+export function getNewItemProfileId(): string | undefined {
+  let id = getCurrentProfileId()
+  return id === kDefaultProfileId ? undefined : id
+}
+
+export function createProfile(name: string): Profile {
+  let trimmed = name.trim()
+  if (trimmed.length === 0) {
+    throw new Error("The profile name is empty")
+  }
+  if (loadProfiles().some(profile => profile.name === trimmed)) {
+    throw new Error(`Profile already exists: ${trimmed}`)
+  }
+  let profile: Profile = {id: crypto.randomUUID(), name: trimmed, createdAt: new Date(), preferences: {}}
+  profiles!.push(profile)
+  saveProfiles()
+  console.log(`✅ Created profile ${trimmed}`)
+  return {...profile}
+}
+
+// Makes the given profile current: saves the preferences of the current
+// profile, restores the preferences of the new one and shows its history.
+export async function switchProfile(id: string) {
+  let profile = findProfileById(id)
+  if (!profile) {
+    throw new Error(`Profile not found: ${id}`)
+  }
+  let current = findProfileById(getCurrentProfileId())
+  if (current?.id === id) {
+    return
+  }
+  if (current) {
+    current.preferences = {}
+    for (const key of kProfilePreferenceKeys) {
+      let value = localStorage.getItem(key)
+      if (value !== null) {
+        current.preferences[key] = value
+      }
+    }
+  }
+  for (const key of kProfilePreferenceKeys) {
+    if (profile.preferences[key] !== undefined) {
+      localStorage.setItem(key, profile.preferences[key])
+    } else {
+      localStorage.removeItem(key)
+    }
+  }
+  saveProfiles()
+  localStorage.setItem("currentProfile", id)
+  reloadSettings()
+  await reloadHistory()
+  emitter.emit("HistoryChanged")
+  console.log(`✅ Switched to profile ${profile.name}`)
+}
+
+// Switches to the next profile in the order the profiles were created.
+export async function cycleProfile() {
+  let all = loadProfiles()
+  let index = all.findIndex(profile => profile.id === getCurrentProfileId())
+  await switchProfile(all[(index + 1) % all.length].id)
+}
@@ -0,0 +1,37 @@
+import {Clip, ClipType} from "@/db";
+import {getHistoryItemById} from "@/data";
+import {hashString} from "@/lib/hash";
+import {requireUnlocked} from "@/applock";
+
+declare const generateQRCode: (text: string, imageFileName: string) => boolean;
+declare const getImagesDir: () => string;
+declare const isFileExists: (filePath: string) => boolean;
+declare const previewFile: (filePath: string) => void;
+
+export function canGenerateQR(item: Clip): boolean {
+  return typeof generateQRCode !== 'undefined' &&
+      item.type !== ClipType.Image && item.type !== ClipType.File && item.content.trim().length > 0
+}
+
+// Renders the text or URL of the history item as a PNG QR code in the images
+// directory. Returns the name of the image file.
+export function generateQR(id: number): string {
+  requireUnlocked()
+  let item = getHistoryItemById(id)
+  if (!item) {
+    throw new Error(`History item not found: ${id}`)
+  }
+  if (!canGenerateQR(item)) {
+    throw new Error(`Cannot generate a QR code for the history item: ${id}`)
+  }
+  // The same text always produces the same image, so it's generated once.
+  let imageFileName = "qr_" + hashString(item.content) + ".png"
+  if (!isFileExists(getImagesDir() + "/" + imageFileName) && !generateQRCode(item.content, imageFileName)) {
+    throw new Error(`The content of the history item is too long for a QR code: ${id}`)
+  }
+  return imageFileName
+}
+
+export function showQR(id: number) {
+  previewFile(getImagesDir() + "/" + generateQR(id))
+}
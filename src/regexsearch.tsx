@@ -0,0 +1,75 @@
+import {Clip, ClipType, getImageText} from "@/db";
+import {searchHistory} from "@/data";
+
+// How long the search may take before it's aborted.
+const kRegexTimeoutMs = 500
+// The number of characters of every field the pattern is matched against, so
+// a single match can't take too long on a huge item.
+const kMaxFieldLength = 64 * 1024
+const kSupportedFlags = /^[imsu]*$/
+// The quantified group that contains another quantifier, e.g. "(a+)+" or
+// "(\w*)*", which may take exponential time to fail.
+const kNestedQuantifier = /\((?:[^()\\]|\\.)*[+*}](?:[^()\\]|\\.)*\)[+*{]/
+
+export class InvalidArgumentError extends Error {
+  constructor(message: string) {
+    super(message)
+    this.name = "InvalidArgumentError"
+  }
+}
+
+export class RegexTimeoutError extends Error {
+  constructor() {
+    super(`The search took longer than ${kRegexTimeoutMs} ms`)
+    this.name = "RegexTimeoutError"
+  }
+}
+
+function compile(pattern: string, flags: string): RegExp {
+  if (pattern.length === 0) {
+    throw new InvalidArgumentError("The pattern is empty")
+  }
+  if (!kSupportedFlags.test(flags)) {
+    throw new InvalidArgumentError(`Unsupported flags: ${flags}`)
+  }
+  if (kNestedQuantifier.test(pattern)) {
+    throw new InvalidArgumentError("The pattern has nested quantifiers that may take too long to match")
+  }
+  try {
+    return new RegExp(pattern, flags)
+  } catch (error) {
+    throw new InvalidArgumentError((error as Error).message)
+  }
+}
+
+function getSearchableFields(item: Clip): string[] {
+  let fields = [item.name ?? "", item.note ?? "", getImageText(item)]
+  if (item.type === ClipType.File) {
+    fields.push(item.filePath)
+  } else if (item.type !== ClipType.Image && !item.sensitive) {
+    fields.push(item.content)
+  }
+  return fields.filter(field => field && field.length > 0)
+      .map(field => field.substring(0, kMaxFieldLength))
+}
+
+// Returns the history items whose text matches the regular expression in the
+// order of the history. The items are matched one by one, and the search is
+// aborted if it takes longer than the timeout.
+export function searchHistoryRegex(pattern: string, flags: string = "i", limit: number = 50): Clip[] {
+  let regex = compile(pattern, flags)
+  let deadline = performance.now() + kRegexTimeoutMs
+  let result: Clip[] = []
+  for (const item of searchHistory("")) {
+    if (getSearchableFields(item).some(field => regex.test(field))) {
+      result.push(item)
+      if (result.length >= limit) {
+        break
+      }
+    }
+    if (performance.now() > deadline) {
+      throw new RegexTimeoutError()
+    }
+  }
+  return result
+}
@@ -0,0 +1,106 @@
+import {Clip, deleteRegisterRow, getRegisterRows, RegisterRow, saveRegisterRow} from "@/db";
+import {getHistoryItemById} from "@/data";
+import {executePickerAction, PickerAction} from "@/picker";
+
+declare const clearRegisterShortcuts: () => void;
+declare const addRegisterShortcut: (name: string, shortcut: string) => void;
+
+// The register names are passed to the native code, so only the safe
+// characters are allowed.
+const kRegisterNamePattern = /^[A-Za-z0-9_-]{1,32}$/
+
+export type Register = {
+  name: string
+  itemId: number
+  shortcut?: string
+  updatedAt: Date
+}
+
+let registers = new Map<string, RegisterRow>()
+
+export function isValidRegisterName(name: string): boolean {
+  return kRegisterNamePattern.test(name)
+}
+
+function requireValidName(name: string) {
+  if (!isValidRegisterName(name)) {
+    throw new Error(`Invalid register name: ${name}`)
+  }
+}
+
+// Registers the global shortcuts of the registers replacing the previous
+// ones.
+function updateRegisterShortcuts() {
+  if (typeof clearRegisterShortcuts === 'undefined') {
+    return
+  }
+  clearRegisterShortcuts()
+  for (const register of registers.values()) {
+    if (register.shortcut) {
+      addRegisterShortcut(register.name, register.shortcut)
+    }
+  }
+}
+
+export async function loadRegisters() {
+  registers = new Map((await getRegisterRows()).map(row => [row.name, row]))
+  updateRegisterShortcuts()
+}
+
+// Puts the history item into the named register replacing the item it
+// kept before. The shortcut of the register is kept.
+export async function copyToRegister(name: string, itemId: number): Promise<Register> {
+  requireValidName(name)
+  if (!getHistoryItemById(itemId)) {
+    throw new Error(`History item not found: ${itemId}`)
+  }
+  let row: RegisterRow = {name, itemId, shortcut: registers.get(name)?.shortcut, updatedAt: new Date()}
+  await saveRegisterRow(row)
+  registers.set(name, row)
+  return {...row}
+}
+
+export function getRegisterItem(name: string): Clip | undefined {
+  let register = registers.get(name)
+  return register ? getHistoryItemById(register.itemId) : undefined
+}
+
+// Pastes the item of the register to the active app.
+export async function pasteRegister(name: string) {
+  let register = registers.get(name)
+  if (!register) {
+    throw new Error(`Register not found: ${name}`)
+  }
+  await executePickerAction(register.itemId, PickerAction.Paste)
+}
+
+// Returns the registers sorted by name. The registers whose items were
+// deleted are skipped.
+export function listRegisters(): Register[] {
+  return [...registers.values()]
+      .filter(register => getHistoryItemById(register.itemId) !== undefined)
+      .sort((a, b) => a.name.localeCompare(b.name))
+      .map(register => ({...register}))
+}
+
+// Binds the global shortcut to the register. An empty shortcut removes
+// the binding.
+export async function setRegisterShortcut(name: string, shortcut: string) {
+  let register = registers.get(name)
+  if (!register) {
+    throw new Error(`Register not found: ${name}`)
+  }
+  let row: RegisterRow = {...register, shortcut: shortcut || undefined}
+  await saveRegisterRow(row)
+  registers.set(name, row)
+  updateRegisterShortcuts()
+}
+
+export async function deleteRegister(name: string) {
+  if (!registers.has(name)) {
+    return
+  }
+  await deleteRegisterRow(name)
+  registers.delete(name)
+  updateRegisterShortcuts()
+}
@@ -0,0 +1,90 @@
+import {injectClipboardChange} from "@/clipboard-sim";
+import {Capability, requireCapability} from "@/capabilities";
+
+declare const readTextFile: (filePath: string) => string;
+
+// A recorded clipboard event. The delay is the time in milliseconds since the
+// previous event.
+export interface ReplayEvent {
+  delay?: number;
+  text?: string;
+  rtf?: string;
+  html?: string;
+  filePath?: string;
+  sourceApp?: string;
+}
+
+export interface ReplayOptions {
+  // The playback speed multiplier: 2 replays the events twice as fast, 0
+  // replays them without delays.
+  speed?: number;
+  onProgress?: (processed: number, total: number) => void;
+}
+
+let replayCancelled = false
+let replayInProgress = false
+
+export function parseReplayScript(json: string): ReplayEvent[] {
+  const data = JSON.parse(json)
+  const events = Array.isArray(data) ? data : data.events
+  if (!Array.isArray(events)) {
+    throw new Error('Invalid replay script: an array of events is required')
+  }
+  return events
+}
+
+export function loadReplayScript(path: string): ReplayEvent[] {
+  requireCapability(Capability.FileSystem)
+  const text = readTextFile(path)
+  if (!text) {
+    throw new Error(`Failed to read replay script: ${path}`)
+  }
+  return parseReplayScript(text)
+}
+
+function sleep(ms: number) {
+  return new Promise(resolve => setTimeout(resolve, ms))
+}
+
+// Replays the events through the capture pipeline. Returns the number of
+// replayed events.
+export async function replayEvents(events: ReplayEvent[], options: ReplayOptions = {}): Promise<number> {
+  if (replayInProgress) {
+    throw new Error('Replay is already in progress')
+  }
+  const speed = options.speed ?? 1
+  replayInProgress = true
+  replayCancelled = false
+  let processed = 0
+  try {
+    for (const event of events) {
+      if (speed > 0 && event.delay && event.delay > 0) {
+        await sleep(event.delay / speed)
+      }
+      if (replayCancelled) {
+        break
+      }
+      await injectClipboardChange({
+        text: event.text,
+        rtf: event.rtf,
+        html: event.html,
+        filePath: event.filePath,
+        sourceAppPath: event.sourceApp,
+      })
+      processed++
+      options.onProgress?.(processed, events.length)
+    }
+    console.log(`✅ Replayed ${processed} of ${events.length} clipboard events`)
+    return processed
+  } finally {
+    replayInProgress = false
+  }
+}
+
+export function cancelReplay() {
+  replayCancelled = true
+}
+
+export function isReplayInProgress(): boolean {
+  return replayInProgress
+}
@@ -0,0 +1,252 @@
+import {
+  addAuditLogEntry,
+  addBackupClips,
+  addBackupLinkPreviews,
+  AuditLogEntry,
+  BackupData,
+  clearAllData,
+  clipHash,
+  getAllClips,
+  getAllLinkPreviews,
+  kSchemaVersion,
+  readBackupFile
+} from "@/db";
+import {reloadHistory} from "@/data";
+import {emitter} from "@/actions";
+import {BackupManifest, getManifestFileName} from "@/backup";
+import {findIntegrityProblems} from "@/integrity";
+import {Capability, requireCapability} from "@/capabilities";
+import {hashString} from "@/lib/hash";
+
+declare const readTextFile: (filePath: string) => string;
+
+// The number of clips written to the database at once.
+const kChunkSize = 500
+
+export enum JobStatus {
+  Running = "running",
+  Completed = "completed",
+  Cancelled = "cancelled",
+  Failed = "failed",
+}
+
+export enum RestoreMode {
+  // Clears the history and restores all the items from the backup.
+  Replace = "replace",
+  // Keeps the history and adds only the items that are missing in it.
+  Merge = "merge",
+}
+
+export interface RestoreValidation {
+  expectedClips: number;
+  actualClips: number;
+  expectedLinkPreviews: number;
+  actualLinkPreviews: number;
+  valid: boolean;
+}
+
+export interface RestoreJob {
+  path: string;
+  mode: RestoreMode;
+  status: JobStatus;
+  startedAt: Date;
+  finishedAt?: Date;
+  processed: number;
+  total: number;
+  // The number of the backup items skipped because they're already in the
+  // history.
+  skipped: number;
+  validation?: RestoreValidation;
+  error?: string;
+}
+
+export interface BackupVerification {
+  path: string;
+  // True if the backup can be restored without losing data.
+  valid: boolean;
+  manifest?: BackupManifest;
+  problems: string[];
+}
+
+let job: RestoreJob | undefined
+let cancelRequested = false
+
+function reportProgress(processed: number, total: number) {
+  job!.processed = processed
+  let percent = total > 0 ? Math.floor(processed * 100 / total) : 100
+  emitter.emit("RestoreProgress", {processed, total, percent})
+}
+
+// Compares the number of rows in the database with the number of rows in
+// the restored backup.
+async function validateRestore(expectedClips: number, expectedLinkPreviews: number): Promise<RestoreValidation> {
+  let actualClips = (await getAllClips()).length
+  let actualLinkPreviews = (await getAllLinkPreviews()).length
+  return {
+    expectedClips,
+    actualClips,
+    expectedLinkPreviews,
+    actualLinkPreviews,
+    valid: actualClips === expectedClips && actualLinkPreviews === expectedLinkPreviews,
+  }
+}
+
+// Returns the clips and link previews from the backup that aren't in the
+// history yet. They get new ids, so they don't collide with the existing rows.
+async function getMissingRows(data: BackupData): Promise<BackupData> {
+  let hashes = new Set((await getAllClips()).map(clipHash))
+  let urls = new Set((await getAllLinkPreviews()).map(preview => preview.url))
+  return {
+    ...data,
+    clips: data.clips.filter(clip => !hashes.has(clipHash(clip))).map(clip => ({...clip, id: undefined})),
+    linkPreviews: data.linkPreviews
+        .filter(preview => !urls.has(preview.url))
+        .map(preview => ({...preview, id: undefined})),
+  }
+}
+
+async function runRestore(path: string, mode: RestoreMode) {
+  let data = readBackupFile(path)
+  data.linkPreviews = data.linkPreviews || []
+  let expectedClips = data.clips.length
+  let expectedLinkPreviews = data.linkPreviews.length
+  if (mode === RestoreMode.Merge) {
+    let missing = await getMissingRows(data)
+    job!.skipped = data.clips.length - missing.clips.length
+    expectedClips = (await getAllClips()).length + missing.clips.length
+    expectedLinkPreviews = (await getAllLinkPreviews()).length + missing.linkPreviews.length
+    data = missing
+  } else {
+    await clearAllData()
+  }
+  let linkPreviews = data.linkPreviews
+  job!.total = data.clips.length
+  try {
+    for (let i = 0; i < data.clips.length; i += kChunkSize) {
+      if (cancelRequested) {
+        job!.status = JobStatus.Cancelled
+        break
+      }
+      let chunk = data.clips.slice(i, i + kChunkSize)
+      await addBackupClips(chunk)
+      reportProgress(i + chunk.length, data.clips.length)
+    }
+    if (job!.status === JobStatus.Running) {
+      if (data.clips.length === 0) {
+        reportProgress(0, 0)
+      }
+      await addBackupLinkPreviews(linkPreviews)
+      job!.validation = await validateRestore(expectedClips, expectedLinkPreviews)
+      if (!job!.validation.valid) {
+        throw new Error(`Expected ${expectedClips} clips after restore, found ${job!.validation.actualClips}`)
+      }
+      job!.status = JobStatus.Completed
+    }
+  } finally {
+    // The partially restored history is shown as is after a cancellation
+    // or a failure.
+    await reloadHistory()
+    emitter.emit("HistoryChanged")
+  }
+}
+
+// Restores the backup file at the given path either replacing the current
+// history or merging the missing items into it. The clips are written in
+// chunks, so the restore reports its progress and can be cancelled between
+// the chunks.
+export async function restoreFromBackup(path: string, mode: RestoreMode = RestoreMode.Replace): Promise<RestoreJob> {
+  if (job?.status === JobStatus.Running) {
+    throw new Error('Restore is already in progress')
+  }
+  cancelRequested = false
+  job = {path, mode, status: JobStatus.Running, startedAt: new Date(), processed: 0, total: 0, skipped: 0}
+  try {
+    await runRestore(path, mode)
+    console.log(`✅ Restored ${job.processed} clips from ${path}: ${job.status}`)
+  } catch (error) {
+    job.status = JobStatus.Failed
+    job.error = error instanceof Error ? error.message : String(error)
+    console.error('❌ Restore failed:', error)
+  } finally {
+    job.finishedAt = new Date()
+  }
+  await addAuditLogEntry(new AuditLogEntry("backup.restore", JSON.stringify({
+    path,
+    mode,
+    status: job.status,
+    processed: job.processed,
+    total: job.total,
+  })))
+  return job
+}
+
+// Requests to stop the running restore after the current chunk. Returns
+// false if there's no running restore.
+export function cancelRestore(): boolean {
+  if (job?.status !== JobStatus.Running) {
+    return false
+  }
+  cancelRequested = true
+  return true
+}
+
+export function getRestoreJob(): RestoreJob | undefined {
+  return job
+}
+
+function readManifest(path: string, problems: string[]): BackupManifest | undefined {
+  let text = readTextFile(getManifestFileName(path))
+  if (!text) {
+    problems.push("The backup has no manifest")
+    return undefined
+  }
+  try {
+    return JSON.parse(text)
+  } catch (error) {
+    problems.push("The manifest is not valid JSON")
+    return undefined
+  }
+}
+
+// Checks that the backup file at the given path is not corrupted and can be
+// restored by this version of the app without restoring it.
+export function verifyBackup(path: string): BackupVerification {
+  requireCapability(Capability.FileSystem)
+  let problems: string[] = []
+  let text = readTextFile(path)
+  if (!text) {
+    return {path, valid: false, problems: ["The backup file can't be read"]}
+  }
+  let manifest = readManifest(path, problems)
+  if (manifest) {
+    if (manifest.contentHash !== hashString(text)) {
+      problems.push("The content doesn't match the manifest hash")
+    }
+    if (manifest.schemaVersion > kSchemaVersion) {
+      problems.push(`The backup was created by a newer version of the app (schema ${manifest.schemaVersion})`)
+    }
+  }
+  let data
+  try {
+    data = JSON.parse(text)
+  } catch (error) {
+    problems.push("The backup file is not valid JSON")
+    return {path, valid: false, manifest, problems}
+  }
+  if (!Array.isArray(data.clips)) {
+    problems.push("The backup has no clips")
+    return {path, valid: false, manifest, problems}
+  }
+  data.linkPreviews = data.linkPreviews || []
+  if (manifest && (manifest.rowCounts.clips !== data.clips.length ||
+      manifest.rowCounts.linkPreviews !== data.linkPreviews.length)) {
+    problems.push("The number of rows doesn't match the manifest")
+  }
+  for (const problem of findIntegrityProblems(data, false)) {
+    problems.push(`${problem.table} ${problem.id ?? "?"}: ${problem.problem}`)
+  }
+  // The backups created before the manifests were introduced are still
+  // restorable.
+  let valid = problems.every(problem => problem === "The backup has no manifest")
+  return {path, valid, manifest, problems}
+}
@@ -0,0 +1,123 @@
+import {Clip, ClipType} from "@/db";
+import {isItemPinned, purgeHistoryItem, searchHistory} from "@/data";
+import {
+  prefGetRetentionDays,
+  prefSetRetentionDays,
+  prefSetRetentionKeepFavorites,
+  prefShouldRetentionKeepFavorites,
+} from "@/pref";
+import {emitter} from "@/actions";
+
+// How often to delete the expired items.
+const kCleanupIntervalMs = 60 * 60 * 1000
+const kDayMs = 24 * 60 * 60 * 1000
+
+// The retention rules stored in the `history.retention.<type>_days` and
+// `history.retention.keep_favorites` preferences, e.g.
+//
+//   [history.retention]
+//   text_days = 90
+//   image_days = 7
+//   keep_favorites = true
+export interface RetentionRules {
+  // The number of days to keep the items of the type. The items of the
+  // types not listed here never expire.
+  maxAgeDays: Partial<Record<keyof typeof ClipType, number>>;
+  // Whether the favorite and tagged items never expire.
+  keepFavorites: boolean;
+}
+
+let cleanupTimer: ReturnType<typeof setInterval> | undefined
+let lastCleanupTime = 0
+
+// The item types that can have the retention rule.
+export const kRetentionTypes = Object.keys(ClipType)
+    .filter(key => isNaN(Number(key))) as (keyof typeof ClipType)[]
+
+// Returns the name of the item type used in the preference names.
+function prefTypeName(type: keyof typeof ClipType): string {
+  return type.toLowerCase()
+}
+
+export function getRetentionRules(): RetentionRules {
+  let maxAgeDays: RetentionRules["maxAgeDays"] = {}
+  for (const type of kRetentionTypes) {
+    let days = prefGetRetentionDays(prefTypeName(type))
+    if (days > 0) {
+      maxAgeDays[type] = days
+    }
+  }
+  return {maxAgeDays, keepFavorites: prefShouldRetentionKeepFavorites()}
+}
+
+// Saves the retention rules. The item types not listed in the rules never
+// expire.
+export function setRetentionRules(rules: RetentionRules) {
+  for (const [type, days] of Object.entries(rules.maxAgeDays)) {
+    if (!(type in ClipType) || !isFinite(days) || days <= 0) {
+      throw new Error(`Invalid retention rule: ${type}=${days}`)
+    }
+  }
+  for (const type of kRetentionTypes) {
+    prefSetRetentionDays(prefTypeName(type), rules.maxAgeDays[type] ?? 0)
+  }
+  prefSetRetentionKeepFavorites(rules.keepFavorites)
+}
+
+function isExpired(item: Clip, rules: RetentionRules, now: number): boolean {
+  if (isItemPinned(item)) {
+    return false
+  }
+  if (rules.keepFavorites && (item.favorite || (item.tags && item.tags.length > 0))) {
+    return false
+  }
+  let days = rules.maxAgeDays[ClipType[item.type] as keyof typeof ClipType]
+  if (!days) {
+    return false
+  }
+  return now - new Date(item.copyTime).getTime() > days * kDayMs
+}
+
+export function findExpiredItems(items: Clip[], rules: RetentionRules, now: number = Date.now()): Clip[] {
+  return items.filter(item => isExpired(item, rules, now))
+}
+
+// Deletes the items that are older than allowed by the retention rules.
+// Returns the number of deleted items.
+export async function enforceRetention(): Promise<number> {
+  let expired = findExpiredItems(searchHistory(""), getRetentionRules())
+  for (const item of expired) {
+    await purgeHistoryItem(item)
+  }
+  if (expired.length > 0) {
+    emitter.emit("HistoryChanged")
+    console.log(`✅ Deleted ${expired.length} expired history items`)
+  }
+  return expired.length
+}
+
+function runCleanup() {
+  lastCleanupTime = Date.now()
+  enforceRetention().catch(error => {
+    console.error('❌ Failed to delete expired history items:', error)
+  })
+}
+
+export function startRetentionCleanup() {
+  if (cleanupTimer) {
+    return
+  }
+  runCleanup()
+  cleanupTimer = setInterval(runCleanup, kCleanupIntervalMs)
+}
+
+export function isRetentionCleanupAlive(): boolean {
+  return cleanupTimer !== undefined && Date.now() - lastCleanupTime < 2 * kCleanupIntervalMs
+}
+
+export function stopRetentionCleanup() {
+  if (cleanupTimer) {
+    clearInterval(cleanupTimer)
+    cleanupTimer = undefined
+  }
+}
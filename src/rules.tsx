@@ -0,0 +1,108 @@
+import {
+  addTagRule,
+  Clip,
+  ClipType,
+  deleteTagRule,
+  getAllClips,
+  getAllTagRules,
+  TagRule,
+  updateTagRule
+} from "@/db";
+
+let rules: TagRule[] = []
+
+function compile(rule: TagRule): RegExp | undefined {
+  try {
+    // The global and sticky flags make test() stateful, so ignore them.
+    return new RegExp(rule.pattern, rule.flags.replace(/[gy]/g, ""))
+  } catch (e) {
+    return undefined
+  }
+}
+
+// Returns the text the rules are matched against.
+function matchText(clip: Clip): string {
+  if (clip.type === ClipType.Image) {
+    return clip.imageText || ""
+  }
+  if (clip.type === ClipType.File) {
+    return clip.filePath || ""
+  }
+  return clip.content
+}
+
+export function isValidPattern(pattern: string, flags: string = ""): boolean {
+  return compile(new TagRule("", pattern, flags, 0)) !== undefined
+}
+
+export async function loadTagRules() {
+  rules = await getAllTagRules()
+}
+
+export function allTagRules(): TagRule[] {
+  return rules
+}
+
+export async function createTagRule(name: string, pattern: string, flags: string, tagId: number): Promise<TagRule> {
+  if (!isValidPattern(pattern, flags)) {
+    throw new Error(`Invalid regular expression: ${pattern}`)
+  }
+  let rule = new TagRule(name, pattern, flags, tagId)
+  rule.id = await addTagRule(rule)
+  rules.push(rule)
+  return rule
+}
+
+export async function editTagRule(id: number, changes: Partial<TagRule>) {
+  let rule = rules.find(rule => rule.id === id)
+  if (!rule) {
+    return
+  }
+  let updated = {...rule, ...changes}
+  if (!isValidPattern(updated.pattern, updated.flags)) {
+    throw new Error(`Invalid regular expression: ${updated.pattern}`)
+  }
+  Object.assign(rule, changes)
+  await updateTagRule(id, changes)
+}
+
+export async function setTagRuleEnabled(id: number, enabled: boolean) {
+  await editTagRule(id, {enabled})
+}
+
+export async function removeTagRule(id: number) {
+  rules = rules.filter(rule => rule.id !== id)
+  await deleteTagRule(id)
+}
+
+// Returns true if the rule matches the given text.
+export function testTagRule(rule: TagRule, text: string): boolean {
+  let regex = compile(rule)
+  return regex !== undefined && regex.test(text)
+}
+
+// Adds the tags of the enabled rules that match the clip. Called for every
+// captured item before it's saved.
+export function applyTagRules(clip: Clip) {
+  let text = matchText(clip)
+  for (const rule of rules) {
+    if (rule.enabled && testTagRule(rule, text)) {
+      if (!clip.tags) {
+        clip.tags = []
+      }
+      if (!clip.tags.includes(rule.tagId)) {
+        clip.tags.push(rule.tagId)
+      }
+    }
+  }
+}
+
+// Returns the history items the rule would tag without modifying them.
+export async function dryRunTagRule(rule: TagRule): Promise<Clip[]> {
+  let regex = compile(rule)
+  if (!regex) {
+    throw new Error(`Invalid regular expression: ${rule.pattern}`)
+  }
+  let clips = await getAllClips()
+  return clips.filter(clip => regex!.test(matchText(clip)))
+}
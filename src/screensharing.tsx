@@ -0,0 +1,68 @@
+import {emitter} from "@/actions";
+import {getCaptureState, pauseCapture, resumeCapture} from "@/capture";
+import {prefShouldPauseWhenScreenSharing} from "@/pref";
+
+declare const isScreenSharingActive: () => boolean;
+
+// How often to check whether the screen is being shared.
+const kScreenSharingCheckIntervalMs = 2 * 1000
+
+let timer: ReturnType<typeof setInterval> | undefined
+let sharing = false
+// Whether the capturing was paused because of the screen sharing, so it's
+// resumed only if it wasn't paused by the user.
+let pausedBySharing = false
+
+export function isScreenSharing(): boolean {
+  return sharing
+}
+
+function setSharing(value: boolean) {
+  if (sharing === value) {
+    return
+  }
+  sharing = value
+  if (value) {
+    if (!getCaptureState().paused) {
+      pauseCapture()
+      pausedBySharing = true
+    }
+    console.log('✅ Screen sharing started, capturing paused')
+  } else {
+    if (pausedBySharing) {
+      resumeCapture()
+      pausedBySharing = false
+    }
+    console.log('✅ Screen sharing stopped')
+  }
+  emitter.emit("ScreenSharingChanged", value)
+}
+
+function checkScreenSharing() {
+  if (!prefShouldPauseWhenScreenSharing()) {
+    setSharing(false)
+    return
+  }
+  try {
+    setSharing(isScreenSharingActive())
+  } catch (error) {
+    console.error('❌ Failed to check screen sharing:', error)
+  }
+}
+
+// Pauses the capturing and hides the previews while the screen is being
+// shared if `app.pause_when_screen_sharing` is enabled.
+export function startScreenSharingMonitor() {
+  if (timer || typeof isScreenSharingActive === 'undefined') {
+    return
+  }
+  timer = setInterval(checkScreenSharing, kScreenSharingCheckIntervalMs)
+}
+
+export function stopScreenSharingMonitor() {
+  if (timer) {
+    clearInterval(timer)
+    timer = undefined
+  }
+  setSharing(false)
+}
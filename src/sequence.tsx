@@ -0,0 +1,57 @@
+import {getHistoryItemById} from "@/data";
+import {executePickerAction, PickerAction} from "@/picker";
+import {requireUnlocked} from "@/applock";
+
+export type PasteSequence = {
+  ids: number[]
+  // The index of the item that will be pasted next.
+  position: number
+}
+
+let sequence: PasteSequence | undefined
+
+// Creates the sequence of the history items that are pasted one after
+// another with the paste next item shortcut. After the last item the
+// sequence starts over from the first one.
+export function createPasteSequence(ids: number[]): PasteSequence {
+  let existing = ids.filter(id => getHistoryItemById(id) !== undefined)
+  if (existing.length === 0) {
+    throw new Error("The paste sequence has no history items")
+  }
+  sequence = {ids: existing, position: 0}
+  console.log(`✅ Created paste sequence of ${existing.length} items`)
+  return getPasteSequence()!
+}
+
+export function getPasteSequence(): PasteSequence | undefined {
+  return sequence ? {ids: [...sequence.ids], position: sequence.position} : undefined
+}
+
+export function hasPasteSequence(): boolean {
+  return sequence !== undefined
+}
+
+export function clearPasteSequence() {
+  sequence = undefined
+}
+
+// Pastes the next item of the sequence to the active app. The deleted items
+// are skipped. Returns the id of the pasted item or undefined if there's
+// nothing to paste.
+export async function pasteSequenceNext(): Promise<number | undefined> {
+  requireUnlocked()
+  while (sequence && sequence.ids.length > 0) {
+    let position = sequence.position % sequence.ids.length
+    let id = sequence.ids[position]
+    if (!getHistoryItemById(id)) {
+      sequence.ids.splice(position, 1)
+      sequence.position = position
+      continue
+    }
+    sequence.position = (position + 1) % sequence.ids.length
+    await executePickerAction(id, PickerAction.Paste)
+    return id
+  }
+  clearPasteSequence()
+  return undefined
+}
@@ -0,0 +1,66 @@
+import {Clip} from "@/db";
+
+// The items copied in the same app with the pauses not longer than this
+// belong to the same copy session.
+const kSessionGapMs = 5 * 60 * 1000
+
+export type CopySession = {
+  id: number
+  sourceApp: string
+  sourceAppBundleId?: string
+  startTime: Date
+  endTime: Date
+  itemCount: number
+}
+
+function isSameApp(a: Clip, b: Clip): boolean {
+  if (a.sourceAppBundleId && b.sourceAppBundleId) {
+    return a.sourceAppBundleId === b.sourceAppBundleId
+  }
+  return a.sourceApp === b.sourceApp
+}
+
+// Returns the session of the new clip. The clip continues the session of the
+// previously captured clip if it's copied in the same app shortly after it,
+// otherwise it starts a new session identified by the capture time.
+export function getSessionId(clip: Clip, previous?: Clip): number {
+  let time = new Date(clip.copyTime).getTime()
+  if (previous?.sessionId !== undefined && isSameApp(clip, previous)) {
+    let previousTime = new Date(previous.copyTime).getTime()
+    if (time - previousTime >= 0 && time - previousTime <= kSessionGapMs) {
+      return previous.sessionId
+    }
+  }
+  return time
+}
+
+// Groups the items by their sessions, the most recent sessions first.
+export function groupSessions(items: Clip[]): CopySession[] {
+  let sessions = new Map<number, CopySession>()
+  for (const item of items) {
+    if (item.sessionId === undefined) {
+      continue
+    }
+    let time = new Date(item.copyTime)
+    let session = sessions.get(item.sessionId)
+    if (!session) {
+      sessions.set(item.sessionId, {
+        id: item.sessionId,
+        sourceApp: item.sourceApp,
+        sourceAppBundleId: item.sourceAppBundleId,
+        startTime: time,
+        endTime: time,
+        itemCount: 1
+      })
+      continue
+    }
+    session.itemCount++
+    if (time < session.startTime) {
+      session.startTime = time
+    }
+    if (time > session.endTime) {
+      session.endTime = time
+    }
+  }
+  return [...sessions.values()].sort((a, b) => b.startTime.getTime() - a.startTime.getTime())
+}
@@ -23,9 +23,21 @@ import {
   prefSetShowIconInMenuBar,
   prefSetTheme,
   prefShouldPlaySoundOnCopy,
+  prefShouldIgnoreOwnChanges,
+  prefShouldShowCopyNotification,
+  prefSetShowCopyNotification,
+  prefIsShowCopyNotificationManaged,
+  prefShouldShowTodayCountInMenuBar,
+  prefSetShowTodayCountInMenuBar,
+  prefIsShowTodayCountInMenuBarManaged,
+  prefSetIgnoreOwnChanges,
+  prefIsIgnoreOwnChangesManaged,
   prefGetVimModeEnabled,
   prefSetVimModeEnabled,
   prefIsVimModeEnabledManaged,
+  prefShouldAnnounceClipboardChanges,
+  prefSetAnnounceClipboardChanges,
+  prefIsAnnounceClipboardChangesManaged,
 } from "@/pref";
 import {ChevronsUpDown, RefreshCcwIcon,} from "lucide-react";
 import {
@@ -45,6 +57,7 @@ import {emitter} from "@/actions";
 
 declare const closeSettingsWindow: () => void;
 declare const checkForUpdates: () => void;
+declare const setTrayBadgeCount: (count: number) => void;
 
 export default function General() {
   const { t } = useTranslation()
@@ -54,10 +67,14 @@ export default function General() {
   const [openAtLogin, setOpenAtLogin] = useState(prefGetOpenAtLogin())
   const [checkForUpdatesAutomatically, setCheckForUpdatesAutomatically] = useState(prefGetCheckForUpdatesAutomatically())
   const [showIconInMenuBar, setShowIconInMenuBar] = useState(prefGetShowIconInMenuBar())
+  const [showTodayCount, setShowTodayCount] = useState(prefShouldShowTodayCountInMenuBar())
   const [openWindowStrategy, setOpenWindowStrategy] = useState(prefGetOpenWindowStrategy())
   const [playSoundOnCopy, setPlaySoundOnCopy] = useState(prefShouldPlaySoundOnCopy())
+  const [ignoreOwnChanges, setIgnoreOwnChanges] = useState(prefShouldIgnoreOwnChanges())
+  const [showCopyNotification, setShowCopyNotification] = useState(prefShouldShowCopyNotification())
   const [vimModeEnabled, setVimModeEnabled] = useState(prefGetVimModeEnabled())
   const [checkingForUpdates, setCheckingForUpdates] = useState(false)
+  const [announceClipboardChanges, setAnnounceClipboardChanges] = useState(prefShouldAnnounceClipboardChanges())
 
   // The map of open strategy enum values to labels.
   const openWindowStrategyLabels = {
@@ -81,6 +98,11 @@ export default function General() {
     return () => document.removeEventListener("keydown", down)
   }, [])
 
+  function handleAnnounceClipboardChangesChange(value: boolean) {
+    setAnnounceClipboardChanges(value)
+    prefSetAnnounceClipboardChanges(value)
+  }
+
   function handleThemeChange(theme: string) {
     setTheme(theme)
     prefSetTheme(theme)
@@ -96,6 +118,15 @@ export default function General() {
     prefSetCheckForUpdatesAutomatically(checkForUpdatesAutomatically)
   }
 
+  function handleShowTodayCountChange(show: boolean) {
+    setShowTodayCount(show)
+    prefSetShowTodayCountInMenuBar(show)
+    // The count is displayed when the next item is copied.
+    if (!show && typeof setTrayBadgeCount !== 'undefined') {
+      setTrayBadgeCount(0)
+    }
+  }
+
   function handleShowIconChange(showIcon: boolean) {
     setShowIconInMenuBar(showIcon)
     prefSetShowIconInMenuBar(showIcon)
@@ -111,6 +142,16 @@ export default function General() {
     prefSetPlaySoundOnCopy(play)
   }
 
+  function handleShowCopyNotificationChange(show: boolean) {
+    setShowCopyNotification(show)
+    prefSetShowCopyNotification(show)
+  }
+
+  function handleIgnoreOwnChangesChange(ignore: boolean) {
+    setIgnoreOwnChanges(ignore)
+    prefSetIgnoreOwnChanges(ignore)
+  }
+
   function handleVimModeEnabledChange(enabled: boolean) {
     setVimModeEnabled(enabled)
     prefSetVimModeEnabled(enabled)
@@ -295,6 +336,42 @@ export default function General() {
                       disabled={prefIsPlaySoundOnCopyManaged()}/>
             </div>
 
+            <div className="flex items-center justify-between space-x-20 py-1">
+              <Label htmlFor="showCopyNotification" className="flex flex-col text-base">
+                <span className="">{t('settings.general.showCopyNotification.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.general.showCopyNotification.description')}
+                </span>
+              </Label>
+              <Switch id="showCopyNotification" checked={showCopyNotification}
+                      onCheckedChange={handleShowCopyNotificationChange}
+                      disabled={prefIsShowCopyNotificationManaged()}/>
+            </div>
+
+            <div className="flex items-center justify-between space-x-20 py-1">
+              <Label htmlFor="ignoreOwnChanges" className="flex flex-col text-base">
+                <span className="">{t('settings.general.ignoreOwnChanges.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.general.ignoreOwnChanges.description')}
+                </span>
+              </Label>
+              <Switch id="ignoreOwnChanges" checked={ignoreOwnChanges}
+                      onCheckedChange={handleIgnoreOwnChangesChange}
+                      disabled={prefIsIgnoreOwnChangesManaged()}/>
+            </div>
+
+            <div className="flex items-center justify-between space-x-20 py-1">
+              <Label htmlFor="announceClipboardChanges" className="flex flex-col text-base">
+                <span className="">{t('settings.general.announceChanges.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.general.announceChanges.description')}
+                </span>
+              </Label>
+              <Switch id="announceClipboardChanges" checked={announceClipboardChanges}
+                      onCheckedChange={handleAnnounceClipboardChangesChange}
+                      disabled={prefIsAnnounceClipboardChangesManaged()}/>
+            </div>
+
             <div className="flex items-center justify-between space-x-20 py-1">
               <Label htmlFor="vimModeEnabled" className="flex flex-col text-base">
                 <span className="">Vim Mode</span>
@@ -329,6 +406,18 @@ export default function General() {
                       disabled={prefIsShowIconInMenuBarManaged()}/>
             </div>
 
+            <div className="flex items-center justify-between space-x-20 py-1">
+              <Label htmlFor="showTodayCount" className="flex flex-col text-base">
+                <span className="">{t('settings.general.showTodayCount.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.general.showTodayCount.description')}
+                </span>
+              </Label>
+              <Switch id="showTodayCount" checked={showTodayCount}
+                      onCheckedChange={handleShowTodayCountChange}
+                      disabled={!showIconInMenuBar || prefIsShowTodayCountInMenuBarManaged()}/>
+            </div>
+
             <div className="flex items-center justify-between space-x-10 py-1">
               <Label htmlFor="openWindowStrategy" className="flex flex-col text-base">
                 <span className="">{t('settings.general.openWindow.title')}</span>
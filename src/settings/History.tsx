@@ -5,6 +5,7 @@ import {useEffect, useState} from "react";
 import {
   CopyAndMergeSeparator,
   DoubleClickStrategy,
+  LargeItemPolicy,
   NumberActionStrategy,
   prefGetClearHistoryOnMacReboot,
   prefGetClearHistoryOnQuit,
@@ -34,6 +35,33 @@ import {
   prefShouldShowPreviewForLinks,
   prefShouldTreatDigitNumbersAsColor,
   prefShouldUpdateHistoryAfterAction,
+  prefGetClearHistoryOnLogout,
+  prefSetClearHistoryOnLogout,
+  prefIsClearHistoryOnLogoutManaged,
+  prefGetAutoClearIntervalHours,
+  prefSetAutoClearIntervalHours,
+  prefGetVaultDirectory,
+  prefSetVaultDirectory,
+  prefShouldSecureDelete,
+  prefSetSecureDelete,
+  prefIsSecureDeleteManaged,
+  prefGetLargeItemPolicy,
+  prefSetLargeItemPolicy,
+  prefGetMaxItemKilobytes,
+  prefShouldDeduplicateSimilarImages,
+  prefSetDeduplicateSimilarImages,
+  prefIsDeduplicateSimilarImagesManaged,
+  prefShouldMonitorFindPasteboard,
+  prefSetMonitorFindPasteboard,
+  prefIsMonitorFindPasteboardManaged,
+  prefGetBackupIntervalHours,
+  prefSetBackupIntervalHours,
+  prefGetMaxBackups,
+  prefSetMaxBackups,
+  prefGetMaxHistoryItems,
+  prefSetMaxHistoryItems,
+  prefGetMaxHistoryMegabytes,
+  prefSetMaxHistoryMegabytes,
 } from "@/pref";
 import {
   Select,
@@ -48,13 +76,21 @@ import {
   DropdownMenuRadioItem,
   DropdownMenuTrigger
 } from "@/components/ui/dropdown-menu";
-import {ChevronsUpDown, Download, Upload} from "lucide-react";
+import {ChevronsUpDown, Download, FolderOpen, Upload} from "lucide-react";
 import {Button} from "@/components/ui/button";
 import { Trans, useTranslation } from 'react-i18next';
-import {exportAllData, importData, BackupData} from "@/db";
+import {exportAllData, importData, BackupData, ClipType} from "@/db";
+import {choosePath, PathKind} from "@/dialogs";
+import {getRetentionRules, kRetentionTypes, RetentionRules, setRetentionRules} from "@/retention";
 
 declare const closeSettingsWindow: () => void;
 
+const kBackupIntervalHours = [0, 1, 6, 12, 24, 24 * 7]
+const kMaxBackups = [3, 5, 10, 20, 50]
+const kRetentionDays = [0, 1, 7, 30, 90, 365]
+const kMaxHistoryItems = [0, 100, 500, 1000, 5000, 10000]
+const kMaxHistoryMegabytes = [0, 100, 250, 500, 1000, 2000]
+
 export default function History() {
   const { t } = useTranslation();
 
@@ -63,6 +99,12 @@ export default function History() {
     [DoubleClickStrategy.PASTE]: t('settings.history.doubleClick.paste'),
   }
 
+  const largeItemPolicyLabels = {
+    [LargeItemPolicy.KEEP]: t('settings.history.largeItems.keep'),
+    [LargeItemPolicy.TRUNCATE]: t('settings.history.largeItems.truncate'),
+    [LargeItemPolicy.SKIP]: t('settings.history.largeItems.skip'),
+  }
+
   const numberActionStrategyLabels = {
     [NumberActionStrategy.COPY]: t('settings.history.numberAction.copy'),
     [NumberActionStrategy.PASTE]: t('settings.history.numberAction.paste'),
@@ -81,8 +123,20 @@ export default function History() {
   const [pasteOnClick, setPasteOnClick] = useState(prefShouldPasteOnClick())
   const [doubleClickStrategy, setDoubleClickStrategy] = useState(prefShouldCopyOnDoubleClick() ? DoubleClickStrategy.COPY : DoubleClickStrategy.PASTE)
   const [numberActionStrategy, setNumberActionStrategy] = useState(prefShouldCopyOnNumberAction() ? NumberActionStrategy.COPY : NumberActionStrategy.PASTE)
+  const [largeItemPolicy, setLargeItemPolicy] = useState(prefGetLargeItemPolicy())
   const [isExporting, setIsExporting] = useState(false)
   const [isImporting, setIsImporting] = useState(false)
+  const [clearHistoryOnLogout, setClearHistoryOnLogout] = useState(prefGetClearHistoryOnLogout())
+  const [autoClearIntervalHours, setAutoClearIntervalHours] = useState(prefGetAutoClearIntervalHours())
+  const [vaultDirectory, setVaultDirectory] = useState(prefGetVaultDirectory())
+  const [secureDelete, setSecureDelete] = useState(prefShouldSecureDelete())
+  const [deduplicateSimilarImages, setDeduplicateSimilarImages] = useState(prefShouldDeduplicateSimilarImages())
+  const [monitorFindPasteboard, setMonitorFindPasteboard] = useState(prefShouldMonitorFindPasteboard())
+  const [backupIntervalHours, setBackupIntervalHours] = useState(prefGetBackupIntervalHours())
+  const [maxBackups, setMaxBackups] = useState(prefGetMaxBackups())
+  const [maxHistoryItems, setMaxHistoryItems] = useState(prefGetMaxHistoryItems())
+  const [maxHistoryMegabytes, setMaxHistoryMegabytes] = useState(prefGetMaxHistoryMegabytes())
+  const [retentionRules, setRetentionRulesState] = useState<RetentionRules>(getRetentionRules())
 
   useEffect(() => {
     const down = (e: KeyboardEvent) => {
@@ -96,6 +150,110 @@ export default function History() {
     return () => document.removeEventListener("keydown", down)
   }, [])
 
+  async function handleChooseVaultDirectory() {
+    let dir = await choosePath(PathKind.Folder)
+    if (dir) {
+      setVaultDirectory(dir)
+      prefSetVaultDirectory(dir)
+    }
+  }
+
+  function handleClearVaultDirectory() {
+    setVaultDirectory("")
+    prefSetVaultDirectory("")
+  }
+
+  function handleAutoClearIntervalHoursChange(value: string) {
+    setAutoClearIntervalHours(parseInt(value))
+    prefSetAutoClearIntervalHours(parseInt(value))
+  }
+
+  function handleClearHistoryOnLogoutChange(value: boolean) {
+    setClearHistoryOnLogout(value)
+    prefSetClearHistoryOnLogout(value)
+  }
+
+  function handleSecureDeleteChange(value: boolean) {
+    setSecureDelete(value)
+    prefSetSecureDelete(value)
+  }
+
+  function handleMaxHistoryItemsChange(value: string) {
+    setMaxHistoryItems(parseInt(value))
+    prefSetMaxHistoryItems(parseInt(value))
+  }
+
+  function handleMaxHistoryMegabytesChange(value: string) {
+    setMaxHistoryMegabytes(parseInt(value))
+    prefSetMaxHistoryMegabytes(parseInt(value))
+  }
+
+  // The limits set in the config file might not be in the list.
+  function withValue(values: number[], value: number): number[] {
+    return values.includes(value) ? values : [...values, value].sort((a, b) => a - b)
+  }
+
+  function saveRetentionRules(rules: RetentionRules) {
+    setRetentionRulesState(rules)
+    setRetentionRules(rules)
+  }
+
+  function handleRetentionDaysChange(type: keyof typeof ClipType, value: string) {
+    let maxAgeDays = {...retentionRules.maxAgeDays}
+    let days = parseInt(value)
+    if (days > 0) {
+      maxAgeDays[type] = days
+    } else {
+      delete maxAgeDays[type]
+    }
+    saveRetentionRules({...retentionRules, maxAgeDays})
+  }
+
+  function handleRetentionKeepFavoritesChange(value: boolean) {
+    saveRetentionRules({...retentionRules, keepFavorites: value})
+  }
+
+  function getRetentionDaysOptions(type: keyof typeof ClipType): number[] {
+    return withValue(kRetentionDays, retentionRules.maxAgeDays[type] ?? 0)
+  }
+
+  function getRetentionDaysLabel(days: number): string {
+    if (days === 0) {
+      return t('settings.history.retention.never')
+    }
+    return t('settings.history.retention.days', {count: days})
+  }
+
+  function handleDeduplicateSimilarImagesChange(value: boolean) {
+    setDeduplicateSimilarImages(value)
+    prefSetDeduplicateSimilarImages(value)
+  }
+
+  function handleMonitorFindPasteboardChange(value: boolean) {
+    setMonitorFindPasteboard(value)
+    prefSetMonitorFindPasteboard(value)
+  }
+
+  function handleBackupIntervalHoursChange(value: string) {
+    setBackupIntervalHours(parseInt(value))
+    prefSetBackupIntervalHours(parseInt(value))
+  }
+
+  function handleMaxBackupsChange(value: string) {
+    setMaxBackups(parseInt(value))
+    prefSetMaxBackups(parseInt(value))
+  }
+
+  function getBackupIntervalLabel(hours: number): string {
+    if (hours === 0) {
+      return t('settings.history.backupInterval.off')
+    }
+    if (hours % 24 === 0) {
+      return t('settings.history.backupInterval.days', {count: hours / 24})
+    }
+    return t('settings.history.backupInterval.hours', {count: hours})
+  }
+
   function handleWarnOnClearHistoryChange(warnOnClearHistory: boolean) {
     setWarnOnClearHistory(warnOnClearHistory)
     prefSetWarnOnClearHistory(warnOnClearHistory)
@@ -161,6 +319,11 @@ export default function History() {
     prefSetCopyOnNumberAction(numberActionStrategy === NumberActionStrategy.COPY)
   }
 
+  function handleLargeItemPolicyChange(policy: string) {
+    setLargeItemPolicy(policy as LargeItemPolicy)
+    prefSetLargeItemPolicy(policy as LargeItemPolicy)
+  }
+
   async function handleExportData() {
     try {
       setIsExporting(true)
@@ -337,6 +500,70 @@ export default function History() {
               </DropdownMenu>
             </div>
 
+            <div className="flex items-center justify-between space-x-12 pb-1">
+              <Label className="flex flex-col text-base">
+                <span className="">{t('settings.history.largeItems.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm mt-1">
+                  {t('settings.history.largeItems.description', {size: prefGetMaxItemKilobytes()})}
+                </span>
+              </Label>
+              <DropdownMenu>
+                <DropdownMenuTrigger asChild>
+                  <Button variant="outline" size="dropdown" className="px-4 outline-none">
+                    {largeItemPolicyLabels[largeItemPolicy]}
+                    <ChevronsUpDown className="ml-2 h-4 w-4 shrink-0 opacity-50"/>
+                  </Button>
+                </DropdownMenuTrigger>
+                <DropdownMenuContent className="p-1.5 bg-actions-background" align="end">
+                  <DropdownMenuRadioGroup value={largeItemPolicy}
+                                          onValueChange={handleLargeItemPolicyChange}>
+                    <DropdownMenuRadioItem value={LargeItemPolicy.TRUNCATE}
+                                           className="py-2 pr-4 pl-10">
+                      <div className="flex flex-col">
+                        <span>{largeItemPolicyLabels[LargeItemPolicy.TRUNCATE]}</span>
+                      </div>
+                    </DropdownMenuRadioItem>
+                    <DropdownMenuRadioItem value={LargeItemPolicy.SKIP}
+                                           className="py-2 pr-4 pl-10">
+                      <div className="flex flex-col">
+                        <span>{largeItemPolicyLabels[LargeItemPolicy.SKIP]}</span>
+                      </div>
+                    </DropdownMenuRadioItem>
+                    <DropdownMenuRadioItem value={LargeItemPolicy.KEEP}
+                                           className="py-2 pr-4 pl-10">
+                      <div className="flex flex-col">
+                        <span>{largeItemPolicyLabels[LargeItemPolicy.KEEP]}</span>
+                      </div>
+                    </DropdownMenuRadioItem>
+                  </DropdownMenuRadioGroup>
+                </DropdownMenuContent>
+              </DropdownMenu>
+            </div>
+
+            <div className="flex items-center justify-between space-x-20 py-1">
+              <Label htmlFor="deduplicateSimilarImages" className="flex flex-col text-base">
+                <span className="">{t('settings.history.deduplicateSimilarImages.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm mt-1">
+                  {t('settings.history.deduplicateSimilarImages.description')}
+                </span>
+              </Label>
+              <Switch id="deduplicateSimilarImages" checked={deduplicateSimilarImages}
+                      onCheckedChange={handleDeduplicateSimilarImagesChange}
+                      disabled={prefIsDeduplicateSimilarImagesManaged()}/>
+            </div>
+
+            <div className="flex items-center justify-between space-x-20 py-1">
+              <Label htmlFor="monitorFindPasteboard" className="flex flex-col text-base">
+                <span className="">{t('settings.history.monitorFindPasteboard.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm mt-1">
+                  {t('settings.history.monitorFindPasteboard.description')}
+                </span>
+              </Label>
+              <Switch id="monitorFindPasteboard" checked={monitorFindPasteboard}
+                      onCheckedChange={handleMonitorFindPasteboardChange}
+                      disabled={prefIsMonitorFindPasteboardManaged()}/>
+            </div>
+
             <hr/>
 
             <div className="flex items-center justify-between space-x-20 pb-1">
@@ -478,6 +705,71 @@ export default function History() {
               </div>
             </div>
 
+            <div className="flex items-center justify-between space-x-10 py-1">
+              <Label className="flex flex-col text-base">
+                <span className="">{t('settings.history.backupInterval.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.history.backupInterval.description')}
+                </span>
+              </Label>
+              <Select defaultValue={backupIntervalHours.toString()}
+                      onValueChange={handleBackupIntervalHoursChange}>
+                <SelectTrigger className="w-[150px]">
+                  <SelectValue/>
+                </SelectTrigger>
+                <SelectContent>
+                  {
+                    kBackupIntervalHours.map(hours =>
+                        <SelectItem key={hours} value={hours.toString()}>{getBackupIntervalLabel(hours)}</SelectItem>)
+                  }
+                </SelectContent>
+              </Select>
+            </div>
+
+            <div className="flex items-center justify-between space-x-10 py-1">
+              <Label className="flex flex-col text-base">
+                <span className="">{t('settings.history.maxBackups.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.history.maxBackups.description')}
+                </span>
+              </Label>
+              <Select defaultValue={maxBackups.toString()}
+                      onValueChange={handleMaxBackupsChange}
+                      disabled={backupIntervalHours === 0}>
+                <SelectTrigger className="w-[150px]">
+                  <SelectValue/>
+                </SelectTrigger>
+                <SelectContent>
+                  {
+                    kMaxBackups.map(count =>
+                        <SelectItem key={count} value={count.toString()}>{count}</SelectItem>)
+                  }
+                </SelectContent>
+              </Select>
+            </div>
+
+            <div className="flex items-center justify-between space-x-10 py-1">
+              <Label className="flex flex-col text-base">
+                <span className="">{t('settings.history.vaultDirectory.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm break-all">
+                  {vaultDirectory || t('settings.history.vaultDirectory.description')}
+                </span>
+              </Label>
+              <div className="flex gap-2">
+                {
+                    vaultDirectory &&
+                    <Button variant="outline" size="sm" onClick={handleClearVaultDirectory}>
+                      {t('settings.history.vaultDirectory.clear')}
+                    </Button>
+                }
+                <Button variant="outline" size="sm" onClick={handleChooseVaultDirectory}
+                        className="flex items-center gap-2">
+                  <FolderOpen className="h-4 w-4"/>
+                  {t('settings.history.vaultDirectory.choose')}
+                </Button>
+              </div>
+            </div>
+
             <hr/>
 
             <div className="flex items-center justify-between space-x-20 py-1">
@@ -502,6 +794,128 @@ export default function History() {
                       onCheckedChange={handleClearHistoryOnMacRebootChange}
                       disabled={prefIsClearHistoryOnMacRebootManaged()}/>
             </div>
+            <div className="flex items-center justify-between space-x-20 py-1">
+              <Label htmlFor="clearHistoryOnLogout" className="flex flex-col text-base">
+                <span className="">{t('settings.history.clearHistoryOnLogout.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.history.clearHistoryOnLogout.description')}
+                </span>
+              </Label>
+              <Switch id="clearHistoryOnLogout" checked={clearHistoryOnLogout}
+                      onCheckedChange={handleClearHistoryOnLogoutChange}
+                      disabled={prefIsClearHistoryOnLogoutManaged()}/>
+            </div>
+            <div className="flex items-center justify-between space-x-10 py-1">
+              <Label className="flex flex-col text-base">
+                <span className="">{t('settings.history.autoClearInterval.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.history.autoClearInterval.description')}
+                </span>
+              </Label>
+              <Select defaultValue={autoClearIntervalHours.toString()}
+                      onValueChange={handleAutoClearIntervalHoursChange}>
+                <SelectTrigger className="w-[150px]">
+                  <SelectValue/>
+                </SelectTrigger>
+                <SelectContent>
+                  {
+                    kBackupIntervalHours.map(hours =>
+                        <SelectItem key={hours} value={hours.toString()}>{getBackupIntervalLabel(hours)}</SelectItem>)
+                  }
+                </SelectContent>
+              </Select>
+            </div>
+            <div className="flex items-center justify-between space-x-10 py-1">
+              <Label className="flex flex-col text-base">
+                <span className="">{t('settings.history.maxItems.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.history.maxItems.description')}
+                </span>
+              </Label>
+              <Select defaultValue={maxHistoryItems.toString()}
+                      onValueChange={handleMaxHistoryItemsChange}>
+                <SelectTrigger className="w-[150px]">
+                  <SelectValue/>
+                </SelectTrigger>
+                <SelectContent>
+                  {
+                    withValue(kMaxHistoryItems, maxHistoryItems).map(count =>
+                        <SelectItem key={count} value={count.toString()}>
+                          {count === 0 ? t('settings.history.maxItems.unlimited') : t('settings.history.maxItems.items', {count})}
+                        </SelectItem>)
+                  }
+                </SelectContent>
+              </Select>
+            </div>
+            <div className="flex items-center justify-between space-x-10 py-1">
+              <Label className="flex flex-col text-base">
+                <span className="">{t('settings.history.maxMegabytes.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.history.maxMegabytes.description')}
+                </span>
+              </Label>
+              <Select defaultValue={maxHistoryMegabytes.toString()}
+                      onValueChange={handleMaxHistoryMegabytesChange}>
+                <SelectTrigger className="w-[150px]">
+                  <SelectValue/>
+                </SelectTrigger>
+                <SelectContent>
+                  {
+                    withValue(kMaxHistoryMegabytes, maxHistoryMegabytes).map(megabytes =>
+                        <SelectItem key={megabytes} value={megabytes.toString()}>
+                          {megabytes === 0 ? t('settings.history.maxItems.unlimited') : t('settings.history.maxMegabytes.megabytes', {count: megabytes})}
+                        </SelectItem>)
+                  }
+                </SelectContent>
+              </Select>
+            </div>
+            <div className="flex flex-col py-1">
+              <Label className="flex flex-col text-base">
+                <span className="">{t('settings.history.retention.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.history.retention.description')}
+                </span>
+              </Label>
+            </div>
+            {
+              kRetentionTypes.map(type =>
+                  <div key={type} className="flex items-center justify-between space-x-10 py-1 pl-4">
+                    <Label className="text-base">{t(`app.sidebar.${type.toLowerCase()}`)}</Label>
+                    <Select defaultValue={(retentionRules.maxAgeDays[type] ?? 0).toString()}
+                            onValueChange={value => handleRetentionDaysChange(type, value)}>
+                      <SelectTrigger className="w-[150px]">
+                        <SelectValue/>
+                      </SelectTrigger>
+                      <SelectContent>
+                        {
+                          getRetentionDaysOptions(type).map(days =>
+                              <SelectItem key={days} value={days.toString()}>{getRetentionDaysLabel(days)}</SelectItem>)
+                        }
+                      </SelectContent>
+                    </Select>
+                  </div>)
+            }
+            <div className="flex items-center justify-between space-x-20 py-1 pl-4">
+              <Label htmlFor="retentionKeepFavorites" className="flex flex-col text-base">
+                <span className="">{t('settings.history.retention.keepFavorites.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.history.retention.keepFavorites.description')}
+                </span>
+              </Label>
+              <Switch id="retentionKeepFavorites" checked={retentionRules.keepFavorites}
+                      onCheckedChange={handleRetentionKeepFavoritesChange}/>
+            </div>
+            <div className="flex items-center justify-between space-x-20 py-1">
+              <Label htmlFor="secureDelete" className="flex flex-col text-base">
+                <span className="">{t('settings.history.secureDelete.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.history.secureDelete.description')}
+                </span>
+              </Label>
+              <Switch id="secureDelete" checked={secureDelete}
+                      onCheckedChange={handleSecureDeleteChange}
+                      disabled={prefIsSecureDeleteManaged()}/>
+            </div>
           </div>
         </div>
       </div>
@@ -6,24 +6,71 @@ import {
   prefGetAppsToIgnore,
   prefGetIgnoreConfidentialContent,
   prefGetIgnoreTransientContent,
+  prefIsAppLockEnabledManaged,
+  prefIsFetchLinkPreviewsManaged,
   prefIsIgnoreConfidentialContentManaged,
   prefIsIgnoreTransientContentManaged,
+  prefSetAppLockEnabled,
   prefSetAppsToIgnore,
+  prefSetFetchLinkPreviews,
   prefSetIgnoreConfidentialContent,
   prefSetIgnoreTransientContent,
+  prefSetPauseWhenScreenSharing,
+  prefShouldAppLockEnabled,
+  prefShouldFetchLinkPreviews,
+  prefShouldPauseWhenScreenSharing,
+  prefIsPauseWhenScreenSharingManaged,
+  prefGetApiServerPort,
+  prefGetAutoLockMinutes,
+  prefSetAutoLockMinutes,
+  prefIsApiServerEnabledManaged,
+  prefSetApiServerEnabled,
+  prefShouldApiServerEnabled,
 } from "@/pref";
+import {getApiToken, regenerateApiToken} from "@/api";
+import {hasAppLockPassphrase, setAppLockPassphrase, verifyUser} from "@/applock";
+import {Input} from "@/components/ui/input";
+import {Select, SelectContent, SelectItem, SelectTrigger, SelectValue} from "@/components/ui/select";
 import IgnoreAppsPane from "@/settings/IgnoreAppsPane";
+import {Button} from "@/components/ui/button";
+import {checkPermission, PermissionKind, PermissionState, requestSystemPermission} from "@/permissions";
 import { Trans, useTranslation } from 'react-i18next';
 
 declare const closeSettingsWindow: () => void;
 declare const selectAppsToIgnore: () => string[];
 
+// The inactivity periods after which the app locks itself. Zero means never.
+const kAutoLockMinutes = [0, 1, 5, 15, 30, 60]
+
 export default function Privacy() {
   const { t } = useTranslation();
   
   const [ignoreTransientContent, setIgnoreTransientContent] = useState(prefGetIgnoreTransientContent());
   const [ignoreConfidentialContent, setIgnoreConfidentialContent] = useState(prefGetIgnoreConfidentialContent());
   const [appsToIgnore, setAppsToIgnore] = useState(prefGetAppsToIgnore());
+  const [appLockEnabled, setAppLockEnabled] = useState(prefShouldAppLockEnabled());
+  const [autoLockMinutes, setAutoLockMinutes] = useState(prefGetAutoLockMinutes());
+  const [hasPassphrase, setHasPassphrase] = useState(hasAppLockPassphrase());
+  const [passphrase, setPassphrase] = useState("");
+  const [fetchLinkPreviews, setFetchLinkPreviews] = useState(prefShouldFetchLinkPreviews());
+  const [pauseWhenScreenSharing, setPauseWhenScreenSharing] = useState(prefShouldPauseWhenScreenSharing());
+  const [permissions, setPermissions] = useState(checkAllPermissions());
+  const [apiServerEnabled, setApiServerEnabled] = useState(prefShouldApiServerEnabled());
+  const [apiToken, setApiToken] = useState(apiServerEnabled ? getApiToken() : "");
+
+  function checkAllPermissions(): Record<PermissionKind, PermissionState> {
+    let result = {} as Record<PermissionKind, PermissionState>
+    for (const kind of Object.values(PermissionKind)) {
+      result[kind] = checkPermission(kind)
+    }
+    return result
+  }
+
+  // Update the statuses when the user returns from System Settings.
+  useEffect(() => {
+    const timer = setInterval(() => setPermissions(checkAllPermissions()), 2000)
+    return () => clearInterval(timer)
+  }, [])
 
   useEffect(() => {
     const down = (e: KeyboardEvent) => {
@@ -37,6 +84,10 @@ export default function Privacy() {
     return () => document.removeEventListener("keydown", down)
   }, [])
 
+  function handleRequestPermission(kind: PermissionKind) {
+    requestSystemPermission(kind)
+  }
+
   function handleIgnoreTransientContentChange(checked: boolean) {
     setIgnoreTransientContent(checked)
     prefSetIgnoreTransientContent(checked)
@@ -47,6 +98,63 @@ export default function Privacy() {
     prefSetIgnoreConfidentialContent(checked)
   }
 
+  async function handleAppLockEnabledChange(checked: boolean) {
+    // Only the user who can unlock the app can turn the lock off.
+    if (!checked && !await verifyUser()) {
+      return
+    }
+    setAppLockEnabled(checked)
+    prefSetAppLockEnabled(checked)
+  }
+
+  function handleAutoLockMinutesChange(value: string) {
+    setAutoLockMinutes(parseInt(value))
+    prefSetAutoLockMinutes(parseInt(value))
+  }
+
+  async function handleSavePassphrase(value: string) {
+    // Replacing or removing the passphrase requires the current user.
+    if (hasPassphrase && !await verifyUser()) {
+      return
+    }
+    try {
+      await setAppLockPassphrase(value)
+      setHasPassphrase(value.length > 0)
+      setPassphrase("")
+    } catch (error) {
+      console.error('❌ Failed to save app lock passphrase:', error)
+    }
+  }
+
+  function getAutoLockLabel(minutes: number) {
+    if (minutes === 0) {
+      return t('settings.privacy.autoLock.never')
+    }
+    return t('settings.privacy.autoLock.minutes', {count: minutes})
+  }
+
+  function handlePauseWhenScreenSharingChange(checked: boolean) {
+    setPauseWhenScreenSharing(checked)
+    prefSetPauseWhenScreenSharing(checked)
+  }
+
+  function handleFetchLinkPreviewsChange(checked: boolean) {
+    setFetchLinkPreviews(checked)
+    prefSetFetchLinkPreviews(checked)
+  }
+
+  function handleApiServerEnabledChange(checked: boolean) {
+    if (checked) {
+      setApiToken(getApiToken())
+    }
+    setApiServerEnabled(checked)
+    prefSetApiServerEnabled(checked)
+  }
+
+  function handleRegenerateApiToken() {
+    setApiToken(regenerateApiToken())
+  }
+
   function handleSelectApps() {
     selectAppsToIgnore()
   }
@@ -96,6 +204,142 @@ export default function Privacy() {
                       onCheckedChange={handleIgnoreTransientContentChange}
                       disabled={prefIsIgnoreTransientContentManaged()}/>
             </div>
+            <div className="flex items-center justify-between space-x-20 py-1">
+              <Label htmlFor="appLock" className="flex flex-col text-base">
+                <span className="">{t('settings.privacy.appLock.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.privacy.appLock.description')}
+                </span>
+              </Label>
+              <Switch id="appLock" checked={appLockEnabled}
+                      onCheckedChange={handleAppLockEnabledChange}
+                      disabled={prefIsAppLockEnabledManaged()}/>
+            </div>
+            {
+                appLockEnabled &&
+                <>
+                  <div className="flex items-center justify-between space-x-10 py-1">
+                    <Label className="flex flex-col text-base">
+                      <span className="">{t('settings.privacy.autoLock.title')}</span>
+                      <span className="text-neutral-500 font-normal text-sm">
+                        {t('settings.privacy.autoLock.description')}
+                      </span>
+                    </Label>
+                    <Select defaultValue={autoLockMinutes.toString()}
+                            onValueChange={handleAutoLockMinutesChange}>
+                      <SelectTrigger className="w-[150px]">
+                        <SelectValue/>
+                      </SelectTrigger>
+                      <SelectContent>
+                        {
+                          kAutoLockMinutes.map(minutes =>
+                              <SelectItem key={minutes} value={minutes.toString()}>
+                                {getAutoLockLabel(minutes)}
+                              </SelectItem>)
+                        }
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  <div className="flex flex-col gap-2 py-1">
+                    <Label htmlFor="appLockPassphrase" className="flex flex-col text-base">
+                      <span className="">{t('settings.privacy.appLockPassphrase.title')}</span>
+                      <span className="text-neutral-500 font-normal text-sm">
+                        {t(hasPassphrase ?
+                            'settings.privacy.appLockPassphrase.descriptionSet' :
+                            'settings.privacy.appLockPassphrase.description')}
+                      </span>
+                    </Label>
+                    <div className="flex items-center space-x-2">
+                      <Input id="appLockPassphrase" type="password" value={passphrase}
+                             onChange={e => setPassphrase(e.target.value)}/>
+                      <Button variant="outline" size="sm" onClick={() => handleSavePassphrase(passphrase)}
+                              disabled={passphrase.length === 0}>
+                        {t(hasPassphrase ?
+                            'settings.privacy.appLockPassphrase.change' :
+                            'settings.privacy.appLockPassphrase.set')}
+                      </Button>
+                      {
+                          hasPassphrase &&
+                          <Button variant="outline" size="sm" onClick={() => handleSavePassphrase("")}>
+                            {t('settings.privacy.appLockPassphrase.remove')}
+                          </Button>
+                      }
+                    </div>
+                  </div>
+                </>
+            }
+            <div className="flex items-center justify-between space-x-20 py-1">
+              <Label htmlFor="pauseWhenScreenSharing" className="flex flex-col text-base">
+                <span className="">{t('settings.privacy.pauseWhenScreenSharing.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.privacy.pauseWhenScreenSharing.description')}
+                </span>
+              </Label>
+              <Switch id="pauseWhenScreenSharing" checked={pauseWhenScreenSharing}
+                      onCheckedChange={handlePauseWhenScreenSharingChange}
+                      disabled={prefIsPauseWhenScreenSharingManaged()}/>
+            </div>
+            <div className="flex items-center justify-between space-x-20 py-1">
+              <Label htmlFor="fetchLinkPreviews" className="flex flex-col text-base">
+                <span className="">{t('settings.privacy.fetchLinkPreviews.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.privacy.fetchLinkPreviews.description')}
+                </span>
+              </Label>
+              <Switch id="fetchLinkPreviews" checked={fetchLinkPreviews}
+                      onCheckedChange={handleFetchLinkPreviewsChange}
+                      disabled={prefIsFetchLinkPreviewsManaged()}/>
+            </div>
+
+            <hr/>
+
+            <div className="flex items-center justify-between space-x-20 py-1">
+              <Label htmlFor="apiServer" className="flex flex-col text-base">
+                <span className="">{t('settings.privacy.apiServer.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.privacy.apiServer.description', {port: prefGetApiServerPort()})}
+                </span>
+              </Label>
+              <Switch id="apiServer" checked={apiServerEnabled}
+                      onCheckedChange={handleApiServerEnabledChange}
+                      disabled={prefIsApiServerEnabledManaged()}/>
+            </div>
+            {
+                apiServerEnabled &&
+                <div className="flex items-center justify-between space-x-10 py-1">
+                  <Label className="flex flex-col text-base min-w-0">
+                    <span className="">{t('settings.privacy.apiServer.token')}</span>
+                    <span className="text-neutral-500 font-normal font-mono text-sm break-all select-text">
+                      {apiToken}
+                    </span>
+                  </Label>
+                  <Button variant="outline" size="sm" onClick={handleRegenerateApiToken}>
+                    {t('settings.privacy.apiServer.regenerate')}
+                  </Button>
+                </div>
+            }
+
+            <hr/>
+
+            {Object.values(PermissionKind).map(kind => (
+                <div key={kind} className="flex items-center justify-between space-x-10 py-1">
+                  <Label className="flex flex-col text-base">
+                    <span className="">{t(`settings.privacy.permissions.${kind}.title`)}</span>
+                    <span className="text-neutral-500 font-normal text-sm">
+                      {t(`settings.privacy.permissions.${kind}.description`)}
+                    </span>
+                  </Label>
+                  {permissions[kind] === PermissionState.Granted ? (
+                      <span className="text-neutral-500 text-sm whitespace-nowrap">
+                        {t('settings.privacy.permissions.granted')}
+                      </span>
+                  ) : (
+                      <Button variant="outline" size="sm" onClick={() => handleRequestPermission(kind)}>
+                        {t('settings.privacy.permissions.grant')}
+                      </Button>
+                  )}
+                </div>
+            ))}
 
             <hr/>
 
@@ -2,6 +2,7 @@ import * as React from "react";
 import {SidebarProvider} from "@/components/ui/sidebar";
 import History from "@/settings/History";
 import Privacy from "@/settings/Privacy";
+import Sync from "@/settings/Sync";
 import Shortcuts from "@/settings/Shortcuts";
 import License from "@/settings/License";
 import General from "@/settings/General";
@@ -9,6 +10,11 @@ import SettingsSidebar from "@/settings/SettingsSidebar";
 import {SettingsSidebarItemType} from "@/settings/SettingsSidebarItem";
 import {useState} from "react";
 import About from "@/settings/About";
+import {pathSelected} from "@/dialogs";
+import {userAuthenticated} from "@/applock";
+import {isGuestMode} from "@/guest";
+
+declare const closeSettingsWindow: () => void;
 
 interface SettingsProps {
   selectedItemType: SettingsSidebarItemType
@@ -21,6 +27,15 @@ export default function Settings(props: SettingsProps) {
     setSelectedItemType(type)
   }
 
+  (window as any).pathSelected = pathSelected;
+  (window as any).userAuthenticated = userAuthenticated;
+
+  // The settings can't be changed in guest mode.
+  if (isGuestMode()) {
+    closeSettingsWindow()
+    return null
+  }
+
   return (
       <SidebarProvider className="">
         <SettingsSidebar onSelect={handleSidebarItemSelect} selectedItemType={selectedItemType}/>
@@ -37,6 +52,9 @@ export default function Settings(props: SettingsProps) {
           {
               selectedItemType === "Privacy" && <Privacy/>
           }
+          {
+              selectedItemType === "Sync" && <Sync/>
+          }
           {
               selectedItemType === "License" && <License/>
           }
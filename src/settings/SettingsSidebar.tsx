@@ -13,6 +13,7 @@ import {
   KeyboardIcon,
   KeyRoundIcon,
   ListIcon,
+  RefreshCwIcon,
   SettingsIcon,
   ShieldCheckIcon
 } from "lucide-react";
@@ -61,6 +62,11 @@ export default function SettingsSidebar(props: SettingsSidebarProps) {
                                      icon={ShieldCheckIcon}
                                      isSelected={selectedItemType === "Privacy"}
                                      onSelect={() => handleSelect("Privacy")}/>
+                <SettingsSidebarItem type={"Sync"}
+                                     url={"/"}
+                                     icon={RefreshCwIcon}
+                                     isSelected={selectedItemType === "Sync"}
+                                     onSelect={() => handleSelect("Sync")}/>
                 <SettingsSidebarItem type={"License"}
                                      url={"/"}
                                      icon={KeyRoundIcon}
@@ -5,15 +5,20 @@ import {isModifierKey, keysToDisplayShortcut, shortcutToDisplayShortcut} from "@
 import {Button} from "@/components/ui/button";
 import {Undo2Icon} from "lucide-react";
 import { useTranslation } from 'react-i18next';
+import {findShortcutBinding, ShortcutConflict, validateShortcut} from "@/shortcuts";
 
 declare const enableOpenAppShortcut: () => void;
 declare const disableOpenAppShortcut: () => void;
 declare const enablePauseResumeShortcut: () => void;
 declare const disablePauseResumeShortcut: () => void;
+declare const enableCycleProfileShortcut: () => void;
+declare const disableCycleProfileShortcut: () => void;
 declare const enablePasteNextItemShortcut: () => void;
 declare const disablePasteNextItemShortcut: () => void;
 
 type ShortcutProps = {
+  // The id of the shortcut binding used to detect the conflicts.
+  id?: string
   shortcut: string
   defaultShortcut?: string
   onSave: (shortcut: string) => void
@@ -25,11 +30,13 @@ export default function ShortcutInput(props: ShortcutProps) {
   const [isEditing, setIsEditing] = useState(false);
   const [currentKeys, setCurrentKeys] = useState<string[]>([]);
   const [shortcut, setShortcut] = useState(props.shortcut);
+  const [conflict, setConflict] = useState<ShortcutConflict | undefined>(undefined);
 
   function startEditing() {
     setIsEditing(true)
     disableOpenAppShortcut()
     disablePauseResumeShortcut()
+    disableCycleProfileShortcut()
     disablePasteNextItemShortcut()
   }
 
@@ -37,10 +44,12 @@ export default function ShortcutInput(props: ShortcutProps) {
     setIsEditing(false)
     enableOpenAppShortcut()
     enablePauseResumeShortcut()
+    enableCycleProfileShortcut()
     enablePasteNextItemShortcut()
   }
 
   function handleClick() {
+    setConflict(undefined)
     setShortcut('')
     startEditing()
   }
@@ -71,9 +80,16 @@ export default function ShortcutInput(props: ShortcutProps) {
 
       if (!isModifierKey(key)) {
         const newShortcut = keys.join(' + ')
-        setShortcut(newShortcut)
         setCurrentKeys([])
         stopEditing()
+        const shortcutConflict = validateShortcut(newShortcut, props.id)
+        if (shortcutConflict) {
+          // Keep the previous shortcut instead of overwriting the existing one.
+          setConflict(shortcutConflict)
+          setShortcut(props.shortcut)
+          return
+        }
+        setShortcut(newShortcut)
         props.onSave(newShortcut)
       }
     }
@@ -90,6 +106,7 @@ export default function ShortcutInput(props: ShortcutProps) {
 
   function handleReset() {
     if (props.defaultShortcut !== undefined) {
+      setConflict(undefined)
       setShortcut(props.defaultShortcut)
       props.onSave(props.defaultShortcut)
     }
@@ -105,23 +122,39 @@ export default function ShortcutInput(props: ShortcutProps) {
     return shortcutToDisplayShortcut(shortcut)
   }
 
+  function getConflictName(conflict: ShortcutConflict) {
+    if (conflict.bindingId) {
+      let binding = findShortcutBinding(conflict.bindingId)
+      return binding ? t(binding.label) : conflict.bindingId
+    }
+    return conflict.systemShortcut
+  }
+
   return (
-      <div className="flex flex-row bg-shortcut shadow hover:shadow-md rounded-md">
-        <Input
-            className={`w-40 h-8 pl-10 text-base text-center caret-transparent border-none bg-shortcut ${isEditing ? "text-neutral-400" : ""}`}
-            title={t('settings.shortcuts.clickToEditShortcut')}
-            readOnly={true}
-            value={getInputValue()}
-            onBlur={handleBlur}
-            onClick={handleClick}
-            onKeyDown={handleKeyDown}
-            onKeyUp={handleKeyUp}/>
-        <Button variant="ghost" onClick={handleReset}
-                className="text-xs my-auto h-5 w-5 p-0 mr-2 text-neutral-400"
-                title="Reset to default" disabled={props.shortcut === props.defaultShortcut}>
-          <Undo2Icon
-              className={props.shortcut !== props.defaultShortcut ? "w-4 h-4" : "invisible"}/>
-        </Button>
+      <div className="flex flex-col items-end">
+        <div className="flex flex-row bg-shortcut shadow hover:shadow-md rounded-md">
+          <Input
+              className={`w-40 h-8 pl-10 text-base text-center caret-transparent border-none bg-shortcut ${isEditing ? "text-neutral-400" : ""}`}
+              title={t('settings.shortcuts.clickToEditShortcut')}
+              readOnly={true}
+              value={getInputValue()}
+              onBlur={handleBlur}
+              onClick={handleClick}
+              onKeyDown={handleKeyDown}
+              onKeyUp={handleKeyUp}/>
+          <Button variant="ghost" onClick={handleReset}
+                  className="text-xs my-auto h-5 w-5 p-0 mr-2 text-neutral-400"
+                  title="Reset to default" disabled={props.shortcut === props.defaultShortcut}>
+            <Undo2Icon
+                className={props.shortcut !== props.defaultShortcut ? "w-4 h-4" : "invisible"}/>
+          </Button>
+        </div>
+        {
+            conflict &&
+            <span className="text-xs text-destructive pt-1">
+              {t('settings.shortcuts.conflict', {name: getConflictName(conflict)})}
+            </span>
+        }
       </div>
   )
 }
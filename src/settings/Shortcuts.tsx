@@ -1,6 +1,8 @@
 import * as React from "react";
 import {useEffect, useState} from "react";
 import ShortcutInput from "@/settings/ShortcutInput";
+import {Label} from "@/components/ui/label";
+import {Switch} from "@/components/ui/switch";
 import { useTranslation } from 'react-i18next';
 import {
   prefGetCapitalizeShortcut,
@@ -16,7 +18,7 @@ import {
   prefGetOpenSettingsShortcut,
   prefGetPasteNextItemShortcut,
   prefGetPasteSelectedItemToActiveAppShortcut, prefGetPasteSelectedObjectToActiveAppShortcut,
-  prefGetPauseResumeShortcut, prefGetQuickLookShortcut, prefGetRemoveEmptyLinesShortcut,
+  prefGetCycleProfileShortcut, prefGetPauseResumeShortcut, prefGetQuickLookShortcut, prefGetRemoveEmptyLinesShortcut,
   prefGetRenameItemShortcut,
   prefGetSaveImageAsFileShortcut,
   prefGetSelectNextItemShortcut,
@@ -26,37 +28,20 @@ import {
   prefGetTogglePreviewShortcut, prefGetTrimSurroundingWhitespacesShortcut,
   prefGetZoomUIInShortcut,
   prefGetZoomUIOutShortcut,
-  prefGetZoomUIResetShortcut, prefSetCapitalizeShortcut,
-  prefSetClearHistoryShortcut,
-  prefSetCloseAppShortcut,
-  prefSetCloseAppShortcut2,
-  prefSetCloseAppShortcut3, prefSetCopyObjectToClipboardShortcut,
-  prefSetCopyToClipboardShortcut,
-  prefSetDeleteHistoryItemShortcut,
-  prefSetEditHistoryItemShortcut, prefSetMakeLowerCaseShortcut, prefSetMakeUpperCaseShortcut,
-  prefSetOpenAppShortcut,
-  prefSetOpenInBrowserShortcut, prefSetOpenInDefaultAppShortcut,
-  prefSetOpenSettingsShortcut,
-  prefSetPasteNextItemShortcut,
-  prefSetPasteSelectedItemToActiveAppShortcut, prefSetPasteSelectedObjectToActiveAppShortcut,
-  prefSetPauseResumeShortcut, prefSetQuickLookShortcut, prefSetRemoveEmptyLinesShortcut,
-  prefSetRenameItemShortcut,
-  prefSetSaveImageAsFileShortcut,
-  prefSetSelectNextItemShortcut,
-  prefSetSelectPreviousItemShortcut, prefSetSentenceCaseShortcut, prefSetShowInFinderShortcut,
-  prefSetShowMoreActionsShortcut, prefSetStripAllWhitespacesShortcut,
-  prefSetToggleFavoriteShortcut, prefSetToggleFilterShortcut,
-  prefSetTogglePreviewShortcut, prefSetTrimSurroundingWhitespacesShortcut,
-  prefSetZoomUIInShortcut,
-  prefSetZoomUIOutShortcut,
-  prefSetZoomUIResetShortcut
+  prefGetZoomUIResetShortcut,
+  prefSetUsePasteRecentShortcuts,
+  prefShouldUsePasteRecentShortcuts
 } from "@/pref";
+import {saveShortcutBinding} from "@/shortcuts";
 
 declare const closeSettingsWindow: () => void;
 declare const enableOpenAppShortcut: () => void;
 declare const enablePauseResumeShortcut: () => void;
+declare const enableCycleProfileShortcut: () => void;
 declare const updateOpenSettingsShortcut: () => void;
 declare const enablePasteNextItemShortcut: () => void;
+declare const enablePasteRecentShortcuts: () => void;
+declare const disablePasteRecentShortcuts: () => void;
 
 export default function Shortcuts() {
   const { t } = useTranslation();
@@ -70,6 +55,7 @@ export default function Shortcuts() {
   const [pasteSelectedItemToActiveAppShortcut, setPasteSelectedItemToActiveAppShortcut] = useState(prefGetPasteSelectedItemToActiveAppShortcut());
   const [pasteSelectedObjectToActiveAppShortcut, setPasteSelectedObjectToActiveAppShortcut] = useState(prefGetPasteSelectedObjectToActiveAppShortcut());
   const [pasteNextItemShortcut, setPasteNextItemShortcut] = useState(prefGetPasteNextItemShortcut());
+  const [usePasteRecentShortcuts, setUsePasteRecentShortcuts] = useState(prefShouldUsePasteRecentShortcuts());
   const [editHistoryItemShortcut, setEditHistoryItemShortcut] = useState(prefGetEditHistoryItemShortcut());
   const [openInBrowserShortcut, setOpenInBrowserShortcut] = useState(prefGetOpenInBrowserShortcut());
   const [showInFinderShortcut, setShowInFinderShortcut] = useState(prefGetShowInFinderShortcut());
@@ -87,6 +73,7 @@ export default function Shortcuts() {
   const [toggleFavoriteShortcut, setToggleFavoriteShortcut] = useState(prefGetToggleFavoriteShortcut());
   const [saveImageAsFileShortcut, setSaveImageAsFileShortcut] = useState(prefGetSaveImageAsFileShortcut());
   const [pauseResumeShortcut, setPauseResumeShortcut] = useState(prefGetPauseResumeShortcut());
+  const [cycleProfileShortcut, setCycleProfileShortcut] = useState(prefGetCycleProfileShortcut());
   const [renameItemShortcut, setRenameItemShortcut] = useState(prefGetRenameItemShortcut());
   const [makeLowerCaseShortcut, setMakeLowerCaseShortcut] = useState(prefGetMakeLowerCaseShortcut());
   const [makeUpperCaseShortcut, setMakeUpperCaseShortcut] = useState(prefGetMakeUpperCaseShortcut());
@@ -110,188 +97,208 @@ export default function Shortcuts() {
     return () => document.removeEventListener("keydown", down)
   }, [])
 
+  function saveShortcut(id: string, shortcut: string) {
+    saveShortcutBinding(id, shortcut).catch(error => console.error('❌ Failed to save shortcut:', error))
+  }
+
   function handleOpenAppShortcutChange(shortcut: string) {
     setOpenAppShortcut(shortcut)
-    prefSetOpenAppShortcut(shortcut)
+    saveShortcut("openApp", shortcut)
     enableOpenAppShortcut()
   }
 
   function handleCloseAppShortcutChange(shortcut: string) {
     setCloseAppShortcut(shortcut)
-    prefSetCloseAppShortcut(shortcut)
+    saveShortcut("closeApp", shortcut)
   }
 
   function handleCloseAppShortcutChange2(shortcut: string) {
     setCloseAppShortcut2(shortcut)
-    prefSetCloseAppShortcut2(shortcut)
+    saveShortcut("closeApp2", shortcut)
   }
 
   function handleCloseAppShortcutChange3(shortcut: string) {
     setCloseAppShortcut3(shortcut)
-    prefSetCloseAppShortcut3(shortcut)
+    saveShortcut("closeApp3", shortcut)
   }
 
   function handleSelectNextItemShortcutChange(shortcut: string) {
     setSelectNextItemShortcut(shortcut)
-    prefSetSelectNextItemShortcut(shortcut)
+    saveShortcut("selectNextItem", shortcut)
   }
 
   function handleSelectPreviousItemShortcutChange(shortcut: string) {
     setSelectPreviousItemShortcut(shortcut)
-    prefSetSelectPreviousItemShortcut(shortcut)
+    saveShortcut("selectPreviousItem", shortcut)
   }
 
   function handlePasteSelectedItemToActiveAppShortcutChange(shortcut: string) {
     setPasteSelectedItemToActiveAppShortcut(shortcut)
-    prefSetPasteSelectedItemToActiveAppShortcut(shortcut)
+    saveShortcut("pasteSelectedItemToActiveApp", shortcut)
   }
 
   function handlePasteSelectedObjectToActiveAppShortcutChange(shortcut: string) {
     setPasteSelectedObjectToActiveAppShortcut(shortcut)
-    prefSetPasteSelectedObjectToActiveAppShortcut(shortcut)
+    saveShortcut("pasteSelectedObjectToActiveApp", shortcut)
   }
 
   function handlePasteNextItemToActiveAppShortcutChange(shortcut: string) {
     setPasteNextItemShortcut(shortcut)
-    prefSetPasteNextItemShortcut(shortcut)
+    saveShortcut("pasteNextItem", shortcut)
     enablePasteNextItemShortcut()
   }
 
+  function handleUsePasteRecentShortcutsChange(enabled: boolean) {
+    setUsePasteRecentShortcuts(enabled)
+    prefSetUsePasteRecentShortcuts(enabled)
+    if (enabled) {
+      enablePasteRecentShortcuts()
+    } else {
+      disablePasteRecentShortcuts()
+    }
+  }
+
   function handleEditHistoryItemShortcutChange(shortcut: string) {
     setEditHistoryItemShortcut(shortcut)
-    prefSetEditHistoryItemShortcut(shortcut)
+    saveShortcut("editHistoryItem", shortcut)
   }
 
   function handleRenameHistoryItemShortcutChange(shortcut: string) {
     setRenameItemShortcut(shortcut)
-    prefSetRenameItemShortcut(shortcut)
+    saveShortcut("renameItem", shortcut)
   }
 
   function handleToggleFilterShortcutChange(shortcut: string) {
     setToggleFilterShortcut(shortcut)
-    prefSetToggleFilterShortcut(shortcut)
+    saveShortcut("toggleFilter", shortcut)
   }
 
   function handleOpenInBrowserShortcutChange(shortcut: string) {
     setOpenInBrowserShortcut(shortcut)
-    prefSetOpenInBrowserShortcut(shortcut)
+    saveShortcut("openInBrowser", shortcut)
   }
 
   function handleShowInFinderShortcutChange(shortcut: string) {
     setShowInFinderShortcut(shortcut)
-    prefSetShowInFinderShortcut(shortcut)
+    saveShortcut("showInFinder", shortcut)
   }
 
   function handleOpenInDefaultAppShortcutChange(shortcut: string) {
     setOpenInDefaultAppShortcut(shortcut)
-    prefSetOpenInDefaultAppShortcut(shortcut)
+    saveShortcut("openInDefaultApp", shortcut)
   }
 
   function handleCopyToClipboardShortcutChange(shortcut: string) {
     setCopyToClipboardShortcut(shortcut)
-    prefSetCopyToClipboardShortcut(shortcut)
+    saveShortcut("copyToClipboard", shortcut)
   }
 
   function handleCopyObjectToClipboardShortcutChange(shortcut: string) {
     setCopyObjectToClipboardShortcut(shortcut)
-    prefSetCopyObjectToClipboardShortcut(shortcut)
+    saveShortcut("copyObjectToClipboard", shortcut)
   }
 
   function handleDeleteHistoryItemShortcutChange(shortcut: string) {
     setDeleteHistoryItemShortcut(shortcut)
-    prefSetDeleteHistoryItemShortcut(shortcut)
+    saveShortcut("deleteHistoryItem", shortcut)
   }
 
   function handleClearHistoryShortcutChange(shortcut: string) {
     setClearHistoryShortcut(shortcut)
-    prefSetClearHistoryShortcut(shortcut)
+    saveShortcut("clearHistory", shortcut)
   }
 
   function handleTogglePreviewShortcutChange(shortcut: string) {
     setTogglePreviewShortcut(shortcut)
-    prefSetTogglePreviewShortcut(shortcut)
+    saveShortcut("togglePreview", shortcut)
   }
 
   function handleShowMoreActionsShortcutChange(shortcut: string) {
     setShowMoreActionsShortcut(shortcut)
-    prefSetShowMoreActionsShortcut(shortcut)
+    saveShortcut("showMoreActions", shortcut)
   }
 
   function handleZoomUIInShortcutChange(shortcut: string) {
     setZoomUIInShortcut(shortcut)
-    prefSetZoomUIInShortcut(shortcut)
+    saveShortcut("zoomUIIn", shortcut)
   }
 
   function handleZoomUIOutShortcutChange(shortcut: string) {
     setZoomUIOutShortcut(shortcut)
-    prefSetZoomUIOutShortcut(shortcut)
+    saveShortcut("zoomUIOut", shortcut)
   }
 
   function handleZoomUIResetShortcutChange(shortcut: string) {
     setZoomUIResetShortcut(shortcut)
-    prefSetZoomUIResetShortcut(shortcut)
+    saveShortcut("zoomUIReset", shortcut)
   }
 
   function handleOpenSettingsShortcutChange(shortcut: string) {
     setOpenSettingsShortcut(shortcut)
-    prefSetOpenSettingsShortcut(shortcut)
+    saveShortcut("openSettings", shortcut)
     updateOpenSettingsShortcut()
   }
 
   function handleToggleFavoriteShortcutChange(shortcut: string) {
     setToggleFavoriteShortcut(shortcut)
-    prefSetToggleFavoriteShortcut(shortcut)
+    saveShortcut("toggleFavorite", shortcut)
   }
 
   function handleQuickLookShortcutChange(shortcut: string) {
     setQuickLookShortcut(shortcut)
-    prefSetQuickLookShortcut(shortcut)
+    saveShortcut("quickLook", shortcut)
   }
 
   function handleSaveImageAsFileShortcutChange(shortcut: string) {
     setSaveImageAsFileShortcut(shortcut)
-    prefSetSaveImageAsFileShortcut(shortcut)
+    saveShortcut("saveImageAsFile", shortcut)
   }
 
   function handlePauseResumeShortcutChange(shortcut: string) {
     setPauseResumeShortcut(shortcut)
-    prefSetPauseResumeShortcut(shortcut)
+    saveShortcut("pauseResume", shortcut)
     enablePauseResumeShortcut()
   }
 
+  function handleCycleProfileShortcutChange(shortcut: string) {
+    setCycleProfileShortcut(shortcut)
+    saveShortcut("cycleProfile", shortcut)
+    enableCycleProfileShortcut()
+  }
+
   function handleMakeLowerCaseShortcutChange(shortcut: string) {
     setMakeLowerCaseShortcut(shortcut)
-    prefSetMakeLowerCaseShortcut(shortcut)
+    saveShortcut("makeLowerCase", shortcut)
   }
 
   function handleMakeUpperCaseShortcutChange(shortcut: string) {
     setMakeUpperCaseShortcut(shortcut)
-    prefSetMakeUpperCaseShortcut(shortcut)
+    saveShortcut("makeUpperCase", shortcut)
   }
 
   function handleCapitalizeShortcutChange(shortcut: string) {
     setCapitalizeShortcut(shortcut)
-    prefSetCapitalizeShortcut(shortcut)
+    saveShortcut("capitalize", shortcut)
   }
 
   function handleSentenceCaseShortcutChange(shortcut: string) {
     setSentenceCaseShortcut(shortcut)
-    prefSetSentenceCaseShortcut(shortcut)
+    saveShortcut("sentenceCase", shortcut)
   }
 
   function handleRemoveEmptyLinesShortcutChange(shortcut: string) {
     setRemoveEmptyLinesShortcut(shortcut)
-    prefSetRemoveEmptyLinesShortcut(shortcut)
+    saveShortcut("removeEmptyLines", shortcut)
   }
 
   function handleStripAllWhitespacesShortcutChange(shortcut: string) {
     setStripAllWhitespacesShortcut(shortcut)
-    prefSetStripAllWhitespacesShortcut(shortcut)
+    saveShortcut("stripAllWhitespaces", shortcut)
   }
 
   function handleTrimSurroundingWhitespacesShortcutChange(shortcut: string) {
     setTrimSurroundingWhitespacesShortcut(shortcut)
-    prefSetTrimSurroundingWhitespacesShortcut(shortcut)
+    saveShortcut("trimSurroundingWhitespaces", shortcut)
   }
 
   return (
@@ -304,222 +311,275 @@ export default function Shortcuts() {
           <div className="flex flex-col px-8 pb-4 gap-4 flex-grow overflow-y-auto scrollbar-thin scrollbar-thumb-scrollbar scrollbar-track-transparent">
             <div className="flex items-center justify-between space-x-20 pt-6">
               <span className="">{t('settings.shortcuts.openApp')}</span>
-              <ShortcutInput shortcut={openAppShortcut}
+              <ShortcutInput id="openApp"
+                             shortcut={openAppShortcut}
                              defaultShortcut="ShiftLeft + MetaLeft + KeyV"
                              onSave={handleOpenAppShortcutChange}/>
             </div>
             <hr/>
             <div className="flex items-center justify-between space-x-20">
               <span>{t('settings.shortcuts.closeApp')}</span>
-              <ShortcutInput shortcut={closeAppShortcut3}
+              <ShortcutInput id="closeApp3"
+                             shortcut={closeAppShortcut3}
                              defaultShortcut="ShiftLeft + MetaLeft + KeyV"
                              onSave={handleCloseAppShortcutChange3}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span></span>
-              <ShortcutInput shortcut={closeAppShortcut2} defaultShortcut="MetaLeft + KeyW"
+              <ShortcutInput id="closeApp2"
+                             shortcut={closeAppShortcut2} defaultShortcut="MetaLeft + KeyW"
                              onSave={handleCloseAppShortcutChange2}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span></span>
-              <ShortcutInput shortcut={closeAppShortcut} defaultShortcut="Escape"
+              <ShortcutInput id="closeApp"
+                             shortcut={closeAppShortcut} defaultShortcut="Escape"
                              onSave={handleCloseAppShortcutChange}/>
             </div>
             <hr/>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.selectNextItem')}</span>
-              <ShortcutInput shortcut={selectNextItemShortcut}
+              <ShortcutInput id="selectNextItem"
+                             shortcut={selectNextItemShortcut}
                              defaultShortcut="ArrowDown"
                              onSave={handleSelectNextItemShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.selectPreviousItem')}</span>
-              <ShortcutInput shortcut={selectPreviousItemShortcut}
+              <ShortcutInput id="selectPreviousItem"
+                             shortcut={selectPreviousItemShortcut}
                              defaultShortcut="ArrowUp"
                              onSave={handleSelectPreviousItemShortcutChange}/>
             </div>
             <hr/>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.pasteNextItemToActiveApp')}</span>
-              <ShortcutInput shortcut={pasteNextItemShortcut}
+              <ShortcutInput id="pasteNextItem"
+                             shortcut={pasteNextItemShortcut}
                              defaultShortcut="ControlLeft + KeyV"
                              onSave={handlePasteNextItemToActiveAppShortcutChange}/>
             </div>
+            <div className="flex items-center justify-between space-x-20">
+              <Label htmlFor="usePasteRecentShortcuts" className="flex flex-col text-base">
+                <span className="">{t('settings.shortcuts.pasteRecentItems.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm mt-1">
+                  {t('settings.shortcuts.pasteRecentItems.description')}
+                </span>
+              </Label>
+              <Switch id="usePasteRecentShortcuts" checked={usePasteRecentShortcuts}
+                      onCheckedChange={handleUsePasteRecentShortcutsChange}/>
+            </div>
             <hr/>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.pasteSelectedItemToActiveApp')}</span>
-              <ShortcutInput shortcut={pasteSelectedItemToActiveAppShortcut}
+              <ShortcutInput id="pasteSelectedItemToActiveApp"
+                             shortcut={pasteSelectedItemToActiveAppShortcut}
                              defaultShortcut="Enter"
                              onSave={handlePasteSelectedItemToActiveAppShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.alternatePasteSelectedItemToActiveApp')}</span>
-              <ShortcutInput shortcut={pasteSelectedObjectToActiveAppShortcut}
+              <ShortcutInput id="pasteSelectedObjectToActiveApp"
+                             shortcut={pasteSelectedObjectToActiveAppShortcut}
                              defaultShortcut="ShiftLeft + Enter"
                              onSave={handlePasteSelectedObjectToActiveAppShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.copyToClipboard')}</span>
-              <ShortcutInput shortcut={copyToClipboardShortcut}
+              <ShortcutInput id="copyToClipboard"
+                             shortcut={copyToClipboardShortcut}
                              defaultShortcut="MetaLeft + KeyC"
                              onSave={handleCopyToClipboardShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.alternateCopyToClipboard')}</span>
-              <ShortcutInput shortcut={copyObjectToClipboardShortcut}
+              <ShortcutInput id="copyObjectToClipboard"
+                             shortcut={copyObjectToClipboardShortcut}
                              defaultShortcut="ShiftLeft + MetaLeft + KeyC"
                              onSave={handleCopyObjectToClipboardShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.openInBrowser')}</span>
-              <ShortcutInput shortcut={openInBrowserShortcut}
+              <ShortcutInput id="openInBrowser"
+                             shortcut={openInBrowserShortcut}
                              defaultShortcut="MetaLeft + Enter"
                              onSave={handleOpenInBrowserShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.showInFinder')}</span>
-              <ShortcutInput shortcut={showInFinderShortcut}
+              <ShortcutInput id="showInFinder"
+                             shortcut={showInFinderShortcut}
                              defaultShortcut="MetaLeft + KeyO"
                              onSave={handleShowInFinderShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.openInDefaultApp')}</span>
-              <ShortcutInput shortcut={openInDefaultAppShortcut}
+              <ShortcutInput id="openInDefaultApp"
+                             shortcut={openInDefaultAppShortcut}
                              defaultShortcut="AltLeft + MetaLeft + KeyO"
                              onSave={handleOpenInDefaultAppShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.addToFavorites')}</span>
-              <ShortcutInput shortcut={toggleFavoriteShortcut}
+              <ShortcutInput id="toggleFavorite"
+                             shortcut={toggleFavoriteShortcut}
                              defaultShortcut="MetaLeft + KeyS"
                              onSave={handleToggleFavoriteShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.quickLook')}</span>
-              <ShortcutInput shortcut={quickLookShortcut}
+              <ShortcutInput id="quickLook"
+                             shortcut={quickLookShortcut}
                              defaultShortcut="MetaLeft + KeyL"
                              onSave={handleQuickLookShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.saveAsFile')}</span>
-              <ShortcutInput shortcut={saveImageAsFileShortcut}
+              <ShortcutInput id="saveImageAsFile"
+                             shortcut={saveImageAsFileShortcut}
                              defaultShortcut="ShiftLeft + MetaLeft + KeyS"
                              onSave={handleSaveImageAsFileShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.editHistoryItem')}</span>
-              <ShortcutInput shortcut={editHistoryItemShortcut}
+              <ShortcutInput id="editHistoryItem"
+                             shortcut={editHistoryItemShortcut}
                              defaultShortcut="MetaLeft + KeyE"
                              onSave={handleEditHistoryItemShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.renameHistoryItem')}</span>
-              <ShortcutInput shortcut={renameItemShortcut}
+              <ShortcutInput id="renameItem"
+                             shortcut={renameItemShortcut}
                              defaultShortcut="MetaLeft + KeyR"
                              onSave={handleRenameHistoryItemShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.deleteHistoryItem')}</span>
-              <ShortcutInput shortcut={deleteHistoryItemShortcut}
+              <ShortcutInput id="deleteHistoryItem"
+                             shortcut={deleteHistoryItemShortcut}
                              defaultShortcut="MetaLeft + Backspace"
                              onSave={handleDeleteHistoryItemShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.deleteAllHistoryItems')}</span>
-              <ShortcutInput shortcut={clearHistoryShortcut}
+              <ShortcutInput id="clearHistory"
+                             shortcut={clearHistoryShortcut}
                              defaultShortcut="ShiftLeft + MetaLeft + Backspace"
                              onSave={handleClearHistoryShortcutChange}/>
             </div>
 
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.showHidePreview')}</span>
-              <ShortcutInput shortcut={togglePreviewShortcut}
+              <ShortcutInput id="togglePreview"
+                             shortcut={togglePreviewShortcut}
                              defaultShortcut="MetaLeft + KeyP"
                              onSave={handleTogglePreviewShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.showHideFilter')}</span>
-              <ShortcutInput shortcut={toggleFilterShortcut}
+              <ShortcutInput id="toggleFilter"
+                             shortcut={toggleFilterShortcut}
                              defaultShortcut="MetaLeft + KeyF"
                              onSave={handleToggleFilterShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.showCommandMenu')}</span>
-              <ShortcutInput shortcut={showMoreActionsShortcut}
+              <ShortcutInput id="showMoreActions"
+                             shortcut={showMoreActionsShortcut}
                              defaultShortcut="MetaLeft + KeyK"
                              onSave={handleShowMoreActionsShortcutChange}/>
             </div>
             <hr/>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.zoomIn')}</span>
-              <ShortcutInput shortcut={zoomUIInShortcut} defaultShortcut="MetaLeft + Equal"
+              <ShortcutInput id="zoomUIIn"
+                             shortcut={zoomUIInShortcut} defaultShortcut="MetaLeft + Equal"
                              onSave={handleZoomUIInShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.zoomOut')}</span>
-              <ShortcutInput shortcut={zoomUIOutShortcut}
+              <ShortcutInput id="zoomUIOut"
+                             shortcut={zoomUIOutShortcut}
                              defaultShortcut="MetaLeft + Minus"
                              onSave={handleZoomUIOutShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.zoomReset')}</span>
-              <ShortcutInput shortcut={zoomUIResetShortcut}
+              <ShortcutInput id="zoomUIReset"
+                             shortcut={zoomUIResetShortcut}
                              defaultShortcut="MetaLeft + Digit0"
                              onSave={handleZoomUIResetShortcutChange}/>
             </div>
             <hr/>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.pauseResumeClipBook')}</span>
-              <ShortcutInput shortcut={pauseResumeShortcut}
+              <ShortcutInput id="pauseResume"
+                             shortcut={pauseResumeShortcut}
                              defaultShortcut=""
                              onSave={handlePauseResumeShortcutChange}/>
             </div>
+            <div className="flex items-center justify-between space-x-20">
+              <span className="">{t('settings.shortcuts.cycleProfile')}</span>
+              <ShortcutInput id="cycleProfile"
+                             shortcut={cycleProfileShortcut}
+                             defaultShortcut=""
+                             onSave={handleCycleProfileShortcutChange}/>
+            </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.openSettings')}</span>
-              <ShortcutInput shortcut={openSettingsShortcut}
+              <ShortcutInput id="openSettings"
+                             shortcut={openSettingsShortcut}
                              defaultShortcut="MetaLeft + Comma"
                              onSave={handleOpenSettingsShortcutChange}/>
             </div>
             <hr/>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.makeLowerCase')}</span>
-              <ShortcutInput shortcut={makeLowerCaseShortcut}
+              <ShortcutInput id="makeLowerCase"
+                             shortcut={makeLowerCaseShortcut}
                              defaultShortcut="ShiftLeft + MetaLeft + KeyL"
                              onSave={handleMakeLowerCaseShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.makeUpperCase')}</span>
-              <ShortcutInput shortcut={makeUpperCaseShortcut}
+              <ShortcutInput id="makeUpperCase"
+                             shortcut={makeUpperCaseShortcut}
                              defaultShortcut="ShiftLeft + MetaLeft + KeyU"
                              onSave={handleMakeUpperCaseShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.capitalizeWords')}</span>
-              <ShortcutInput shortcut={capitalizeShortcut}
+              <ShortcutInput id="capitalize"
+                             shortcut={capitalizeShortcut}
                              defaultShortcut="ShiftLeft + MetaLeft + KeyA"
                              onSave={handleCapitalizeShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.sentenceCase')}</span>
-              <ShortcutInput shortcut={sentenceCaseShortcut}
+              <ShortcutInput id="sentenceCase"
+                             shortcut={sentenceCaseShortcut}
                              defaultShortcut="ShiftLeft + MetaLeft + KeyS"
                              onSave={handleSentenceCaseShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.removeEmptyLines')}</span>
-              <ShortcutInput shortcut={removeEmptyLinesShortcut}
+              <ShortcutInput id="removeEmptyLines"
+                             shortcut={removeEmptyLinesShortcut}
                              defaultShortcut="ShiftLeft + MetaLeft + KeyR"
                              onSave={handleRemoveEmptyLinesShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.stripAllWhitespaces')}</span>
-              <ShortcutInput shortcut={stripAllWhitespacesShortcut}
+              <ShortcutInput id="stripAllWhitespaces"
+                             shortcut={stripAllWhitespacesShortcut}
                              defaultShortcut="ShiftLeft + MetaLeft + KeyT"
                              onSave={handleStripAllWhitespacesShortcutChange}/>
             </div>
             <div className="flex items-center justify-between space-x-20">
               <span className="">{t('settings.shortcuts.trimSurroundingWhitespaces')}</span>
-              <ShortcutInput shortcut={trimSurroundingWhitespacesShortcut}
+              <ShortcutInput id="trimSurroundingWhitespaces"
+                             shortcut={trimSurroundingWhitespacesShortcut}
                              defaultShortcut="ShiftLeft + MetaLeft + KeyM"
                              onSave={handleTrimSurroundingWhitespacesShortcutChange}/>
             </div>
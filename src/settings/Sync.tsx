@@ -0,0 +1,257 @@
+import * as React from "react";
+import {Label} from "@/components/ui/label";
+import {Input} from "@/components/ui/input";
+import {Button} from "@/components/ui/button";
+import {useEffect, useState} from "react";
+import {useTranslation} from 'react-i18next';
+import {Switch} from "@/components/ui/switch";
+import {Select, SelectContent, SelectItem, SelectTrigger, SelectValue} from "@/components/ui/select";
+import {configureSync, getSyncConfig, getSyncStatus} from "@/sync";
+import {choosePath, PathKind} from "@/dialogs";
+import {generateSharingKey} from "@/lanshare";
+import {
+  prefGetLanSharingKey,
+  prefIsLanSharingEnabledManaged,
+  prefSetLanSharingEnabled,
+  prefSetLanSharingKey,
+  prefShouldLanSharingEnabled,
+} from "@/pref";
+
+declare const closeSettingsWindow: () => void;
+declare const requestSync: () => void;
+
+// Where the synced history is stored.
+type SyncTarget = "server" | "folder"
+
+export default function Sync() {
+  const {t} = useTranslation();
+
+  const [config] = useState(() => getSyncConfig())
+  const [target, setTarget] = useState<SyncTarget>(config?.folder !== undefined ? "folder" : "server")
+  const [folder, setFolder] = useState(config?.folder ?? "")
+  const [url, setUrl] = useState(config?.url ?? "")
+  const [username, setUsername] = useState(config?.username ?? "")
+  const [password, setPassword] = useState(config?.password ?? "")
+  const [passphrase, setPassphrase] = useState(config?.passphrase ?? "")
+  const [lanSharingEnabled, setLanSharingEnabled] = useState(prefShouldLanSharingEnabled())
+  const [lanSharingKey, setLanSharingKey] = useState(prefGetLanSharingKey())
+  const [status, setStatus] = useState(getSyncStatus())
+  const [errorMessage, setErrorMessage] = useState("")
+
+  // The history is synced by the app window, so poll the status it saves.
+  useEffect(() => {
+    const timer = setInterval(() => setStatus(getSyncStatus()), 2000)
+    return () => clearInterval(timer)
+  }, [])
+
+  useEffect(() => {
+    const down = (e: KeyboardEvent) => {
+      if (e.key === "Escape") {
+        closeSettingsWindow()
+        e.preventDefault()
+      }
+    }
+
+    document.addEventListener("keydown", down)
+    return () => document.removeEventListener("keydown", down)
+  }, [])
+
+  async function handleChooseFolder() {
+    let dir = await choosePath(PathKind.Folder)
+    if (dir) {
+      setFolder(dir)
+    }
+  }
+
+  function handleSave() {
+    try {
+      if (target === "folder") {
+        configureSync({folder, passphrase})
+      } else {
+        configureSync({
+          url: url.trim(),
+          username: username.trim() || undefined,
+          password: password || undefined,
+          passphrase,
+        })
+      }
+      setErrorMessage("")
+      setStatus(getSyncStatus())
+      requestSync()
+    } catch (error) {
+      setErrorMessage(error instanceof Error ? error.message : String(error))
+    }
+  }
+
+  function handleTurnOff() {
+    configureSync(undefined)
+    setFolder("")
+    setUrl("")
+    setUsername("")
+    setPassword("")
+    setPassphrase("")
+    setErrorMessage("")
+    setStatus(getSyncStatus())
+  }
+
+  function handleSyncNow() {
+    requestSync()
+  }
+
+  function handleLanSharingEnabledChange(checked: boolean) {
+    if (checked && !lanSharingKey) {
+      let key = generateSharingKey()
+      setLanSharingKey(key)
+      prefSetLanSharingKey(key)
+    }
+    setLanSharingEnabled(checked)
+    prefSetLanSharingEnabled(checked)
+  }
+
+  // The key is saved when the field loses focus, so that the sharing isn't
+  // restarted on every keystroke.
+  function handleLanSharingKeyBlur() {
+    let key = lanSharingKey.trim()
+    if (key && key !== prefGetLanSharingKey()) {
+      prefSetLanSharingKey(key)
+    }
+  }
+
+  function renderStatus() {
+    if (!status.configured) {
+      return t('settings.sync.status.notConfigured')
+    }
+    if (status.lastError) {
+      return t('settings.sync.status.failed', {error: status.lastError})
+    }
+    if (status.lastSyncTime) {
+      return t('settings.sync.status.synced', {time: status.lastSyncTime.toLocaleString()})
+    }
+    return t('settings.sync.status.pending')
+  }
+
+  return (
+      <div className="flex h-screen select-none">
+        <div className="flex flex-col flex-grow">
+          <div className="flex pt-8 px-8 border-b border-b-border draggable sticky">
+            <span className="text-2xl pb-3 font-semibold">{t('settings.sync.title')}</span>
+          </div>
+
+          <div className="flex flex-col px-8 pb-8 gap-4 flex-grow overflow-y-auto scrollbar-thin scrollbar-thumb-scrollbar scrollbar-track-transparent">
+            <p className="pt-6 text-neutral-500 text-sm text-pretty">{t('settings.sync.description')}</p>
+            <div className="flex items-center justify-between space-x-10 py-1">
+              <Label htmlFor="syncTarget" className="text-base">{t('settings.sync.target.title')}</Label>
+              <Select value={target} onValueChange={value => setTarget(value as SyncTarget)}>
+                <SelectTrigger id="syncTarget" className="w-[180px]">
+                  <SelectValue/>
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="server">{t('settings.sync.target.server')}</SelectItem>
+                  <SelectItem value="folder">{t('settings.sync.target.folder')}</SelectItem>
+                </SelectContent>
+              </Select>
+            </div>
+            {
+                target === "folder" &&
+                <div className="flex items-center justify-between space-x-10 py-1">
+                  <Label className="flex flex-col text-base min-w-0">
+                    <span className="">{t('settings.sync.folder.title')}</span>
+                    <span className="text-neutral-500 font-normal text-sm break-all">
+                      {folder || t('settings.sync.folder.description')}
+                    </span>
+                  </Label>
+                  <Button variant="outline" size="sm" onClick={handleChooseFolder}>
+                    {t('settings.sync.folder.choose')}
+                  </Button>
+                </div>
+            }
+            {
+                target === "server" &&
+                <>
+                  <div className="flex flex-col gap-2">
+                    <Label htmlFor="syncUrl" className="text-base">{t('settings.sync.url')}</Label>
+                    <Input id="syncUrl" value={url} onChange={e => setUrl(e.target.value)}
+                           placeholder="https://example.com/webdav/clipbook.json"
+                           className="placeholder:text-settings-inputPlaceholder"/>
+                  </div>
+                  <div className="grid grid-cols-2 gap-4">
+                    <div className="flex flex-col gap-2">
+                      <Label htmlFor="syncUsername" className="text-base">{t('settings.sync.username')}</Label>
+                      <Input id="syncUsername" value={username} onChange={e => setUsername(e.target.value)}/>
+                    </div>
+                    <div className="flex flex-col gap-2">
+                      <Label htmlFor="syncPassword" className="text-base">{t('settings.sync.password')}</Label>
+                      <Input id="syncPassword" type="password" value={password}
+                             onChange={e => setPassword(e.target.value)}/>
+                    </div>
+                  </div>
+                </>
+            }
+            <div className="flex flex-col gap-2">
+              <Label htmlFor="syncPassphrase" className="flex flex-col text-base">
+                <span className="">{t('settings.sync.passphrase.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.sync.passphrase.description')}
+                </span>
+              </Label>
+              <Input id="syncPassphrase" type="password" value={passphrase}
+                     onChange={e => setPassphrase(e.target.value)}/>
+            </div>
+            {
+                errorMessage &&
+                <p className="text-actions-danger text-sm">{errorMessage}</p>
+            }
+            <div className="flex items-center justify-between space-x-10 py-1">
+              <span className="text-neutral-500 text-sm select-text">{renderStatus()}</span>
+              <div className="flex space-x-2">
+                {
+                    status.configured &&
+                    <Button variant="outline" size="sm" onClick={handleTurnOff}>
+                      {t('settings.sync.turnOff')}
+                    </Button>
+                }
+                {
+                    status.configured &&
+                    <Button variant="outline" size="sm" onClick={handleSyncNow}>
+                      {t('settings.sync.syncNow')}
+                    </Button>
+                }
+                <Button variant="outline" size="sm" onClick={handleSave}
+                        disabled={!passphrase || (target === "folder" && !folder)}>
+                  {t('settings.sync.save')}
+                </Button>
+              </div>
+            </div>
+
+            <hr/>
+
+            <div className="flex items-center justify-between space-x-20 py-1">
+              <Label htmlFor="lanSharing" className="flex flex-col text-base">
+                <span className="">{t('settings.sync.lanSharing.title')}</span>
+                <span className="text-neutral-500 font-normal text-sm">
+                  {t('settings.sync.lanSharing.description')}
+                </span>
+              </Label>
+              <Switch id="lanSharing" checked={lanSharingEnabled}
+                      onCheckedChange={handleLanSharingEnabledChange}
+                      disabled={prefIsLanSharingEnabledManaged()}/>
+            </div>
+            {
+                lanSharingEnabled &&
+                <div className="flex flex-col gap-2">
+                  <Label htmlFor="lanSharingKey" className="flex flex-col text-base">
+                    <span className="">{t('settings.sync.lanSharing.key.title')}</span>
+                    <span className="text-neutral-500 font-normal text-sm">
+                      {t('settings.sync.lanSharing.key.description')}
+                    </span>
+                  </Label>
+                  <Input id="lanSharingKey" value={lanSharingKey} className="font-mono"
+                         onChange={e => setLanSharingKey(e.target.value)}
+                         onBlur={handleLanSharingKeyBlur}/>
+                </div>
+            }
+          </div>
+        </div>
+      </div>
+  )
+}
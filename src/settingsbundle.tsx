@@ -0,0 +1,222 @@
+import {AppRuleAction, ClipType} from "@/db";
+import {addTag, allTags, findTagById, findTagByName, loadTags, Tag, TagColor} from "@/tags";
+import {allAppRules, createAppRule, loadAppRules, setAppRuleEnabled} from "@/apprules";
+import {allTagRules, createTagRule, loadTagRules, setTagRuleEnabled} from "@/rules";
+import {createSmartCollection, listSmartCollections} from "@/collections";
+import {findShortcutBinding, getShortcuts, saveShortcutBinding} from "@/shortcuts";
+import {emitter} from "@/actions";
+import {requireNotGuest} from "@/guest";
+
+declare const exportPreferences: () => string;
+declare const applyPreferences: (json: string) => number;
+declare const readTextFile: (filePath: string) => string;
+declare const writeTextFile: (filePath: string, content: string) => boolean;
+
+const kSettingsBundleVersion = 1
+
+// The rules and the collections refer to the tags by name, because the tag
+// ids are different on every machine.
+export interface SettingsBundle {
+  version: number;
+  createdAt: string;
+  preferences: Record<string, string | number | boolean>;
+  shortcuts: { id: string, shortcut: string }[];
+  tags: { name: string, color: string }[];
+  appRules: { bundleId: string, action: AppRuleAction, tag?: string, enabled: boolean }[];
+  tagRules: { name: string, pattern: string, flags: string, tag: string, enabled: boolean }[];
+  smartCollections: {
+    name: string,
+    query: string,
+    types: ClipType[],
+    tags: string[],
+    fromDate?: string,
+    toDate?: string,
+    lastDays?: number,
+  }[];
+}
+
+export interface SettingsImportReport {
+  preferences: number;
+  shortcuts: number;
+  tags: number;
+  appRules: number;
+  tagRules: number;
+  smartCollections: number;
+}
+
+function tagName(id: number | undefined): string | undefined {
+  return id === undefined ? undefined : findTagById(id)?.name
+}
+
+export async function createSettingsBundle(): Promise<SettingsBundle> {
+  loadTags()
+  let collections = await listSmartCollections()
+  return {
+    version: kSettingsBundleVersion,
+    createdAt: new Date().toISOString(),
+    preferences: typeof exportPreferences !== 'undefined' ? JSON.parse(exportPreferences()) : {},
+    shortcuts: getShortcuts()
+        .filter(shortcut => shortcut.editable)
+        .map(shortcut => ({id: shortcut.id, shortcut: shortcut.shortcut})),
+    tags: allTags().map(tag => ({name: tag.name, color: tag.color})),
+    appRules: allAppRules().map(rule => ({
+      bundleId: rule.bundleId,
+      action: rule.action,
+      tag: tagName(rule.tagId),
+      enabled: rule.enabled,
+    })),
+    tagRules: allTagRules()
+        .filter(rule => tagName(rule.tagId) !== undefined)
+        .map(rule => ({
+          name: rule.name,
+          pattern: rule.pattern,
+          flags: rule.flags,
+          tag: tagName(rule.tagId)!,
+          enabled: rule.enabled,
+        })),
+    smartCollections: collections.map(collection => ({
+      name: collection.name,
+      query: collection.query,
+      types: collection.types,
+      tags: collection.tags.map(tagName).filter((name): name is string => name !== undefined),
+      fromDate: collection.fromDate?.toISOString(),
+      toDate: collection.toDate?.toISOString(),
+      lastDays: collection.lastDays,
+    })),
+  }
+}
+
+// Saves the preferences, the shortcuts, the privacy rules, the tag rules, and
+// the smart collections to the JSON file that can be imported on another
+// machine.
+export async function exportSettings(path: string): Promise<SettingsBundle> {
+  let bundle = await createSettingsBundle()
+  if (typeof writeTextFile === 'undefined' || !writeTextFile(path, JSON.stringify(bundle, null, 2))) {
+    throw new Error(`Failed to write file: ${path}`)
+  }
+  console.log(`✅ Exported settings to ${path}`)
+  return bundle
+}
+
+function parseBundle(text: string): SettingsBundle {
+  let bundle = JSON.parse(text)
+  if (typeof bundle !== "object" || bundle === null || typeof bundle.version !== "number") {
+    throw new Error("Not a settings file")
+  }
+  if (bundle.version > kSettingsBundleVersion) {
+    throw new Error(`Unsupported settings file version: ${bundle.version}`)
+  }
+  let preferences = bundle.preferences ?? {}
+  if (typeof preferences !== "object" || Array.isArray(preferences)) {
+    throw new Error("Invalid preferences in the settings file")
+  }
+  return {
+    version: bundle.version,
+    createdAt: bundle.createdAt ?? "",
+    preferences: preferences,
+    shortcuts: bundle.shortcuts ?? [],
+    tags: bundle.tags ?? [],
+    appRules: bundle.appRules ?? [],
+    tagRules: bundle.tagRules ?? [],
+    smartCollections: bundle.smartCollections ?? [],
+  }
+}
+
+// Returns the id of the tag with the given name creating the tag if it
+// doesn't exist.
+function resolveTag(name: string, bundle: SettingsBundle, report: SettingsImportReport): number {
+  let tag = findTagByName(name)
+  if (tag) {
+    return tag.id
+  }
+  let color = bundle.tags.find(tag => tag.name === name)?.color ?? TagColor.Blue
+  tag = new Tag(name, color)
+  addTag(tag)
+  report.tags++
+  return tag.id
+}
+
+// Merges the settings from the file into the current ones. The rules and
+// the collections that already exist are skipped, so importing the same
+// file twice doesn't duplicate them. The preferences are checked against
+// the same list of the portable preferences and their types as the config
+// file, so the import can't turn on the API server or the LAN sharing.
+export async function importSettings(path: string): Promise<SettingsImportReport> {
+  requireNotGuest()
+  if (typeof readTextFile === 'undefined') {
+    throw new Error("Reading files is not supported")
+  }
+  let text = readTextFile(path)
+  if (!text) {
+    throw new Error(`Failed to read file: ${path}`)
+  }
+  let bundle = parseBundle(text)
+  let report: SettingsImportReport = {
+    preferences: 0,
+    shortcuts: 0,
+    tags: 0,
+    appRules: 0,
+    tagRules: 0,
+    smartCollections: 0,
+  }
+  loadTags()
+  await loadAppRules()
+  await loadTagRules()
+
+  if (typeof applyPreferences !== 'undefined') {
+    report.preferences = applyPreferences(JSON.stringify(bundle.preferences))
+  }
+  for (const {id, shortcut} of bundle.shortcuts) {
+    if (findShortcutBinding(id)?.set) {
+      await saveShortcutBinding(id, shortcut)
+      report.shortcuts++
+    }
+  }
+  for (const tag of bundle.tags) {
+    resolveTag(tag.name, bundle, report)
+  }
+  for (const rule of bundle.appRules) {
+    if (rule.action === AppRuleAction.Tag && !rule.tag) {
+      continue
+    }
+    let tagId = rule.tag ? resolveTag(rule.tag, bundle, report) : undefined
+    let exists = allAppRules().some(existing => existing.bundleId === rule.bundleId && existing.action === rule.action)
+    if (!exists) {
+      let created = await createAppRule(rule.bundleId, rule.action, tagId)
+      if (!rule.enabled) {
+        await setAppRuleEnabled(created.id!, false)
+      }
+      report.appRules++
+    }
+  }
+  for (const rule of bundle.tagRules) {
+    let tagId = resolveTag(rule.tag, bundle, report)
+    let exists = allTagRules().some(existing => existing.pattern === rule.pattern && existing.tagId === tagId)
+    if (!exists) {
+      let created = await createTagRule(rule.name, rule.pattern, rule.flags, tagId)
+      if (!rule.enabled) {
+        await setTagRuleEnabled(created.id!, false)
+      }
+      report.tagRules++
+    }
+  }
+  let collections = await listSmartCollections()
+  for (const collection of bundle.smartCollections) {
+    if (collections.some(existing => existing.name === collection.name)) {
+      continue
+    }
+    let created = await createSmartCollection(collection.name, collection.query, {
+      types: collection.types,
+      tags: collection.tags.map(name => resolveTag(name, bundle, report)),
+      fromDate: collection.fromDate ? new Date(collection.fromDate) : undefined,
+      toDate: collection.toDate ? new Date(collection.toDate) : undefined,
+      lastDays: collection.lastDays,
+    })
+    collections.push(created)
+    report.smartCollections++
+  }
+  console.log(`✅ Imported settings from ${path}`)
+  emitter.emit("PreferencesChanged", Object.keys(bundle.preferences))
+  emitter.emit("UpdateTags")
+  return report
+}
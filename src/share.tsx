@@ -0,0 +1,32 @@
+import {Clip, ClipType, getImageFileName} from "@/db";
+import {getHistoryItemById} from "@/data";
+import {getFullContent} from "@/blobs";
+import {requireUnlocked} from "@/applock";
+import {classify, DetectedKind} from "@/lib/classifier";
+
+declare const shareItem: (text: string, url: string, imageFileName: string) => void;
+
+export function canShare(item: Clip): boolean {
+  return typeof shareItem !== 'undefined' &&
+      (item.type === ClipType.Image || (item.type !== ClipType.File && item.content.trim().length > 0))
+}
+
+// Shows the system share sheet for the history item, so the text, the link,
+// or the image can be sent to Messages, Mail, AirDrop, etc.
+export function share(id: number) {
+  requireUnlocked()
+  let item = getHistoryItemById(id)
+  if (!item) {
+    throw new Error(`History item not found: ${id}`)
+  }
+  if (!canShare(item)) {
+    throw new Error(`Cannot share the history item: ${id}`)
+  }
+  if (item.type === ClipType.Image) {
+    shareItem("", "", getImageFileName(item))
+    return
+  }
+  let content = getFullContent(item)
+  let url = classify(content) === DetectedKind.Url ? content.trim() : ""
+  shareItem(content, url, "")
+}
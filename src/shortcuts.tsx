@@ -0,0 +1,418 @@
+import {
+  prefGetCapitalizeShortcut,
+  prefGetClearHistoryShortcut,
+  prefGetCloseAppShortcut,
+  prefGetCloseAppShortcut2,
+  prefGetCloseAppShortcut3,
+  prefGetCopyObjectToClipboardShortcut,
+  prefGetCopyToClipboardShortcut,
+  prefGetCycleProfileShortcut,
+  prefGetDeleteHistoryItemShortcut,
+  prefGetEditHistoryItemShortcut,
+  prefGetMakeLowerCaseShortcut,
+  prefGetMakeUpperCaseShortcut,
+  prefGetOpenAppShortcut,
+  prefGetOpenInBrowserShortcut,
+  prefGetOpenInDefaultAppShortcut,
+  prefGetOpenSettingsShortcut,
+  prefGetPasteNextItemShortcut,
+  prefGetPasteRecentShortcut,
+  prefGetPasteSelectedItemToActiveAppShortcut,
+  prefGetPasteSelectedObjectToActiveAppShortcut,
+  prefGetPauseResumeShortcut,
+  prefGetQuickLookShortcut,
+  prefGetRemoveEmptyLinesShortcut,
+  prefGetRenameItemShortcut,
+  prefGetSaveImageAsFileShortcut,
+  prefGetSelectNextItemShortcut,
+  prefGetSelectPreviousItemShortcut,
+  prefGetSentenceCaseShortcut,
+  prefGetShowInFinderShortcut,
+  prefGetShowMoreActionsShortcut,
+  prefGetStripAllWhitespacesShortcut,
+  prefGetToggleFavoriteShortcut,
+  prefGetToggleFilterShortcut,
+  prefGetTogglePreviewShortcut,
+  prefGetTrimSurroundingWhitespacesShortcut,
+  prefGetZoomUIInShortcut,
+  prefGetZoomUIOutShortcut,
+  prefGetZoomUIResetShortcut,
+  prefShouldUsePasteRecentShortcuts,
+  prefSetCapitalizeShortcut,
+  prefSetClearHistoryShortcut,
+  prefSetCloseAppShortcut,
+  prefSetCloseAppShortcut2,
+  prefSetCloseAppShortcut3,
+  prefSetCopyObjectToClipboardShortcut,
+  prefSetCopyToClipboardShortcut,
+  prefSetCycleProfileShortcut,
+  prefSetDeleteHistoryItemShortcut,
+  prefSetEditHistoryItemShortcut,
+  prefSetMakeLowerCaseShortcut,
+  prefSetMakeUpperCaseShortcut,
+  prefSetOpenAppShortcut,
+  prefSetOpenInBrowserShortcut,
+  prefSetOpenInDefaultAppShortcut,
+  prefSetOpenSettingsShortcut,
+  prefSetPasteNextItemShortcut,
+  prefSetPasteSelectedItemToActiveAppShortcut,
+  prefSetPasteSelectedObjectToActiveAppShortcut,
+  prefSetPauseResumeShortcut,
+  prefSetQuickLookShortcut,
+  prefSetRemoveEmptyLinesShortcut,
+  prefSetRenameItemShortcut,
+  prefSetSaveImageAsFileShortcut,
+  prefSetSelectNextItemShortcut,
+  prefSetSelectPreviousItemShortcut,
+  prefSetSentenceCaseShortcut,
+  prefSetShowInFinderShortcut,
+  prefSetShowMoreActionsShortcut,
+  prefSetStripAllWhitespacesShortcut,
+  prefSetToggleFavoriteShortcut,
+  prefSetToggleFilterShortcut,
+  prefSetTogglePreviewShortcut,
+  prefSetTrimSurroundingWhitespacesShortcut,
+  prefSetZoomUIInShortcut,
+  prefSetZoomUIOutShortcut,
+  prefSetZoomUIResetShortcut,
+  DoubleTapAction,
+  DoubleTapModifier,
+  prefGetDoubleTapAction,
+  prefGetDoubleTapModifier,
+  prefSetDoubleTapAction,
+  prefSetDoubleTapModifier,
+} from "@/pref";
+import {isShortcutMatch, normalizeShortcut} from "@/lib/shortcuts";
+import {getShortcutRows, saveShortcutRow, ShortcutRow} from "@/db";
+import {emitter} from "@/actions";
+import {PermissionKind, PermissionState} from "@/permissions";
+
+declare const enableOpenAppShortcut: () => void;
+declare const enablePauseResumeShortcut: () => void;
+declare const enableCycleProfileShortcut: () => void;
+declare const enablePasteNextItemShortcut: () => void;
+declare const updateOpenSettingsShortcut: () => void;
+declare const enableDoubleTapShortcut: () => void;
+declare const isDoubleTapShortcutActive: () => boolean;
+
+// Where the shortcut works. The shortcuts of the different contexts can
+// share the same key combination unless one of them is global.
+export enum ShortcutContext {
+  // Registered system-wide and works when ClipBook is in the background.
+  Global = "global",
+  // Works in the ClipBook window.
+  Window = "window",
+  // Works in the ClipBook window when a text item is selected.
+  Text = "text",
+  // Works in the ClipBook window when an image item is selected.
+  Image = "image",
+}
+
+export type ShortcutBinding = {
+  id: string
+  // The translation key of the shortcut name.
+  label: string
+  context: ShortcutContext
+  get: () => string
+  // Not set for the shortcuts that can't be changed.
+  set?: (shortcut: string) => void
+  // Registers the global shortcut again after it's changed.
+  register?: () => void
+  // The shortcuts that may intentionally use the same key combination,
+  // e.g. the shortcut that opens ClipBook also closes it.
+  compatibleWith?: string[]
+}
+
+export const kShortcutBindings: ShortcutBinding[] = [
+  {id: "openApp", label: "settings.shortcuts.openApp", context: ShortcutContext.Global, get: prefGetOpenAppShortcut, set: prefSetOpenAppShortcut, register: () => typeof enableOpenAppShortcut !== 'undefined' && enableOpenAppShortcut()},
+  {id: "closeApp", label: "settings.shortcuts.closeApp", context: ShortcutContext.Window, get: prefGetCloseAppShortcut, set: prefSetCloseAppShortcut, compatibleWith: ["openApp"]},
+  {id: "closeApp2", label: "settings.shortcuts.closeApp", context: ShortcutContext.Window, get: prefGetCloseAppShortcut2, set: prefSetCloseAppShortcut2, compatibleWith: ["openApp"]},
+  {id: "closeApp3", label: "settings.shortcuts.closeApp", context: ShortcutContext.Window, get: prefGetCloseAppShortcut3, set: prefSetCloseAppShortcut3, compatibleWith: ["openApp"]},
+  {id: "selectNextItem", label: "settings.shortcuts.selectNextItem", context: ShortcutContext.Window, get: prefGetSelectNextItemShortcut, set: prefSetSelectNextItemShortcut},
+  {id: "selectPreviousItem", label: "settings.shortcuts.selectPreviousItem", context: ShortcutContext.Window, get: prefGetSelectPreviousItemShortcut, set: prefSetSelectPreviousItemShortcut},
+  {id: "pasteNextItem", label: "settings.shortcuts.pasteNextItemToActiveApp", context: ShortcutContext.Global, get: prefGetPasteNextItemShortcut, set: prefSetPasteNextItemShortcut, register: () => typeof enablePasteNextItemShortcut !== 'undefined' && enablePasteNextItemShortcut()},
+  // The paste recent item shortcuts are registered only when they are enabled.
+  ...[1, 2, 3, 4, 5, 6, 7, 8, 9].map(number => ({
+    id: `pasteRecent${number}`,
+    label: "settings.shortcuts.pasteRecentItems.title",
+    context: ShortcutContext.Global,
+    get: () => prefShouldUsePasteRecentShortcuts() ? prefGetPasteRecentShortcut(number) : ""
+  })),
+  {id: "pasteSelectedItemToActiveApp", label: "settings.shortcuts.pasteSelectedItemToActiveApp", context: ShortcutContext.Window, get: prefGetPasteSelectedItemToActiveAppShortcut, set: prefSetPasteSelectedItemToActiveAppShortcut},
+  {id: "pasteSelectedObjectToActiveApp", label: "settings.shortcuts.alternatePasteSelectedItemToActiveApp", context: ShortcutContext.Window, get: prefGetPasteSelectedObjectToActiveAppShortcut, set: prefSetPasteSelectedObjectToActiveAppShortcut},
+  {id: "copyToClipboard", label: "settings.shortcuts.copyToClipboard", context: ShortcutContext.Window, get: prefGetCopyToClipboardShortcut, set: prefSetCopyToClipboardShortcut},
+  {id: "copyObjectToClipboard", label: "settings.shortcuts.alternateCopyToClipboard", context: ShortcutContext.Window, get: prefGetCopyObjectToClipboardShortcut, set: prefSetCopyObjectToClipboardShortcut},
+  {id: "openInBrowser", label: "settings.shortcuts.openInBrowser", context: ShortcutContext.Window, get: prefGetOpenInBrowserShortcut, set: prefSetOpenInBrowserShortcut},
+  {id: "showInFinder", label: "settings.shortcuts.showInFinder", context: ShortcutContext.Window, get: prefGetShowInFinderShortcut, set: prefSetShowInFinderShortcut},
+  {id: "openInDefaultApp", label: "settings.shortcuts.openInDefaultApp", context: ShortcutContext.Window, get: prefGetOpenInDefaultAppShortcut, set: prefSetOpenInDefaultAppShortcut},
+  {id: "toggleFavorite", label: "settings.shortcuts.addToFavorites", context: ShortcutContext.Window, get: prefGetToggleFavoriteShortcut, set: prefSetToggleFavoriteShortcut},
+  {id: "quickLook", label: "settings.shortcuts.quickLook", context: ShortcutContext.Window, get: prefGetQuickLookShortcut, set: prefSetQuickLookShortcut},
+  {id: "saveImageAsFile", label: "settings.shortcuts.saveAsFile", context: ShortcutContext.Image, get: prefGetSaveImageAsFileShortcut, set: prefSetSaveImageAsFileShortcut},
+  {id: "editHistoryItem", label: "settings.shortcuts.editHistoryItem", context: ShortcutContext.Window, get: prefGetEditHistoryItemShortcut, set: prefSetEditHistoryItemShortcut},
+  {id: "renameItem", label: "settings.shortcuts.renameHistoryItem", context: ShortcutContext.Window, get: prefGetRenameItemShortcut, set: prefSetRenameItemShortcut},
+  {id: "deleteHistoryItem", label: "settings.shortcuts.deleteHistoryItem", context: ShortcutContext.Window, get: prefGetDeleteHistoryItemShortcut, set: prefSetDeleteHistoryItemShortcut},
+  {id: "clearHistory", label: "settings.shortcuts.deleteAllHistoryItems", context: ShortcutContext.Window, get: prefGetClearHistoryShortcut, set: prefSetClearHistoryShortcut},
+  {id: "togglePreview", label: "settings.shortcuts.showHidePreview", context: ShortcutContext.Window, get: prefGetTogglePreviewShortcut, set: prefSetTogglePreviewShortcut},
+  {id: "toggleFilter", label: "settings.shortcuts.showHideFilter", context: ShortcutContext.Window, get: prefGetToggleFilterShortcut, set: prefSetToggleFilterShortcut},
+  {id: "showMoreActions", label: "settings.shortcuts.showCommandMenu", context: ShortcutContext.Window, get: prefGetShowMoreActionsShortcut, set: prefSetShowMoreActionsShortcut},
+  {id: "zoomUIIn", label: "settings.shortcuts.zoomIn", context: ShortcutContext.Window, get: prefGetZoomUIInShortcut, set: prefSetZoomUIInShortcut},
+  {id: "zoomUIOut", label: "settings.shortcuts.zoomOut", context: ShortcutContext.Window, get: prefGetZoomUIOutShortcut, set: prefSetZoomUIOutShortcut},
+  {id: "zoomUIReset", label: "settings.shortcuts.zoomReset", context: ShortcutContext.Window, get: prefGetZoomUIResetShortcut, set: prefSetZoomUIResetShortcut},
+  {id: "pauseResume", label: "settings.shortcuts.pauseResumeClipBook", context: ShortcutContext.Global, get: prefGetPauseResumeShortcut, set: prefSetPauseResumeShortcut, register: () => typeof enablePauseResumeShortcut !== 'undefined' && enablePauseResumeShortcut()},
+  {id: "cycleProfile", label: "settings.shortcuts.cycleProfile", context: ShortcutContext.Global, get: prefGetCycleProfileShortcut, set: prefSetCycleProfileShortcut, register: () => typeof enableCycleProfileShortcut !== 'undefined' && enableCycleProfileShortcut()},
+  {id: "openSettings", label: "settings.shortcuts.openSettings", context: ShortcutContext.Window, get: prefGetOpenSettingsShortcut, set: prefSetOpenSettingsShortcut, register: () => typeof updateOpenSettingsShortcut !== 'undefined' && updateOpenSettingsShortcut()},
+  {id: "makeLowerCase", label: "settings.shortcuts.makeLowerCase", context: ShortcutContext.Text, get: prefGetMakeLowerCaseShortcut, set: prefSetMakeLowerCaseShortcut},
+  {id: "makeUpperCase", label: "settings.shortcuts.makeUpperCase", context: ShortcutContext.Text, get: prefGetMakeUpperCaseShortcut, set: prefSetMakeUpperCaseShortcut},
+  {id: "capitalize", label: "settings.shortcuts.capitalizeWords", context: ShortcutContext.Text, get: prefGetCapitalizeShortcut, set: prefSetCapitalizeShortcut},
+  {id: "sentenceCase", label: "settings.shortcuts.sentenceCase", context: ShortcutContext.Text, get: prefGetSentenceCaseShortcut, set: prefSetSentenceCaseShortcut},
+  {id: "removeEmptyLines", label: "settings.shortcuts.removeEmptyLines", context: ShortcutContext.Text, get: prefGetRemoveEmptyLinesShortcut, set: prefSetRemoveEmptyLinesShortcut},
+  {id: "stripAllWhitespaces", label: "settings.shortcuts.stripAllWhitespaces", context: ShortcutContext.Text, get: prefGetStripAllWhitespacesShortcut, set: prefSetStripAllWhitespacesShortcut},
+  {id: "trimSurroundingWhitespaces", label: "settings.shortcuts.trimSurroundingWhitespaces", context: ShortcutContext.Text, get: prefGetTrimSurroundingWhitespacesShortcut, set: prefSetTrimSurroundingWhitespacesShortcut},
+]
+
+// The well-known macOS shortcuts that are handled by the system before they
+// reach ClipBook. The list is not exhaustive, because the system shortcuts
+// can be customized in System Settings.
+const kSystemShortcuts: { shortcut: string, name: string }[] = [
+  {shortcut: "MetaLeft + Space", name: "Spotlight"},
+  {shortcut: "AltLeft + MetaLeft + Space", name: "Finder search window"},
+  {shortcut: "ControlLeft + Space", name: "Select the previous input source"},
+  {shortcut: "ControlLeft + AltLeft + Space", name: "Select the next input source"},
+  {shortcut: "ControlLeft + MetaLeft + Space", name: "Emoji & Symbols"},
+  {shortcut: "MetaLeft + Tab", name: "App Switcher"},
+  {shortcut: "ShiftLeft + MetaLeft + Tab", name: "App Switcher"},
+  {shortcut: "MetaLeft + Backquote", name: "Move focus to the next window"},
+  {shortcut: "ShiftLeft + MetaLeft + Digit3", name: "Screenshot"},
+  {shortcut: "ShiftLeft + MetaLeft + Digit4", name: "Screenshot of selected area"},
+  {shortcut: "ShiftLeft + MetaLeft + Digit5", name: "Screenshot and recording options"},
+  {shortcut: "ControlLeft + ShiftLeft + MetaLeft + Digit3", name: "Copy screenshot to the clipboard"},
+  {shortcut: "ControlLeft + ShiftLeft + MetaLeft + Digit4", name: "Copy screenshot of selected area to the clipboard"},
+  {shortcut: "ControlLeft + MetaLeft + KeyQ", name: "Lock Screen"},
+  {shortcut: "AltLeft + MetaLeft + Escape", name: "Force Quit Applications"},
+  {shortcut: "AltLeft + MetaLeft + KeyD", name: "Show/hide the Dock"},
+  {shortcut: "ControlLeft + ArrowUp", name: "Mission Control"},
+  {shortcut: "ControlLeft + ArrowDown", name: "Application windows"},
+  {shortcut: "ControlLeft + ArrowLeft", name: "Move left a space"},
+  {shortcut: "ControlLeft + ArrowRight", name: "Move right a space"},
+  {shortcut: "MetaLeft + KeyH", name: "Hide the active app"},
+  {shortcut: "MetaLeft + KeyQ", name: "Quit the active app"},
+]
+
+// Describes the binding or the system shortcut that already uses the key
+// combination of the shortcut being registered.
+export class ShortcutConflict extends Error {
+  shortcut: string
+  // The id of the conflicting ClipBook shortcut.
+  bindingId?: string
+  // The name of the conflicting system shortcut.
+  systemShortcut?: string
+
+  constructor(shortcut: string, bindingId?: string, systemShortcut?: string) {
+    super(`The shortcut ${shortcut} is already used by ${bindingId ?? systemShortcut}`)
+    this.name = "ShortcutConflict"
+    this.shortcut = shortcut
+    this.bindingId = bindingId
+    this.systemShortcut = systemShortcut
+  }
+}
+
+export function findShortcutBinding(id: string): ShortcutBinding | undefined {
+  return kShortcutBindings.find(binding => binding.id === id)
+}
+
+function isSameContext(a: ShortcutContext, b: ShortcutContext): boolean {
+  if (a === ShortcutContext.Global || b === ShortcutContext.Global) {
+    return true
+  }
+  // The text and image shortcuts are never active at the same time.
+  return a === b || a === ShortcutContext.Window || b === ShortcutContext.Window
+}
+
+function isCompatible(a: ShortcutBinding, b: ShortcutBinding): boolean {
+  return a.compatibleWith?.includes(b.id) === true || b.compatibleWith?.includes(a.id) === true
+}
+
+// Returns the conflict if the key combination is already used by another
+// ClipBook shortcut or by a well-known system shortcut. The id is the
+// binding the shortcut is validated for, so it doesn't conflict with itself.
+export function validateShortcut(shortcut: string, id?: string): ShortcutConflict | undefined {
+  if (shortcut.length === 0) {
+    return undefined
+  }
+  let normalized = normalizeShortcut(shortcut)
+  let binding = id ? findShortcutBinding(id) : undefined
+  let context = binding?.context ?? ShortcutContext.Global
+  for (const other of kShortcutBindings) {
+    if (other.id === id || (binding && isCompatible(binding, other))) {
+      continue
+    }
+    if (isSameContext(context, other.context) && normalizeShortcut(other.get()) === normalized) {
+      return new ShortcutConflict(shortcut, other.id)
+    }
+  }
+  for (const system of kSystemShortcuts) {
+    if (normalizeShortcut(system.shortcut) === normalized) {
+      return new ShortcutConflict(shortcut, undefined, system.name)
+    }
+  }
+  return undefined
+}
+
+// Throws the ShortcutConflict error if the shortcut can't be registered for
+// the binding.
+export function checkShortcut(shortcut: string, id: string) {
+  let conflict = validateShortcut(shortcut, id)
+  if (conflict) {
+    throw conflict
+  }
+}
+
+// The shortcuts changed through the API or the settings and the usage of
+// the shortcuts by the binding id.
+let shortcutRows = new Map<string, ShortcutRow>()
+
+// Applies the shortcuts saved in the database, so the bindings changed
+// through the API are restored at startup.
+export async function loadShortcutBindings() {
+  shortcutRows.clear()
+  for (const row of await getShortcutRows()) {
+    shortcutRows.set(row.id, row)
+    let binding = findShortcutBinding(row.id)
+    if (binding?.set && row.shortcut !== binding.get()) {
+      binding.set(row.shortcut)
+      binding.register?.()
+    }
+  }
+}
+
+function getShortcutRow(binding: ShortcutBinding): ShortcutRow {
+  return shortcutRows.get(binding.id) ?? {id: binding.id, shortcut: binding.get(), useCount: 0}
+}
+
+// Saves the shortcut of the binding in the preferences and the database.
+// The caller registers the global shortcut again.
+export async function saveShortcutBinding(id: string, shortcut: string) {
+  let binding = findShortcutBinding(id)
+  if (!binding?.set) {
+    throw new Error(`The shortcut can't be changed: ${id}`)
+  }
+  binding.set(shortcut)
+  let row = {...getShortcutRow(binding), shortcut}
+  shortcutRows.set(id, row)
+  await saveShortcutRow(row)
+}
+
+// Changes the shortcut of the binding if it doesn't conflict with another
+// shortcut and registers it again if it's global.
+export async function setShortcut(id: string, shortcut: string) {
+  checkShortcut(shortcut, id)
+  await saveShortcutBinding(id, shortcut)
+  findShortcutBinding(id)?.register?.()
+}
+
+export function recordShortcutUse(id: string) {
+  let binding = findShortcutBinding(id)
+  if (!binding) {
+    return
+  }
+  let row = getShortcutRow(binding)
+  row = {...row, useCount: row.useCount + 1, lastUsed: new Date()}
+  shortcutRows.set(id, row)
+  saveShortcutRow(row).catch(error => console.error('❌ Failed to save shortcut usage:', error))
+}
+
+// Records the use of the window shortcut matching the key event.
+export function recordShortcutEvent(event: KeyboardEvent) {
+  for (const binding of kShortcutBindings) {
+    let shortcut = binding.get()
+    if (binding.context !== ShortcutContext.Global && shortcut.length > 0 && isShortcutMatch(shortcut, event)) {
+      recordShortcutUse(binding.id)
+      return
+    }
+  }
+}
+
+export type ShortcutInfo = {
+  id: string
+  context: ShortcutContext
+  shortcut: string
+  editable: boolean
+  useCount: number
+  lastUsed?: Date
+}
+
+export function getShortcuts(): ShortcutInfo[] {
+  return kShortcutBindings.map(binding => {
+    let row = shortcutRows.get(binding.id)
+    return {
+      id: binding.id,
+      context: binding.context,
+      shortcut: binding.get(),
+      editable: binding.set !== undefined,
+      useCount: row?.useCount ?? 0,
+      lastUsed: row?.lastUsed,
+    }
+  })
+}
+
+// The double press of a modifier key, e.g. ⌘⌘, is detected by the app
+// separately from the regular shortcuts, so it's not a shortcut binding.
+export type DoubleTapShortcut = {
+  modifier: DoubleTapModifier
+  action: DoubleTapAction
+}
+
+export type DoubleTapShortcutInfo = DoubleTapShortcut & {
+  // False if the modifier is set, but the app can't listen to the key
+  // presses, because the Input Monitoring permission isn't granted.
+  active: boolean
+}
+
+export function getDoubleTapShortcut(): DoubleTapShortcutInfo {
+  return {
+    modifier: prefGetDoubleTapModifier(),
+    action: prefGetDoubleTapAction(),
+    active: typeof isDoubleTapShortcutActive !== 'undefined' && isDoubleTapShortcutActive(),
+  }
+}
+
+// Saves the double-tap shortcut and restarts its detection. The detection
+// is stopped if the modifier is none.
+export function setDoubleTapShortcut(shortcut: DoubleTapShortcut) {
+  prefSetDoubleTapModifier(shortcut.modifier)
+  prefSetDoubleTapAction(shortcut.action)
+  if (typeof enableDoubleTapShortcut !== 'undefined') {
+    enableDoubleTapShortcut()
+  }
+}
+
+let watchingChanges = false
+
+function registerGlobalShortcuts() {
+  for (const binding of kShortcutBindings) {
+    binding.register?.()
+  }
+  if (typeof enableDoubleTapShortcut !== 'undefined') {
+    enableDoubleTapShortcut()
+  }
+}
+
+// Registers the global shortcuts again when they are changed in the config
+// file and starts detecting the double-tap shortcut as soon as the user
+// grants the Input Monitoring permission, so the app doesn't need to be
+// restarted.
+export function watchShortcutChanges() {
+  if (watchingChanges) {
+    return
+  }
+  watchingChanges = true
+  emitter.on("PreferencesChanged", registerGlobalShortcuts)
+  emitter.on("PermissionChanged", change => {
+    if (change.kind !== PermissionKind.InputMonitoring || change.state !== PermissionState.Granted) {
+      return
+    }
+    if (prefGetDoubleTapModifier() !== DoubleTapModifier.NONE && typeof enableDoubleTapShortcut !== 'undefined') {
+      enableDoubleTapShortcut()
+    }
+  })
+}
@@ -0,0 +1,47 @@
+import {Clip, ClipType} from "@/db";
+import {putFullContent} from "@/blobs";
+import {notifyItemSkipped} from "@/notifications";
+import {LargeItemPolicy, prefGetLargeItemPolicy, prefGetMaxItemKilobytes} from "@/pref";
+
+export class ItemTooLargeError extends Error {
+  constructor(size: number) {
+    super(`The item of ${size} bytes exceeds the size limit`)
+    this.name = "ItemTooLargeError"
+  }
+}
+
+// The number of characters kept in the content of the truncated items.
+const kPreviewLength = 4 * 1024
+
+function getContentSizeInBytes(item: Clip): number {
+  return new TextEncoder().encode(item.content).length
+}
+
+// Applies `app.large_item_policy` to the new item whose text exceeds
+// `app.max_item_kilobytes`. Throws ItemTooLargeError if the item must not be
+// added to the history.
+export async function applySizeLimit(item: Clip) {
+  let maxKilobytes = prefGetMaxItemKilobytes()
+  if (maxKilobytes <= 0 || item.type === ClipType.Image || item.type === ClipType.File) {
+    return
+  }
+  let size = getContentSizeInBytes(item)
+  if (size <= maxKilobytes * 1024) {
+    return
+  }
+  switch (prefGetLargeItemPolicy()) {
+    case LargeItemPolicy.KEEP:
+      return
+    case LargeItemPolicy.SKIP:
+      notifyItemSkipped(size)
+      throw new ItemTooLargeError(size)
+    case LargeItemPolicy.TRUNCATE:
+      try {
+        item.fullContentBlob = await putFullContent(item.content)
+        item.content = item.content.substring(0, kPreviewLength)
+      } catch (error) {
+        // Keep the full content rather than lose it.
+        console.error('❌ Failed to store the full content of the large item:', error)
+      }
+  }
+}
@@ -0,0 +1,138 @@
+import {
+  addSnippet,
+  deleteSnippet,
+  getAllSnippets,
+  getSnippet,
+  Snippet,
+  updateSnippet
+} from "@/db";
+
+declare const pasteItemInFrontApp: (text: string, rtf: string, html: string, imageFileName: string, filePath: string) => void;
+
+// The snippets are kept in memory, so they can be searched without reading
+// the database, e.g. by the quick picker.
+let cachedSnippets: Snippet[] = []
+
+// The actions the snippet commands can perform on the selected snippet.
+export enum SnippetAction {
+  Paste = "paste",
+  Update = "update",
+  Remove = "remove",
+}
+
+const kMonthNames = ["January", "February", "March", "April", "May", "June", "July",
+  "August", "September", "October", "November", "December"]
+const kDayNames = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"]
+
+function pad(value: number, length: number = 2): string {
+  return value.toString().padStart(length, "0")
+}
+
+// Formats the date using the strftime-like format, e.g. "%Y-%m-%d".
+export function formatDate(date: Date, format: string): string {
+  return format.replace(/%([YymdHMSBbAa%])/g, (_, spec: string) => {
+    switch (spec) {
+      case "Y":
+        return date.getFullYear().toString()
+      case "y":
+        return pad(date.getFullYear() % 100)
+      case "m":
+        return pad(date.getMonth() + 1)
+      case "d":
+        return pad(date.getDate())
+      case "H":
+        return pad(date.getHours())
+      case "M":
+        return pad(date.getMinutes())
+      case "S":
+        return pad(date.getSeconds())
+      case "B":
+        return kMonthNames[date.getMonth()]
+      case "b":
+        return kMonthNames[date.getMonth()].substring(0, 3)
+      case "A":
+        return kDayNames[date.getDay()]
+      case "a":
+        return kDayNames[date.getDay()].substring(0, 3)
+      default:
+        return "%"
+    }
+  })
+}
+
+// Replaces the {name} placeholders with the given values and the
+// {date:<format>} placeholders with the current date. Unknown placeholders
+// are kept as is.
+export function expandSnippet(content: string, vars: Record<string, string> = {}, now: Date = new Date()): string {
+  return content.replace(/\{([A-Za-z_][\w-]*)(?::([^}]*))?}/g, (placeholder, name: string, format?: string) => {
+    if (name === "date") {
+      return formatDate(now, format ?? "%Y-%m-%d")
+    }
+    if (name === "time" && format === undefined) {
+      return formatDate(now, "%H:%M")
+    }
+    if (Object.prototype.hasOwnProperty.call(vars, name)) {
+      return vars[name]
+    }
+    return placeholder
+  })
+}
+
+// Returns the names of the custom placeholders used in the snippet content.
+export function getSnippetPlaceholders(content: string): string[] {
+  let names: string[] = []
+  for (const match of content.matchAll(/\{([A-Za-z_][\w-]*)(?::[^}]*)?}/g)) {
+    const name = match[1]
+    if (name !== "date" && name !== "time" && !names.includes(name)) {
+      names.push(name)
+    }
+  }
+  return names
+}
+
+export async function loadSnippets() {
+  cachedSnippets = await getAllSnippets()
+}
+
+export function getCachedSnippets(): Snippet[] {
+  return cachedSnippets
+}
+
+export async function listSnippets(): Promise<Snippet[]> {
+  let snippets = await getAllSnippets()
+  return snippets.sort((a, b) => a.name.localeCompare(b.name))
+}
+
+export async function createSnippet(name: string, content: string): Promise<Snippet> {
+  let snippet = new Snippet(name, content)
+  snippet.id = await addSnippet(snippet)
+  cachedSnippets.push(snippet)
+  return snippet
+}
+
+export async function editSnippet(id: number, name: string, content: string) {
+  let updatedAt = new Date()
+  await updateSnippet(id, {name, content, updatedAt})
+  let snippet = cachedSnippets.find(snippet => snippet.id === id)
+  if (snippet) {
+    Object.assign(snippet, {name, content, updatedAt})
+  }
+}
+
+export async function removeSnippet(id: number) {
+  await deleteSnippet(id)
+  cachedSnippets = cachedSnippets.filter(snippet => snippet.id !== id)
+}
+
+// Expands the snippet with the given variables and pastes it to the active app.
+export async function pasteSnippet(id: number, vars: Record<string, string> = {}): Promise<string> {
+  let snippet = await getSnippet(id)
+  if (!snippet) {
+    throw new Error(`Snippet not found: ${id}`)
+  }
+  let text = expandSnippet(snippet.content, vars)
+  if (typeof pasteItemInFrontApp !== 'undefined') {
+    pasteItemInFrontApp(text, "", "", "", "")
+  }
+  return text
+}
@@ -0,0 +1,192 @@
+import {Clip, ClipType} from "@/db";
+import {getHistoryItems, getSourceAppInfo} from "@/data";
+import {findTagById} from "@/tags";
+import {emitter} from "@/actions";
+
+export type StatisticsPeriod = "day" | "week" | "month" | "year" | "all"
+
+export const kStatisticsPeriods: StatisticsPeriod[] = ["day", "week", "month", "year", "all"]
+
+// The number of the source apps and tags in the statistics.
+const kTopCount = 10
+const kDayMs = 24 * 60 * 60 * 1000
+// The cached statistics are recomputed after this time even if the history
+// hasn't changed, because the old items leave the period.
+const kCacheTtlMs = 5 * 60 * 1000
+const kPeriodDays: Record<StatisticsPeriod, number> = {
+  day: 1,
+  week: 7,
+  month: 30,
+  year: 365,
+  all: Infinity,
+}
+
+export type DayCount = {
+  // The local date in the YYYY-MM-DD format.
+  date: string
+  count: number
+}
+
+export type NamedCount = {
+  name: string
+  count: number
+}
+
+export interface UsageStatistics {
+  period: StatisticsPeriod;
+  totalItems: number;
+  itemsPerDay: DayCount[];
+  byType: Record<string, number>;
+  bySourceApp: NamedCount[];
+  // The number of the items copied at every hour of the day, 0 to 23.
+  busiestHours: number[];
+  // The average size of the item in bytes.
+  averageItemSize: number;
+  topTags: NamedCount[];
+}
+
+export interface TodaySummary {
+  // The local date in the YYYY-MM-DD format.
+  date: string;
+  itemsCopied: number;
+  topSourceApp?: NamedCount;
+  recentItems: Clip[];
+}
+
+// The number of the recent items in the today summary.
+const kRecentItemsCount = 5
+
+type CachedStatistics = {
+  statistics: UsageStatistics
+  computedAt: number
+}
+
+let cache = new Map<StatisticsPeriod, CachedStatistics>()
+let todaySummary: TodaySummary | undefined
+let cacheSubscribed = false
+
+function invalidateCache() {
+  cache.clear()
+  todaySummary = undefined
+}
+
+function subscribeToHistoryChanges() {
+  if (cacheSubscribed) {
+    return
+  }
+  cacheSubscribed = true
+  emitter.on("HistoryChanged", invalidateCache)
+  emitter.on("HistoryItemAdded", invalidateCache)
+  emitter.on("HistoryItemUpdated", invalidateCache)
+  emitter.on("HistoryItemDeleted", invalidateCache)
+  emitter.on("HistoryCleared", invalidateCache)
+}
+
+function formatDate(date: Date): string {
+  let month = String(date.getMonth() + 1).padStart(2, "0")
+  let day = String(date.getDate()).padStart(2, "0")
+  return `${date.getFullYear()}-${month}-${day}`
+}
+
+function getItemSize(item: Clip): number {
+  switch (item.type) {
+    case ClipType.Image:
+      return item.imageSizeInBytes || 0
+    case ClipType.File:
+      return item.fileSizeInBytes || 0
+    default:
+      return new TextEncoder().encode(item.content).length
+  }
+}
+
+function topCounts(counts: Map<string, number>): NamedCount[] {
+  return [...counts.entries()]
+      .map(([name, count]) => ({name, count}))
+      .sort((a, b) => b.count - a.count)
+      .slice(0, kTopCount)
+}
+
+function increment(counts: Map<string, number>, key: string) {
+  counts.set(key, (counts.get(key) ?? 0) + 1)
+}
+
+function computeStatistics(period: StatisticsPeriod): UsageStatistics {
+  let since = Date.now() - kPeriodDays[period] * kDayMs
+  let items = getHistoryItems().filter(item => new Date(item.copyTime).getTime() >= since)
+  let days = new Map<string, number>()
+  let types = new Map<string, number>()
+  let apps = new Map<string, number>()
+  let tags = new Map<string, number>()
+  let busiestHours: number[] = new Array(24).fill(0)
+  let totalSize = 0
+  for (const item of items) {
+    let time = new Date(item.copyTime)
+    increment(days, formatDate(time))
+    increment(types, ClipType[item.type])
+    let appInfo = item.sourceApp ? getSourceAppInfo(item.sourceApp) : undefined
+    increment(apps, appInfo?.name ?? item.sourceApp ?? "")
+    for (const tagId of item.tags ?? []) {
+      let tag = findTagById(tagId)
+      if (tag) {
+        increment(tags, tag.name)
+      }
+    }
+    busiestHours[time.getHours()]++
+    totalSize += getItemSize(item)
+  }
+  return {
+    period,
+    totalItems: items.length,
+    itemsPerDay: [...days.entries()]
+        .map(([date, count]) => ({date, count}))
+        .sort((a, b) => a.date.localeCompare(b.date)),
+    byType: Object.fromEntries(types),
+    bySourceApp: topCounts(apps),
+    busiestHours,
+    averageItemSize: items.length > 0 ? Math.round(totalSize / items.length) : 0,
+    topTags: topCounts(tags),
+  }
+}
+
+// Returns the statistics of the items copied during the given period. The
+// statistics are cached until the history changes or for 5 minutes.
+export function getUsageStatistics(period: StatisticsPeriod): UsageStatistics {
+  subscribeToHistoryChanges()
+  let cached = cache.get(period)
+  if (!cached || Date.now() - cached.computedAt > kCacheTtlMs) {
+    cached = {statistics: computeStatistics(period), computedAt: Date.now()}
+    cache.set(period, cached)
+  }
+  return cached.statistics
+}
+
+function computeTodaySummary(today: Date): TodaySummary {
+  let midnight = new Date(today.getFullYear(), today.getMonth(), today.getDate()).getTime()
+  let items = getHistoryItems()
+      .filter(item => new Date(item.copyTime).getTime() >= midnight)
+      .sort((a, b) => new Date(b.copyTime).getTime() - new Date(a.copyTime).getTime())
+  let apps = new Map<string, number>()
+  for (const item of items) {
+    if (item.sourceApp) {
+      increment(apps, getSourceAppInfo(item.sourceApp)?.name ?? item.sourceApp)
+    }
+  }
+  return {
+    date: formatDate(today),
+    itemsCopied: items.length,
+    topSourceApp: topCounts(apps)[0],
+    recentItems: items.slice(0, kRecentItemsCount),
+  }
+}
+
+// Returns the number of the items copied today, the app they were copied
+// from most often, and the most recent of them. The summary is cached until
+// the history changes or the day ends, so it's cheap to call frequently.
+export function getTodaySummary(): TodaySummary {
+  subscribeToHistoryChanges()
+  let today = new Date()
+  if (!todaySummary || todaySummary.date !== formatDate(today)) {
+    todaySummary = computeTodaySummary(today)
+  }
+  return todaySummary
+}
@@ -0,0 +1,259 @@
+import type {Clip, LinkPreviewDetails} from "@/db";
+
+export interface HistorySnapshot {
+  clips: Clip[];
+  linkPreviews: LinkPreviewDetails[];
+}
+
+// The storage backend of the clipboard history. The default backend stores
+// the data in IndexedDB. Alternative backends can be installed via
+// setHistoryStore() in db.tsx.
+export interface HistoryStore {
+  open(): Promise<void>;
+
+  getAllClips(): Promise<Clip[]>;
+  // Adds the clip, assigns its id, and returns it.
+  addClip(clip: Clip): Promise<number>;
+  // Adds the clips in a single transaction and returns their ids in the
+  // same order.
+  addClips(clips: Clip[]): Promise<number[]>;
+  updateClip(id: number, changes: Partial<Clip>): Promise<void>;
+  deleteClip(id: number): Promise<void>;
+  deleteAllClips(): Promise<void>;
+  // Overwrites the content of the clips before deleting them, so the content
+  // isn't left in the storage freed by the deletion.
+  secureDeleteClip(id: number): Promise<void>;
+  secureDeleteAllClips(): Promise<void>;
+
+  getAllLinkPreviews(): Promise<LinkPreviewDetails[]>;
+  getLinkPreview(url: string): Promise<LinkPreviewDetails | undefined>;
+  addLinkPreviews(previews: LinkPreviewDetails[]): Promise<void>;
+  deleteLinkPreview(url: string): Promise<void>;
+  deleteAllLinkPreviews(): Promise<void>;
+
+  // Returns a consistent snapshot of all clips and link previews.
+  snapshot(): Promise<HistorySnapshot>;
+}
+
+// A store that keeps the data in memory only. Useful for testing and for
+// running the UI without a persistent database.
+export class MemoryHistoryStore implements HistoryStore {
+  private clips = new Map<number, Clip>()
+  private previews: LinkPreviewDetails[] = []
+  private nextClipId = 1
+  private nextPreviewId = 1
+
+  async open() {
+  }
+
+  async getAllClips(): Promise<Clip[]> {
+    return Array.from(this.clips.values()).map(clip => ({...clip}))
+  }
+
+  async addClip(clip: Clip): Promise<number> {
+    clip.id = this.nextClipId++
+    this.clips.set(clip.id, {...clip})
+    return clip.id
+  }
+
+  async addClips(clips: Clip[]): Promise<number[]> {
+    let ids: number[] = []
+    for (const clip of clips) {
+      ids.push(await this.addClip(clip))
+    }
+    return ids
+  }
+
+  async updateClip(id: number, changes: Partial<Clip>) {
+    const clip = this.clips.get(id)
+    if (clip) {
+      this.clips.set(id, {...clip, ...changes, id})
+    }
+  }
+
+  async deleteClip(id: number) {
+    this.clips.delete(id)
+  }
+
+  async deleteAllClips() {
+    this.clips.clear()
+  }
+
+  async secureDeleteClip(id: number) {
+    await this.deleteClip(id)
+  }
+
+  async secureDeleteAllClips() {
+    await this.deleteAllClips()
+  }
+
+  async getAllLinkPreviews(): Promise<LinkPreviewDetails[]> {
+    return this.previews.map(preview => ({...preview}))
+  }
+
+  async getLinkPreview(url: string): Promise<LinkPreviewDetails | undefined> {
+    const preview = this.previews.find(preview => preview.url === url)
+    return preview ? {...preview} : undefined
+  }
+
+  async addLinkPreviews(previews: LinkPreviewDetails[]) {
+    for (const preview of previews) {
+      preview.id = this.nextPreviewId++
+      this.previews.push({...preview})
+    }
+  }
+
+  async deleteLinkPreview(url: string) {
+    this.previews = this.previews.filter(preview => preview.url !== url)
+  }
+
+  async deleteAllLinkPreviews() {
+    this.previews = []
+  }
+
+  async snapshot(): Promise<HistorySnapshot> {
+    return {
+      clips: await this.getAllClips(),
+      linkPreviews: await this.getAllLinkPreviews()
+    }
+  }
+}
+
+export interface RetryConfig {
+  // The maximum number of attempts including the first one.
+  maxAttempts: number;
+  // The delay before the first retry. Each next delay is twice as long.
+  baseDelayMs: number;
+  maxDelayMs: number;
+}
+
+export const kDefaultRetryConfig: RetryConfig = {
+  maxAttempts: 3,
+  baseDelayMs: 50,
+  maxDelayMs: 1000,
+}
+
+export interface RetryMetrics {
+  // The number of the retried operations and the total number of retries.
+  retriedOperations: number;
+  retries: number;
+  // The number of the operations that failed after all attempts.
+  exhaustedOperations: number;
+}
+
+function sleep(ms: number) {
+  return new Promise(resolve => setTimeout(resolve, ms))
+}
+
+// Retries the operations that fail because the database is temporarily
+// busy, waiting between the attempts with the exponential backoff and
+// jitter.
+export class RetryPolicy {
+  private metrics: RetryMetrics = {retriedOperations: 0, retries: 0, exhaustedOperations: 0}
+
+  constructor(private config: RetryConfig,
+              private isRetryable: (error: unknown) => boolean) {
+  }
+
+  getMetrics(): RetryMetrics {
+    return {...this.metrics}
+  }
+
+  private getDelay(attempt: number): number {
+    let delay = Math.min(this.config.maxDelayMs, this.config.baseDelayMs * Math.pow(2, attempt - 1))
+    // The full jitter spreads the retries of the concurrent operations.
+    return Math.random() * delay
+  }
+
+  async run<T>(operation: () => Promise<T>): Promise<T> {
+    let maxAttempts = Math.max(1, this.config.maxAttempts)
+    for (let attempt = 1; ; attempt++) {
+      try {
+        return await operation()
+      } catch (error) {
+        if (!this.isRetryable(error)) {
+          throw error
+        }
+        if (attempt >= maxAttempts) {
+          this.metrics.exhaustedOperations++
+          throw error
+        }
+        if (attempt === 1) {
+          this.metrics.retriedOperations++
+        }
+        this.metrics.retries++
+        await sleep(this.getDelay(attempt))
+      }
+    }
+  }
+}
+
+// Applies the retry policy to all operations of another store.
+export class RetryingHistoryStore implements HistoryStore {
+  constructor(private store: HistoryStore, private policy: RetryPolicy) {
+  }
+
+  private retry<T>(operation: () => Promise<T>): Promise<T> {
+    return this.policy.run(operation)
+  }
+
+  open() {
+    return this.retry(() => this.store.open())
+  }
+
+  getAllClips() {
+    return this.retry(() => this.store.getAllClips())
+  }
+
+  addClip(clip: Clip) {
+    return this.retry(() => this.store.addClip(clip))
+  }
+
+  addClips(clips: Clip[]) {
+    return this.retry(() => this.store.addClips(clips))
+  }
+
+  updateClip(id: number, changes: Partial<Clip>) {
+    return this.retry(() => this.store.updateClip(id, changes))
+  }
+
+  deleteClip(id: number) {
+    return this.retry(() => this.store.deleteClip(id))
+  }
+
+  deleteAllClips() {
+    return this.retry(() => this.store.deleteAllClips())
+  }
+
+  secureDeleteClip(id: number) {
+    return this.retry(() => this.store.secureDeleteClip(id))
+  }
+
+  secureDeleteAllClips() {
+    return this.retry(() => this.store.secureDeleteAllClips())
+  }
+
+  getAllLinkPreviews() {
+    return this.retry(() => this.store.getAllLinkPreviews())
+  }
+
+  getLinkPreview(url: string) {
+    return this.retry(() => this.store.getLinkPreview(url))
+  }
+
+  addLinkPreviews(previews: LinkPreviewDetails[]) {
+    return this.retry(() => this.store.addLinkPreviews(previews))
+  }
+
+  deleteLinkPreview(url: string) {
+    return this.retry(() => this.store.deleteLinkPreview(url))
+  }
+
+  deleteAllLinkPreviews() {
+    return this.retry(() => this.store.deleteAllLinkPreviews())
+  }
+
+  snapshot() {
+    return this.retry(() => this.store.snapshot())
+  }
+}
@@ -0,0 +1,129 @@
+import {flushPendingClips} from "@/db";
+
+// A background task watched by the supervisor.
+export interface SupervisedTask {
+  name: string;
+  start: () => void;
+  stop?: () => void;
+  // Returns false if the task has died and must be restarted.
+  isAlive: () => boolean;
+  // Called on app exit after the task is stopped to save its pending work.
+  shutdown?: () => Promise<void>;
+}
+
+export interface TaskHealth {
+  name: string;
+  alive: boolean;
+  restarts: number;
+  lastRestart?: Date;
+  lastError?: string;
+}
+
+interface TaskState {
+  task: SupervisedTask;
+  health: TaskHealth;
+  nextRestartTime: number;
+}
+
+// How often the supervisor checks the tasks.
+const kCheckIntervalMs = 30 * 1000
+const kMinBackoffMs = 1000
+const kMaxBackoffMs = 5 * 60 * 1000
+// The maximum time the tasks are given to finish their work on app exit.
+const kShutdownTimeoutMs = 3000
+
+let tasks: TaskState[] = []
+let supervisorTimer: ReturnType<typeof setInterval> | undefined
+let shuttingDown = false
+let shutdownComplete = false
+
+function backoff(restarts: number): number {
+  return Math.min(kMinBackoffMs * Math.pow(2, restarts), kMaxBackoffMs)
+}
+
+function restart(state: TaskState) {
+  let health = state.health
+  console.error(`❌ Background task "${health.name}" is not running, restarting it`)
+  try {
+    state.task.stop?.()
+    state.task.start()
+    health.lastError = undefined
+  } catch (error) {
+    health.lastError = error instanceof Error ? error.message : String(error)
+    console.error(`❌ Failed to restart background task "${health.name}":`, error)
+  }
+  health.restarts++
+  health.lastRestart = new Date()
+  state.nextRestartTime = Date.now() + backoff(health.restarts)
+}
+
+function checkTasks() {
+  for (const state of tasks) {
+    let alive = false
+    try {
+      alive = state.task.isAlive()
+    } catch (error) {
+      state.health.lastError = error instanceof Error ? error.message : String(error)
+    }
+    state.health.alive = alive
+    if (!alive && Date.now() >= state.nextRestartTime) {
+      restart(state)
+    }
+  }
+}
+
+// Starts the task and restarts it with exponential backoff whenever it dies.
+export function superviseTask(task: SupervisedTask) {
+  if (shuttingDown || tasks.some(state => state.task.name === task.name)) {
+    return
+  }
+  task.start()
+  tasks.push({
+    task,
+    health: {name: task.name, alive: true, restarts: 0},
+    nextRestartTime: 0,
+  })
+  if (!supervisorTimer) {
+    supervisorTimer = setInterval(checkTasks, kCheckIntervalMs)
+  }
+}
+
+export function getTasksHealth(): TaskHealth[] {
+  return tasks.map(state => ({...state.health}))
+}
+
+async function shutdownTask(task: SupervisedTask) {
+  try {
+    task.stop?.()
+    await task.shutdown?.()
+  } catch (error) {
+    console.error(`❌ Failed to shut down background task "${task.name}":`, error)
+  }
+}
+
+// Stops restarting the tasks, stops them in the reverse order and waits
+// until they save their pending work and the buffered clips are written or
+// the timeout expires.
+export async function shutdownTasks(timeoutMs: number = kShutdownTimeoutMs) {
+  if (shuttingDown) {
+    return
+  }
+  shuttingDown = true
+  if (supervisorTimer) {
+    clearInterval(supervisorTimer)
+    supervisorTimer = undefined
+  }
+  let shutdown = Promise.all([...tasks].reverse().map(state => shutdownTask(state.task)))
+      .then(() => flushPendingClips())
+      .catch(error => {
+        console.error('❌ Failed to write the pending clips:', error)
+      })
+  let timeout = new Promise<void>(resolve => setTimeout(resolve, timeoutMs))
+  await Promise.race([shutdown, timeout])
+  shutdownComplete = true
+  console.log('✅ Background tasks have been shut down')
+}
+
+export function isShutdownComplete(): boolean {
+  return shutdownComplete
+}
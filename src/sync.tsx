@@ -0,0 +1,571 @@
+import {addClip, Clip, ClipType, deleteClip, getAllClips, updateClip} from "@/db";
+import {reloadHistory} from "@/data";
+import {allTags, getTags} from "@/tags";
+import {emitter} from "@/actions";
+import {decryptText, EncryptedData, encryptText} from "@/lib/crypto";
+import {hashString} from "@/lib/hash";
+
+declare const createDirectory: (dirPath: string) => boolean;
+declare const getFilesInDir: (dirPath: string, extension: string) => string;
+declare const readTextFile: (filePath: string) => string;
+declare const writeTextFileAtomically: (filePath: string, content: string) => boolean;
+declare const saveSecret: (name: string, value: string) => boolean;
+declare const getSecret: (name: string) => string;
+
+// The number of attempts to write the sync document when other devices keep
+// changing it concurrently.
+const kMaxWriteAttempts = 3
+
+const kChangelogExtension = ".clipbooksync"
+
+// How long to keep the records of the deleted items, so that the devices
+// that were offline meanwhile delete the items too.
+const kTombstoneTtlMs = 30 * 24 * 60 * 60 * 1000
+
+// The names of the secrets stored in the Keychain instead of localStorage.
+const kPasswordSecret = "sync.password"
+const kPassphraseSecret = "sync.passphrase"
+
+// The number of changes made by every device.
+export type VectorClock = Record<string, number>
+
+// The content of a history item shared between devices. Tags are shared by
+// name because tag ids are local to every device.
+export interface SyncedClip {
+  name: string;
+  content: string;
+  type: ClipType;
+  sourceApp: string;
+  favorite: boolean;
+  tags: string[];
+  copyTime: number;
+  numberOfCopies: number;
+}
+
+export interface SyncRecord {
+  id: string;
+  clock: VectorClock;
+  modifiedAt: number;
+  modifiedBy: string;
+  deleted?: boolean;
+  clip?: SyncedClip;
+}
+
+// Reads and writes the encrypted sync documents. The version is used to
+// detect that another device has changed the documents in the meantime.
+export interface SyncTransport {
+  // True if every device writes its own document with only the changes made
+  // on that device, so the documents of the other devices are never
+  // overwritten.
+  readonly perDevice: boolean;
+  read(): Promise<{ documents: string[], version?: string }>;
+  // Returns false if the document has been changed since it was read.
+  write(content: string, version?: string): Promise<boolean>;
+}
+
+export interface SyncConfig {
+  // The URL of the sync document on a WebDAV server, an S3 bucket, or a
+  // relay that supports GET and PUT requests with ETags.
+  url?: string;
+  // The folder, e.g. in iCloud Drive, where every device keeps its own
+  // changelog file. Used instead of the URL.
+  folder?: string;
+  username?: string;
+  password?: string;
+  passphrase: string;
+}
+
+export interface SyncStatus {
+  configured: boolean;
+  deviceId: string;
+  inProgress: boolean;
+  lastSyncTime?: Date;
+  lastError?: string;
+  syncedItems: number;
+}
+
+let syncInProgress = false
+
+// The version of an existing document without ETag and Last-Modified.
+const kExistingVersion = "exists"
+
+// Stores the sync document with plain HTTP GET and PUT requests.
+export class HttpTransport implements SyncTransport {
+  readonly perDevice = false
+
+  constructor(private config: SyncConfig) {
+  }
+
+  private headers(): Record<string, string> {
+    let headers: Record<string, string> = {}
+    if (this.config.username) {
+      headers["Authorization"] = "Basic " + btoa(`${this.config.username}:${this.config.password ?? ""}`)
+    }
+    return headers
+  }
+
+  async read() {
+    let response = await fetch(this.config.url!, {headers: this.headers(), cache: "no-store"})
+    if (response.status === 404) {
+      return {documents: []}
+    }
+    if (!response.ok) {
+      throw new Error(`Failed to read sync data: HTTP ${response.status}`)
+    }
+    // The ETag header isn't readable across origins unless the server
+    // exposes it, but Last-Modified always is.
+    let etag = response.headers.get("ETag")
+    let lastModified = response.headers.get("Last-Modified")
+    let version = etag ? "etag:" + etag : lastModified ? "modified:" + lastModified : kExistingVersion
+    return {documents: [await response.text()], version}
+  }
+
+  async write(content: string, version?: string) {
+    let headers = this.headers()
+    headers["Content-Type"] = "application/json"
+    if (!version) {
+      headers["If-None-Match"] = "*"
+    } else if (version.startsWith("etag:")) {
+      headers["If-Match"] = version.substring("etag:".length)
+    } else if (version.startsWith("modified:")) {
+      headers["If-Unmodified-Since"] = version.substring("modified:".length)
+    }
+    // Otherwise, the document exists but the server doesn't report its
+    // version, so the concurrent changes can't be detected.
+    let response = await fetch(this.config.url!, {method: "PUT", headers, body: content})
+    if (response.status === 412) {
+      return false
+    }
+    if (!response.ok) {
+      throw new Error(`Failed to write sync data: HTTP ${response.status}`)
+    }
+    return true
+  }
+}
+
+// Keeps a changelog file per device in a shared folder, e.g. in iCloud Drive.
+// Every device writes only its own file, so the writes never conflict, and
+// reads the files written by all devices.
+export class FolderTransport implements SyncTransport {
+  readonly perDevice = true
+
+  constructor(private folder: string) {
+  }
+
+  private get fileName(): string {
+    return getDeviceId() + kChangelogExtension
+  }
+
+  async read() {
+    if (typeof getFilesInDir === 'undefined') {
+      throw new Error('Folder sync is not supported')
+    }
+    let documents: string[] = []
+    let fileNames = getFilesInDir(this.folder, kChangelogExtension).split("*")
+    for (const fileName of fileNames) {
+      if (fileName.length === 0) {
+        continue
+      }
+      let content = readTextFile(this.folder + "/" + fileName)
+      if (content) {
+        documents.push(content)
+      }
+    }
+    return {documents}
+  }
+
+  async write(content: string) {
+    // Write the file atomically, so that the other devices never read a
+    // partially written changelog.
+    if (!createDirectory(this.folder) || !writeTextFileAtomically(this.folder + "/" + this.fileName, content)) {
+      throw new Error(`Failed to write sync data to ${this.folder}`)
+    }
+    return true
+  }
+}
+
+export function getDeviceId(): string {
+  let deviceId = localStorage.getItem("deviceId")
+  if (!deviceId) {
+    deviceId = crypto.randomUUID()
+    localStorage.setItem("deviceId", deviceId)
+  }
+  return deviceId
+}
+
+function readSecret(name: string): string {
+  return typeof getSecret === 'undefined' ? "" : getSecret(name)
+}
+
+function writeSecret(name: string, value: string) {
+  if (typeof saveSecret !== 'undefined' && !saveSecret(name, value)) {
+    throw new Error('Failed to save the sync credentials')
+  }
+}
+
+export function getSyncConfig(): SyncConfig | undefined {
+  let value = localStorage.getItem("syncConfig")
+  if (!value) {
+    return undefined
+  }
+  let config: SyncConfig = JSON.parse(value)
+  if (config.password || config.passphrase) {
+    // Move the secrets saved by the previous versions to the Keychain.
+    configureSync(config)
+    return getSyncConfig()
+  }
+  let password = readSecret(kPasswordSecret)
+  return {...config, password: password || undefined, passphrase: readSecret(kPassphraseSecret)}
+}
+
+// Saves the sync configuration. The password and the passphrase are kept in
+// the Keychain. Pass undefined to turn the sync off.
+export function configureSync(config: SyncConfig | undefined) {
+  if (!config) {
+    localStorage.removeItem("syncConfig")
+    writeSecret(kPasswordSecret, "")
+    writeSecret(kPassphraseSecret, "")
+  } else {
+    if (config.folder === undefined && !/^https?:\/\//.test(config.url ?? "")) {
+      throw new Error(`Invalid sync URL: ${config.url}`)
+    }
+    if (!config.passphrase) {
+      throw new Error('The sync passphrase is required')
+    }
+    writeSecret(kPasswordSecret, config.password ?? "")
+    writeSecret(kPassphraseSecret, config.passphrase)
+    let {password, passphrase, ...rest} = config
+    localStorage.setItem("syncConfig", JSON.stringify(rest))
+  }
+  localStorage.removeItem("syncLastError")
+  emitStatus()
+}
+
+export function getSyncStatus(): SyncStatus {
+  let lastSyncTime = localStorage.getItem("lastSyncTime")
+  return {
+    configured: localStorage.getItem("syncConfig") !== null,
+    deviceId: getDeviceId(),
+    inProgress: syncInProgress,
+    lastSyncTime: lastSyncTime ? new Date(lastSyncTime) : undefined,
+    // Kept in localStorage, so that the settings window can show it.
+    lastError: localStorage.getItem("syncLastError") ?? undefined,
+    syncedItems: getKnownIds().size,
+  }
+}
+
+function emitStatus() {
+  emitter.emit("SyncStatusChanged", getSyncStatus())
+}
+
+// Returns the ids of the items that were in the history after the last sync.
+function getKnownIds(): Set<string> {
+  return new Set(JSON.parse(localStorage.getItem("syncKnownIds") || "[]"))
+}
+
+export function compareClocks(a: VectorClock, b: VectorClock): "equal" | "before" | "after" | "concurrent" {
+  let aNewer = false
+  let bNewer = false
+  for (const device of new Set([...Object.keys(a), ...Object.keys(b)])) {
+    let aCount = a[device] ?? 0
+    let bCount = b[device] ?? 0
+    aNewer = aNewer || aCount > bCount
+    bNewer = bNewer || bCount > aCount
+  }
+  if (aNewer && bNewer) {
+    return "concurrent"
+  }
+  return aNewer ? "after" : bNewer ? "before" : "equal"
+}
+
+function mergeClocks(a: VectorClock, b: VectorClock): VectorClock {
+  let result = {...a}
+  for (const device of Object.keys(b)) {
+    result[device] = Math.max(result[device] ?? 0, b[device])
+  }
+  return result
+}
+
+function tick(clock: VectorClock, deviceId: string): VectorClock {
+  return {...clock, [deviceId]: (clock[deviceId] ?? 0) + 1}
+}
+
+function isSyncable(clip: Clip): boolean {
+  // Images and files refer to local files, so only text items are shared.
+  return clip.type !== ClipType.Image && clip.type !== ClipType.File
+}
+
+function toSyncedClip(clip: Clip): SyncedClip {
+  return {
+    name: clip.name,
+    content: clip.content,
+    type: clip.type,
+    sourceApp: clip.sourceApp,
+    favorite: clip.favorite,
+    tags: getTags(clip.tags).map(tag => tag.name),
+    copyTime: new Date(clip.copyTime).getTime(),
+    numberOfCopies: clip.numberOfCopies,
+  }
+}
+
+function applySyncedClip(clip: Clip, synced: SyncedClip) {
+  clip.name = synced.name
+  clip.content = synced.content
+  clip.type = synced.type
+  clip.sourceApp = synced.sourceApp
+  clip.favorite = synced.favorite
+  clip.tags = allTags().filter(tag => synced.tags.includes(tag.name)).map(tag => tag.id)
+  clip.copyTime = new Date(synced.copyTime)
+  clip.numberOfCopies = synced.numberOfCopies
+}
+
+function syncHash(clip: Clip): string {
+  return hashString(JSON.stringify(toSyncedClip(clip)))
+}
+
+// Assigns the sync ids to the new items and advances the clocks of the items
+// changed since the last sync.
+async function collectLocalRecords(deviceId: string): Promise<Map<string, Clip>> {
+  let clips = new Map<string, Clip>()
+  for (const clip of await getAllClips()) {
+    // The items in the trash are synced as deleted.
+    if (!isSyncable(clip) || clip.deletedAt) {
+      continue
+    }
+    let hash = syncHash(clip)
+    if (!clip.syncId || clip.syncHash !== hash) {
+      clip.syncId = clip.syncId ?? crypto.randomUUID()
+      clip.syncClock = tick(clip.syncClock ?? {}, deviceId)
+      clip.syncModifiedAt = Date.now()
+      clip.syncModifiedBy = deviceId
+      clip.syncHash = hash
+      await updateClip(clip.id!, {
+        syncId: clip.syncId,
+        syncClock: clip.syncClock,
+        syncModifiedAt: clip.syncModifiedAt,
+        syncModifiedBy: clip.syncModifiedBy,
+        syncHash: clip.syncHash,
+      })
+    }
+    clips.set(clip.syncId, clip)
+  }
+  return clips
+}
+
+function toRecord(clip: Clip, deviceId: string): SyncRecord {
+  return {
+    id: clip.syncId!,
+    clock: clip.syncClock ?? {},
+    modifiedAt: clip.syncModifiedAt ?? Date.now(),
+    modifiedBy: clip.syncModifiedBy ?? deviceId,
+    clip: toSyncedClip(clip),
+  }
+}
+
+// Resolves the concurrent changes in favor of the most recent one.
+function resolveConflict(local: SyncRecord, remote: SyncRecord): SyncRecord {
+  if (local.modifiedAt !== remote.modifiedAt) {
+    return local.modifiedAt > remote.modifiedAt ? local : remote
+  }
+  return local.modifiedBy > remote.modifiedBy ? local : remote
+}
+
+interface MergeResult {
+  records: SyncRecord[];
+  // The remote records that must be applied to the local history.
+  incoming: SyncRecord[];
+  // True if the records differ from the remote ones and must be written.
+  changed: boolean;
+}
+
+export function mergeRecords(localClips: Map<string, Clip>, remoteRecords: SyncRecord[],
+                             knownIds: Set<string>, deviceId: string): MergeResult {
+  let result: MergeResult = {records: [], incoming: [], changed: false}
+  let remoteById = new Map(remoteRecords.map(record => [record.id, record]))
+  for (const [id, clip] of localClips) {
+    let local = toRecord(clip, deviceId)
+    let remote = remoteById.get(id)
+    remoteById.delete(id)
+    if (!remote) {
+      result.records.push(local)
+      result.changed = true
+      continue
+    }
+    switch (compareClocks(local.clock, remote.clock)) {
+      case "equal":
+        result.records.push(local)
+        break
+      case "after":
+        result.records.push(local)
+        result.changed = true
+        break
+      case "before":
+        result.records.push(remote)
+        result.incoming.push(remote)
+        break
+      case "concurrent": {
+        let winner = resolveConflict(local, remote)
+        // The merge is a change made by this device.
+        let merged = {...winner, clock: tick(mergeClocks(local.clock, remote.clock), deviceId), modifiedBy: deviceId}
+        result.records.push(merged)
+        result.incoming.push(merged)
+        result.changed = true
+        break
+      }
+    }
+  }
+  // The remote records that don't have a local item.
+  for (const remote of remoteById.values()) {
+    if (!remote.deleted && knownIds.has(remote.id)) {
+      // The item was deleted on this device after the last sync.
+      result.records.push({
+        id: remote.id,
+        clock: tick(remote.clock, deviceId),
+        modifiedAt: Date.now(),
+        modifiedBy: deviceId,
+        deleted: true,
+      })
+      result.changed = true
+      continue
+    }
+    if (remote.deleted && Date.now() - remote.modifiedAt > kTombstoneTtlMs) {
+      // Forget the old deletions, so that the document doesn't grow forever.
+      result.changed = true
+      continue
+    }
+    result.records.push(remote)
+    if (!remote.deleted) {
+      result.incoming.push(remote)
+    }
+  }
+  return result
+}
+
+async function applyIncoming(records: SyncRecord[], localClips: Map<string, Clip>) {
+  for (const record of records) {
+    let clip = localClips.get(record.id)
+    if (record.deleted || !record.clip) {
+      if (clip) {
+        await deleteClip(clip.id!)
+      }
+      continue
+    }
+    let isNew = !clip
+    if (!clip) {
+      clip = new Clip(record.clip.type, record.clip.content, record.clip.sourceApp)
+    }
+    applySyncedClip(clip, record.clip)
+    clip.syncId = record.id
+    clip.syncClock = record.clock
+    clip.syncModifiedAt = record.modifiedAt
+    clip.syncModifiedBy = record.modifiedBy
+    clip.syncHash = syncHash(clip)
+    if (isNew) {
+      await addClip(clip)
+    } else {
+      await updateClip(clip.id!, clip)
+    }
+  }
+}
+
+// Combines the records from several documents keeping the latest change of
+// every item.
+export function combineRecords(lists: SyncRecord[][]): SyncRecord[] {
+  let result = new Map<string, SyncRecord>()
+  for (const records of lists) {
+    for (const record of records) {
+      let existing = result.get(record.id)
+      if (!existing) {
+        result.set(record.id, record)
+        continue
+      }
+      switch (compareClocks(existing.clock, record.clock)) {
+        case "before":
+          result.set(record.id, record)
+          break
+        case "concurrent":
+          result.set(record.id, {
+            ...resolveConflict(existing, record),
+            clock: mergeClocks(existing.clock, record.clock),
+          })
+          break
+      }
+    }
+  }
+  return Array.from(result.values())
+}
+
+async function readRecords(transport: SyncTransport, passphrase: string) {
+  let {documents, version} = await transport.read()
+  let lists: SyncRecord[][] = []
+  for (const document of documents) {
+    try {
+      let encrypted: EncryptedData = JSON.parse(document)
+      lists.push(JSON.parse(await decryptText(encrypted, passphrase)))
+    } catch (error) {
+      // A shared document that can't be read must not be overwritten, but a
+      // damaged document of another device only means its changes are lost.
+      if (!transport.perDevice) {
+        throw error
+      }
+      console.error('❌ Skipping unreadable sync document:', error)
+    }
+  }
+  return {records: combineRecords(lists), version}
+}
+
+// Exchanges the history changes with the other devices through the transport.
+// Returns the number of items received from the other devices.
+export async function syncWithTransport(transport: SyncTransport, passphrase: string): Promise<number> {
+  if (syncInProgress) {
+    throw new Error('Sync is already in progress')
+  }
+  syncInProgress = true
+  emitStatus()
+  try {
+    let deviceId = getDeviceId()
+    let knownIds = getKnownIds()
+    let localClips = await collectLocalRecords(deviceId)
+    for (let attempt = 1; attempt <= kMaxWriteAttempts; attempt++) {
+      let remote = await readRecords(transport, passphrase)
+      let merge = mergeRecords(localClips, remote.records, knownIds, deviceId)
+      // Don't rewrite the whole document when nothing has changed locally.
+      if (merge.changed) {
+        let records = transport.perDevice ?
+            merge.records.filter(record => record.modifiedBy === deviceId) : merge.records
+        let content = JSON.stringify(await encryptText(JSON.stringify(records), passphrase))
+        if (!await transport.write(content, remote.version)) {
+          continue
+        }
+      }
+      await applyIncoming(merge.incoming, localClips)
+      let ids = merge.records.filter(record => !record.deleted).map(record => record.id)
+      localStorage.setItem("syncKnownIds", JSON.stringify(ids))
+      localStorage.setItem("lastSyncTime", new Date().toISOString())
+      localStorage.removeItem("syncLastError")
+      if (merge.incoming.length > 0) {
+        await reloadHistory()
+        emitter.emit("HistoryChanged")
+      }
+      console.log(`✅ Synced history: ${merge.incoming.length} items received`)
+      return merge.incoming.length
+    }
+    throw new Error('The sync data is being changed by another device, try again later')
+  } catch (error) {
+    localStorage.setItem("syncLastError", error instanceof Error ? error.message : String(error))
+    console.error('❌ Sync failed:', error)
+    throw error
+  } finally {
+    syncInProgress = false
+    emitStatus()
+  }
+}
+
+export async function syncNow(): Promise<number> {
+  let config = getSyncConfig()
+  if (!config || !config.passphrase) {
+    throw new Error('Sync is not configured')
+  }
+  let transport = config.folder !== undefined ? new FolderTransport(config.folder) : new HttpTransport(config)
+  return syncWithTransport(transport, config.passphrase)
+}
@@ -0,0 +1,83 @@
+import {getSyncConfig, getSyncStatus, SyncStatus, syncNow} from "@/sync";
+import {emitter} from "@/actions";
+
+// How often to import the changes written by the other devices.
+const kSyncIntervalMs = 60 * 1000
+// How long to wait after a history change before exporting it, so that a
+// burst of changes is written at once.
+const kChangeDelayMs = 5 * 1000
+
+// Keeps the history in sync in the background: exports the local changes
+// shortly after they are made and periodically imports the changes made on
+// the other devices. The status changes are reported with the
+// "SyncStatusChanged" event.
+export class SyncManager {
+  private timer: ReturnType<typeof setInterval> | undefined
+  private changeTimer: ReturnType<typeof setTimeout> | undefined
+  private lastCheckTime = 0
+  private readonly onHistoryChange = () => this.scheduleSync()
+
+  start() {
+    if (this.timer) {
+      return
+    }
+    this.lastCheckTime = Date.now()
+    this.timer = setInterval(() => {
+      this.lastCheckTime = Date.now()
+      this.sync()
+    }, kSyncIntervalMs)
+    emitter.on("HistoryItemAdded", this.onHistoryChange)
+    emitter.on("HistoryItemUpdated", this.onHistoryChange)
+    emitter.on("HistoryItemDeleted", this.onHistoryChange)
+    emitter.on("HistoryCleared", this.onHistoryChange)
+    this.sync()
+  }
+
+  stop() {
+    if (this.timer) {
+      clearInterval(this.timer)
+      this.timer = undefined
+    }
+    if (this.changeTimer) {
+      clearTimeout(this.changeTimer)
+      this.changeTimer = undefined
+    }
+    emitter.off("HistoryItemAdded", this.onHistoryChange)
+    emitter.off("HistoryItemUpdated", this.onHistoryChange)
+    emitter.off("HistoryItemDeleted", this.onHistoryChange)
+    emitter.off("HistoryCleared", this.onHistoryChange)
+  }
+
+  isAlive(): boolean {
+    return this.timer !== undefined && Date.now() - this.lastCheckTime < 2 * kSyncIntervalMs
+  }
+
+  // Syncs the history right away, e.g. when the user asks for it.
+  syncNow() {
+    this.sync()
+  }
+
+  get status(): SyncStatus {
+    return getSyncStatus()
+  }
+
+  private scheduleSync() {
+    if (this.changeTimer) {
+      clearTimeout(this.changeTimer)
+    }
+    this.changeTimer = setTimeout(() => {
+      this.changeTimer = undefined
+      this.sync()
+    }, kChangeDelayMs)
+  }
+
+  private sync() {
+    if (!getSyncConfig() || getSyncStatus().inProgress) {
+      return
+    }
+    // The errors are reported in the sync status.
+    syncNow().catch(() => {})
+  }
+}
+
+export const syncManager = new SyncManager()
@@ -0,0 +1,104 @@
+import {CaptureState, getCaptureState} from "@/capture";
+
+declare const getSystemInfo: () => string;
+
+// How often to check whether the user has been idle long enough.
+const kIdleCheckIntervalMs = 15 * 1000
+// How long the system info is reused before it's read again.
+const kSystemInfoTtlMs = 30 * 1000
+
+export type IdleHandler = (idleMs: number) => void
+
+// Tracks the user activity in the app to detect idle periods.
+export class SystemManager {
+  private lastActivityTime = Date.now()
+  private idleHandlers: IdleHandler[] = []
+  private timer: ReturnType<typeof setInterval> | undefined
+  private readonly onActivity = () => this.recordActivity()
+
+  start() {
+    if (this.timer) {
+      return
+    }
+    this.lastActivityTime = Date.now()
+    for (const event of ["keydown", "mousedown", "mousemove", "wheel"]) {
+      window.addEventListener(event, this.onActivity, {passive: true})
+    }
+    this.timer = setInterval(() => {
+      let idleMs = this.getIdleTime()
+      this.idleHandlers.forEach(handler => handler(idleMs))
+    }, kIdleCheckIntervalMs)
+  }
+
+  stop() {
+    if (this.timer) {
+      clearInterval(this.timer)
+      this.timer = undefined
+    }
+    for (const event of ["keydown", "mousedown", "mousemove", "wheel"]) {
+      window.removeEventListener(event, this.onActivity)
+    }
+  }
+
+  recordActivity() {
+    this.lastActivityTime = Date.now()
+  }
+
+  // Returns the time in milliseconds since the last user activity.
+  getIdleTime(): number {
+    return Date.now() - this.lastActivityTime
+  }
+
+  // Calls the handler periodically with the current idle time.
+  onIdle(handler: IdleHandler) {
+    this.idleHandlers.push(handler)
+  }
+}
+
+export type SystemState = {
+  capture: CaptureState
+  idleMs: number
+}
+
+export const systemManager = new SystemManager()
+
+export function getSystemState(): SystemState {
+  return {
+    capture: getCaptureState(),
+    idleMs: systemManager.getIdleTime(),
+  }
+}
+
+// The state of the system the app runs on. The sizes are in bytes.
+export type SystemInfo = {
+  osVersion: string
+  cpuCount: number
+  // The average number of the runnable processes over the last minute.
+  loadAverage: number
+  totalMemory: number
+  availableMemory: number
+  // The space of the volume the app data is stored on.
+  diskTotalSpace: number
+  diskFreeSpace: number
+  hasBattery: boolean
+  // The battery charge in percent or -1 if there's no battery.
+  batteryLevel: number
+  batteryCharging: boolean
+  onAcPower: boolean
+}
+
+let systemInfo: SystemInfo | undefined
+let systemInfoTime = 0
+
+// Returns the system info read at most kSystemInfoTtlMs ago, so the frequent
+// requests don't query the system every time.
+export function getSystemInfoCached(): SystemInfo | undefined {
+  if (typeof getSystemInfo === 'undefined') {
+    return undefined
+  }
+  if (!systemInfo || Date.now() - systemInfoTime > kSystemInfoTtlMs) {
+    systemInfo = JSON.parse(getSystemInfo())
+    systemInfoTime = Date.now()
+  }
+  return systemInfo
+}
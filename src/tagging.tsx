@@ -0,0 +1,226 @@
+import {Clip, updateClip} from "@/db";
+import {getHistoryItemById, getTrashItems, searchHistory} from "@/data";
+import {allTags, findTagById, findTagByName, kTagPathSeparator, normalizeTagName, removeTag, renameTag} from "@/tags";
+import {emitter} from "@/actions";
+import {requireNotGuest} from "@/guest";
+
+export interface TagUsage {
+  id: number;
+  name: string;
+  color: string;
+  // The name of the namespace the tag is nested in.
+  parent?: string;
+  // The number of the history items tagged with the tag.
+  itemCount: number;
+}
+
+// Returns the history and trash items, so the tags are changed everywhere.
+function getAllItems(): Clip[] {
+  return searchHistory("").concat(getTrashItems())
+}
+
+// Replaces the tags of every item that has the given tag.
+async function updateItemTags(tagId: number, update: (tags: number[]) => number[]): Promise<number> {
+  let count = 0
+  for (const item of getAllItems()) {
+    if (item.tags && item.tags.includes(tagId)) {
+      item.tags = update(item.tags)
+      await updateClip(item.id!, {tags: item.tags})
+      emitter.emit("UpdateItemById", item.id)
+      count++
+    }
+  }
+  return count
+}
+
+function getParentName(name: string): string | undefined {
+  let index = name.lastIndexOf(kTagPathSeparator)
+  return index > 0 ? name.substring(0, index) : undefined
+}
+
+// Returns all the tags sorted by name, so the nested tags follow their
+// namespaces, with the number of the items tagged with every tag.
+export function getAllTags(): TagUsage[] {
+  let counts = new Map<number, number>()
+  for (const item of searchHistory("")) {
+    for (const tagId of item.tags ?? []) {
+      counts.set(tagId, (counts.get(tagId) ?? 0) + 1)
+    }
+  }
+  return allTags()
+      .map(tag => ({
+        id: tag.id,
+        name: tag.name,
+        color: tag.color,
+        parent: getParentName(tag.name),
+        itemCount: counts.get(tag.id) ?? 0,
+      }))
+      .sort((a, b) => a.name.localeCompare(b.name))
+}
+
+// Moves the items of the source tag to the target tag and deletes the
+// source tag. Returns the number of the updated items.
+export async function mergeTags(sourceId: number, targetId: number): Promise<number> {
+  requireNotGuest()
+  let source = findTagById(sourceId)
+  if (!source || !findTagById(targetId)) {
+    throw new Error(`Tag not found: ${source ? targetId : sourceId}`)
+  }
+  if (sourceId === targetId) {
+    return 0
+  }
+  let count = await updateItemTags(sourceId, tags => {
+    let result = tags.filter(tagId => tagId !== sourceId)
+    return result.includes(targetId) ? result : result.concat(targetId)
+  })
+  removeTag(source)
+  emitter.emit("UpdateTags")
+  console.log(`✅ Merged tag ${source.name} into ${findTagById(targetId)!.name}, updated ${count} items`)
+  return count
+}
+
+// Renames the tag together with the tags nested in its namespace. If a tag
+// with the new name already exists, the tag is merged into it.
+export async function renameTagGlobally(id: number, name: string) {
+  requireNotGuest()
+  let tag = findTagById(id)
+  if (!tag) {
+    throw new Error(`Tag not found: ${id}`)
+  }
+  let newName = normalizeTagName(name)
+  if (newName.length === 0) {
+    throw new Error("The tag name is empty")
+  }
+  let existing = findTagByName(newName)
+  if (existing && existing.id !== id) {
+    await mergeTags(id, existing.id)
+    return
+  }
+  renameTag(id, newName)
+  emitter.emit("UpdateTagById", id)
+  emitter.emit("UpdateTags")
+}
+
+// Deletes the tag and removes it from all the items. The tags nested in its
+// namespace are kept. Returns the number of the updated items.
+export async function deleteTagGlobally(id: number): Promise<number> {
+  requireNotGuest()
+  let tag = findTagById(id)
+  if (!tag) {
+    throw new Error(`Tag not found: ${id}`)
+  }
+  let count = await updateItemTags(id, tags => tags.filter(tagId => tagId !== id))
+  removeTag(tag)
+  emitter.emit("UpdateTags")
+  return count
+}
+
+export interface TagSuggestion {
+  id: number;
+  name: string;
+  color: string;
+  // The number of the items tagged with the tag, or with both the tag and
+  // one of the item tags for the co-occurring tags.
+  score: number;
+  reason: "prefix" | "cooccurrence";
+}
+
+// The number of the items tagged with every tag and with every pair of tags.
+type TagIndex = {
+  frequency: Map<number, number>
+  cooccurrence: Map<number, Map<number, number>>
+}
+
+let tagIndex: TagIndex | undefined
+let tagIndexSubscribed = false
+
+function invalidateTagIndex() {
+  tagIndex = undefined
+}
+
+function buildTagIndex(): TagIndex {
+  let result: TagIndex = {frequency: new Map(), cooccurrence: new Map()}
+  for (const item of searchHistory("")) {
+    let tags = item.tags ?? []
+    for (const tagId of tags) {
+      result.frequency.set(tagId, (result.frequency.get(tagId) ?? 0) + 1)
+      let pairs = result.cooccurrence.get(tagId)
+      if (!pairs) {
+        pairs = new Map()
+        result.cooccurrence.set(tagId, pairs)
+      }
+      for (const otherId of tags) {
+        if (otherId !== tagId) {
+          pairs.set(otherId, (pairs.get(otherId) ?? 0) + 1)
+        }
+      }
+    }
+  }
+  return result
+}
+
+// Returns the tag index that is rebuilt after the history or the tags
+// change.
+function getTagIndex(): TagIndex {
+  if (!tagIndexSubscribed) {
+    tagIndexSubscribed = true
+    emitter.on("HistoryChanged", invalidateTagIndex)
+    emitter.on("HistoryItemAdded", invalidateTagIndex)
+    emitter.on("HistoryItemUpdated", invalidateTagIndex)
+    emitter.on("HistoryItemDeleted", invalidateTagIndex)
+    emitter.on("HistoryCleared", invalidateTagIndex)
+    emitter.on("UpdateItemById", invalidateTagIndex)
+    emitter.on("UpdateTags", invalidateTagIndex)
+  }
+  if (!tagIndex) {
+    tagIndex = buildTagIndex()
+  }
+  return tagIndex
+}
+
+// Returns the tags to autocomplete the tag entry of the item: the tags whose
+// name or any namespace segment starts with the prefix, the most used first,
+// followed by the tags most often used together with the current tags of the
+// item. The tags the item already has are skipped.
+export function suggestTags(prefix: string, itemId?: number, limit: number = 10): TagSuggestion[] {
+  let index = getTagIndex()
+  let itemTags = itemId !== undefined ? getHistoryItemById(itemId)?.tags ?? [] : []
+  let query = prefix.trim().toLowerCase()
+  let candidates = allTags().filter(tag => !itemTags.includes(tag.id))
+
+  let matches = candidates
+      .filter(tag => tag.name.toLowerCase().split(kTagPathSeparator).some(segment => segment.startsWith(query)))
+      .map(tag => ({
+        id: tag.id,
+        name: tag.name,
+        color: tag.color,
+        score: index.frequency.get(tag.id) ?? 0,
+        reason: "prefix" as const,
+      }))
+      .sort((a, b) => b.score - a.score || a.name.localeCompare(b.name))
+
+  let cooccurring = new Map<number, number>()
+  for (const tagId of itemTags) {
+    for (const [otherId, count] of index.cooccurrence.get(tagId) ?? []) {
+      cooccurring.set(otherId, (cooccurring.get(otherId) ?? 0) + count)
+    }
+  }
+  let matched = new Set(query.length > 0 ? matches.map(match => match.id) : [])
+  let related = candidates
+      .filter(tag => cooccurring.has(tag.id) && !matched.has(tag.id) &&
+          tag.name.toLowerCase().includes(query))
+      .map(tag => ({
+        id: tag.id,
+        name: tag.name,
+        color: tag.color,
+        score: cooccurring.get(tag.id)!,
+        reason: "cooccurrence" as const,
+      }))
+      .sort((a, b) => b.score - a.score || a.name.localeCompare(b.name))
+
+  // Without the prefix the related tags are more relevant than the most used
+  // ones.
+  let result = query.length > 0 ? matches.concat(related) :
+      related.concat(matches.filter(match => !cooccurring.has(match.id)))
+  return result.slice(0, limit)
+}
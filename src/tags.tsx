@@ -100,3 +100,48 @@ export function updateTag(id: number, name: string, color: string) {
     saveTags()
   }
 }
+
+// The separator of the tag namespaces, e.g. "work/project-x" is nested in
+// the "work" namespace.
+export const kTagPathSeparator = "/"
+
+// Removes the empty segments and the whitespaces around the segments of the
+// tag path.
+export function normalizeTagName(name: string): string {
+  return name.split(kTagPathSeparator)
+      .map(segment => segment.trim())
+      .filter(segment => segment.length > 0)
+      .join(kTagPathSeparator)
+}
+
+export function findTagByName(name: string): Tag | undefined {
+  return tags.find(tag => tag.name === name)
+}
+
+// Returns true if the tag is nested in the namespace of the given tag.
+export function isDescendantTag(tag: Tag, ancestor: Tag): boolean {
+  return tag.name.startsWith(ancestor.name + kTagPathSeparator)
+}
+
+// Returns the ids of the tag and all the tags nested in its namespace.
+export function getTagAndDescendantIds(tag: Tag): number[] {
+  return tags.filter(other => other.id === tag.id || isDescendantTag(other, tag)).map(other => other.id)
+}
+
+// Renames the tag and moves the tags nested in its namespace to the new
+// namespace.
+export function renameTag(id: number, name: string) {
+  let tag = findTagById(id)
+  if (!tag) {
+    return
+  }
+  let oldPrefix = tag.name + kTagPathSeparator
+  let newPrefix = name + kTagPathSeparator
+  for (const other of tags) {
+    if (other.name.startsWith(oldPrefix)) {
+      other.name = newPrefix + other.name.substring(oldPrefix.length)
+    }
+  }
+  tag.name = name
+  saveTags()
+}
@@ -0,0 +1,67 @@
+import {Clip} from "@/db";
+import {searchHistory} from "@/data";
+
+export enum TimelineGranularity {
+  Day = "day",
+  Week = "week",
+}
+
+// The items copied during a calendar day or week. The count is the number
+// of all the items of the period, while the items are limited, so the
+// timeline can show the periods with many items without loading them all.
+export type TimelineBucket = {
+  start: Date
+  end: Date
+  count: number
+  items: Clip[]
+}
+
+export function isTimelineGranularity(value: string): value is TimelineGranularity {
+  return Object.values(TimelineGranularity).includes(value as TimelineGranularity)
+}
+
+// Returns the local midnight of the day or of the Monday of the week.
+function getPeriodStart(time: Date, granularity: TimelineGranularity): Date {
+  let start = new Date(time.getFullYear(), time.getMonth(), time.getDate())
+  if (granularity === TimelineGranularity.Week) {
+    start.setDate(start.getDate() - (start.getDay() + 6) % 7)
+  }
+  return start
+}
+
+function getPeriodEnd(start: Date, granularity: TimelineGranularity): Date {
+  let end = new Date(start)
+  end.setDate(end.getDate() + (granularity === TimelineGranularity.Week ? 7 : 1))
+  return end
+}
+
+// Groups the items copied within the range by the calendar period, newest
+// first. The periods without items are omitted.
+export function getHistoryGrouped(granularity: TimelineGranularity,
+                                  from?: Date,
+                                  to?: Date,
+                                  itemsPerBucket = 20): TimelineBucket[] {
+  let fromTime = from?.getTime() ?? -Infinity
+  let toTime = to?.getTime() ?? Infinity
+  let items = searchHistory("")
+      .filter(item => {
+        let time = new Date(item.copyTime).getTime()
+        return time >= fromTime && time < toTime
+      })
+      .sort((a, b) => new Date(b.copyTime).getTime() - new Date(a.copyTime).getTime())
+  let buckets: TimelineBucket[] = []
+  let bucket: TimelineBucket | undefined
+  for (const item of items) {
+    let time = new Date(item.copyTime)
+    if (!bucket || time < bucket.start) {
+      let start = getPeriodStart(time, granularity)
+      bucket = {start, end: getPeriodEnd(start, granularity), count: 0, items: []}
+      buckets.push(bucket)
+    }
+    bucket.count++
+    if (bucket.items.length < itemsPerBucket) {
+      bucket.items.push(item)
+    }
+  }
+  return buckets
+}
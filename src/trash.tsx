@@ -0,0 +1,71 @@
+import {Clip} from "@/db";
+import {getTrashItems, purgeHistoryItem, restoreHistoryItem} from "@/data";
+import {prefGetTrashRetentionDays} from "@/pref";
+import {emitter} from "@/actions";
+
+// How often to delete the items that stay in the trash for too long.
+const kPurgeIntervalMs = 60 * 60 * 1000
+const kDayMs = 24 * 60 * 60 * 1000
+
+let purgeTimer: ReturnType<typeof setInterval> | undefined
+let lastPurgeTime = 0
+
+export async function restoreItem(id: number): Promise<Clip> {
+  let item = await restoreHistoryItem(id)
+  emitter.emit("HistoryChanged")
+  return item
+}
+
+// Deletes all the items in the trash permanently. Returns the number of
+// deleted items.
+export async function emptyTrash(): Promise<number> {
+  let items = getTrashItems()
+  for (const item of items) {
+    await purgeHistoryItem(item)
+  }
+  if (items.length > 0) {
+    console.log(`✅ Emptied trash, deleted ${items.length} items`)
+  }
+  return items.length
+}
+
+// Deletes the items that were moved to the trash more than
+// `history.trash_retention_days` days ago. Returns the number of deleted
+// items.
+export async function purgeExpiredTrash(now: number = Date.now()): Promise<number> {
+  let maxAgeMs = Math.max(0, prefGetTrashRetentionDays()) * kDayMs
+  let expired = getTrashItems().filter(item => now - new Date(item.deletedAt!).getTime() >= maxAgeMs)
+  for (const item of expired) {
+    await purgeHistoryItem(item)
+  }
+  if (expired.length > 0) {
+    console.log(`✅ Deleted ${expired.length} expired items from the trash`)
+  }
+  return expired.length
+}
+
+function runPurge() {
+  lastPurgeTime = Date.now()
+  purgeExpiredTrash().catch(error => {
+    console.error('❌ Failed to purge the trash:', error)
+  })
+}
+
+export function startTrashPurge() {
+  if (purgeTimer) {
+    return
+  }
+  runPurge()
+  purgeTimer = setInterval(runPurge, kPurgeIntervalMs)
+}
+
+export function isTrashPurgeAlive(): boolean {
+  return purgeTimer !== undefined && Date.now() - lastPurgeTime < 2 * kPurgeIntervalMs
+}
+
+export function stopTrashPurge() {
+  if (purgeTimer) {
+    clearInterval(purgeTimer)
+    purgeTimer = undefined
+  }
+}
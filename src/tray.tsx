@@ -0,0 +1,123 @@
+import i18n from "i18next";
+import {Clip, ClipType} from "@/db";
+import {getHistoryItems, getHistorySorted, SortHistoryType} from "@/data";
+import {prefShouldShowTodayCountInMenuBar} from "@/pref";
+import {emitter} from "@/actions";
+import {SyncStatus} from "@/sync";
+import {isAppLocked} from "@/applock";
+
+declare const clearTrayRecentItems: () => void;
+declare const addTrayRecentItem: (id: number, title: string) => void;
+declare const setTraySyncing: (syncing: boolean) => void;
+declare const setTrayError: (error: boolean) => void;
+declare const setTrayBadgeCount: (count: number) => void;
+
+// The number of the recent items displayed in the menu bar tray menu.
+const kTrayRecentItemsCount = 10
+// The maximum length of the item title in the tray menu.
+const kMaxTitleLength = 40
+// The delay to collect several history changes into one update.
+const kUpdateDelayMs = 500
+
+let updateTimer: ReturnType<typeof setTimeout> | undefined
+let lastEntries = ""
+let lastUpdateTime: Date | undefined
+let recentItemsCount = 0
+
+export type TrayStatus = {
+  available: boolean
+  recentItems: number
+  lastUpdate?: Date
+}
+
+function truncate(text: string): string {
+  let line = text.trim().split("\n")[0].trim()
+  if (line.length > kMaxTitleLength) {
+    return line.substring(0, kMaxTitleLength - 1) + "…"
+  }
+  return line
+}
+
+export function getTrayItemTitle(item: Clip): string {
+  if (item.sensitive) {
+    return i18n.t("app.tray.sensitiveItem")
+  }
+  if (item.type === ClipType.Image) {
+    return "🖼 " + truncate(item.name || item.imageText || i18n.t("app.tray.image"))
+  }
+  if (item.type === ClipType.File) {
+    return "📄 " + truncate(item.name || item.filePathFileName)
+  }
+  return truncate(item.name || item.content)
+}
+
+function getTodayItemsCount(): number {
+  let startOfDay = new Date()
+  startOfDay.setHours(0, 0, 0, 0)
+  return getHistoryItems().filter(item => new Date(item.copyTime) >= startOfDay).length
+}
+
+function updateTrayBadge() {
+  if (typeof setTrayBadgeCount === 'undefined') {
+    return
+  }
+  setTrayBadgeCount(prefShouldShowTodayCountInMenuBar() ? getTodayItemsCount() : 0)
+}
+
+function updateTray() {
+  updateTimer = undefined
+  lastUpdateTime = new Date()
+  updateTrayBadge()
+  // The locked history must not be visible in the menu bar.
+  let items = isAppLocked() ? [] : getHistorySorted(SortHistoryType.TimeOfLastCopy).slice(0, kTrayRecentItemsCount)
+  let entries: [number, string][] = items.map(item => [item.id!, getTrayItemTitle(item)])
+  // Rebuild the tray menu items only when they change.
+  let key = JSON.stringify(entries)
+  if (key === lastEntries) {
+    return
+  }
+  lastEntries = key
+  clearTrayRecentItems()
+  for (const [id, title] of entries) {
+    addTrayRecentItem(id, title)
+  }
+  recentItemsCount = entries.length
+}
+
+// Refreshes the recent items in the menu bar tray menu, so the user can
+// paste them with one click.
+export function updateTrayRecentItems() {
+  if (typeof clearTrayRecentItems === 'undefined' || typeof addTrayRecentItem === 'undefined') {
+    return
+  }
+  if (!updateTimer) {
+    updateTimer = setTimeout(updateTray, kUpdateDelayMs)
+  }
+}
+
+function handleSyncStatusChanged(status: SyncStatus) {
+  if (typeof setTraySyncing === 'undefined' || typeof setTrayError === 'undefined') {
+    return
+  }
+  setTraySyncing(status.inProgress)
+  setTrayError(!!status.lastError)
+}
+
+export function getTrayStatus(): TrayStatus {
+  return {
+    available: typeof addTrayRecentItem !== 'undefined',
+    recentItems: recentItemsCount,
+    lastUpdate: lastUpdateTime,
+  }
+}
+
+// Reflects the state of the background subsystems in the tray icon.
+export function startTrayStateUpdates() {
+  emitter.on("SyncStatusChanged", handleSyncStatusChanged)
+  emitter.on("AppLockChanged", updateTrayRecentItems)
+}
+
+export function stopTrayStateUpdates() {
+  emitter.off("SyncStatusChanged", handleSyncStatusChanged)
+  emitter.off("AppLockChanged", updateTrayRecentItems)
+}
@@ -0,0 +1,129 @@
+import {Clip, getAllClips} from "@/db";
+import {clipContent, tagNames, typeName} from "@/export";
+import {prefGetVaultDirectory} from "@/pref";
+
+declare const createDirectory: (dirPath: string) => boolean;
+declare const getFilesInDir: (dirPath: string, extension: string) => string;
+declare const deleteFile: (filePath: string) => void;
+declare const readTextFile: (filePath: string) => string;
+declare const writeTextFile: (filePath: string, content: string) => boolean;
+
+// The delay before exporting the vault after the history has changed.
+const kExportDelayMs = 5000
+// Every exported file starts with this line, so that only the files written
+// by ClipBook are removed from the vault.
+const kFrontMatterMarker = "---\nclipbook_id: "
+
+let exportTimer: ReturnType<typeof setTimeout> | undefined
+// The contents of the files written by the last export by file name.
+let writtenFiles = new Map<string, string>()
+
+function slugify(text: string): string {
+  return text.toLowerCase()
+      .normalize("NFKD")
+      .replace(/[\u0300-\u036f]/g, "")
+      .replace(/[^a-z0-9]+/g, "-")
+      .replace(/^-+|-+$/g, "")
+      .substring(0, 40)
+}
+
+function yamlString(value: string): string {
+  return JSON.stringify(value)
+}
+
+// Returns the file name that stays the same as long as the item name is not
+// changed.
+export function vaultFileName(clip: Clip): string {
+  return `${slugify(clip.name) || "item"}-${clip.id}.md`
+}
+
+export function toVaultNote(clip: Clip): string {
+  let lines = [
+    `---`,
+    `clipbook_id: ${clip.id}`,
+    `title: ${yamlString(clip.name)}`,
+    `type: ${typeName(clip.type)}`,
+    `source_app: ${yamlString(clip.sourceApp)}`,
+    `copied: ${new Date(clip.copyTime).toISOString()}`,
+    `favorite: ${clip.favorite}`,
+    `tags: [${tagNames(clip).map(yamlString).join(", ")}]`,
+    `---`,
+    ``,
+    clipContent(clip),
+    ``,
+  ]
+  return lines.join("\n")
+}
+
+function isVaultItem(clip: Clip): boolean {
+  // The vault is a plain folder, often synced to other apps, so the items
+  // that contain secrets are never written to it.
+  if (clip.sensitive === true) {
+    return false
+  }
+  return clip.favorite || (clip.tags !== undefined && clip.tags.length > 0)
+}
+
+function isExportedNote(filePath: string): boolean {
+  return readTextFile(filePath).startsWith(kFrontMatterMarker)
+}
+
+function isVaultSupported(): boolean {
+  return typeof createDirectory !== 'undefined' && typeof writeTextFile !== 'undefined'
+}
+
+// Writes favorites and tagged items to the vault directory as Markdown files,
+// one file per item, and removes the files of the items that are no longer
+// favorite or tagged, or have become sensitive. Only the changed files are rewritten. Returns the number
+// of written files.
+export async function exportVault(): Promise<number> {
+  let dir = prefGetVaultDirectory()
+  if (!dir || !isVaultSupported()) {
+    return 0
+  }
+  if (!createDirectory(dir)) {
+    throw new Error(`Failed to create vault directory: ${dir}`)
+  }
+  let clips = (await getAllClips()).filter(isVaultItem)
+  let notes = new Map<string, string>()
+  for (const clip of clips) {
+    notes.set(vaultFileName(clip), toVaultNote(clip))
+  }
+  let written = 0
+  notes.forEach((note, fileName) => {
+    if (writtenFiles.get(fileName) === note) {
+      return
+    }
+    if (!writeTextFile(`${dir}/${fileName}`, note)) {
+      throw new Error(`Failed to write vault file: ${fileName}`)
+    }
+    written++
+  })
+  let existingFiles = getFilesInDir(dir, ".md").split("*").filter(name => name.length > 0)
+  for (const fileName of existingFiles) {
+    let filePath = `${dir}/${fileName}`
+    if (!notes.has(fileName) && /-\d+\.md$/.test(fileName) && isExportedNote(filePath)) {
+      deleteFile(filePath)
+    }
+  }
+  writtenFiles = notes
+  if (written > 0) {
+    console.log(`✅ Exported ${written} items to the vault`)
+  }
+  return written
+}
+
+// Schedules the vault export. Subsequent calls within the delay are merged
+// into a single export.
+export function scheduleVaultExport() {
+  if (!prefGetVaultDirectory()) {
+    return
+  }
+  if (exportTimer) {
+    clearTimeout(exportTimer)
+  }
+  exportTimer = setTimeout(() => {
+    exportTimer = undefined
+    exportVault().catch(error => console.error('❌ Vault export failed:', error))
+  }, kExportDelayMs)
+}